@@ -19,7 +19,14 @@ class TrashBloc extends Bloc<TrashEvent, TrashState> {
     on<TrashEvent>((event, emit) async {
       await event.map(
         initial: (e) async {
-          _listener.start(trashUpdated: _listenTrashUpdated);
+          final workspaceId = await _service.readCurrentWorkspaceId();
+          workspaceId.fold(
+            (workspaceId) => _listener.start(
+              workspaceId: workspaceId,
+              trashUpdated: _listenTrashUpdated,
+            ),
+            (error) => Log.error(error),
+          );
           final result = await _service.readTrash();
           emit(
             result.fold(
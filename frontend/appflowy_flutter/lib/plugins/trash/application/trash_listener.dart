@@ -17,10 +17,14 @@ class TrashListener {
   TrashUpdatedCallback? _trashUpdated;
   FolderNotificationParser? _parser;
 
-  void start({TrashUpdatedCallback? trashUpdated}) {
+  /// Listens to the trash of the workspace.
+  void start({
+    required String workspaceId,
+    TrashUpdatedCallback? trashUpdated,
+  }) {
     _trashUpdated = trashUpdated;
     _parser = FolderNotificationParser(
-      id: "trash",
+      id: workspaceId,
       callback: _observableCallback,
     );
     _subscription =
@@ -3,10 +3,29 @@ import 'package:dartz/dartz.dart';
 import 'package:appflowy_backend/dispatch/dispatch.dart';
 import 'package:appflowy_backend/protobuf/flowy-error/errors.pb.dart';
 import 'package:appflowy_backend/protobuf/flowy-folder2/trash.pb.dart';
+import 'package:appflowy_backend/protobuf/flowy-folder2/workspace.pb.dart';
 
 class TrashService {
-  Future<Either<RepeatedTrashPB, FlowyError>> readTrash() {
-    return FolderEventReadTrash().send();
+  /// Reads the id of the current workspace. The trash is read and notified
+  /// per workspace.
+  Future<Either<String, FlowyError>> readCurrentWorkspaceId() async {
+    final result = await FolderEventReadCurrentWorkspace().send();
+    return result.fold(
+      (workspace) => left(workspace.id),
+      (error) => right(error),
+    );
+  }
+
+  /// Reads the trash of the current workspace.
+  Future<Either<RepeatedTrashPB, FlowyError>> readTrash() async {
+    final result = await readCurrentWorkspaceId();
+    return result.fold(
+      (workspaceId) {
+        final payload = WorkspaceIdPB.create()..value = workspaceId;
+        return FolderEventReadTrash(payload).send();
+      },
+      (error) => Future.value(right(error)),
+    );
   }
 
   Future<Either<Unit, FlowyError>> putback(String trashId) {
@@ -25,7 +44,12 @@ class TrashService {
   }
 
   Future<Either<Unit, FlowyError>> restoreAll() {
-    return FolderEventRestoreAllTrash().send();
+    return FolderEventRestoreAllTrash().send().then((result) {
+      return result.fold(
+        (_) => left(unit),
+        (error) => right(error),
+      );
+    });
   }
 
   Future<Either<Unit, FlowyError>> deleteAll() {
@@ -17,7 +17,12 @@ class ImportBackendService {
       ..viewLayout = importType.toLayout()
       ..name = name
       ..importType = importType;
-    return await FolderEventImportData(payload).send();
+    return await FolderEventImportData(payload).send().then((result) {
+      return result.fold(
+        (_) => left(unit),
+        (error) => right(error),
+      );
+    });
   }
 }
 
@@ -117,12 +117,22 @@ class ViewBackendService {
 
   static Future<Either<Unit, FlowyError>> delete({required String viewId}) {
     final request = RepeatedViewIdPB.create()..items.add(viewId);
-    return FolderEventDeleteView(request).send();
+    return FolderEventDeleteView(request).send().then((result) {
+      return result.fold(
+        (_) => left(unit),
+        (error) => right(error),
+      );
+    });
   }
 
   static Future<Either<Unit, FlowyError>> deleteView({required String viewId}) {
     final request = RepeatedViewIdPB.create()..items.add(viewId);
-    return FolderEventDeleteView(request).send();
+    return FolderEventDeleteView(request).send().then((result) {
+      return result.fold(
+        (_) => left(unit),
+        (error) => right(error),
+      );
+    });
   }
 
   static Future<Either<Unit, FlowyError>> duplicate({required ViewPB view}) {
@@ -18,11 +18,12 @@ impl EventIntegrationTest {
       name,
       desc: "".to_string(),
       thumbnail: None,
-      layout: ViewLayoutPB::Grid,
+      layout: Some(ViewLayoutPB::Grid),
       initial_data,
       meta: Default::default(),
       set_as_current: true,
       index: None,
+      view_id: None,
     };
     EventBuilder::new(self.clone())
       .event(FolderEvent::CreateView)
@@ -48,11 +49,12 @@ impl EventIntegrationTest {
       name,
       desc: "".to_string(),
       thumbnail: None,
-      layout: ViewLayoutPB::Board,
+      layout: Some(ViewLayoutPB::Board),
       initial_data,
       meta: Default::default(),
       set_as_current: true,
       index: None,
+      view_id: None,
     };
     EventBuilder::new(self.clone())
       .event(FolderEvent::CreateView)
@@ -73,11 +75,12 @@ impl EventIntegrationTest {
       name,
       desc: "".to_string(),
       thumbnail: None,
-      layout: ViewLayoutPB::Calendar,
+      layout: Some(ViewLayoutPB::Calendar),
       initial_data,
       meta: Default::default(),
       set_as_current: true,
       index: None,
+      view_id: None,
     };
     EventBuilder::new(self.clone())
       .event(FolderEvent::CreateView)
@@ -46,11 +46,12 @@ impl DocumentEventTest {
       name: "document".to_string(),
       desc: "".to_string(),
       thumbnail: None,
-      layout: ViewLayoutPB::Document,
+      layout: Some(ViewLayoutPB::Document),
       initial_data: vec![],
       meta: Default::default(),
       set_as_current: true,
       index: None,
+      view_id: None,
     };
     EventBuilder::new(core.clone())
       .event(FolderEvent::CreateView)
@@ -28,11 +28,12 @@ impl EventIntegrationTest {
       name,
       desc: "".to_string(),
       thumbnail: None,
-      layout: ViewLayoutPB::Document,
+      layout: Some(ViewLayoutPB::Document),
       initial_data,
       meta: Default::default(),
       set_as_current: true,
       index: None,
+      view_id: None,
     };
     let view = EventBuilder::new(self.clone())
       .event(FolderEvent::CreateView)
@@ -78,6 +78,7 @@ impl EventIntegrationTest {
   pub async fn delete_view(&self, view_id: &str) {
     let payload = RepeatedViewIdPB {
       items: vec![view_id.to_string()],
+      cascade: None,
     };
 
     // delete the view. the view will be moved to trash
@@ -88,6 +89,18 @@ impl EventIntegrationTest {
       .await;
   }
 
+  pub async fn read_trash(&self, workspace_id: &str) -> RepeatedTrashPB {
+    EventBuilder::new(self.clone())
+      .event(FolderEvent::ReadTrash)
+      .payload(WorkspaceIdPB {
+        value: workspace_id.to_string(),
+        preload_depth: None,
+      })
+      .async_send()
+      .await
+      .parse::<RepeatedTrashPB>()
+  }
+
   pub async fn update_view(&self, changeset: UpdateViewPayloadPB) -> Option<FlowyError> {
     // delete the view. the view will be moved to trash
     EventBuilder::new(self.clone())
@@ -113,11 +126,12 @@ impl EventIntegrationTest {
       name,
       desc: "".to_string(),
       thumbnail: None,
-      layout: Default::default(),
+      layout: Some(ViewLayoutPB::Document),
       initial_data: vec![],
       meta: Default::default(),
       set_as_current: false,
       index: None,
+      view_id: None,
     };
     EventBuilder::new(self.clone())
       .event(FolderEvent::CreateView)
@@ -151,6 +165,7 @@ impl ViewTest {
     let workspace = sdk.folder_manager.get_current_workspace().await.unwrap();
     let payload = WorkspaceIdPB {
       value: workspace.id.clone(),
+      preload_depth: None,
     };
     let _ = EventBuilder::new(sdk.clone())
       .event(OpenWorkspace)
@@ -163,11 +178,12 @@ impl ViewTest {
       name: "View A".to_string(),
       desc: "".to_string(),
       thumbnail: Some("http://1.png".to_string()),
-      layout,
+      layout: Some(layout),
       initial_data: data,
       meta: Default::default(),
       set_as_current: true,
       index: None,
+      view_id: None,
     };
 
     let view = EventBuilder::new(sdk.clone())
@@ -201,6 +217,7 @@ async fn create_workspace(sdk: &EventIntegrationTest, name: &str, desc: &str) ->
   let request = CreateWorkspacePayloadPB {
     name: name.to_owned(),
     desc: desc.to_owned(),
+    icon: None,
   };
 
   EventBuilder::new(sdk.clone())
@@ -207,6 +207,7 @@ pub async fn create_workspace(sdk: &EventIntegrationTest, name: &str, desc: &str
   let request = CreateWorkspacePayloadPB {
     name: name.to_owned(),
     desc: desc.to_owned(),
+    icon: None,
   };
 
   EventBuilder::new(sdk.clone())
@@ -220,6 +221,7 @@ pub async fn create_workspace(sdk: &EventIntegrationTest, name: &str, desc: &str
 pub async fn read_workspace(sdk: &EventIntegrationTest, workspace_id: String) -> WorkspacePB {
   let request = WorkspaceIdPB {
     value: workspace_id,
+    preload_depth: None,
   };
   EventBuilder::new(sdk.clone())
     .event(ReadCurrentWorkspace)
@@ -241,11 +243,12 @@ pub async fn create_view(
     name: name.to_string(),
     desc: desc.to_string(),
     thumbnail: None,
-    layout: layout.into(),
+    layout: Some(layout.into()),
     initial_data: vec![],
     meta: Default::default(),
     set_as_current: true,
     index: None,
+    view_id: None,
   };
   EventBuilder::new(sdk.clone())
     .event(CreateView)
@@ -320,7 +323,10 @@ pub async fn update_view_icon(sdk: &EventIntegrationTest, view_id: &str, icon: O
 }
 
 pub async fn delete_view(sdk: &EventIntegrationTest, view_ids: Vec<String>) {
-  let request = RepeatedViewIdPB { items: view_ids };
+  let request = RepeatedViewIdPB {
+    items: view_ids,
+    cascade: None,
+  };
   EventBuilder::new(sdk.clone())
     .event(DeleteView)
     .payload(request)
@@ -329,11 +335,8 @@ pub async fn delete_view(sdk: &EventIntegrationTest, view_ids: Vec<String>) {
 }
 
 pub async fn read_trash(sdk: &EventIntegrationTest) -> RepeatedTrashPB {
-  EventBuilder::new(sdk.clone())
-    .event(ReadTrash)
-    .async_send()
-    .await
-    .parse::<RepeatedTrashPB>()
+  let workspace = sdk.get_current_workspace().await;
+  sdk.read_trash(&workspace.id).await
 }
 
 pub async fn restore_app_from_trash(sdk: &EventIntegrationTest, app_id: &str) {
@@ -366,7 +369,10 @@ pub async fn delete_all_trash(sdk: &EventIntegrationTest) {
 }
 
 pub async fn toggle_favorites(sdk: &EventIntegrationTest, view_id: Vec<String>) {
-  let request = RepeatedViewIdPB { items: view_id };
+  let request = RepeatedViewIdPB {
+    items: view_id,
+    cascade: None,
+  };
   EventBuilder::new(sdk.clone())
     .event(ToggleFavorite)
     .payload(request)
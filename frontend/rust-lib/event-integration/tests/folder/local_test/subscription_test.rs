@@ -1,8 +1,18 @@
 use std::time::Duration;
 
+use event_integration::event_builder::EventBuilder;
 use event_integration::EventIntegrationTest;
-use flowy_folder2::entities::{ChildViewUpdatePB, RepeatedViewPB, UpdateViewPayloadPB};
+use flowy_folder2::entities::icon::{
+  RepeatedUpdateViewIconPayloadPB, UpdateViewIconPayloadPB, ViewIconPB, ViewIconTypePB,
+};
+use flowy_folder2::entities::{
+  ChildViewUpdatePB, ImportIdPB, ImportPB, ImportProgressPB, ImportResultPB, ImportTypePB,
+  MoveViewPayloadPB, RepeatedSetArchivedPB, RepeatedTrashPB, RepeatedViewIdPB, RepeatedViewPB,
+  SetArchivedPB, SetTemplatePayloadPB, TrashViewPayloadPB, UpdateViewPayloadPB, ViewLayoutPB,
+  ViewPB,
+};
 use flowy_folder2::notification::FolderNotification;
+use flowy_user::errors::ErrorCode;
 
 use crate::util::receive_with_timeout;
 
@@ -134,3 +144,441 @@ async fn update_view_subscription_test() {
   assert_eq!(expected_view.name, "hello world".to_string());
   assert!(expected_view.is_favorite);
 }
+
+#[tokio::test]
+async fn move_view_subscription_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let workspace = test.get_current_workspace().await;
+  let parent = test
+    .create_view(&workspace.id, "parent view".to_string())
+    .await;
+  let child_1 = test
+    .create_view(&parent.id, "child view 1".to_string())
+    .await;
+  let child_2 = test
+    .create_view(&parent.id, "child view 2".to_string())
+    .await;
+  let rx = test
+    .notification_sender
+    .subscribe::<ChildViewUpdatePB>(&parent.id, FolderNotification::DidUpdateChildViews);
+
+  let cloned_test = test.clone();
+  let move_view_id = child_2.id.clone();
+  test.inner.dispatcher().spawn(async move {
+    EventBuilder::new(cloned_test)
+      .event(flowy_folder2::event_map::FolderEvent::MoveView)
+      .payload(MoveViewPayloadPB {
+        view_id: move_view_id,
+        from: 1,
+        to: 0,
+      })
+      .async_send()
+      .await;
+  });
+
+  let update = receive_with_timeout(rx, Duration::from_secs(30))
+    .await
+    .unwrap();
+  assert_eq!(update.parent_view_id, parent.id);
+  let child_view_ids = update
+    .child_views
+    .into_iter()
+    .map(|view| view.id)
+    .collect::<Vec<_>>();
+  assert_eq!(child_view_ids, vec![child_2.id, child_1.id]);
+}
+
+#[tokio::test]
+async fn update_view_notification_excludes_template_views_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let workspace = test.get_current_workspace().await;
+  let parent = test
+    .create_view(&workspace.id, "parent view".to_string())
+    .await;
+  let template = test
+    .create_view(&parent.id, "template view".to_string())
+    .await;
+  let child_1 = test
+    .create_view(&parent.id, "child view 1".to_string())
+    .await;
+  EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::SetViewTemplate)
+    .payload(SetTemplatePayloadPB {
+      view_id: template.id.clone(),
+      is_template: true,
+    })
+    .async_send()
+    .await;
+  EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::ToggleViewPin)
+    .payload(RepeatedViewIdPB {
+      items: vec![child_1.id.clone()],
+      cascade: None,
+    })
+    .async_send()
+    .await;
+
+  // Wait for the notification that carries the new child view
+  let rx = test
+    .notification_sender
+    .subscribe_with_condition::<ViewPB, _>(&parent.id, |view| {
+      view
+        .child_views
+        .iter()
+        .any(|child_view| child_view.name == "child view 2")
+    });
+  let cloned_test = test.clone();
+  let parent_id = parent.id.clone();
+  test.inner.dispatcher().spawn(async move {
+    cloned_test
+      .create_view(&parent_id, "child view 2".to_string())
+      .await;
+  });
+
+  // The notified parent is the same as the queried one
+  let view = receive_with_timeout(rx, Duration::from_secs(30))
+    .await
+    .unwrap();
+  assert_eq!(
+    view.child_views,
+    test.get_view(&parent.id).await.child_views
+  );
+  assert!(view
+    .child_views
+    .iter()
+    .all(|child_view| child_view.id != template.id));
+  assert_eq!(view.child_views[0].id, child_1.id);
+  assert!(view.child_views[0].is_pinned);
+}
+
+#[tokio::test]
+async fn update_workspace_notification_excludes_archived_views_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let workspace = test.get_current_workspace().await;
+  let archived = test
+    .create_view(&workspace.id, "archived view".to_string())
+    .await;
+  EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::SetViewArchived)
+    .payload(RepeatedSetArchivedPB {
+      items: vec![SetArchivedPB {
+        view_id: archived.id.clone(),
+        is_archived: true,
+      }],
+    })
+    .async_send()
+    .await;
+
+  let rx = test
+    .notification_sender
+    .subscribe_with_condition::<RepeatedViewPB, _>(&workspace.id, |views| {
+      views
+        .items
+        .iter()
+        .any(|view| view.name == "workspace child view")
+    });
+  let cloned_test = test.clone();
+  let workspace_id = workspace.id.clone();
+  test.inner.dispatcher().spawn(async move {
+    cloned_test
+      .create_view(&workspace_id, "workspace child view".to_string())
+      .await;
+  });
+
+  let views = receive_with_timeout(rx, Duration::from_secs(30))
+    .await
+    .unwrap()
+    .items;
+  let view_ids = views.into_iter().map(|view| view.id).collect::<Vec<_>>();
+  assert!(!view_ids.contains(&archived.id));
+  let queried_view_ids = test
+    .get_all_workspace_views()
+    .await
+    .into_iter()
+    .map(|view| view.id)
+    .collect::<Vec<_>>();
+  assert_eq!(view_ids, queried_view_ids);
+}
+
+#[tokio::test]
+async fn trash_notification_contains_reason_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let workspace = test.get_current_workspace().await;
+  let view = test.create_view(&workspace.id, "My view".to_string()).await;
+  let rx = test
+    .notification_sender
+    .subscribe::<RepeatedTrashPB>(&workspace.id, FolderNotification::DidUpdateTrash);
+
+  let cloned_test = test.clone();
+  let view_id = view.id.clone();
+  test.inner.dispatcher().spawn(async move {
+    EventBuilder::new(cloned_test)
+      .event(flowy_folder2::event_map::FolderEvent::TrashViewWithReason)
+      .payload(TrashViewPayloadPB {
+        view_id,
+        reason: Some("Outdated".to_string()),
+      })
+      .async_send()
+      .await;
+  });
+
+  let trash = receive_with_timeout(rx, Duration::from_secs(30))
+    .await
+    .unwrap()
+    .items;
+  assert_eq!(trash.len(), 1);
+  assert_eq!(trash[0].id, view.id);
+  assert_eq!(trash[0].reason, "Outdated");
+}
+
+#[tokio::test]
+async fn update_view_icons_notifies_once_per_parent_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let workspace = test.get_current_workspace().await;
+  let first = test.create_view(&workspace.id, "first".to_string()).await;
+  let second = test.create_view(&workspace.id, "second".to_string()).await;
+  let mut rx = test
+    .notification_sender
+    .subscribe::<ChildViewUpdatePB>(&workspace.id, FolderNotification::DidUpdateChildViews);
+
+  let items = [first.id.clone(), second.id.clone()]
+    .into_iter()
+    .map(|view_id| UpdateViewIconPayloadPB {
+      view_id,
+      icon: Some(ViewIconPB {
+        ty: ViewIconTypePB::Emoji,
+        value: "👍".to_string(),
+      }),
+    })
+    .collect();
+  EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::UpdateViewIconsBatch)
+    .payload(RepeatedUpdateViewIconPayloadPB { items })
+    .async_send()
+    .await;
+
+  // The updated views are only notified by the batch, not by the view change listener.
+  let mut updates = vec![];
+  while let Ok(Some(update)) = tokio::time::timeout(Duration::from_secs(2), rx.recv()).await {
+    if !update.update_child_views.is_empty() {
+      updates.push(update);
+    }
+  }
+  assert_eq!(updates.len(), 1);
+  let updated_ids = updates[0]
+    .update_child_views
+    .iter()
+    .map(|view| view.id.clone())
+    .collect::<Vec<_>>();
+  assert!(updated_ids.contains(&first.id));
+  assert!(updated_ids.contains(&second.id));
+}
+
+#[tokio::test]
+async fn trash_notification_is_debounced_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let workspace = test.get_current_workspace().await;
+  let mut view_ids = vec![];
+  for i in 0..5 {
+    let view = test
+      .create_view(&workspace.id, format!("My {} view", i))
+      .await;
+    view_ids.push(view.id);
+  }
+  let mut rx = test
+    .notification_sender
+    .subscribe::<RepeatedTrashPB>(&workspace.id, FolderNotification::DidUpdateTrash);
+
+  EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::DeleteView)
+    .payload(RepeatedViewIdPB {
+      items: view_ids.clone(),
+      cascade: None,
+    })
+    .async_send()
+    .await;
+
+  // Trashing the views one by one is coalesced into fewer notifications, and the last one
+  // carries all of them.
+  let mut notifications = vec![];
+  while let Ok(Some(trash)) = tokio::time::timeout(Duration::from_secs(2), rx.recv()).await {
+    notifications.push(trash);
+  }
+  assert!(!notifications.is_empty());
+  assert!(notifications.len() < view_ids.len());
+  let trash_ids = notifications
+    .last()
+    .unwrap()
+    .items
+    .iter()
+    .map(|trash| trash.id.clone())
+    .collect::<Vec<_>>();
+  for view_id in &view_ids {
+    assert!(trash_ids.contains(view_id));
+  }
+
+  // Deleting all the trash notifies the empty trash
+  EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::DeleteAllTrash)
+    .async_send()
+    .await;
+  let trash = receive_with_timeout(rx, Duration::from_secs(30))
+    .await
+    .unwrap();
+  assert!(trash.items.is_empty());
+}
+
+#[tokio::test]
+async fn workspace_views_notification_is_coalesced_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let workspace = test.get_current_workspace().await;
+  let mut rx = test
+    .notification_sender
+    .subscribe::<RepeatedViewPB>(&workspace.id, FolderNotification::DidUpdateWorkspaceViews);
+
+  // Each created view updates the workspace views. The notifications sent close together are
+  // merged, and the last one carries the latest views.
+  let (first, second, third) = tokio::join!(
+    test.create_view(&workspace.id, "My 1 view".to_string()),
+    test.create_view(&workspace.id, "My 2 view".to_string()),
+    test.create_view(&workspace.id, "My 3 view".to_string()),
+  );
+  let mut notifications = vec![];
+  while let Ok(Some(views)) = tokio::time::timeout(Duration::from_secs(2), rx.recv()).await {
+    notifications.push(views);
+  }
+  assert!(!notifications.is_empty());
+  assert!(notifications.len() <= 3);
+  let view_ids = notifications
+    .last()
+    .unwrap()
+    .items
+    .iter()
+    .map(|view| view.id.clone())
+    .collect::<Vec<_>>();
+  for view in [first, second, third] {
+    assert!(view_ids.contains(&view.id));
+  }
+}
+
+#[tokio::test]
+async fn import_progress_notification_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let workspace = test.get_current_workspace().await;
+  let export_dir = tempdir::TempDir::new("notion_export").unwrap();
+  for i in 0..24 {
+    std::fs::write(export_dir.path().join(format!("Page {}.md", i)), "Hello").unwrap();
+  }
+  let mut rx = test
+    .notification_sender
+    .subscribe::<ImportProgressPB>(&workspace.id, FolderNotification::ImportProgress);
+
+  let result = EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::ImportData)
+    .payload(ImportPB {
+      parent_view_id: workspace.id.clone(),
+      name: "Imported".to_string(),
+      file_path: Some(export_dir.path().to_string_lossy().to_string()),
+      view_layout: ViewLayoutPB::Document,
+      import_type: ImportTypePB::NotionZip,
+      ..Default::default()
+    })
+    .async_send()
+    .await
+    .parse::<ImportResultPB>();
+  assert_eq!(result.created_count, 25);
+
+  // The progress is sent in batches rather than for every page, and ends with all the pages.
+  let mut progresses = vec![];
+  while let Ok(Some(progress)) = tokio::time::timeout(Duration::from_secs(2), rx.recv()).await {
+    progresses.push(progress);
+  }
+  assert_eq!(progresses.len(), 3);
+  assert!(progresses
+    .windows(2)
+    .all(|pair| pair[0].processed < pair[1].processed));
+  let last = progresses.last().unwrap();
+  assert_eq!(last.processed, 25);
+  assert_eq!(last.total, 25);
+}
+
+#[tokio::test]
+async fn cancel_running_import_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let workspace = test.get_current_workspace().await;
+  let export_dir = tempdir::TempDir::new("notion_export").unwrap();
+  for i in 0..200 {
+    std::fs::write(export_dir.path().join(format!("Page {}.md", i)), "Hello").unwrap();
+  }
+  let payload = ImportPB {
+    parent_view_id: workspace.id.clone(),
+    name: "Cancelled".to_string(),
+    file_path: Some(export_dir.path().to_string_lossy().to_string()),
+    view_layout: ViewLayoutPB::Document,
+    import_type: ImportTypePB::NotionZip,
+    import_id: Some("import id".to_string()),
+    ..Default::default()
+  };
+  let mut rx = test
+    .notification_sender
+    .subscribe::<ImportProgressPB>(&workspace.id, FolderNotification::ImportProgress);
+
+  let import = EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::ImportData)
+    .payload(payload.clone())
+    .async_send();
+  let cancel = async {
+    // The first progress means the import is running.
+    tokio::time::timeout(Duration::from_secs(30), rx.recv())
+      .await
+      .unwrap()
+      .unwrap();
+    let duplicate_error = EventBuilder::new(test.clone())
+      .event(flowy_folder2::event_map::FolderEvent::ImportData)
+      .payload(payload.clone())
+      .async_send()
+      .await
+      .error();
+    let cancel_error = EventBuilder::new(test.clone())
+      .event(flowy_folder2::event_map::FolderEvent::CancelImport)
+      .payload(ImportIdPB {
+        value: "import id".to_string(),
+      })
+      .async_send()
+      .await
+      .error();
+    (duplicate_error, cancel_error)
+  };
+  let (import, (duplicate_error, cancel_error)) = tokio::join!(import, cancel);
+
+  // The import may finish before it's cancelled on a fast machine, then there is nothing to
+  // check about the cancellation.
+  if cancel_error.is_none() {
+    // The import was still running when the import with the same id was sent.
+    assert_eq!(duplicate_error.unwrap().code, ErrorCode::InvalidParams);
+    assert_eq!(import.error().unwrap().code, ErrorCode::ImportCancelled);
+    assert!(!test
+      .get_all_workspace_views()
+      .await
+      .iter()
+      .any(|view| view.name.starts_with("Cancelled")));
+  } else {
+    assert_eq!(cancel_error.unwrap().code, ErrorCode::RecordNotFound);
+  }
+
+  // The id can be used again after the import is done.
+  let small_export_dir = tempdir::TempDir::new("notion_export").unwrap();
+  std::fs::write(small_export_dir.path().join("Notes.md"), "Hello").unwrap();
+  let result = EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::ImportData)
+    .payload(ImportPB {
+      name: "Imported".to_string(),
+      file_path: Some(small_export_dir.path().to_string_lossy().to_string()),
+      ..payload
+    })
+    .async_send()
+    .await
+    .parse::<ImportResultPB>();
+  assert_eq!(result.created_count, 2);
+  assert_eq!(result.view.name, "Imported");
+}
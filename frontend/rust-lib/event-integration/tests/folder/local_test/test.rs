@@ -1,8 +1,11 @@
 use event_integration::event_builder::EventBuilder;
 use event_integration::EventIntegrationTest;
-use flowy_folder2::entities::icon::{UpdateViewIconPayloadPB, ViewIconPB, ViewIconTypePB};
+use flowy_folder2::entities::icon::{
+  RepeatedUpdateViewIconPayloadPB, UpdateViewIconPayloadPB, UpdateViewIconsResultPB, ViewIconPB,
+  ViewIconTypePB,
+};
 use flowy_folder2::entities::*;
-use flowy_user::errors::ErrorCode;
+use flowy_user::errors::{ErrorCode, FlowyError};
 
 #[tokio::test]
 async fn create_workspace_event_test() {
@@ -10,6 +13,7 @@ async fn create_workspace_event_test() {
   let request = CreateWorkspacePayloadPB {
     name: "my second workspace".to_owned(),
     desc: "".to_owned(),
+    icon: None,
   };
   let resp = EventBuilder::new(test)
     .event(flowy_folder2::event_map::FolderEvent::CreateWorkspace)
@@ -21,6 +25,132 @@ async fn create_workspace_event_test() {
   assert_eq!(resp.code, ErrorCode::NotSupportYet);
 }
 
+#[tokio::test]
+async fn update_workspace_event_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+  let workspace = EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::UpdateWorkspace)
+    .payload(UpdateWorkspacePayloadPB {
+      id: current_workspace.id.clone(),
+      name: Some("My renamed workspace".to_string()),
+      desc: None,
+      icon: Some(ViewIconPB {
+        ty: ViewIconTypePB::Emoji,
+        value: "👍".to_string(),
+      }),
+    })
+    .async_send()
+    .await
+    .parse::<WorkspacePB>();
+  assert_eq!(workspace.name, "My renamed workspace");
+  assert_eq!(workspace.icon.unwrap().value, "👍");
+  assert_eq!(
+    test.get_current_workspace().await.name,
+    "My renamed workspace"
+  );
+
+  let error = EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::UpdateWorkspace)
+    .payload(UpdateWorkspacePayloadPB {
+      id: current_workspace.id,
+      name: Some("".to_string()),
+      desc: None,
+      icon: None,
+    })
+    .async_send()
+    .await
+    .error()
+    .unwrap();
+  assert_eq!(error.code, ErrorCode::WorkspaceNameInvalid);
+}
+
+#[tokio::test]
+async fn update_workspace_icon_event_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+  assert!(current_workspace.icon.is_none());
+
+  let update_icon = |icon: Option<ViewIconPB>| {
+    let test = test.clone();
+    let workspace_id = current_workspace.id.clone();
+    async move {
+      EventBuilder::new(test)
+        .event(flowy_folder2::event_map::FolderEvent::UpdateWorkspace)
+        .payload(UpdateWorkspacePayloadPB {
+          id: workspace_id,
+          name: None,
+          desc: None,
+          icon,
+        })
+        .async_send()
+        .await
+        .parse::<WorkspacePB>()
+    }
+  };
+  let icon = ViewIconPB {
+    ty: ViewIconTypePB::Emoji,
+    value: "🏠".to_string(),
+  };
+  update_icon(Some(icon.clone())).await;
+  assert_eq!(test.get_current_workspace().await.icon, Some(icon.clone()));
+
+  // The icon remains unchanged if it's not given
+  update_icon(None).await;
+  assert_eq!(test.get_current_workspace().await.icon, Some(icon));
+
+  // The emoji is stored in its fully-qualified form
+  let workspace = update_icon(Some(ViewIconPB {
+    ty: ViewIconTypePB::Emoji,
+    value: "\u{2764}".to_string(),
+  }))
+  .await;
+  assert_eq!(workspace.icon.unwrap().value, "\u{2764}\u{FE0F}");
+
+  // The empty icon removes the icon
+  let workspace = update_icon(Some(ViewIconPB {
+    ty: ViewIconTypePB::Emoji,
+    value: "".to_string(),
+  }))
+  .await;
+  assert!(workspace.icon.is_none());
+  assert!(test.get_current_workspace().await.icon.is_none());
+}
+
+#[tokio::test]
+async fn delete_last_workspace_event_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+  let error = EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::DeleteWorkspace)
+    .payload(WorkspaceIdPB {
+      value: current_workspace.id.clone(),
+      preload_depth: None,
+    })
+    .async_send()
+    .await
+    .error()
+    .unwrap();
+  assert_eq!(error.code, ErrorCode::CannotDeleteLastWorkspace);
+  assert_eq!(test.get_current_workspace().await.id, current_workspace.id);
+}
+
+#[tokio::test]
+async fn read_all_workspaces_event_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+  let workspaces = EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::ReadAllWorkspaces)
+    .async_send()
+    .await
+    .parse::<RepeatedWorkspacePB>()
+    .items;
+  assert_eq!(workspaces.len(), 1);
+  assert_eq!(workspaces[0].id, current_workspace.id);
+  assert_eq!(workspaces[0].name, current_workspace.name);
+  assert!(workspaces[0].views.is_empty());
+}
+
 // #[tokio::test]
 // async fn open_workspace_event_test() {
 //   let test = EventIntegrationTest::new_with_guest_user().await;
@@ -85,458 +215,5542 @@ async fn update_view_event_with_name_test() {
 }
 
 #[tokio::test]
-async fn update_view_icon_event_test() {
+async fn update_view_description_event_test() {
   let test = EventIntegrationTest::new_with_guest_user().await;
   let current_workspace = test.get_current_workspace().await;
   let view = test
     .create_view(&current_workspace.id, "My first view".to_string())
     .await;
+  assert!(view.description.is_empty());
 
-  let new_icon = ViewIconPB {
-    ty: ViewIconTypePB::Emoji,
-    value: "👍".to_owned(),
-  };
   let error = test
-    .update_view_icon(UpdateViewIconPayloadPB {
+    .update_view(UpdateViewPayloadPB {
       view_id: view.id.clone(),
-      icon: Some(new_icon.clone()),
+      desc: Some("The notes of the weekly meeting".to_string()),
+      ..Default::default()
     })
     .await;
   assert!(error.is_none());
-
   let view = test.get_view(&view.id).await;
-  assert_eq!(view.icon, Some(new_icon));
+  assert_eq!(view.description, "The notes of the weekly meeting");
+
+  let error = test
+    .update_view(UpdateViewPayloadPB {
+      view_id: view.id.clone(),
+      desc: Some("a".repeat(1001)),
+      ..Default::default()
+    })
+    .await
+    .unwrap();
+  assert_eq!(error.code, ErrorCode::ViewDescTooLong);
 }
 
 #[tokio::test]
-async fn delete_view_event_test() {
+async fn update_view_cover_event_test() {
   let test = EventIntegrationTest::new_with_guest_user().await;
   let current_workspace = test.get_current_workspace().await;
   let view = test
     .create_view(&current_workspace.id, "My first view".to_string())
     .await;
-  test.delete_view(&view.id).await;
+  assert!(view.cover.is_none());
 
-  // Try the read the view
-  let payload = ViewIdPB {
-    value: view.id.clone(),
+  let cover = ViewCoverPB {
+    ty: ViewCoverTypePB::Image,
+    value: "https://appflowy.io/cover.png".to_string(),
   };
-  let error = EventBuilder::new(test.clone())
-    .event(flowy_folder2::event_map::FolderEvent::ReadView)
-    .payload(payload)
+  update_view_cover(&test, &view.id, Some(cover.clone())).await;
+  assert_eq!(test.get_view(&view.id).await.cover, Some(cover));
+  // The cover is stored in the view of the folder, so it's synced with the folder.
+  assert_eq!(
+    read_view_extra_json(&test, &view.id)["cover"],
+    serde_json::json!({ "ty": "Image", "value": "https://appflowy.io/cover.png" })
+  );
+
+  update_view_cover(
+    &test,
+    &view.id,
+    Some(ViewCoverPB {
+      ty: ViewCoverTypePB::Color,
+      value: "".to_string(),
+    }),
+  )
+  .await;
+  assert!(test.get_view(&view.id).await.cover.is_none());
+  assert!(read_view_extra_json(&test, &view.id)["cover"].is_null());
+}
+
+async fn update_view_cover(test: &EventIntegrationTest, view_id: &str, cover: Option<ViewCoverPB>) {
+  EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::UpdateViewCover)
+    .payload(UpdateViewCoverPayloadPB {
+      view_id: view_id.to_string(),
+      cover,
+    })
     .async_send()
-    .await
-    .error()
-    .unwrap();
-  assert_eq!(error.code, ErrorCode::RecordNotFound);
+    .await;
+}
+
+/// Reads the extra data of the view that is stored in the map of the view in the folder collab.
+fn read_view_extra_json(test: &EventIntegrationTest, view_id: &str) -> serde_json::Value {
+  let folder_json = {
+    let folder = test.folder_manager.get_mutex_folder().lock();
+    folder.as_ref().unwrap().to_json_value()
+  };
+  folder_json["views"][view_id]["extra"]
+    .as_str()
+    .map(|view_extra| serde_json::from_str(view_extra).unwrap())
+    .unwrap_or_default()
+}
+
+/// Reads the part of the workspace extra data that is stored under the `key` of the extra map of
+/// the folder collab.
+fn read_folder_extra_json(test: &EventIntegrationTest, key: &str) -> serde_json::Value {
+  let folder_json = {
+    let folder = test.folder_manager.get_mutex_folder().lock();
+    folder.as_ref().unwrap().to_json_value()
+  };
+  folder_json["extra"][key]
+    .as_str()
+    .map(|value| serde_json::from_str(value).unwrap())
+    .unwrap_or_default()
 }
 
 #[tokio::test]
-async fn put_back_trash_event_test() {
+async fn lock_view_event_test() {
   let test = EventIntegrationTest::new_with_guest_user().await;
   let current_workspace = test.get_current_workspace().await;
   let view = test
     .create_view(&current_workspace.id, "My first view".to_string())
     .await;
-  test.delete_view(&view.id).await;
+  set_view_locked(&test, &view.id, true).await;
+  assert!(test.get_view(&view.id).await.locked);
+
+  let error = test
+    .update_view(UpdateViewPayloadPB {
+      view_id: view.id.clone(),
+      name: Some("My second view".to_string()),
+      ..Default::default()
+    })
+    .await
+    .unwrap();
+  assert_eq!(error.code, ErrorCode::ViewIsLocked);
 
-  // After delete view, the view will be moved to trash
-  let payload = ViewIdPB {
-    value: view.id.clone(),
-  };
   let error = EventBuilder::new(test.clone())
-    .event(flowy_folder2::event_map::FolderEvent::ReadView)
-    .payload(payload)
+    .event(flowy_folder2::event_map::FolderEvent::DeleteView)
+    .payload(RepeatedViewIdPB {
+      items: vec![view.id.clone()],
+      cascade: None,
+    })
     .async_send()
     .await
     .error()
     .unwrap();
-  assert_eq!(error.code, ErrorCode::RecordNotFound);
+  assert_eq!(error.code, ErrorCode::ViewIsLocked);
+  assert!(test.get_view(&view.id).await.locked);
 
-  let payload = TrashIdPB {
-    id: view.id.clone(),
-  };
-  EventBuilder::new(test.clone())
-    .event(flowy_folder2::event_map::FolderEvent::PutbackTrash)
-    .payload(payload)
-    .async_send()
-    .await;
+  let error = test
+    .update_view_icon(UpdateViewIconPayloadPB {
+      view_id: view.id.clone(),
+      icon: Some(ViewIconPB {
+        ty: ViewIconTypePB::Emoji,
+        value: "👍".to_string(),
+      }),
+    })
+    .await
+    .unwrap();
+  assert_eq!(error.code, ErrorCode::ViewIsLocked);
 
-  let payload = ViewIdPB {
-    value: view.id.clone(),
-  };
   let error = EventBuilder::new(test.clone())
-    .event(flowy_folder2::event_map::FolderEvent::ReadView)
-    .payload(payload)
+    .event(flowy_folder2::event_map::FolderEvent::UpdateViewCover)
+    .payload(UpdateViewCoverPayloadPB {
+      view_id: view.id.clone(),
+      cover: Some(ViewCoverPB {
+        ty: ViewCoverTypePB::Color,
+        value: "#FF0000".to_string(),
+      }),
+    })
     .async_send()
     .await
-    .error();
-  assert!(error.is_none());
-}
+    .error()
+    .unwrap();
+  assert_eq!(error.code, ErrorCode::ViewIsLocked);
+  assert!(test.get_view(&view.id).await.cover.is_none());
 
-#[tokio::test]
-async fn delete_view_permanently_event_test() {
-  let test = EventIntegrationTest::new_with_guest_user().await;
-  let current_workspace = test.get_current_workspace().await;
-  let view = test
-    .create_view(&current_workspace.id, "My first view".to_string())
-    .await;
-  let payload = RepeatedViewIdPB {
-    items: vec![view.id.clone()],
-  };
+  let error = EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::AddViewTags)
+    .payload(ViewTagsPayloadPB {
+      view_id: view.id.clone(),
+      tags: vec!["Work".to_string()],
+    })
+    .async_send()
+    .await
+    .error()
+    .unwrap();
+  assert_eq!(error.code, ErrorCode::ViewIsLocked);
+  assert!(test.get_view(&view.id).await.tags.is_empty());
 
-  // delete the view. the view will be moved to trash
-  EventBuilder::new(test.clone())
-    .event(flowy_folder2::event_map::FolderEvent::DeleteView)
-    .payload(payload)
+  let error = EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::SetViewMeta)
+    .payload(ViewMetaPayloadPB {
+      view_id: view.id.clone(),
+      key: "plugin.key".to_string(),
+      value: "value".to_string(),
+    })
     .async_send()
-    .await;
+    .await
+    .error()
+    .unwrap();
+  assert_eq!(error.code, ErrorCode::ViewIsLocked);
 
-  let trash = EventBuilder::new(test.clone())
-    .event(flowy_folder2::event_map::FolderEvent::ReadTrash)
+  let error = EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::SetViewTemplate)
+    .payload(SetTemplatePayloadPB {
+      view_id: view.id.clone(),
+      is_template: true,
+    })
     .async_send()
     .await
-    .parse::<flowy_folder2::entities::RepeatedTrashPB>()
-    .items;
-  assert_eq!(trash.len(), 1);
-  assert_eq!(trash[0].id, view.id);
+    .error()
+    .unwrap();
+  assert_eq!(error.code, ErrorCode::ViewIsLocked);
 
-  // delete the view from trash
-  let payload = RepeatedTrashIdPB {
-    items: vec![TrashIdPB {
-      id: view.id.clone(),
-    }],
-  };
-  EventBuilder::new(test.clone())
-    .event(flowy_folder2::event_map::FolderEvent::DeleteTrash)
-    .payload(payload)
+  // The locked view is kept in the view tree
+  let error = EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::SetViewArchived)
+    .payload(RepeatedSetArchivedPB {
+      items: vec![SetArchivedPB {
+        view_id: view.id.clone(),
+        is_archived: true,
+      }],
+    })
     .async_send()
-    .await;
+    .await
+    .error()
+    .unwrap();
+  assert_eq!(error.code, ErrorCode::ViewIsLocked);
+  assert!(get_archived_views(&test).await.is_empty());
 
-  // After delete the last view, the trash should be empty
-  let trash = EventBuilder::new(test.clone())
-    .event(flowy_folder2::event_map::FolderEvent::ReadTrash)
+  // The unlocked view can't be deleted permanently while any of its descendants is locked
+  let child_view = test
+    .create_view(&view.id, "My child view".to_string())
+    .await;
+  set_view_locked(&test, &child_view.id, true).await;
+  set_view_locked(&test, &view.id, false).await;
+  let error = EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::DeleteViewPermanently)
+    .payload(RepeatedViewIdPB {
+      items: vec![view.id.clone()],
+      cascade: None,
+    })
     .async_send()
     .await
-    .parse::<flowy_folder2::entities::RepeatedTrashPB>()
-    .items;
-  assert!(trash.is_empty());
+    .error()
+    .unwrap();
+  assert_eq!(error.code, ErrorCode::ViewIsLocked);
+  assert_eq!(test.get_view(&child_view.id).await.name, "My child view");
+  set_view_locked(&test, &child_view.id, false).await;
+
+  let error = test
+    .update_view(UpdateViewPayloadPB {
+      view_id: view.id.clone(),
+      name: Some("My second view".to_string()),
+      ..Default::default()
+    })
+    .await;
+  assert!(error.is_none());
+  let view = test.get_view(&view.id).await;
+  assert!(!view.locked);
+  assert_eq!(view.name, "My second view");
 }
 
 #[tokio::test]
-async fn delete_all_trash_test() {
+async fn toggle_view_pin_event_test() {
   let test = EventIntegrationTest::new_with_guest_user().await;
   let current_workspace = test.get_current_workspace().await;
+  let parent = test
+    .create_view(&current_workspace.id, "My view".to_string())
+    .await;
+  let child_1 = test.create_view(&parent.id, "My 1 view".to_string()).await;
+  let child_2 = test.create_view(&parent.id, "My 2 view".to_string()).await;
+  let child_3 = test.create_view(&parent.id, "My 3 view".to_string()).await;
+  toggle_view_pin(&test, vec![child_3.id.clone(), child_2.id.clone()]).await;
 
-  for i in 0..3 {
-    let view = test
-      .create_view(&current_workspace.id, format!("My {} view", i))
-      .await;
-    let payload = RepeatedViewIdPB {
-      items: vec![view.id.clone()],
-    };
-    // delete the view. the view will be moved to trash
-    EventBuilder::new(test.clone())
-      .event(flowy_folder2::event_map::FolderEvent::DeleteView)
-      .payload(payload)
-      .async_send()
-      .await;
-  }
+  let child_views = test.get_view(&parent.id).await.child_views;
+  let child_view_ids = child_views
+    .iter()
+    .map(|view| view.id.clone())
+    .collect::<Vec<_>>();
+  assert_eq!(
+    child_view_ids,
+    vec![child_2.id.clone(), child_3.id.clone(), child_1.id.clone()]
+  );
+  assert!(child_views[0].is_pinned);
+  assert!(!child_views[2].is_pinned);
+  assert!(!child_views[0].is_favorite);
 
-  let trash = EventBuilder::new(test.clone())
-    .event(flowy_folder2::event_map::FolderEvent::ReadTrash)
-    .async_send()
+  toggle_view_pin(&test, vec![child_2.id.clone()]).await;
+  let child_view_ids = test
+    .get_view(&parent.id)
     .await
-    .parse::<flowy_folder2::entities::RepeatedTrashPB>()
-    .items;
-  assert_eq!(trash.len(), 3);
+    .child_views
+    .into_iter()
+    .map(|view| view.id)
+    .collect::<Vec<_>>();
+  assert_eq!(child_view_ids, vec![child_3.id, child_1.id, child_2.id]);
+}
 
-  // Delete all the trash
+async fn toggle_view_pin(test: &EventIntegrationTest, view_ids: Vec<String>) {
   EventBuilder::new(test.clone())
-    .event(flowy_folder2::event_map::FolderEvent::DeleteAllTrash)
+    .event(flowy_folder2::event_map::FolderEvent::ToggleViewPin)
+    .payload(RepeatedViewIdPB {
+      items: view_ids,
+      cascade: None,
+    })
     .async_send()
     .await;
-
-  // After delete the last view, the trash should be empty
-  let trash = EventBuilder::new(test.clone())
-    .event(flowy_folder2::event_map::FolderEvent::ReadTrash)
-    .async_send()
-    .await
-    .parse::<flowy_folder2::entities::RepeatedTrashPB>()
-    .items;
-  assert!(trash.is_empty());
 }
 
 #[tokio::test]
-async fn multiple_hierarchy_view_test() {
+async fn get_view_ancestors_event_test() {
   let test = EventIntegrationTest::new_with_guest_user().await;
   let current_workspace = test.get_current_workspace().await;
-  for i in 1..4 {
-    let parent = test
-      .create_view(&current_workspace.id, format!("My {} view", i))
-      .await;
-    for j in 1..3 {
-      let child = test
-        .create_view(&parent.id, format!("My {}-{} view", i, j))
-        .await;
-      for k in 1..2 {
-        let _sub_child = test
-          .create_view(&child.id, format!("My {}-{}-{} view", i, j, k))
-          .await;
-      }
-    }
-  }
+  let parent = test
+    .create_view(&current_workspace.id, "My view".to_string())
+    .await;
+  let child = test.create_view(&parent.id, "My 1 view".to_string()).await;
+  let grandchild = test.create_view(&child.id, "My 1-1 view".to_string()).await;
 
-  let mut views = test.get_all_workspace_views().await;
-  // There will be one default view when AppFlowy is initialized. So there will be 4 views in total
-  assert_eq!(views.len(), 4);
-  views.remove(0);
+  let ancestors = get_view_ancestors(&test, &grandchild.id).await;
+  assert_eq!(ancestors.len(), 2);
+  assert_eq!(ancestors[0].id, parent.id);
+  assert_eq!(ancestors[1].id, child.id);
+  assert!(ancestors.iter().all(|view| !view.is_trashed));
+  assert!(get_view_ancestors(&test, &parent.id).await.is_empty());
 
-  // workspace
-  //   - view1
-  //     - view1-1
-  //       - view1-1-1
-  //     - view1-2
-  //       - view1-2-1
-  //   - view2
-  //     - view2-1
-  //       - view2-1-1
-  //     - view2-2
-  //       - view2-2-1
-  //   - view3
-  //     - view3-1
-  //       - view3-1-1
-  //     - view3-2
-  //       - view3-2-1
-  assert_eq!(views[0].name, "My 1 view");
-  assert_eq!(views[1].name, "My 2 view");
-  assert_eq!(views[2].name, "My 3 view");
+  test.delete_view(&parent.id).await;
+  let ancestors = get_view_ancestors(&test, &grandchild.id).await;
+  assert_eq!(ancestors.len(), 2);
+  assert!(ancestors.iter().all(|view| view.is_trashed));
+}
 
-  assert_eq!(views[0].child_views.len(), 2);
-  // By default only the first level of child views will be loaded
-  assert!(views[0].child_views[0].child_views.is_empty());
+async fn get_view_ancestors(test: &EventIntegrationTest, view_id: &str) -> Vec<ViewPB> {
+  EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::GetViewAncestors)
+    .payload(ViewIdPB {
+      value: view_id.to_string(),
+    })
+    .async_send()
+    .await
+    .parse::<RepeatedViewPB>()
+    .items
+}
 
-  for (i, view) in views.into_iter().enumerate() {
-    for (j, child_view) in view.child_views.into_iter().enumerate() {
-      let payload = ViewIdPB {
-        value: child_view.id.clone(),
-      };
+#[tokio::test]
+async fn move_views_event_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+  let parent = test
+    .create_view(&current_workspace.id, "My view".to_string())
+    .await;
+  let child = test.create_view(&parent.id, "My 1 view".to_string()).await;
+  let view_1 = test
+    .create_view(&current_workspace.id, "My 2 view".to_string())
+    .await;
+  let view_2 = test
+    .create_view(&current_workspace.id, "My 3 view".to_string())
+    .await;
 
-      let child = EventBuilder::new(test.clone())
-        .event(flowy_folder2::event_map::FolderEvent::ReadView)
-        .payload(payload)
-        .async_send()
-        .await
-        .parse::<flowy_folder2::entities::ViewPB>();
-      assert_eq!(child.name, format!("My {}-{} view", i + 1, j + 1));
-      assert_eq!(child.child_views.len(), 1);
-      // By default only the first level of child views will be loaded
-      assert!(child.child_views[0].child_views.is_empty());
+  let error = move_views(
+    &test,
+    vec![view_2.id.clone(), view_1.id.clone()],
+    &parent.id,
+  )
+  .await;
+  assert!(error.is_none());
+  let child_view_ids = test
+    .get_view(&parent.id)
+    .await
+    .child_views
+    .into_iter()
+    .map(|view| view.id)
+    .collect::<Vec<_>>();
+  assert_eq!(
+    child_view_ids,
+    vec![child.id.clone(), view_2.id.clone(), view_1.id.clone()]
+  );
 
-      for (k, _child_view) in child_view.child_views.into_iter().enumerate() {
-        // Get the last level view
-        let sub_child = test.get_view(&child.id).await;
-        assert_eq!(child.name, format!("My {}-{}-{} view", i + 1, j + 1, k + 1));
-        assert!(sub_child.child_views.is_empty());
-      }
-    }
-  }
+  // The view can't be moved into its descendant.
+  let error = move_views(&test, vec![parent.id.clone()], &view_1.id)
+    .await
+    .unwrap();
+  assert_eq!(error.code, ErrorCode::InvalidParams);
+  assert_eq!(test.get_view(&view_1.id).await.parent_view_id, parent.id);
+}
+
+async fn move_views(
+  test: &EventIntegrationTest,
+  view_ids: Vec<String>,
+  new_parent_id: &str,
+) -> Option<FlowyError> {
+  EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::MoveViews)
+    .payload(MoveViewsPayloadPB {
+      view_ids,
+      new_parent_id: new_parent_id.to_string(),
+    })
+    .async_send()
+    .await
+    .error()
 }
 
 #[tokio::test]
-async fn move_view_event_test() {
+async fn recent_views_event_test() {
   let test = EventIntegrationTest::new_with_guest_user().await;
   let current_workspace = test.get_current_workspace().await;
-  for i in 1..4 {
-    let parent = test
-      .create_view(&current_workspace.id, format!("My {} view", i))
+  let view_1 = test
+    .create_view(&current_workspace.id, "My view".to_string())
+    .await;
+  let view_2 = test
+    .create_view(&current_workspace.id, "My 1 view".to_string())
+    .await;
+  for view_id in [&view_1.id, &view_2.id, &view_1.id] {
+    EventBuilder::new(test.clone())
+      .event(flowy_folder2::event_map::FolderEvent::SetLatestView)
+      .payload(ViewIdPB {
+        value: view_id.clone(),
+      })
+      .async_send()
       .await;
-    for j in 1..3 {
-      let _ = test
-        .create_view(&parent.id, format!("My {}-{} view", i, j))
-        .await;
-    }
   }
-  let views = test.get_all_workspace_views().await;
-  // There will be one default view when AppFlowy is initialized. So there will be 4 views in total
-  assert_eq!(views.len(), 4);
-  assert_eq!(views[1].name, "My 1 view");
-  assert_eq!(views[2].name, "My 2 view");
-  assert_eq!(views[3].name, "My 3 view");
 
-  let payload = MoveViewPayloadPB {
-    view_id: views[1].id.clone(),
-    from: 1,
-    to: 2,
-  };
-  let _ = EventBuilder::new(test.clone())
-    .event(flowy_folder2::event_map::FolderEvent::MoveView)
-    .payload(payload)
+  let recent_view_ids = get_recent_views(&test)
+    .await
+    .into_iter()
+    .map(|view| view.id)
+    .collect::<Vec<_>>();
+  assert_eq!(recent_view_ids, vec![view_1.id.clone(), view_2.id.clone()]);
+  // The recent views are stored in the folder, so they're synced with the folder.
+  assert_eq!(
+    read_folder_extra_json(&test, "recent_view_ids"),
+    serde_json::json!([view_1.id, view_2.id])
+  );
+
+  test.delete_view(&view_2.id).await;
+  let recent_views = get_recent_views(&test).await;
+  assert_eq!(recent_views.len(), 1);
+  assert_eq!(recent_views[0].id, view_1.id);
+
+  EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::ClearRecentViews)
     .async_send()
     .await;
+  assert!(get_recent_views(&test).await.is_empty());
+}
 
-  let views = test.get_all_workspace_views().await;
-  assert_eq!(views[1].name, "My 2 view");
-  assert_eq!(views[2].name, "My 1 view");
-  assert_eq!(views[3].name, "My 3 view");
+async fn get_recent_views(test: &EventIntegrationTest) -> Vec<ViewPB> {
+  EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::GetRecentViews)
+    .async_send()
+    .await
+    .parse::<RepeatedViewPB>()
+    .items
 }
 
 #[tokio::test]
-async fn move_view_event_after_delete_view_test() {
+async fn export_workspace_event_test() {
   let test = EventIntegrationTest::new_with_guest_user().await;
   let current_workspace = test.get_current_workspace().await;
-  for i in 1..6 {
-    let _ = test
-      .create_view(&current_workspace.id, format!("My {} view", i))
-      .await;
-  }
-  let views = test.get_all_workspace_views().await;
-  assert_eq!(views[1].name, "My 1 view");
-  assert_eq!(views[2].name, "My 2 view");
-  assert_eq!(views[3].name, "My 3 view");
-  assert_eq!(views[4].name, "My 4 view");
-  assert_eq!(views[5].name, "My 5 view");
-  test.delete_view(&views[3].id).await;
-
-  // There will be one default view when AppFlowy is initialized. So there will be 4 views in total
-  let views = test.get_all_workspace_views().await;
-  assert_eq!(views[1].name, "My 1 view");
-  assert_eq!(views[2].name, "My 2 view");
-  assert_eq!(views[3].name, "My 4 view");
-  assert_eq!(views[4].name, "My 5 view");
+  let parent = test
+    .create_view(&current_workspace.id, "My view".to_string())
+    .await;
+  let child = test.create_view(&parent.id, "My 1 view".to_string()).await;
 
-  let payload = MoveViewPayloadPB {
-    view_id: views[1].id.clone(),
-    from: 1,
-    to: 3,
-  };
-  let _ = EventBuilder::new(test.clone())
-    .event(flowy_folder2::event_map::FolderEvent::MoveView)
-    .payload(payload)
+  let file_path = EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::ExportWorkspace)
+    .payload(ExportWorkspacePayloadPB {
+      workspace_id: current_workspace.id.clone(),
+      format: ExportWorkspaceFormatPB::Zip,
+    })
     .async_send()
-    .await;
+    .await
+    .parse::<ExportWorkspaceDataPB>()
+    .file_path;
 
-  let views = test.get_all_workspace_views().await;
-  assert_eq!(views[1].name, "My 2 view");
-  assert_eq!(views[2].name, "My 4 view");
-  assert_eq!(views[3].name, "My 1 view");
-  assert_eq!(views[4].name, "My 5 view");
+  let file = std::fs::File::open(&file_path).unwrap();
+  let mut archive = zip::ZipArchive::new(file).unwrap();
+  let manifest: serde_json::Value =
+    serde_json::from_reader(archive.by_name("manifest.json").unwrap()).unwrap();
+  assert_eq!(manifest["workspace_id"], current_workspace.id.as_str());
+  let view_ids = manifest["views"]
+    .as_array()
+    .unwrap()
+    .iter()
+    .map(|view| view["id"].as_str().unwrap().to_string())
+    .collect::<Vec<_>>();
+  let parent_index = view_ids.iter().position(|id| id == &parent.id).unwrap();
+  let child_index = view_ids.iter().position(|id| id == &child.id).unwrap();
+  assert!(parent_index < child_index);
+  assert!(archive.by_name(&format!("views/{}", child.id)).is_ok());
+  std::fs::remove_file(file_path).unwrap();
 }
 
 #[tokio::test]
-async fn move_view_event_after_delete_view_test2() {
+async fn import_exported_workspace_event_test() {
   let test = EventIntegrationTest::new_with_guest_user().await;
   let current_workspace = test.get_current_workspace().await;
   let parent = test
     .create_view(&current_workspace.id, "My view".to_string())
     .await;
+  let child_1 = test.create_view(&parent.id, "My 1 view".to_string()).await;
+  let child_2 = test.create_view(&parent.id, "My 2 view".to_string()).await;
+  set_view_meta(&test, &parent.id, "plugin.key", "value").await;
+  EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::ToggleFavorite)
+    .payload(RepeatedViewIdPB {
+      items: vec![child_2.id.clone()],
+      cascade: None,
+    })
+    .async_send()
+    .await;
+  let file_path = EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::ExportWorkspace)
+    .payload(ExportWorkspacePayloadPB {
+      workspace_id: current_workspace.id.clone(),
+      format: ExportWorkspaceFormatPB::Zip,
+    })
+    .async_send()
+    .await
+    .parse::<ExportWorkspaceDataPB>()
+    .file_path;
+  let data = std::fs::read(&file_path).unwrap();
+  std::fs::remove_file(file_path).unwrap();
 
-  for j in 1..6 {
-    let _ = test
-      .create_view(&parent.id, format!("My 1-{} view", j))
-      .await;
-  }
+  // Import into the same workspace, so all the views are imported with new ids.
+  let imported_views = EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::ImportWorkspace)
+    .payload(ImportWorkspacePayloadPB {
+      target_workspace_id: "".to_string(),
+      data,
+    })
+    .async_send()
+    .await
+    .parse::<RepeatedViewPB>()
+    .items;
+  let imported_parent = imported_views
+    .iter()
+    .find(|view| view.name == "My view")
+    .unwrap();
+  assert_ne!(imported_parent.id, parent.id);
+  assert_eq!(imported_parent.parent_view_id, current_workspace.id);
 
-  let views = test.get_view(&parent.id).await.child_views;
-  assert_eq!(views.len(), 5);
-  assert_eq!(views[0].name, "My 1-1 view");
-  assert_eq!(views[1].name, "My 1-2 view");
-  assert_eq!(views[2].name, "My 1-3 view");
-  assert_eq!(views[3].name, "My 1-4 view");
-  assert_eq!(views[4].name, "My 1-5 view");
-  test.delete_view(&views[2].id).await;
+  let imported_children = test.get_view(&imported_parent.id).await.child_views;
+  assert_eq!(imported_children.len(), 2);
+  assert_eq!(imported_children[0].name, "My 1 view");
+  assert_eq!(imported_children[1].name, "My 2 view");
+  assert_ne!(imported_children[0].id, child_1.id);
+  assert_ne!(imported_children[1].id, child_2.id);
 
-  let payload = MoveViewPayloadPB {
-    view_id: views[0].id.clone(),
-    from: 0,
-    to: 2,
-  };
-  let _ = EventBuilder::new(test.clone())
-    .event(flowy_folder2::event_map::FolderEvent::MoveView)
-    .payload(payload)
-    .async_send()
-    .await;
+  // The favorites and the extra data are restored on the new ids.
+  assert!(!imported_children[0].is_favorite);
+  assert!(imported_children[1].is_favorite);
+  assert_eq!(
+    get_view_meta(&test, &imported_parent.id, "plugin.key").await,
+    "value"
+  );
 
-  let views = test.get_view(&parent.id).await.child_views;
-  assert_eq!(views[0].name, "My 1-2 view");
-  assert_eq!(views[1].name, "My 1-4 view");
-  assert_eq!(views[2].name, "My 1-1 view");
-  assert_eq!(views[3].name, "My 1-5 view");
+  // The existing views are kept.
+  assert_eq!(test.get_view(&parent.id).await.child_views.len(), 2);
 }
 
 #[tokio::test]
-async fn create_parent_view_with_invalid_name() {
-  for (name, code) in invalid_workspace_name_test_case() {
-    let sdk = EventIntegrationTest::new().await;
-    let request = CreateWorkspacePayloadPB {
-      name,
-      desc: "".to_owned(),
-    };
-    assert_eq!(
-      EventBuilder::new(sdk)
-        .event(flowy_folder2::event_map::FolderEvent::CreateWorkspace)
-        .payload(request)
-        .async_send()
-        .await
-        .error()
-        .unwrap()
-        .code,
-      code
-    )
-  }
+async fn get_workspace_stats_event_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+  let before = get_workspace_stats(&test, &current_workspace.id).await;
+
+  let view_1 = test
+    .create_view(&current_workspace.id, "My view".to_string())
+    .await;
+  let view_2 = test.create_view(&view_1.id, "My 1 view".to_string()).await;
+  let view_3 = test
+    .create_view(&current_workspace.id, "My 2 view".to_string())
+    .await;
+  set_favorites(&test, vec![(view_2.id.clone(), true)]).await;
+  test.delete_view(&view_3.id).await;
+
+  let after = get_workspace_stats(&test, &current_workspace.id).await;
+  assert_eq!(after.total_views, before.total_views + 3);
+  assert_eq!(after.trashed_views, before.trashed_views + 1);
+  assert_eq!(after.favorite_views, before.favorite_views + 1);
+  let document_count = |stats: &WorkspaceStatsPB| {
+    stats
+      .views_by_layout
+      .get("Document")
+      .cloned()
+      .unwrap_or_default()
+  };
+  assert_eq!(document_count(&after), document_count(&before) + 3);
 }
 
-fn invalid_workspace_name_test_case() -> Vec<(String, ErrorCode)> {
-  vec![
-    ("".to_owned(), ErrorCode::WorkspaceNameInvalid),
-    ("1234".repeat(100), ErrorCode::WorkspaceNameTooLong),
-  ]
+async fn get_workspace_stats(test: &EventIntegrationTest, workspace_id: &str) -> WorkspaceStatsPB {
+  EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::GetWorkspaceStats)
+    .payload(WorkspaceIdPB {
+      value: workspace_id.to_string(),
+      preload_depth: None,
+    })
+    .async_send()
+    .await
+    .parse::<WorkspaceStatsPB>()
 }
 
 #[tokio::test]
-async fn move_view_across_parent_test() {
+async fn get_views_by_layout_event_test() {
   let test = EventIntegrationTest::new_with_guest_user().await;
   let current_workspace = test.get_current_workspace().await;
-  let parent_1 = test
-    .create_view(&current_workspace.id, "My view 1".to_string())
+  let document = test
+    .create_view(&current_workspace.id, "My document".to_string())
     .await;
-  let parent_2 = test
-    .create_view(&current_workspace.id, "My view 2".to_string())
+  // The grid is nested in the document, it should be found as well.
+  let grid = test
+    .create_grid(&document.id, "My grid".to_string(), vec![])
+    .await;
+  let trashed_grid = test
+    .create_grid(&current_workspace.id, "My trashed grid".to_string(), vec![])
     .await;
+  test.delete_view(&trashed_grid.id).await;
+  let board = create_view_with_layout(
+    &test,
+    &current_workspace.id,
+    "My board",
+    ViewLayoutPB::Board,
+  )
+  .await;
 
-  for j in 1..6 {
-    let _ = test
+  let views = get_views_by_layout(&test, &current_workspace.id, vec![ViewLayoutPB::Grid]).await;
+  let view_ids = views.iter().map(|view| view.id.clone()).collect::<Vec<_>>();
+  assert!(views.iter().all(|view| view.layout == ViewLayoutPB::Grid));
+  assert!(view_ids.contains(&grid.id));
+  assert!(!view_ids.contains(&trashed_grid.id));
+
+  let views = get_views_by_layout(
+    &test,
+    &current_workspace.id,
+    vec![ViewLayoutPB::Grid, ViewLayoutPB::Board],
+  )
+  .await;
+  let view_ids = views.iter().map(|view| view.id.clone()).collect::<Vec<_>>();
+  assert!(view_ids.contains(&grid.id));
+  assert!(view_ids.contains(&board.id));
+  assert!(!view_ids.contains(&document.id));
+}
+
+async fn create_view_with_layout(
+  test: &EventIntegrationTest,
+  parent_view_id: &str,
+  name: &str,
+  layout: ViewLayoutPB,
+) -> ViewPB {
+  EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::CreateView)
+    .payload(CreateViewPayloadPB {
+      parent_view_id: parent_view_id.to_string(),
+      name: name.to_string(),
+      layout: Some(layout),
+      ..Default::default()
+    })
+    .async_send()
+    .await
+    .parse::<ViewPB>()
+}
+
+async fn get_views_by_layout(
+  test: &EventIntegrationTest,
+  workspace_id: &str,
+  layouts: Vec<ViewLayoutPB>,
+) -> Vec<ViewPB> {
+  EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::GetViewsByLayout)
+    .payload(ViewsByLayoutPayloadPB {
+      workspace_id: workspace_id.to_string(),
+      layouts: layouts.into_iter().map(ViewLayoutItemPB::from).collect(),
+    })
+    .async_send()
+    .await
+    .parse::<RepeatedViewPB>()
+    .items
+}
+
+#[tokio::test]
+async fn add_and_remove_view_tags_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+  let view_1 = test
+    .create_view(&current_workspace.id, "My 1 view".to_string())
+    .await;
+  let view_2 = test.create_view(&view_1.id, "My 2 view".to_string()).await;
+
+  // The tags are deduplicated case-insensitively
+  update_view_tags(
+    &test,
+    flowy_folder2::event_map::FolderEvent::AddViewTags,
+    &view_1.id,
+    vec!["Work", "work", "Ideas"],
+  )
+  .await;
+  update_view_tags(
+    &test,
+    flowy_folder2::event_map::FolderEvent::AddViewTags,
+    &view_2.id,
+    vec!["WORK"],
+  )
+  .await;
+  assert_eq!(test.get_view(&view_1.id).await.tags, vec!["Work", "Ideas"]);
+  // The tags are stored in the view of the folder, so they're synced with the folder.
+  assert_eq!(
+    read_view_extra_json(&test, &view_1.id)["tags"],
+    serde_json::json!(["Work", "Ideas"])
+  );
+
+  let view_ids = get_views_by_tag(&test, "work")
+    .await
+    .into_iter()
+    .map(|view| view.id)
+    .collect::<Vec<_>>();
+  assert_eq!(view_ids, vec![view_1.id.clone(), view_2.id.clone()]);
+
+  update_view_tags(
+    &test,
+    flowy_folder2::event_map::FolderEvent::RemoveViewTags,
+    &view_1.id,
+    vec!["WORK"],
+  )
+  .await;
+  assert_eq!(test.get_view(&view_1.id).await.tags, vec!["Ideas"]);
+  let views = get_views_by_tag(&test, "Work").await;
+  assert_eq!(views.len(), 1);
+  assert_eq!(views[0].id, view_2.id);
+}
+
+async fn update_view_tags(
+  test: &EventIntegrationTest,
+  event: flowy_folder2::event_map::FolderEvent,
+  view_id: &str,
+  tags: Vec<&str>,
+) {
+  let error = EventBuilder::new(test.clone())
+    .event(event)
+    .payload(ViewTagsPayloadPB {
+      view_id: view_id.to_string(),
+      tags: tags.into_iter().map(|tag| tag.to_string()).collect(),
+    })
+    .async_send()
+    .await
+    .error();
+  assert!(error.is_none());
+}
+
+async fn get_views_by_tag(test: &EventIntegrationTest, tag: &str) -> Vec<ViewPB> {
+  EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::GetViewsByTag)
+    .payload(ViewTagPB {
+      value: tag.to_string(),
+    })
+    .async_send()
+    .await
+    .parse::<RepeatedViewPB>()
+    .items
+}
+
+#[tokio::test]
+async fn get_and_repair_orphaned_views_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+  // The parent of the view doesn't exist
+  let orphaned_view = test
+    .create_view("missing_parent_id", "My orphaned view".to_string())
+    .await;
+  // The parent of the view doesn't list it as a child view
+  let parent_view = test
+    .create_view(&current_workspace.id, "My parent view".to_string())
+    .await;
+  let child_view = test
+    .create_view(&parent_view.id, "My child view".to_string())
+    .await;
+  {
+    let folder = test.folder_manager.get_mutex_folder().lock();
+    let folder = folder.as_ref().unwrap();
+    folder
+      .views
+      .dissociate_parent_child(&parent_view.id, &child_view.id);
+  }
+  // The child view of a trashed view is not orphaned
+  let trashed_parent = test
+    .create_view(&current_workspace.id, "My trashed view".to_string())
+    .await;
+  let trashed_child = test
+    .create_view(&trashed_parent.id, "My trashed child view".to_string())
+    .await;
+  test.delete_view(&trashed_parent.id).await;
+
+  let views = EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::GetOrphanedViews)
+    .async_send()
+    .await
+    .parse::<RepeatedViewPB>()
+    .items;
+  let view_ids = views.into_iter().map(|view| view.id).collect::<Vec<_>>();
+  assert_eq!(view_ids.len(), 2);
+  assert!(view_ids.contains(&orphaned_view.id));
+  assert!(view_ids.contains(&child_view.id));
+  assert!(!view_ids.contains(&trashed_child.id));
+
+  // Nothing is changed in the dry run
+  let views = repair_orphaned_views(&test, true).await;
+  assert_eq!(views.len(), 2);
+  assert!(views
+    .iter()
+    .all(|view| view.parent_view_id != current_workspace.id));
+
+  let views = repair_orphaned_views(&test, false).await;
+  assert!(views
+    .iter()
+    .all(|view| view.parent_view_id == current_workspace.id));
+  let workspace_view_ids = test
+    .get_all_workspace_views()
+    .await
+    .into_iter()
+    .map(|view| view.id)
+    .collect::<Vec<_>>();
+  assert!(workspace_view_ids.contains(&orphaned_view.id));
+  assert!(workspace_view_ids.contains(&child_view.id));
+  assert!(repair_orphaned_views(&test, true).await.is_empty());
+  assert_eq!(
+    test.get_view(&trashed_child.id).await.parent_view_id,
+    trashed_parent.id
+  );
+}
+
+async fn repair_orphaned_views(test: &EventIntegrationTest, dry_run: bool) -> Vec<ViewPB> {
+  EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::RepairOrphanedViews)
+    .payload(RepairOrphanedViewsPayloadPB { dry_run })
+    .async_send()
+    .await
+    .parse::<RepeatedViewPB>()
+    .items
+}
+
+#[tokio::test]
+async fn create_view_with_default_layout_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+  // The document layout is used if the workspace has no default layout
+  let view = create_view_without_layout(&test, &current_workspace.id, "My view").await;
+  assert_eq!(view.layout, ViewLayoutPB::Document);
+
+  let error = EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::SetDefaultViewLayout)
+    .payload(DefaultLayoutPayloadPB {
+      workspace_id: current_workspace.id.clone(),
+      layout: ViewLayoutPB::Grid,
+    })
+    .async_send()
+    .await
+    .error();
+  assert!(error.is_none());
+
+  let view = create_view_without_layout(&test, &current_workspace.id, "My grid").await;
+  assert_eq!(view.layout, ViewLayoutPB::Grid);
+  // The default layout is stored in the settings of the workspace in the folder.
+  assert_eq!(
+    read_folder_extra_json(&test, "setting")["default_view_layout"],
+    serde_json::to_value(collab_folder::ViewLayout::Grid).unwrap()
+  );
+
+  // The layout in the payload takes precedence over the default layout
+  let view = test
+    .create_view(&current_workspace.id, "My document".to_string())
+    .await;
+  assert_eq!(view.layout, ViewLayoutPB::Document);
+}
+
+async fn create_view_without_layout(
+  test: &EventIntegrationTest,
+  parent_view_id: &str,
+  name: &str,
+) -> ViewPB {
+  EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::CreateView)
+    .payload(CreateViewPayloadPB {
+      parent_view_id: parent_view_id.to_string(),
+      name: name.to_string(),
+      layout: None,
+      ..Default::default()
+    })
+    .async_send()
+    .await
+    .parse::<ViewPB>()
+}
+
+#[tokio::test]
+async fn set_latest_view_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+  let view = test
+    .create_view(&current_workspace.id, "My view".to_string())
+    .await;
+  let latest_view = set_latest_view(&test, &view.id).await.unwrap();
+  assert_eq!(latest_view.id, view.id);
+
+  let error = set_latest_view(&test, "missing_view_id").await.unwrap_err();
+  assert_eq!(error.code, ErrorCode::RecordNotFound);
+
+  let trashed_view = test
+    .create_view(&current_workspace.id, "My trashed view".to_string())
+    .await;
+  test.delete_view(&trashed_view.id).await;
+  let error = set_latest_view(&test, &trashed_view.id).await.unwrap_err();
+  assert_eq!(error.code, ErrorCode::InvalidParams);
+}
+
+async fn set_latest_view(test: &EventIntegrationTest, view_id: &str) -> Result<ViewPB, FlowyError> {
+  EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::SetLatestView)
+    .payload(ViewIdPB {
+      value: view_id.to_string(),
+    })
+    .async_send()
+    .await
+    .try_parse::<ViewPB>()
+}
+
+async fn set_view_locked(test: &EventIntegrationTest, view_id: &str, locked: bool) {
+  EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::SetViewLocked)
+    .payload(SetViewLockedPayloadPB {
+      view_id: view_id.to_string(),
+      locked,
+    })
+    .async_send()
+    .await;
+}
+
+#[tokio::test]
+async fn update_view_icon_event_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+  let view = test
+    .create_view(&current_workspace.id, "My first view".to_string())
+    .await;
+
+  let new_icon = ViewIconPB {
+    ty: ViewIconTypePB::Emoji,
+    value: "👍".to_owned(),
+  };
+  let error = test
+    .update_view_icon(UpdateViewIconPayloadPB {
+      view_id: view.id.clone(),
+      icon: Some(new_icon.clone()),
+    })
+    .await;
+  assert!(error.is_none());
+
+  let view = test.get_view(&view.id).await;
+  assert_eq!(view.icon, Some(new_icon));
+}
+
+#[tokio::test]
+async fn update_view_icon_normalize_emoji_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+  let view = test
+    .create_view(&current_workspace.id, "My first view".to_string())
+    .await;
+
+  // Each emoji is stored in the fully-qualified form defined by the Unicode emoji-test.txt
+  let encodings = vec![
+    (
+      "\u{2764}\u{FE0F}",
+      vec!["\u{2764}", "\u{2764}\u{FE0F}", "\u{2764}\u{FE0E}"],
+    ),
+    (
+      "\u{1F44D}\u{1F3FD}",
+      vec![
+        "\u{1F44D}\u{1F3FD}",
+        "\u{1F44D}\u{FE0F}\u{1F3FD}",
+        "\u{1F44D}\u{1F3FD}\u{FE0F}",
+      ],
+    ),
+    (
+      "\u{261D}\u{1F3FB}",
+      vec!["\u{261D}\u{1F3FB}", "\u{261D}\u{FE0F}\u{1F3FB}"],
+    ),
+    (
+      "1\u{FE0F}\u{20E3}",
+      vec!["1\u{20E3}", "1\u{FE0F}\u{20E3}", "1\u{FE0E}\u{20E3}"],
+    ),
+    ("\u{1F600}", vec!["\u{1F600}", "\u{1F600}\u{FE0F}"]),
+    (
+      "\u{2764}\u{FE0F}\u{200D}\u{1F525}",
+      vec![
+        "\u{2764}\u{200D}\u{1F525}",
+        "\u{2764}\u{FE0F}\u{200D}\u{1F525}",
+      ],
+    ),
+    (
+      "\u{1F3F3}\u{FE0F}\u{200D}\u{1F308}",
+      vec![
+        "\u{1F3F3}\u{200D}\u{1F308}",
+        "\u{1F3F3}\u{FE0F}\u{200D}\u{1F308}",
+      ],
+    ),
+    (
+      "\u{1F9D1}\u{200D}\u{2695}\u{FE0F}",
+      vec![
+        "\u{1F9D1}\u{200D}\u{2695}",
+        "\u{1F9D1}\u{FE0F}\u{200D}\u{2695}\u{FE0F}",
+      ],
+    ),
+  ];
+  for (expected, emojis) in encodings {
+    let mut stored_emojis = vec![];
+    for emoji in emojis {
+      let error = test
+        .update_view_icon(UpdateViewIconPayloadPB {
+          view_id: view.id.clone(),
+          icon: Some(ViewIconPB {
+            ty: ViewIconTypePB::Emoji,
+            value: emoji.to_string(),
+          }),
+        })
+        .await;
+      assert!(error.is_none());
+      stored_emojis.push(test.get_view(&view.id).await.icon.unwrap().value);
+    }
+    stored_emojis.dedup();
+    assert_eq!(stored_emojis, vec![expected.to_string()]);
+  }
+}
+
+#[tokio::test]
+async fn delete_view_event_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+  let view = test
+    .create_view(&current_workspace.id, "My first view".to_string())
+    .await;
+  test.delete_view(&view.id).await;
+
+  // Try the read the view
+  let payload = ViewIdPB {
+    value: view.id.clone(),
+  };
+  let error = EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::ReadView)
+    .payload(payload)
+    .async_send()
+    .await
+    .error()
+    .unwrap();
+  assert_eq!(error.code, ErrorCode::RecordNotFound);
+}
+
+#[tokio::test]
+async fn put_back_trash_event_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+  let view = test
+    .create_view(&current_workspace.id, "My first view".to_string())
+    .await;
+  test.delete_view(&view.id).await;
+
+  // After delete view, the view will be moved to trash
+  let payload = ViewIdPB {
+    value: view.id.clone(),
+  };
+  let error = EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::ReadView)
+    .payload(payload)
+    .async_send()
+    .await
+    .error()
+    .unwrap();
+  assert_eq!(error.code, ErrorCode::RecordNotFound);
+
+  let payload = TrashIdPB {
+    id: view.id.clone(),
+  };
+  EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::PutbackTrash)
+    .payload(payload)
+    .async_send()
+    .await;
+
+  let payload = ViewIdPB {
+    value: view.id.clone(),
+  };
+  let error = EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::ReadView)
+    .payload(payload)
+    .async_send()
+    .await
+    .error();
+  assert!(error.is_none());
+}
+
+#[tokio::test]
+async fn delete_view_permanently_event_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+  let view = test
+    .create_view(&current_workspace.id, "My first view".to_string())
+    .await;
+  let payload = RepeatedViewIdPB {
+    items: vec![view.id.clone()],
+    cascade: None,
+  };
+
+  // delete the view. the view will be moved to trash
+  EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::DeleteView)
+    .payload(payload)
+    .async_send()
+    .await;
+
+  let trash = test.read_trash(&current_workspace.id).await.items;
+  assert_eq!(trash.len(), 1);
+  assert_eq!(trash[0].id, view.id);
+
+  // delete the view from trash
+  let payload = RepeatedTrashIdPB {
+    items: vec![TrashIdPB {
+      id: view.id.clone(),
+    }],
+  };
+  EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::DeleteTrash)
+    .payload(payload)
+    .async_send()
+    .await;
+
+  // After delete the last view, the trash should be empty
+  let trash = test.read_trash(&current_workspace.id).await.items;
+  assert!(trash.is_empty());
+}
+
+#[tokio::test]
+async fn delete_view_permanently_without_trash_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+  let parent = test
+    .create_view(&current_workspace.id, "My view".to_string())
+    .await;
+  let child = test.create_view(&parent.id, "My 1 view".to_string()).await;
+
+  // The workspace root can't be deleted
+  let error = delete_views_permanently(&test, vec![current_workspace.id.clone()])
+    .await
+    .unwrap();
+  assert_eq!(error.code, ErrorCode::InvalidParams);
+
+  // Nothing is deleted if any of the views can't be found
+  let error =
+    delete_views_permanently(&test, vec![parent.id.clone(), "not exist view".to_string()])
+      .await
+      .unwrap();
+  assert_eq!(error.code, ErrorCode::RecordNotFound);
+  assert!(error.msg.contains("not exist view"));
+  assert_eq!(test.get_view(&parent.id).await.child_views.len(), 1);
+
+  // The view is deleted with its descendants, and nothing is moved to the trash
+  let error = delete_views_permanently(&test, vec![parent.id.clone()]).await;
+  assert!(error.is_none());
+  for view_id in [&parent.id, &child.id] {
+    let error = EventBuilder::new(test.clone())
+      .event(flowy_folder2::event_map::FolderEvent::ReadView)
+      .payload(ViewIdPB {
+        value: view_id.clone(),
+      })
+      .async_send()
+      .await
+      .error()
+      .unwrap();
+    assert_eq!(error.code, ErrorCode::RecordNotFound);
+  }
+  assert!(test
+    .read_trash(&current_workspace.id)
+    .await
+    .items
+    .is_empty());
+}
+
+async fn delete_views_permanently(
+  test: &EventIntegrationTest,
+  view_ids: Vec<String>,
+) -> Option<FlowyError> {
+  EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::DeleteViewPermanently)
+    .payload(RepeatedViewIdPB {
+      items: view_ids,
+      cascade: None,
+    })
+    .async_send()
+    .await
+    .error()
+}
+
+#[tokio::test]
+async fn set_trash_retention_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+  let view = test
+    .create_view(&current_workspace.id, "My view".to_string())
+    .await;
+  test.delete_view(&view.id).await;
+
+  let error = set_trash_retention(&test, 30).await;
+  assert!(error.is_none());
+  // The trash item that hasn't expired is kept
+  let trash = test.read_trash(&current_workspace.id).await.items;
+  assert_eq!(trash.len(), 1);
+  assert_eq!(trash[0].id, view.id);
+
+  // The retention is persisted in the settings of the workspace in the folder, so it's synced
+  // with the folder.
+  assert_eq!(
+    read_folder_extra_json(&test, "setting")["trash_retention_days"],
+    30
+  );
+  let settings = EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::UpdateWorkspaceSettings)
+    .payload(WorkspaceSettingsPayloadPB {
+      workspace_id: current_workspace.id.clone(),
+      ..Default::default()
+    })
+    .async_send()
+    .await
+    .parse::<WorkspaceSettingsPB>();
+  assert_eq!(settings.trash_retention_days, 30);
+
+  let error = set_trash_retention(&test, 0).await;
+  assert!(error.is_none());
+  let settings = EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::UpdateWorkspaceSettings)
+    .payload(WorkspaceSettingsPayloadPB {
+      workspace_id: current_workspace.id.clone(),
+      ..Default::default()
+    })
+    .async_send()
+    .await
+    .parse::<WorkspaceSettingsPB>();
+  assert_eq!(settings.trash_retention_days, 0);
+}
+
+async fn set_trash_retention(test: &EventIntegrationTest, days: u32) -> Option<FlowyError> {
+  EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::SetTrashRetention)
+    .payload(TrashRetentionPayloadPB { days })
+    .async_send()
+    .await
+    .error()
+}
+
+#[tokio::test]
+async fn delete_all_trash_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+
+  for i in 0..3 {
+    let view = test
+      .create_view(&current_workspace.id, format!("My {} view", i))
+      .await;
+    let payload = RepeatedViewIdPB {
+      items: vec![view.id.clone()],
+      cascade: None,
+    };
+    // delete the view. the view will be moved to trash
+    EventBuilder::new(test.clone())
+      .event(flowy_folder2::event_map::FolderEvent::DeleteView)
+      .payload(payload)
+      .async_send()
+      .await;
+  }
+
+  let trash = test.read_trash(&current_workspace.id).await.items;
+  assert_eq!(trash.len(), 3);
+
+  // Delete all the trash
+  EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::DeleteAllTrash)
+    .async_send()
+    .await;
+
+  // After delete the last view, the trash should be empty
+  let trash = test.read_trash(&current_workspace.id).await.items;
+  assert!(trash.is_empty());
+}
+
+#[tokio::test]
+async fn multiple_hierarchy_view_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+  for i in 1..4 {
+    let parent = test
+      .create_view(&current_workspace.id, format!("My {} view", i))
+      .await;
+    for j in 1..3 {
+      let child = test
+        .create_view(&parent.id, format!("My {}-{} view", i, j))
+        .await;
+      for k in 1..2 {
+        let _sub_child = test
+          .create_view(&child.id, format!("My {}-{}-{} view", i, j, k))
+          .await;
+      }
+    }
+  }
+
+  let mut views = test.get_all_workspace_views().await;
+  // There will be one default view when AppFlowy is initialized. So there will be 4 views in total
+  assert_eq!(views.len(), 4);
+  views.remove(0);
+
+  // workspace
+  //   - view1
+  //     - view1-1
+  //       - view1-1-1
+  //     - view1-2
+  //       - view1-2-1
+  //   - view2
+  //     - view2-1
+  //       - view2-1-1
+  //     - view2-2
+  //       - view2-2-1
+  //   - view3
+  //     - view3-1
+  //       - view3-1-1
+  //     - view3-2
+  //       - view3-2-1
+  assert_eq!(views[0].name, "My 1 view");
+  assert_eq!(views[1].name, "My 2 view");
+  assert_eq!(views[2].name, "My 3 view");
+
+  assert_eq!(views[0].child_views.len(), 2);
+  // By default only the first level of child views will be loaded
+  assert!(views[0].child_views[0].child_views.is_empty());
+
+  for (i, view) in views.into_iter().enumerate() {
+    for (j, child_view) in view.child_views.into_iter().enumerate() {
+      let payload = ViewIdPB {
+        value: child_view.id.clone(),
+      };
+
+      let child = EventBuilder::new(test.clone())
+        .event(flowy_folder2::event_map::FolderEvent::ReadView)
+        .payload(payload)
+        .async_send()
+        .await
+        .parse::<flowy_folder2::entities::ViewPB>();
+      assert_eq!(child.name, format!("My {}-{} view", i + 1, j + 1));
+      assert_eq!(child.child_views.len(), 1);
+      // By default only the first level of child views will be loaded
+      assert!(child.child_views[0].child_views.is_empty());
+
+      for (k, _child_view) in child_view.child_views.into_iter().enumerate() {
+        // Get the last level view
+        let sub_child = test.get_view(&child.id).await;
+        assert_eq!(child.name, format!("My {}-{}-{} view", i + 1, j + 1, k + 1));
+        assert!(sub_child.child_views.is_empty());
+      }
+    }
+  }
+}
+
+#[tokio::test]
+async fn move_view_event_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+  for i in 1..4 {
+    let parent = test
+      .create_view(&current_workspace.id, format!("My {} view", i))
+      .await;
+    for j in 1..3 {
+      let _ = test
+        .create_view(&parent.id, format!("My {}-{} view", i, j))
+        .await;
+    }
+  }
+  let views = test.get_all_workspace_views().await;
+  // There will be one default view when AppFlowy is initialized. So there will be 4 views in total
+  assert_eq!(views.len(), 4);
+  assert_eq!(views[1].name, "My 1 view");
+  assert_eq!(views[2].name, "My 2 view");
+  assert_eq!(views[3].name, "My 3 view");
+
+  let payload = MoveViewPayloadPB {
+    view_id: views[1].id.clone(),
+    from: 1,
+    to: 2,
+  };
+  let _ = EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::MoveView)
+    .payload(payload)
+    .async_send()
+    .await;
+
+  let views = test.get_all_workspace_views().await;
+  assert_eq!(views[1].name, "My 2 view");
+  assert_eq!(views[2].name, "My 1 view");
+  assert_eq!(views[3].name, "My 3 view");
+}
+
+#[tokio::test]
+async fn move_view_event_after_delete_view_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+  for i in 1..6 {
+    let _ = test
+      .create_view(&current_workspace.id, format!("My {} view", i))
+      .await;
+  }
+  let views = test.get_all_workspace_views().await;
+  assert_eq!(views[1].name, "My 1 view");
+  assert_eq!(views[2].name, "My 2 view");
+  assert_eq!(views[3].name, "My 3 view");
+  assert_eq!(views[4].name, "My 4 view");
+  assert_eq!(views[5].name, "My 5 view");
+  test.delete_view(&views[3].id).await;
+
+  // There will be one default view when AppFlowy is initialized. So there will be 4 views in total
+  let views = test.get_all_workspace_views().await;
+  assert_eq!(views[1].name, "My 1 view");
+  assert_eq!(views[2].name, "My 2 view");
+  assert_eq!(views[3].name, "My 4 view");
+  assert_eq!(views[4].name, "My 5 view");
+
+  let payload = MoveViewPayloadPB {
+    view_id: views[1].id.clone(),
+    from: 1,
+    to: 3,
+  };
+  let _ = EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::MoveView)
+    .payload(payload)
+    .async_send()
+    .await;
+
+  let views = test.get_all_workspace_views().await;
+  assert_eq!(views[1].name, "My 2 view");
+  assert_eq!(views[2].name, "My 4 view");
+  assert_eq!(views[3].name, "My 1 view");
+  assert_eq!(views[4].name, "My 5 view");
+}
+
+#[tokio::test]
+async fn move_view_event_after_delete_view_test2() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+  let parent = test
+    .create_view(&current_workspace.id, "My view".to_string())
+    .await;
+
+  for j in 1..6 {
+    let _ = test
+      .create_view(&parent.id, format!("My 1-{} view", j))
+      .await;
+  }
+
+  let views = test.get_view(&parent.id).await.child_views;
+  assert_eq!(views.len(), 5);
+  assert_eq!(views[0].name, "My 1-1 view");
+  assert_eq!(views[1].name, "My 1-2 view");
+  assert_eq!(views[2].name, "My 1-3 view");
+  assert_eq!(views[3].name, "My 1-4 view");
+  assert_eq!(views[4].name, "My 1-5 view");
+  test.delete_view(&views[2].id).await;
+
+  let payload = MoveViewPayloadPB {
+    view_id: views[0].id.clone(),
+    from: 0,
+    to: 2,
+  };
+  let _ = EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::MoveView)
+    .payload(payload)
+    .async_send()
+    .await;
+
+  let views = test.get_view(&parent.id).await.child_views;
+  assert_eq!(views[0].name, "My 1-2 view");
+  assert_eq!(views[1].name, "My 1-4 view");
+  assert_eq!(views[2].name, "My 1-1 view");
+  assert_eq!(views[3].name, "My 1-5 view");
+}
+
+#[tokio::test]
+async fn create_views_in_batch_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+  let items = (1..4)
+    .map(|i| CreateViewPayloadPB {
+      parent_view_id: current_workspace.id.clone(),
+      name: format!("My {} view", i),
+      ..Default::default()
+    })
+    .collect::<Vec<_>>();
+
+  let views = EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::CreateViews)
+    .payload(RepeatedCreateViewPayloadPB { items })
+    .async_send()
+    .await
+    .parse::<RepeatedViewPB>()
+    .items;
+  assert_eq!(views.len(), 3);
+  assert_eq!(views[0].name, "My 1 view");
+  assert_eq!(views[1].name, "My 2 view");
+  assert_eq!(views[2].name, "My 3 view");
+
+  let workspace_views = test.get_all_workspace_views().await;
+  assert_eq!(workspace_views.len(), 4);
+}
+
+#[tokio::test]
+async fn create_parent_view_with_invalid_name() {
+  for (name, code) in invalid_workspace_name_test_case() {
+    let sdk = EventIntegrationTest::new().await;
+    let request = CreateWorkspacePayloadPB {
+      name,
+      desc: "".to_owned(),
+      icon: None,
+    };
+    assert_eq!(
+      EventBuilder::new(sdk)
+        .event(flowy_folder2::event_map::FolderEvent::CreateWorkspace)
+        .payload(request)
+        .async_send()
+        .await
+        .error()
+        .unwrap()
+        .code,
+      code
+    )
+  }
+}
+
+fn invalid_workspace_name_test_case() -> Vec<(String, ErrorCode)> {
+  vec![
+    ("".to_owned(), ErrorCode::WorkspaceNameInvalid),
+    ("1234".repeat(100), ErrorCode::WorkspaceNameTooLong),
+  ]
+}
+
+#[tokio::test]
+async fn move_view_across_parent_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+  let parent_1 = test
+    .create_view(&current_workspace.id, "My view 1".to_string())
+    .await;
+  let parent_2 = test
+    .create_view(&current_workspace.id, "My view 2".to_string())
+    .await;
+
+  for j in 1..6 {
+    let _ = test
       .create_view(&parent_1.id, format!("My 1-{} view 1", j))
       .await;
   }
 
-  let views = test.get_view(&parent_1.id).await.child_views;
-  // Move `My 1-1 view 1` to `My view 2`
-  let move_view_id = views[0].id.clone();
-  let new_parent_id = parent_2.id.clone();
-  let prev_id = None;
-  move_folder_nested_view(test.clone(), move_view_id, new_parent_id, prev_id).await;
-  let parent1_views = test.get_view(&parent_1.id).await.child_views;
-  let parent2_views = test.get_view(&parent_2.id).await.child_views;
-  assert_eq!(parent2_views.len(), 1);
-  assert_eq!(parent2_views[0].name, "My 1-1 view 1");
-  assert_eq!(parent1_views[0].name, "My 1-2 view 1");
+  let views = test.get_view(&parent_1.id).await.child_views;
+  // Move `My 1-1 view 1` to `My view 2`
+  let move_view_id = views[0].id.clone();
+  let new_parent_id = parent_2.id.clone();
+  let prev_id = None;
+  move_folder_nested_view(test.clone(), move_view_id, new_parent_id, prev_id).await;
+  let parent1_views = test.get_view(&parent_1.id).await.child_views;
+  let parent2_views = test.get_view(&parent_2.id).await.child_views;
+  assert_eq!(parent2_views.len(), 1);
+  assert_eq!(parent2_views[0].name, "My 1-1 view 1");
+  assert_eq!(parent1_views[0].name, "My 1-2 view 1");
+
+  // Move My 1-2 view 1 from My view 1 to the current workspace and insert it after My view 1.
+  let move_view_id = parent1_views[0].id.clone();
+  let new_parent_id = current_workspace.id.clone();
+  let prev_id = Some(parent_1.id.clone());
+  move_folder_nested_view(test.clone(), move_view_id, new_parent_id, prev_id).await;
+  let parent1_views = test.get_view(&parent_1.id).await.child_views;
+  let workspace_views = test.get_all_workspace_views().await;
+  let workspace_views_len = workspace_views.len();
+  assert_eq!(parent1_views[0].name, "My 1-3 view 1");
+  assert_eq!(workspace_views[workspace_views_len - 3].name, "My view 1");
+  assert_eq!(
+    workspace_views[workspace_views_len - 2].name,
+    "My 1-2 view 1"
+  );
+  assert_eq!(workspace_views[workspace_views_len - 1].name, "My view 2");
+}
+
+#[tokio::test]
+async fn duplicate_view_recursive_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+  let parent = test
+    .create_view(&current_workspace.id, "My view".to_string())
+    .await;
+  let child = test.create_view(&parent.id, "My 1 view".to_string()).await;
+  let _ = test.create_view(&child.id, "My 1-1 view".to_string()).await;
+
+  let duplicated_view = EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::DuplicateViewRecursive)
+    .payload(DuplicateViewPayloadPB {
+      view_id: parent.id.clone(),
+      include_children: true,
+      suffix: None,
+    })
+    .async_send()
+    .await
+    .parse::<ViewPB>();
+  assert_ne!(duplicated_view.id, parent.id);
+  assert_eq!(duplicated_view.name, "My view (copy)");
+  assert_eq!(duplicated_view.parent_view_id, current_workspace.id);
+
+  let duplicated_children = test.get_view(&duplicated_view.id).await.child_views;
+  assert_eq!(duplicated_children.len(), 1);
+  assert_ne!(duplicated_children[0].id, child.id);
+  assert_eq!(duplicated_children[0].name, "My 1 view");
+
+  let duplicated_grandchildren = test.get_view(&duplicated_children[0].id).await.child_views;
+  assert_eq!(duplicated_grandchildren.len(), 1);
+  assert_eq!(duplicated_grandchildren[0].name, "My 1-1 view");
+  assert_eq!(
+    duplicated_grandchildren[0].parent_view_id,
+    duplicated_children[0].id
+  );
+
+  // The original view keeps its child views.
+  assert_eq!(test.get_view(&parent.id).await.child_views.len(), 1);
+}
+
+#[tokio::test]
+async fn create_named_folder_snapshot_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+  let snapshot = EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::CreateFolderSnapshot)
+    .payload(CreateSnapshotPayloadPB {
+      workspace_id: current_workspace.id.clone(),
+      name: "a".repeat(300),
+    })
+    .async_send()
+    .await
+    .parse::<FolderSnapshotPB>();
+  assert_eq!(snapshot.name, "a".repeat(255));
+
+  let snapshots = EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::GetFolderSnapshots)
+    .payload(FolderSnapshotQueryPB {
+      workspace_id: current_workspace.id.clone(),
+      limit: 10,
+      offset: 0,
+    })
+    .async_send()
+    .await
+    .parse::<RepeatedFolderSnapshotPB>();
+  assert_eq!(snapshots.total_count, 1);
+  assert_eq!(snapshots.items[0].snapshot_id, snapshot.snapshot_id);
+  assert_eq!(snapshots.items[0].name, snapshot.name);
+}
+
+#[tokio::test]
+async fn diff_folder_snapshots_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+  let parent = test
+    .create_view(&current_workspace.id, "My view".to_string())
+    .await;
+  let child = test
+    .create_view(&current_workspace.id, "My 1 view".to_string())
+    .await;
+  let from_snapshot = create_folder_snapshot(&test, &current_workspace.id).await;
+
+  test
+    .update_view(UpdateViewPayloadPB {
+      view_id: parent.id.clone(),
+      name: Some("My renamed view".to_string()),
+      ..Default::default()
+    })
+    .await;
+  move_folder_nested_view(test.clone(), child.id.clone(), parent.id.clone(), None).await;
+  let new_view = test.create_view(&parent.id, "My 2 view".to_string()).await;
+  let to_snapshot = create_folder_snapshot(&test, &current_workspace.id).await;
+
+  let diff = EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::DiffFolderSnapshots)
+    .payload(DiffSnapshotsPayloadPB {
+      workspace_id: current_workspace.id.clone(),
+      from_snapshot_id: from_snapshot.snapshot_id,
+      to_snapshot_id: to_snapshot.snapshot_id,
+    })
+    .async_send()
+    .await
+    .parse::<FolderDiffPB>();
+  assert_eq!(diff.added.len(), 1);
+  assert_eq!(diff.added[0].id, new_view.id);
+  assert!(diff.removed.is_empty());
+  assert_eq!(diff.renamed.len(), 1);
+  assert_eq!(diff.renamed[0].view_id, parent.id);
+  assert_eq!(diff.renamed[0].old_name, "My view");
+  assert_eq!(diff.renamed[0].new_name, "My renamed view");
+  assert_eq!(diff.moved.len(), 1);
+  assert_eq!(diff.moved[0].view_id, child.id);
+  assert_eq!(diff.moved[0].old_parent_view_id, current_workspace.id);
+  assert_eq!(diff.moved[0].new_parent_view_id, parent.id);
+}
+
+#[tokio::test]
+async fn set_favorites_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+  let view_1 = test
+    .create_view(&current_workspace.id, "My view".to_string())
+    .await;
+  let view_2 = test
+    .create_view(&current_workspace.id, "My 1 view".to_string())
+    .await;
+
+  // Setting the same state twice keeps the view favorite.
+  for _ in 0..2 {
+    let views = set_favorites(
+      &test,
+      vec![(view_1.id.clone(), true), (view_2.id.clone(), false)],
+    )
+    .await;
+    assert_eq!(views.items.len(), 2);
+    assert!(views.items[0].is_favorite);
+    assert!(!views.items[1].is_favorite);
+  }
+
+  let views = set_favorites(&test, vec![(view_1.id.clone(), false)]).await;
+  assert!(!views.items[0].is_favorite);
+  assert!(!test.get_view(&view_1.id).await.is_favorite);
+}
+
+#[tokio::test]
+async fn reorder_favorite_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+  let mut view_ids = vec![];
+  for name in ["My 1 view", "My 2 view", "My 3 view"] {
+    let view = test
+      .create_view(&current_workspace.id, name.to_string())
+      .await;
+    view_ids.push(view.id);
+  }
+  set_favorites(
+    &test,
+    view_ids.iter().map(|id| (id.clone(), true)).collect(),
+  )
+  .await;
+
+  // The index out of range is clamped
+  let error = reorder_favorite(&test, &view_ids[0], 0, 10).await;
+  assert!(error.is_none());
+  assert_eq!(
+    read_favorite_ids(&test).await,
+    vec![
+      view_ids[1].clone(),
+      view_ids[2].clone(),
+      view_ids[0].clone()
+    ]
+  );
+
+  // The view that is not a favorite can't be reordered, and the order is kept
+  let other_view = test
+    .create_view(&current_workspace.id, "My other view".to_string())
+    .await;
+  let error = reorder_favorite(&test, &other_view.id, 0, 0).await.unwrap();
+  assert_eq!(error.code, ErrorCode::RecordNotFound);
+  assert_eq!(
+    read_favorite_ids(&test).await,
+    vec![
+      view_ids[1].clone(),
+      view_ids[2].clone(),
+      view_ids[0].clone()
+    ]
+  );
+}
+
+async fn reorder_favorite(
+  test: &EventIntegrationTest,
+  view_id: &str,
+  from_index: u32,
+  to_index: u32,
+) -> Option<FlowyError> {
+  EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::ReorderFavorite)
+    .payload(ReorderFavoritePayloadPB {
+      view_id: view_id.to_string(),
+      from_index,
+      to_index,
+    })
+    .async_send()
+    .await
+    .error()
+}
+
+async fn read_favorite_ids(test: &EventIntegrationTest) -> Vec<String> {
+  EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::ReadFavorites)
+    .async_send()
+    .await
+    .parse::<RepeatedViewPB>()
+    .items
+    .into_iter()
+    .map(|view| view.id)
+    .collect()
+}
+
+async fn set_favorites(test: &EventIntegrationTest, items: Vec<(String, bool)>) -> RepeatedViewPB {
+  EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::SetFavorites)
+    .payload(SetFavoritePayloadPB {
+      items: items
+        .into_iter()
+        .map(|(view_id, is_favorite)| FavoriteStatePB {
+          view_id,
+          is_favorite,
+        })
+        .collect(),
+    })
+    .async_send()
+    .await
+    .parse::<RepeatedViewPB>()
+}
+
+#[tokio::test]
+async fn update_show_favorites_setting_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+  // The favorites are shown by default
+  assert!(get_current_workspace_setting(&test).await.show_favorites);
+
+  update_show_favorites(&test, &current_workspace.id, false).await;
+  assert!(!get_current_workspace_setting(&test).await.show_favorites);
+
+  // The setting is restored with the snapshot
+  let snapshot = create_folder_snapshot(&test, &current_workspace.id).await;
+  update_show_favorites(&test, &current_workspace.id, true).await;
+  assert!(get_current_workspace_setting(&test).await.show_favorites);
+  let error = EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::RestoreFolderSnapshot)
+    .payload(RestoreFolderSnapshotPayloadPB {
+      workspace_id: current_workspace.id.clone(),
+      snapshot_id: snapshot.snapshot_id,
+    })
+    .async_send()
+    .await
+    .error();
+  assert!(error.is_none());
+  assert!(!get_current_workspace_setting(&test).await.show_favorites);
+}
+
+async fn get_current_workspace_setting(test: &EventIntegrationTest) -> WorkspaceSettingPB {
+  EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::GetCurrentWorkspaceSetting)
+    .async_send()
+    .await
+    .parse::<WorkspaceSettingPB>()
+}
+
+async fn update_show_favorites(test: &EventIntegrationTest, workspace_id: &str, show: bool) {
+  let error = EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::UpdateWorkspaceUISettings)
+    .payload(WorkspaceUISettingsPayloadPB {
+      workspace_id: workspace_id.to_string(),
+      show_favorites: Some(show),
+    })
+    .async_send()
+    .await
+    .error();
+  assert!(error.is_none());
+}
+
+#[tokio::test]
+async fn copy_view_to_current_workspace_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+  let parent = test
+    .create_view(&current_workspace.id, "My view".to_string())
+    .await;
+  let child = test.create_view(&parent.id, "My 1 view".to_string()).await;
+
+  let copied_view = copy_view_to_workspace(&test, &parent.id, &current_workspace.id, true).await;
+  assert_ne!(copied_view.id, parent.id);
+  assert_eq!(copied_view.name, "My view");
+  assert_eq!(copied_view.parent_view_id, current_workspace.id);
+  assert_eq!(copied_view.child_views.len(), 1);
+  assert_ne!(copied_view.child_views[0].id, child.id);
+  assert_eq!(copied_view.child_views[0].name, "My 1 view");
+
+  // The original view is untouched
+  let original_view = test.get_view(&parent.id).await;
+  assert_eq!(original_view.parent_view_id, current_workspace.id);
+  assert_eq!(original_view.child_views.len(), 1);
+  assert_eq!(original_view.child_views[0].id, child.id);
+
+  // Only the view itself is copied without the children
+  let copied_view = copy_view_to_workspace(&test, &parent.id, &current_workspace.id, false).await;
+  assert!(copied_view.child_views.is_empty());
+}
+
+async fn copy_view_to_workspace(
+  test: &EventIntegrationTest,
+  view_id: &str,
+  target_workspace_id: &str,
+  include_children: bool,
+) -> ViewPB {
+  EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::CopyViewToWorkspace)
+    .payload(CopyViewToWorkspacePayloadPB {
+      view_id: view_id.to_string(),
+      target_workspace_id: target_workspace_id.to_string(),
+      include_children,
+    })
+    .async_send()
+    .await
+    .parse::<ViewPB>()
+}
+
+#[tokio::test]
+async fn copy_view_to_other_workspace_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let uid = test.get_user_profile().await.unwrap().id;
+  let current_workspace = test.get_current_workspace().await;
+  let parent = test
+    .create_view(&current_workspace.id, "My view".to_string())
+    .await;
+  test.create_view(&parent.id, "My 1 view".to_string()).await;
+  let other_workspace = EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::DuplicateWorkspace)
+    .payload(DuplicateWorkspacePayloadPB {
+      workspace_id: current_workspace.id.clone(),
+      new_name: "My other workspace".to_string(),
+    })
+    .async_send()
+    .await
+    .parse::<WorkspacePB>();
+  let copy_to_other_workspace = || {
+    EventBuilder::new(test.clone())
+      .event(flowy_folder2::event_map::FolderEvent::CopyViewToWorkspace)
+      .payload(CopyViewToWorkspacePayloadPB {
+        view_id: parent.id.clone(),
+        target_workspace_id: other_workspace.id.clone(),
+        include_children: true,
+      })
+  };
+
+  // The view limit of the target workspace applies to the copies
+  set_view_limit(&test, &other_workspace.id, 1).await;
+  let error = copy_to_other_workspace()
+    .async_send()
+    .await
+    .error()
+    .unwrap();
+  assert_eq!(error.code, ErrorCode::ViewLimitReached);
+  set_view_limit(&test, &other_workspace.id, 0).await;
+
+  // So does the maximum nesting depth, the copied view has one child view
+  let error = EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::SetMaxNestingDepth)
+    .payload(MaxNestingDepthPayloadPB {
+      workspace_id: other_workspace.id.clone(),
+      depth: 1,
+    })
+    .async_send()
+    .await
+    .error();
+  assert!(error.is_none());
+  let error = copy_to_other_workspace()
+    .async_send()
+    .await
+    .error()
+    .unwrap();
+  assert_eq!(error.code, ErrorCode::MaxNestingDepthExceeded);
+  let error = EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::SetMaxNestingDepth)
+    .payload(MaxNestingDepthPayloadPB {
+      workspace_id: other_workspace.id.clone(),
+      depth: 0,
+    })
+    .async_send()
+    .await
+    .error();
+  assert!(error.is_none());
+
+  let copied_view = copy_to_other_workspace()
+    .async_send()
+    .await
+    .parse::<ViewPB>();
+  assert_eq!(copied_view.parent_view_id, other_workspace.id);
+  assert_eq!(copied_view.child_views.len(), 1);
+
+  // The current user is recorded as the creator of the copies
+  open_workspace(&test, &other_workspace.id, Some(2)).await;
+  assert_eq!(test.get_view(&copied_view.id).await.created_by, uid);
+  let child_id = copied_view.child_views[0].id.clone();
+  assert_eq!(test.get_view(&child_id).await.created_by, uid);
+}
+
+#[tokio::test]
+async fn move_view_to_workspace_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+  let parent = test
+    .create_view(&current_workspace.id, "My view".to_string())
+    .await;
+  let child = test.create_view(&parent.id, "My 1 view".to_string()).await;
+  let other_workspace = EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::DuplicateWorkspace)
+    .payload(DuplicateWorkspacePayloadPB {
+      workspace_id: current_workspace.id.clone(),
+      new_name: "My other workspace".to_string(),
+    })
+    .async_send()
+    .await
+    .parse::<WorkspacePB>();
+
+  let error = move_view_to_workspace(&test, &parent.id, "not exist workspace", None)
+    .await
+    .unwrap();
+  assert_eq!(error.code, ErrorCode::RecordNotFound);
+
+  // The view can't become its own ancestor
+  let error = move_view_to_workspace(
+    &test,
+    &parent.id,
+    &current_workspace.id,
+    Some(child.id.clone()),
+  )
+  .await
+  .unwrap();
+  assert_eq!(error.code, ErrorCode::InvalidParams);
+
+  // The locked descendant can't be moved
+  set_view_locked(&test, &child.id, true).await;
+  let error = move_view_to_workspace(&test, &parent.id, &other_workspace.id, None)
+    .await
+    .unwrap();
+  assert_eq!(error.code, ErrorCode::ViewIsLocked);
+  set_view_locked(&test, &child.id, false).await;
+
+  let trashed_child = test
+    .create_view(&parent.id, "My trashed view".to_string())
+    .await;
+  test.delete_view(&trashed_child.id).await;
+  update_view_tags(
+    &test,
+    flowy_folder2::event_map::FolderEvent::AddViewTags,
+    &parent.id,
+    vec!["Work"],
+  )
+  .await;
+  set_latest_view(&test, &child.id).await.unwrap();
+
+  let error = move_view_to_workspace(&test, &parent.id, &other_workspace.id, None).await;
+  assert!(error.is_none());
+  let view_ids = test
+    .get_all_workspace_views()
+    .await
+    .into_iter()
+    .map(|view| view.id)
+    .collect::<Vec<_>>();
+  assert!(!view_ids.contains(&parent.id));
+  // The trashed descendant is deleted instead of being moved, and the moved current view is
+  // cleared
+  assert!(test
+    .read_trash(&current_workspace.id)
+    .await
+    .items
+    .is_empty());
+  let current_view_id = {
+    let folder = test.folder_manager.get_mutex_folder().lock();
+    folder.as_ref().unwrap().get_current_view()
+  };
+  assert!(current_view_id.unwrap_or_default().is_empty());
+
+  // The view is moved with its descendants
+  let other_workspace = open_workspace(&test, &other_workspace.id, Some(2)).await;
+  let moved_view = other_workspace
+    .views
+    .iter()
+    .find(|view| view.id == parent.id)
+    .unwrap();
+  assert_eq!(moved_view.parent_view_id, other_workspace.id);
+  assert_eq!(moved_view.child_views.len(), 1);
+  assert_eq!(moved_view.child_views[0].id, child.id);
+  // The extra data is moved with the view
+  assert_eq!(test.get_view(&parent.id).await.tags, vec!["Work"]);
+}
+
+async fn move_view_to_workspace(
+  test: &EventIntegrationTest,
+  view_id: &str,
+  target_workspace_id: &str,
+  parent_view_id: Option<String>,
+) -> Option<FlowyError> {
+  EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::MoveViewToWorkspace)
+    .payload(MoveViewToWorkspacePayloadPB {
+      view_id: view_id.to_string(),
+      target_workspace_id: target_workspace_id.to_string(),
+      parent_view_id,
+    })
+    .async_send()
+    .await
+    .error()
+}
+
+#[tokio::test]
+async fn view_attribution_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let uid = test.get_user_profile().await.unwrap().id;
+  let current_workspace = test.get_current_workspace().await;
+  let view = test
+    .create_view(&current_workspace.id, "My view".to_string())
+    .await;
+  let view = test.get_view(&view.id).await;
+  assert_eq!(view.created_by, uid);
+  assert_eq!(view.last_edited_by, uid);
+  // The attribution is stored in the view of the folder, so it's synced with the folder.
+  assert_eq!(read_view_extra_json(&test, &view.id)["created_by"], uid);
+
+  test
+    .update_view(UpdateViewPayloadPB {
+      view_id: view.id.clone(),
+      name: Some("My renamed view".to_string()),
+      ..Default::default()
+    })
+    .await;
+  let edited_views = get_views_edited_by(&test, uid).await;
+  assert!(edited_views
+    .iter()
+    .any(|edited_view| edited_view.id == view.id));
+  assert!(get_views_edited_by(&test, uid + 1).await.is_empty());
+}
+
+async fn get_views_edited_by(test: &EventIntegrationTest, uid: i64) -> Vec<ViewPB> {
+  EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::GetViewsEditedBy)
+    .payload(ViewsEditedByPayloadPB { uid })
+    .async_send()
+    .await
+    .parse::<RepeatedViewPB>()
+    .items
+}
+
+#[tokio::test]
+async fn view_exists_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+  let parent = test
+    .create_view(&current_workspace.id, "My view".to_string())
+    .await;
+  let child = test.create_view(&parent.id, "My 1 view".to_string()).await;
+
+  let result = view_exists(&test, &child.id).await;
+  assert!(result.exists);
+  assert!(!result.is_trashed);
+
+  // The child view is trashed along with its parent
+  test.delete_view(&parent.id).await;
+  let result = view_exists(&test, &child.id).await;
+  assert!(result.exists);
+  assert!(result.is_trashed);
+
+  let result = view_exists(&test, "not exist view id").await;
+  assert!(!result.exists);
+  assert!(!result.is_trashed);
+}
+
+async fn view_exists(test: &EventIntegrationTest, view_id: &str) -> ViewExistsPB {
+  EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::ViewExists)
+    .payload(ViewIdPB::from(view_id))
+    .async_send()
+    .await
+    .parse::<ViewExistsPB>()
+}
+
+#[tokio::test]
+async fn restore_trash_to_parent_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+  let parent = test
+    .create_view(&current_workspace.id, "My view".to_string())
+    .await;
+  let child = test.create_view(&parent.id, "My 1 view".to_string()).await;
+  let target = test
+    .create_view(&current_workspace.id, "My target view".to_string())
+    .await;
+  let trashed_target = test
+    .create_view(&current_workspace.id, "My trashed view".to_string())
+    .await;
+  test.delete_view(&child.id).await;
+  test.delete_view(&parent.id).await;
+  test.delete_view(&trashed_target.id).await;
+
+  let error = restore_trash_to_parent(&test, &child.id, "not exist view")
+    .await
+    .unwrap();
+  assert_eq!(error.code, ErrorCode::RecordNotFound);
+  let error = restore_trash_to_parent(&test, &child.id, &trashed_target.id)
+    .await
+    .unwrap();
+  assert_eq!(error.code, ErrorCode::InvalidParams);
+
+  // The child is restored under the target instead of its trashed parent
+  let error = restore_trash_to_parent(&test, &child.id, &target.id).await;
+  assert!(error.is_none());
+  let child = test.get_view(&child.id).await;
+  assert_eq!(child.parent_view_id, target.id);
+  let target = test.get_view(&target.id).await;
+  assert_eq!(target.child_views.len(), 1);
+  assert_eq!(target.child_views[0].id, child.id);
+  let trash_ids = test
+    .read_trash(&current_workspace.id)
+    .await
+    .items
+    .into_iter()
+    .map(|trash| trash.id)
+    .collect::<Vec<_>>();
+  assert!(!trash_ids.contains(&child.id));
+  assert!(trash_ids.contains(&parent.id));
+}
+
+async fn restore_trash_to_parent(
+  test: &EventIntegrationTest,
+  trash_id: &str,
+  target_parent_id: &str,
+) -> Option<FlowyError> {
+  EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::RestoreTrashToParent)
+    .payload(RestoreTrashPayloadPB {
+      trash_id: trash_id.to_string(),
+      target_parent_id: target_parent_id.to_string(),
+    })
+    .async_send()
+    .await
+    .error()
+}
+
+#[tokio::test]
+async fn restore_trash_batch_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+  let parent = test
+    .create_view(&current_workspace.id, "My view".to_string())
+    .await;
+  let child = test.create_view(&parent.id, "My 1 view".to_string()).await;
+  let other = test
+    .create_view(&current_workspace.id, "My other view".to_string())
+    .await;
+  test.delete_view(&child.id).await;
+  test.delete_view(&other.id).await;
+  test.delete_view(&parent.id).await;
+
+  // Delete the parent permanently, so the child has no parent to go back to
+  EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::DeleteTrash)
+    .payload(RepeatedTrashIdPB {
+      items: vec![TrashIdPB {
+        id: parent.id.clone(),
+      }],
+    })
+    .async_send()
+    .await;
+
+  let result = EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::RestoreTrashBatch)
+    .payload(RepeatedTrashIdPB {
+      items: vec![
+        TrashIdPB {
+          id: child.id.clone(),
+        },
+        TrashIdPB {
+          id: parent.id.clone(),
+        },
+        TrashIdPB {
+          id: other.id.clone(),
+        },
+      ],
+    })
+    .async_send()
+    .await
+    .parse::<RestoreTrashBatchPB>();
+  assert_eq!(
+    result.restored_ids,
+    vec![child.id.clone(), other.id.clone()]
+  );
+  assert_eq!(result.failed_ids, vec![parent.id.clone()]);
+
+  // The child is attached to the workspace root because its parent no longer exists
+  let child = test.get_view(&child.id).await;
+  assert_eq!(child.parent_view_id, current_workspace.id);
+  let other = test.get_view(&other.id).await;
+  assert_eq!(other.parent_view_id, current_workspace.id);
+
+  let trash = test.read_trash(&current_workspace.id).await.items;
+  assert!(trash.is_empty());
+}
+
+#[tokio::test]
+async fn cancel_not_running_import_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let error = EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::CancelImport)
+    .payload(ImportIdPB {
+      value: "not running import id".to_string(),
+    })
+    .async_send()
+    .await
+    .error()
+    .unwrap();
+  assert_eq!(error.code, ErrorCode::RecordNotFound);
+}
+
+#[tokio::test]
+async fn duplicate_workspace_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+  let parent = test
+    .create_view(&current_workspace.id, "My view".to_string())
+    .await;
+  let _ = test.create_view(&parent.id, "My 1 view".to_string()).await;
+  let trashed_view = test
+    .create_view(&current_workspace.id, "My trashed view".to_string())
+    .await;
+  test.delete_view(&trashed_view.id).await;
+  let current_workspace = test.get_current_workspace().await;
+
+  let new_workspace = EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::DuplicateWorkspace)
+    .payload(DuplicateWorkspacePayloadPB {
+      workspace_id: current_workspace.id.clone(),
+      new_name: "My new workspace".to_string(),
+    })
+    .async_send()
+    .await
+    .parse::<WorkspacePB>();
+  assert_ne!(new_workspace.id, current_workspace.id);
+  assert_eq!(new_workspace.name, "My new workspace");
+
+  // The views keep their names and order with new ids. The trashed view is not copied.
+  let names = |workspace: &WorkspacePB| {
+    workspace
+      .views
+      .iter()
+      .map(|view| view.name.clone())
+      .collect::<Vec<_>>()
+  };
+  assert_eq!(names(&new_workspace), names(&current_workspace));
+  assert!(!names(&new_workspace).contains(&"My trashed view".to_string()));
+  for view in &new_workspace.views {
+    assert_eq!(view.parent_view_id, new_workspace.id);
+    assert!(current_workspace
+      .views
+      .iter()
+      .all(|original_view| original_view.id != view.id));
+  }
+
+  // The original workspace is untouched
+  let workspace = test.get_current_workspace().await;
+  assert_eq!(workspace.id, current_workspace.id);
+  assert_eq!(names(&workspace), names(&current_workspace));
+}
+
+#[tokio::test]
+async fn sort_child_views_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+  let parent = test
+    .create_view(&current_workspace.id, "My view".to_string())
+    .await;
+  for name in ["b", "A", "D", "c"] {
+    let _ = test.create_view(&parent.id, name.to_string()).await;
+  }
+  let child_names = |view: ViewPB| {
+    view
+      .child_views
+      .into_iter()
+      .map(|child_view| child_view.name)
+      .collect::<Vec<_>>()
+  };
+
+  sort_child_views(&test, &parent.id, ViewSortOrderPB::Ascending).await;
+  assert_eq!(
+    child_names(test.get_view(&parent.id).await),
+    vec!["A", "b", "c", "D"]
+  );
+
+  // The pinned view stays on top
+  let view_c = test.get_view(&parent.id).await.child_views[2].clone();
+  toggle_view_pin(&test, vec![view_c.id.clone()]).await;
+  sort_child_views(&test, &parent.id, ViewSortOrderPB::Ascending).await;
+  assert_eq!(
+    child_names(test.get_view(&parent.id).await),
+    vec!["c", "A", "b", "D"]
+  );
+
+  toggle_view_pin(&test, vec![view_c.id.clone()]).await;
+  sort_child_views(&test, &parent.id, ViewSortOrderPB::Descending).await;
+  assert_eq!(
+    child_names(test.get_view(&parent.id).await),
+    vec!["D", "c", "b", "A"]
+  );
+}
+
+async fn sort_child_views(test: &EventIntegrationTest, parent_id: &str, order: ViewSortOrderPB) {
+  let error = EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::SortChildViews)
+    .payload(SortChildViewsPayloadPB {
+      parent_id: parent_id.to_string(),
+      order,
+    })
+    .async_send()
+    .await
+    .error();
+  assert!(error.is_none());
+}
+
+#[tokio::test]
+async fn get_view_path_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+  let parent = test
+    .create_view(&current_workspace.id, "Projects".to_string())
+    .await;
+  let child = test.create_view(&parent.id, "Q3 / Q4".to_string()).await;
+  let grandchild = test.create_view(&child.id, "Plan".to_string()).await;
+
+  let path = EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::GetViewPath)
+    .payload(ViewIdPB::from(grandchild.id.as_str()))
+    .async_send()
+    .await
+    .parse::<ViewPathPB>();
+  // The names containing the separator are kept as they are
+  assert_eq!(
+    path.segments,
+    vec![
+      current_workspace.name.clone(),
+      "Projects".to_string(),
+      "Q3 / Q4".to_string(),
+      "Plan".to_string()
+    ]
+  );
+  assert_eq!(
+    path.joined,
+    format!("{} / Projects / Q3 / Q4 / Plan", current_workspace.name)
+  );
+}
+
+#[tokio::test]
+async fn delete_view_result_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+  let parent = test
+    .create_view(&current_workspace.id, "My view".to_string())
+    .await;
+  let child = test.create_view(&parent.id, "My 1 view".to_string()).await;
+
+  let result = EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::DeleteView)
+    .payload(RepeatedViewIdPB {
+      items: vec![parent.id.clone(), "not_exist_view_id".to_string()],
+      cascade: None,
+    })
+    .async_send()
+    .await
+    .parse::<DeleteResultPB>();
+  assert_eq!(
+    result.trashed_ids.items,
+    vec![parent.id.clone(), child.id.clone()]
+  );
+  assert_eq!(result.failures.len(), 1);
+  assert_eq!(result.failures[0].view_id, "not_exist_view_id");
+  assert_eq!(result.failures[0].code, ErrorCode::RecordNotFound as i32);
+
+  // The views that are already in the trash are not reported again
+  let result = EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::DeleteView)
+    .payload(RepeatedViewIdPB {
+      items: vec![child.id.clone()],
+      cascade: None,
+    })
+    .async_send()
+    .await
+    .parse::<DeleteResultPB>();
+  assert!(result.trashed_ids.items.is_empty());
+  assert!(result.failures.is_empty());
+}
+
+#[tokio::test]
+async fn create_view_with_existing_id_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+  let view_id = "my_explicit_view_id".to_string();
+  let view = EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::CreateView)
+    .payload(CreateViewPayloadPB {
+      parent_view_id: current_workspace.id.clone(),
+      name: "My view".to_string(),
+      view_id: Some(view_id.clone()),
+      ..Default::default()
+    })
+    .async_send()
+    .await
+    .parse::<ViewPB>();
+  assert_eq!(view.id, view_id);
+
+  let error = EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::CreateView)
+    .payload(CreateViewPayloadPB {
+      parent_view_id: current_workspace.id.clone(),
+      name: "My other view".to_string(),
+      view_id: Some(view_id.clone()),
+      ..Default::default()
+    })
+    .async_send()
+    .await
+    .error()
+    .unwrap();
+  assert_eq!(error.code, ErrorCode::RecordAlreadyExists);
+
+  // The existing view is kept as it is
+  assert_eq!(test.get_view(&view_id).await.name, "My view");
+}
+
+#[tokio::test]
+async fn read_view_tree_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+  let parent = test
+    .create_view(&current_workspace.id, "My view".to_string())
+    .await;
+  let child = test.create_view(&parent.id, "My 1 view".to_string()).await;
+  let grandchild = test.create_view(&child.id, "My 1-1 view".to_string()).await;
+  let trashed = test.create_view(&parent.id, "My 2 view".to_string()).await;
+  test.delete_view(&trashed.id).await;
+
+  // Zero depth includes all the descendants, and the trashed view is excluded
+  let tree = read_view_tree(&test, &parent.id, None).await;
+  assert_eq!(tree.view.id, parent.id);
+  assert!(tree.view.child_views.is_empty());
+  assert_eq!(tree.child_views.len(), 1);
+  assert_eq!(tree.child_views[0].view.id, child.id);
+  assert_eq!(tree.child_views[0].child_views.len(), 1);
+  assert_eq!(tree.child_views[0].child_views[0].view.id, grandchild.id);
+
+  let tree = read_view_tree(&test, &parent.id, Some(1)).await;
+  assert_eq!(tree.child_views.len(), 1);
+  assert!(tree.child_views[0].child_views.is_empty());
+
+  // The trashed view can't be read
+  let error = EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::ReadViewTree)
+    .payload(ViewTreePayloadPB {
+      view_id: trashed.id.clone(),
+      depth: None,
+    })
+    .async_send()
+    .await
+    .error()
+    .unwrap();
+  assert_eq!(error.code, ErrorCode::RecordNotFound);
+}
+
+async fn read_view_tree(
+  test: &EventIntegrationTest,
+  view_id: &str,
+  depth: Option<u32>,
+) -> ViewTreePB {
+  EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::ReadViewTree)
+    .payload(ViewTreePayloadPB {
+      view_id: view_id.to_string(),
+      depth,
+    })
+    .async_send()
+    .await
+    .parse::<ViewTreePB>()
+}
+
+#[tokio::test]
+async fn get_child_views_with_count_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+  let parent = test
+    .create_view(&current_workspace.id, "My view".to_string())
+    .await;
+  let first = test.create_view(&parent.id, "My 1 view".to_string()).await;
+  test.create_view(&first.id, "My 1-1 view".to_string()).await;
+  let second = test.create_view(&parent.id, "My 2 view".to_string()).await;
+  let trashed = test
+    .create_view(&second.id, "My 2-1 view".to_string())
+    .await;
+  test.delete_view(&trashed.id).await;
+
+  let views = EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::GetChildViews)
+    .payload(ViewIdPB {
+      value: parent.id.clone(),
+    })
+    .async_send()
+    .await
+    .parse::<RepeatedViewPB>()
+    .items;
+  assert_eq!(views.len(), 2);
+  assert_eq!(views[0].id, first.id);
+  assert_eq!(views[0].child_count, 1);
+  assert!(views[0].child_views.is_empty());
+  // The trashed child view is not counted
+  assert_eq!(views[1].id, second.id);
+  assert_eq!(views[1].child_count, 0);
+  // The other views count the child views the same way
+  assert_eq!(test.get_view(&second.id).await.child_count, 0);
+  let parent = test.get_view(&parent.id).await;
+  assert_eq!(parent.child_count, 2);
+  assert_eq!(parent.child_views[1].child_count, 0);
+}
+
+#[tokio::test]
+async fn favorite_subtree_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+  let parent = test
+    .create_view(&current_workspace.id, "My view".to_string())
+    .await;
+  let child_1 = test.create_view(&parent.id, "My 1 view".to_string()).await;
+  let child_2 = test.create_view(&parent.id, "My 2 view".to_string()).await;
+  set_favorites(&test, vec![(child_1.id.clone(), true)]).await;
+
+  // The subtree contains three views, exceeding the limit
+  let error = EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::FavoriteSubtree)
+    .payload(FavoriteSubtreePayloadPB {
+      view_id: parent.id.clone(),
+      is_favorite: true,
+      limit: Some(2),
+    })
+    .async_send()
+    .await
+    .error()
+    .unwrap();
+  assert_eq!(error.code, ErrorCode::TooManyViews);
+  assert!(!test.get_view(&parent.id).await.is_favorite);
+
+  // The view that is already favorite is not reported
+  let view_ids = EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::FavoriteSubtree)
+    .payload(FavoriteSubtreePayloadPB {
+      view_id: parent.id.clone(),
+      is_favorite: true,
+      limit: None,
+    })
+    .async_send()
+    .await
+    .parse::<RepeatedViewIdPB>()
+    .items;
+  assert_eq!(view_ids, vec![parent.id.clone(), child_2.id.clone()]);
+  for view_id in [&parent.id, &child_1.id, &child_2.id] {
+    assert!(test.get_view(view_id).await.is_favorite);
+  }
+}
+
+#[tokio::test]
+async fn reorder_view_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+  let parent = test
+    .create_view(&current_workspace.id, "My view".to_string())
+    .await;
+  let view_1 = test.create_view(&parent.id, "My 1 view".to_string()).await;
+  let view_2 = test.create_view(&parent.id, "My 2 view".to_string()).await;
+  let view_3 = test.create_view(&parent.id, "My 3 view".to_string()).await;
+
+  reorder_view(&test, &view_3.id, 0).await;
+  let child_view_ids = get_child_view_ids(&test, &parent.id).await;
+  assert_eq!(
+    child_view_ids,
+    vec![view_3.id.clone(), view_1.id.clone(), view_2.id.clone()]
+  );
+
+  // The index beyond the last sibling moves the view to the end
+  reorder_view(&test, &view_3.id, 100).await;
+  let child_view_ids = get_child_view_ids(&test, &parent.id).await;
+  assert_eq!(child_view_ids, vec![view_1.id, view_2.id, view_3.id]);
+}
+
+async fn reorder_view(test: &EventIntegrationTest, view_id: &str, new_index: u32) {
+  let error = EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::ReorderView)
+    .payload(ReorderViewPayloadPB {
+      view_id: view_id.to_string(),
+      new_index,
+    })
+    .async_send()
+    .await
+    .error();
+  assert!(error.is_none());
+}
+
+async fn get_child_view_ids(test: &EventIntegrationTest, view_id: &str) -> Vec<String> {
+  test
+    .get_view(view_id)
+    .await
+    .child_views
+    .into_iter()
+    .map(|view| view.id)
+    .collect()
+}
+
+#[tokio::test]
+async fn get_folder_sync_status_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+  test
+    .create_view(&current_workspace.id, "My view".to_string())
+    .await;
+
+  // The local folder is never synced
+  let status = EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::GetFolderSyncStatus)
+    .async_send()
+    .await
+    .parse::<FolderSyncStatusPB>();
+  assert!(!status.pending_changes);
+  assert_eq!(status.last_synced_at, 0);
+}
+
+#[tokio::test]
+async fn create_view_from_template_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+  let template = test
+    .create_view(&current_workspace.id, "Weekly report".to_string())
+    .await;
+  let template_child = test.create_view(&template.id, "Summary".to_string()).await;
+  let parent = test
+    .create_view(&current_workspace.id, "Reports".to_string())
+    .await;
+
+  let view = EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::CreateViewFromTemplate)
+    .payload(CreateFromTemplatePayloadPB {
+      template_id: template.id.clone(),
+      parent_id: parent.id.clone(),
+      name: "Week 1".to_string(),
+    })
+    .async_send()
+    .await
+    .parse::<ViewPB>();
+  assert_ne!(view.id, template.id);
+  assert_eq!(view.name, "Week 1");
+  assert_eq!(view.parent_view_id, parent.id);
+  assert_eq!(view.child_views.len(), 1);
+  assert_ne!(view.child_views[0].id, template_child.id);
+  assert_eq!(view.child_views[0].name, "Summary");
+
+  // The template is left untouched
+  let template = test.get_view(&template.id).await;
+  assert_eq!(template.name, "Weekly report");
+  assert_eq!(template.child_views.len(), 1);
+}
+
+#[tokio::test]
+async fn set_view_template_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+  let parent = test
+    .create_view(&current_workspace.id, "My view".to_string())
+    .await;
+  let document = test.create_view(&parent.id, "My 1 view".to_string()).await;
+  let grid = test
+    .create_grid(&parent.id, "My grid".to_string(), vec![])
+    .await;
+  let other = test.create_view(&parent.id, "My 2 view".to_string()).await;
+  for view_id in [&document.id, &grid.id] {
+    set_view_template(&test, view_id, true).await;
+  }
+
+  // The templates are excluded from the child views of the parent
+  let child_view_ids = get_child_view_ids(&test, &parent.id).await;
+  assert_eq!(child_view_ids, vec![other.id.clone()]);
+
+  let templates = get_templates(&test).await;
+  assert_eq!(templates.len(), 2);
+  assert_eq!(templates[0].id, document.id);
+  assert_eq!(templates[1].id, grid.id);
+  assert!(templates.iter().all(|view| view.is_template));
+
+  set_view_template(&test, &grid.id, false).await;
+  let templates = get_templates(&test).await;
+  assert_eq!(templates.len(), 1);
+  let child_view_ids = get_child_view_ids(&test, &parent.id).await;
+  assert_eq!(child_view_ids, vec![grid.id, other.id]);
+}
+
+async fn set_view_template(test: &EventIntegrationTest, view_id: &str, is_template: bool) {
+  let error = EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::SetViewTemplate)
+    .payload(SetTemplatePayloadPB {
+      view_id: view_id.to_string(),
+      is_template,
+    })
+    .async_send()
+    .await
+    .error();
+  assert!(error.is_none());
+}
+
+async fn get_templates(test: &EventIntegrationTest) -> Vec<ViewPB> {
+  EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::GetTemplates)
+    .async_send()
+    .await
+    .parse::<RepeatedViewPB>()
+    .items
+}
+
+#[tokio::test]
+async fn rename_view_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+  let view = test
+    .create_view(&current_workspace.id, "My view".to_string())
+    .await;
+
+  let error = EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::RenameView)
+    .payload(RenameViewPayloadPB {
+      view_id: view.id.clone(),
+      name: "  My renamed view ".to_string(),
+    })
+    .async_send()
+    .await
+    .error();
+  assert!(error.is_none());
+  let renamed_view = test.get_view(&view.id).await;
+  assert_eq!(renamed_view.name, "My renamed view");
+  assert_eq!(renamed_view.layout, view.layout);
+
+  let error = EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::RenameView)
+    .payload(RenameViewPayloadPB {
+      view_id: view.id.clone(),
+      name: "   ".to_string(),
+    })
+    .async_send()
+    .await
+    .error()
+    .unwrap();
+  assert_eq!(error.code, ErrorCode::ViewNameInvalid);
+  assert_eq!(test.get_view(&view.id).await.name, "My renamed view");
+}
+
+#[tokio::test]
+async fn get_views_in_range_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+  let view = test
+    .create_view(&current_workspace.id, "My view".to_string())
+    .await;
+  let trashed_view = test
+    .create_view(&current_workspace.id, "My trashed view".to_string())
+    .await;
+  test.delete_view(&trashed_view.id).await;
+
+  let views = get_views_in_range(&test, &current_workspace.id, view.last_edited_time, None).await;
+  assert!(views.iter().any(|item| item.id == view.id));
+  assert!(views.iter().all(|item| item.id != trashed_view.id));
+  assert!(views
+    .windows(2)
+    .all(|pair| pair[0].last_edited_time >= pair[1].last_edited_time));
+
+  // The range ends before the view was edited
+  let views = get_views_in_range(
+    &test,
+    &current_workspace.id,
+    0,
+    Some(view.last_edited_time - 1),
+  )
+  .await;
+  assert!(views.iter().all(|item| item.id != view.id));
+}
+
+async fn get_views_in_range(
+  test: &EventIntegrationTest,
+  workspace_id: &str,
+  start: i64,
+  end: Option<i64>,
+) -> Vec<ViewPB> {
+  EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::GetViewsInRange)
+    .payload(ViewsTimeRangePayloadPB {
+      workspace_id: workspace_id.to_string(),
+      start,
+      end,
+    })
+    .async_send()
+    .await
+    .parse::<RepeatedViewPB>()
+    .items
+}
+
+#[tokio::test]
+async fn get_trash_count_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+  assert_eq!(get_trash_count(&test, &current_workspace.id).await, 0);
+
+  for i in 0..3 {
+    let view = test
+      .create_view(&current_workspace.id, format!("My {} view", i))
+      .await;
+    test.delete_view(&view.id).await;
+  }
+  assert_eq!(get_trash_count(&test, &current_workspace.id).await, 3);
+}
+
+async fn get_trash_count(test: &EventIntegrationTest, workspace_id: &str) -> u32 {
+  EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::GetTrashCount)
+    .payload(WorkspaceIdPB {
+      value: workspace_id.to_string(),
+      preload_depth: None,
+    })
+    .async_send()
+    .await
+    .parse::<TrashCountPB>()
+    .count
+}
+
+#[tokio::test]
+async fn read_trash_paged_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+  let mut view_ids = vec![];
+  for i in 0..3 {
+    let view = test
+      .create_view(&current_workspace.id, format!("My {} view", i))
+      .await;
+    test.delete_view(&view.id).await;
+    view_ids.push(view.id);
+    // The deletion time is in seconds.
+    tokio::time::sleep(std::time::Duration::from_millis(1100)).await;
+  }
+
+  // The newest deleted first
+  let page = read_trash_paged(&test, &current_workspace.id, 0, 2).await;
+  assert_eq!(page.total_count, 3);
+  let ids = page
+    .trash
+    .items
+    .iter()
+    .map(|trash| trash.id.clone())
+    .collect::<Vec<_>>();
+  assert_eq!(ids, vec![view_ids[2].clone(), view_ids[1].clone()]);
+
+  let page = read_trash_paged(&test, &current_workspace.id, 2, 2).await;
+  assert_eq!(page.total_count, 3);
+  assert_eq!(page.trash.items.len(), 1);
+  assert_eq!(page.trash.items[0].id, view_ids[0]);
+
+  let page = read_trash_paged(&test, &current_workspace.id, 3, 2).await;
+  assert_eq!(page.total_count, 3);
+  assert!(page.trash.items.is_empty());
+}
+
+async fn read_trash_paged(
+  test: &EventIntegrationTest,
+  workspace_id: &str,
+  offset: u32,
+  limit: u32,
+) -> TrashPagePB {
+  EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::ReadTrashPaged)
+    .payload(TrashPagePayloadPB {
+      offset,
+      limit,
+      workspace_id: workspace_id.to_string(),
+    })
+    .async_send()
+    .await
+    .parse::<TrashPagePB>()
+}
+
+#[tokio::test]
+async fn update_view_url_icon_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+  let view = test
+    .create_view(&current_workspace.id, "My view".to_string())
+    .await;
+
+  let icon = ViewIconPB {
+    ty: ViewIconTypePB::Url,
+    value: "https://appflowy.io/icon.png".to_string(),
+  };
+  let error = test
+    .update_view_icon(UpdateViewIconPayloadPB {
+      view_id: view.id.clone(),
+      icon: Some(icon.clone()),
+    })
+    .await;
+  assert!(error.is_none());
+  assert_eq!(test.get_view(&view.id).await.icon, Some(icon.clone()));
+
+  for value in ["appflowy.io/icon.png", "ftp://appflowy.io/icon.png"] {
+    let error = test
+      .update_view_icon(UpdateViewIconPayloadPB {
+        view_id: view.id.clone(),
+        icon: Some(ViewIconPB {
+          ty: ViewIconTypePB::Url,
+          value: value.to_string(),
+        }),
+      })
+      .await
+      .unwrap();
+    assert_eq!(error.code, ErrorCode::InvalidURL);
+  }
+  assert_eq!(test.get_view(&view.id).await.icon, Some(icon));
+
+  // The empty value clears the icon
+  let error = test
+    .update_view_icon(UpdateViewIconPayloadPB {
+      view_id: view.id.clone(),
+      icon: Some(ViewIconPB {
+        ty: ViewIconTypePB::Url,
+        value: "".to_string(),
+      }),
+    })
+    .await;
+  assert!(error.is_none());
+  assert!(test.get_view(&view.id).await.icon.is_none());
+}
+
+#[tokio::test]
+async fn set_view_expanded_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+  let parent = test
+    .create_view(&current_workspace.id, "My view".to_string())
+    .await;
+  let child = test.create_view(&parent.id, "My 1 view".to_string()).await;
+  assert!(!parent.is_expanded);
+
+  let error = set_view_expanded(
+    &test,
+    vec![(parent.id.clone(), true), (child.id.clone(), true)],
+  )
+  .await;
+  assert!(error.is_none());
+  let parent = test.get_view(&parent.id).await;
+  assert!(parent.is_expanded);
+  assert!(parent.child_views[0].is_expanded);
+
+  // Nothing is changed if any of the views doesn't exist
+  let error = set_view_expanded(
+    &test,
+    vec![(parent.id.clone(), false), ("not_exist".to_string(), false)],
+  )
+  .await
+  .unwrap();
+  assert_eq!(error.code, ErrorCode::RecordNotFound);
+  assert!(test.get_view(&parent.id).await.is_expanded);
+}
+
+async fn set_view_expanded(
+  test: &EventIntegrationTest,
+  items: Vec<(String, bool)>,
+) -> Option<FlowyError> {
+  EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::SetViewExpanded)
+    .payload(RepeatedSetExpandedPB {
+      items: items
+        .into_iter()
+        .map(|(view_id, is_expanded)| SetExpandedPB {
+          view_id,
+          is_expanded,
+        })
+        .collect(),
+    })
+    .async_send()
+    .await
+    .error()
+}
+
+#[tokio::test]
+async fn create_view_at_index_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+  let parent = test
+    .create_view(&current_workspace.id, "My view".to_string())
+    .await;
+  let trashed_view = test.create_view(&parent.id, "My 1 view".to_string()).await;
+  let view_2 = test.create_view(&parent.id, "My 2 view".to_string()).await;
+  let view_3 = test.create_view(&parent.id, "My 3 view".to_string()).await;
+  test.delete_view(&trashed_view.id).await;
+
+  // The trashed view is not counted
+  let view = create_view_at(&test, &parent.id, "My new view", 1).await;
+  let child_view_ids = get_child_view_ids(&test, &parent.id).await;
+  assert_eq!(
+    child_view_ids,
+    vec![view_2.id.clone(), view.id.clone(), view_3.id.clone()]
+  );
+
+  // The view is appended if the index is out of range
+  let last_view = create_view_at(&test, &parent.id, "My last view", 100).await;
+  let child_view_ids = get_child_view_ids(&test, &parent.id).await;
+  assert_eq!(child_view_ids.last(), Some(&last_view.id));
+}
+
+async fn create_view_at(
+  test: &EventIntegrationTest,
+  parent_view_id: &str,
+  name: &str,
+  index: u32,
+) -> ViewPB {
+  EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::CreateViewAt)
+    .payload(CreateViewAtPayloadPB {
+      view: CreateViewPayloadPB {
+        parent_view_id: parent_view_id.to_string(),
+        name: name.to_string(),
+        ..Default::default()
+      },
+      index,
+    })
+    .async_send()
+    .await
+    .parse::<ViewPB>()
+}
+
+#[tokio::test]
+async fn import_preview_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+  let parent = test
+    .create_view(&current_workspace.id, "My view".to_string())
+    .await;
+  test.create_view(&parent.id, "Imported".to_string()).await;
+
+  let preview = import_preview(&test, &parent.id, "Imported").await;
+  assert_eq!(preview.items.len(), 1);
+  assert_eq!(preview.items[0].name, "Imported");
+  assert_eq!(preview.items[0].layout, ViewLayoutPB::Document);
+  assert_eq!(preview.items[0].depth, 1);
+  assert!(preview.items[0].name_collision);
+  assert!(!import_preview(&test, &parent.id, "Other").await.items[0].name_collision);
+
+  // The preview doesn't create any view.
+  assert_eq!(get_child_view_ids(&test, &parent.id).await.len(), 1);
+}
+
+#[tokio::test]
+async fn import_dry_run_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+  let parent = test
+    .create_view(&current_workspace.id, "My view".to_string())
+    .await;
+  test.create_view(&parent.id, "Imported".to_string()).await;
+  EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::SetUniqueSiblingNames)
+    .payload(UniqueSiblingNamesPayloadPB {
+      workspace_id: current_workspace.id.clone(),
+      enabled: true,
+    })
+    .async_send()
+    .await;
+
+  // Notes.md
+  // Notes/
+  //   Tasks.csv
+  // Notes.md
+  let export_dir = tempdir::TempDir::new("notion_export").unwrap();
+  let notes_dir = export_dir
+    .path()
+    .join("Notes 0a1b2c3d4e5f60718293a4b5c6d7e8f9");
+  std::fs::create_dir(&notes_dir).unwrap();
+  std::fs::write(
+    export_dir
+      .path()
+      .join("Notes 0a1b2c3d4e5f60718293a4b5c6d7e8f9.md"),
+    "Hello",
+  )
+  .unwrap();
+  std::fs::write(notes_dir.join("Tasks.csv"), "Name,Done\nWrite,Yes\n").unwrap();
+  std::fs::write(
+    export_dir
+      .path()
+      .join("Notes 9f8e7d6c5b4a30291807f6e5d4c3b2a1.md"),
+    "World",
+  )
+  .unwrap();
+  let payload = ImportPB {
+    file_path: Some(export_dir.path().to_string_lossy().to_string()),
+    ..import_payload(&parent.id, ViewLayoutPB::Document, ImportTypePB::NotionZip)
+  };
+
+  // The names are suffixed the same way as the import does
+  let preview = EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::ImportPreview)
+    .payload(payload.clone())
+    .async_send()
+    .await
+    .parse::<ImportPreviewPB>();
+  let items = preview
+    .items
+    .iter()
+    .map(|item| (item.name.as_str(), item.depth, item.name_collision))
+    .collect::<Vec<_>>();
+  assert_eq!(
+    items,
+    vec![
+      ("Imported (2)", 1, true),
+      ("Notes", 2, true),
+      ("Tasks", 3, false),
+      ("Notes (2)", 2, true)
+    ]
+  );
+  assert!(preview.skipped.is_empty());
+
+  // The pages over the view limit are skipped
+  let view_count = EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::GetWorkspaceStats)
+    .payload(WorkspaceIdPB {
+      value: current_workspace.id.clone(),
+      preload_depth: None,
+    })
+    .async_send()
+    .await
+    .parse::<WorkspaceStatsPB>()
+    .total_views;
+  set_view_limit(&test, &current_workspace.id, view_count as u32 + 2).await;
+  let result = EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::ImportData)
+    .payload(ImportPB {
+      dry_run: true,
+      ..payload.clone()
+    })
+    .async_send()
+    .await
+    .parse::<ImportResultPB>();
+  assert_eq!(result.created_count, 2);
+  assert_eq!(result.requested_count, 4);
+  assert_eq!(result.imported_names, vec!["Imported (2)", "Notes"]);
+  let skipped = result
+    .skipped
+    .iter()
+    .map(|item| item.name.as_str())
+    .collect::<Vec<_>>();
+  assert_eq!(skipped, vec!["Tasks", "Notes (2)"]);
+  assert!(result
+    .skipped
+    .iter()
+    .all(|item| item.code == ErrorCode::ViewLimitReached.value()));
+  // The dry run doesn't create any view.
+  assert_eq!(get_child_view_ids(&test, &parent.id).await.len(), 1);
+
+  // The import creates the planned views
+  let result = EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::ImportData)
+    .payload(payload)
+    .async_send()
+    .await
+    .parse::<ImportResultPB>();
+  assert_eq!(result.created_count, 2);
+  assert_eq!(result.imported_names, vec!["Imported (2)", "Notes"]);
+  assert_eq!(result.skipped.len(), 2);
+  assert_eq!(test.get_view(&result.view.id).await.name, "Imported (2)");
+}
+
+async fn import_preview(
+  test: &EventIntegrationTest,
+  parent_view_id: &str,
+  name: &str,
+) -> ImportPreviewPB {
+  EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::ImportPreview)
+    .payload(ImportPB {
+      parent_view_id: parent_view_id.to_string(),
+      name: name.to_string(),
+      data: Some("Hello".as_bytes().to_vec()),
+      file_path: None,
+      view_layout: ViewLayoutPB::Document,
+      import_type: ImportTypePB::HistoryDocument,
+      import_id: None,
+      dry_run: true,
+    })
+    .async_send()
+    .await
+    .parse::<ImportPreviewPB>()
+}
+
+#[tokio::test]
+async fn update_view_color_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+  let view = test
+    .create_view(&current_workspace.id, "My view".to_string())
+    .await;
+  let other_view = test
+    .create_view(&current_workspace.id, "My other view".to_string())
+    .await;
+  assert_eq!(view.color, ViewColorPB::None);
+
+  let error = test
+    .update_view(UpdateViewPayloadPB {
+      view_id: view.id.clone(),
+      color: Some(ViewColorPB::Blue),
+      ..Default::default()
+    })
+    .await;
+  assert!(error.is_none());
+  assert_eq!(test.get_view(&view.id).await.color, ViewColorPB::Blue);
+  let views = get_views_by_color(&test, ViewColorPB::Blue).await;
+  assert_eq!(views.len(), 1);
+  assert_eq!(views[0].id, view.id);
+  let views = get_views_by_color(&test, ViewColorPB::None).await;
+  assert!(views.iter().any(|view| view.id == other_view.id));
+  assert!(views.iter().all(|item| item.id != view.id));
+
+  // Clear the color
+  let error = test
+    .update_view(UpdateViewPayloadPB {
+      view_id: view.id.clone(),
+      color: Some(ViewColorPB::None),
+      ..Default::default()
+    })
+    .await;
+  assert!(error.is_none());
+  assert_eq!(test.get_view(&view.id).await.color, ViewColorPB::None);
+  assert!(get_views_by_color(&test, ViewColorPB::Blue)
+    .await
+    .is_empty());
+}
+
+async fn get_views_by_color(test: &EventIntegrationTest, color: ViewColorPB) -> Vec<ViewPB> {
+  EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::GetViewsByColor)
+    .payload(ViewColorFilterPB { color })
+    .async_send()
+    .await
+    .parse::<RepeatedViewPB>()
+    .items
+}
+
+#[tokio::test]
+async fn subscribe_view_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+  let view = test
+    .create_view(&current_workspace.id, "My view".to_string())
+    .await;
+  assert!(subscribe_view(&test, &view.id).await.is_none());
+  assert!(unsubscribe_view(&test, &view.id).await.is_none());
+  // Unsubscribing the view that isn't subscribed does nothing.
+  assert!(unsubscribe_view(&test, &view.id).await.is_none());
+
+  let error = subscribe_view(&test, "not exist view id").await.unwrap();
+  assert_eq!(error.code, ErrorCode::RecordNotFound);
+}
+
+async fn subscribe_view(test: &EventIntegrationTest, view_id: &str) -> Option<FlowyError> {
+  EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::SubscribeView)
+    .payload(ViewIdPB {
+      value: view_id.to_string(),
+    })
+    .async_send()
+    .await
+    .error()
+}
+
+async fn unsubscribe_view(test: &EventIntegrationTest, view_id: &str) -> Option<FlowyError> {
+  EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::UnsubscribeView)
+    .payload(ViewIdPB {
+      value: view_id.to_string(),
+    })
+    .async_send()
+    .await
+    .error()
+}
+
+#[tokio::test]
+async fn get_default_open_view_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+  let view = test
+    .create_view(&current_workspace.id, "My view".to_string())
+    .await;
+  set_latest_view(&test, &view.id).await.unwrap();
+  let default_view = get_default_open_view(&test, &current_workspace.id).await;
+  assert_eq!(default_view.unwrap().id, view.id);
+
+  // Fall back to the first top level view if the latest view is in the trash.
+  test.delete_view(&view.id).await;
+  let first_view = test.get_current_workspace().await.views.remove(0);
+  let default_view = get_default_open_view(&test, &current_workspace.id).await;
+  assert_eq!(default_view.unwrap().id, first_view.id);
+}
+
+async fn get_default_open_view(test: &EventIntegrationTest, workspace_id: &str) -> Option<ViewPB> {
+  EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::GetDefaultOpenView)
+    .payload(WorkspaceIdPB {
+      value: workspace_id.to_string(),
+      preload_depth: None,
+    })
+    .async_send()
+    .await
+    .parse::<DefaultOpenViewPB>()
+    .view
+}
+
+#[tokio::test]
+async fn trash_view_with_reason_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+  let view = test
+    .create_view(&current_workspace.id, "My view".to_string())
+    .await;
+  let trashed_ids = trash_view_with_reason(&test, &view.id, Some(" Outdated ".to_string()))
+    .await
+    .unwrap();
+  assert_eq!(trashed_ids, vec![view.id.clone()]);
+
+  let trash = test.read_trash(&current_workspace.id).await.items;
+  assert_eq!(trash.len(), 1);
+  assert_eq!(trash[0].reason, "Outdated");
+
+  // The reason is kept after the view is restored.
+  EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::PutbackTrash)
+    .payload(TrashIdPB {
+      id: view.id.clone(),
+    })
+    .async_send()
+    .await;
+  let view = test.get_view(&view.id).await;
+  assert_eq!(view.trash_reason, Some("Outdated".to_string()));
+
+  let error = trash_view_with_reason(&test, &view.id, Some("a".repeat(201)))
+    .await
+    .unwrap_err();
+  assert_eq!(error.code, ErrorCode::TrashReasonTooLong);
+}
+
+async fn trash_view_with_reason(
+  test: &EventIntegrationTest,
+  view_id: &str,
+  reason: Option<String>,
+) -> Result<Vec<String>, FlowyError> {
+  EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::TrashViewWithReason)
+    .payload(TrashViewPayloadPB {
+      view_id: view_id.to_string(),
+      reason,
+    })
+    .async_send()
+    .await
+    .try_parse::<RepeatedViewIdPB>()
+    .map(|ids| ids.items)
+}
+
+#[tokio::test]
+async fn trash_view_cascade_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+  // The descendants are trashed along with the view by default
+  let (parent, child, grandchild_1, grandchild_2) =
+    create_three_level_tree(&test, &current_workspace.id).await;
+  let result = trash_view_with_cascade(&test, &child.id, None).await;
+  assert_eq!(result.trashed_ids.items.len(), 3);
+  assert!(result.reparented_ids.is_empty());
+  for view_id in [&child.id, &grandchild_1.id, &grandchild_2.id] {
+    assert!(result.trashed_ids.items.contains(view_id));
+  }
+  assert!(test.get_view(&parent.id).await.child_views.is_empty());
+
+  // The child views are moved to the grandparent and kept
+  let (parent, child, grandchild_1, grandchild_2) =
+    create_three_level_tree(&test, &current_workspace.id).await;
+  // The trashed child view stays in the trash and is not reparented
+  test.delete_view(&grandchild_1.id).await;
+  let result = trash_view_with_cascade(&test, &child.id, Some(false)).await;
+  assert_eq!(result.trashed_ids.items, vec![child.id.clone()]);
+  assert_eq!(result.reparented_ids, vec![grandchild_2.id.clone()]);
+  let child_view_ids = test
+    .get_view(&parent.id)
+    .await
+    .child_views
+    .into_iter()
+    .map(|view| view.id)
+    .collect::<Vec<_>>();
+  assert_eq!(child_view_ids, vec![grandchild_2.id.clone()]);
+  assert_eq!(
+    test.get_view(&grandchild_2.id).await.parent_view_id,
+    parent.id
+  );
+}
+
+/// Creates a parent with one child, which has two child views of its own.
+async fn create_three_level_tree(
+  test: &EventIntegrationTest,
+  workspace_id: &str,
+) -> (ViewPB, ViewPB, ViewPB, ViewPB) {
+  let parent = test.create_view(workspace_id, "Parent".to_string()).await;
+  let child = test.create_view(&parent.id, "Child".to_string()).await;
+  let grandchild_1 = test
+    .create_view(&child.id, "Grandchild 1".to_string())
+    .await;
+  let grandchild_2 = test
+    .create_view(&child.id, "Grandchild 2".to_string())
+    .await;
+  (parent, child, grandchild_1, grandchild_2)
+}
+
+async fn trash_view_with_cascade(
+  test: &EventIntegrationTest,
+  view_id: &str,
+  cascade: Option<bool>,
+) -> DeleteResultPB {
+  EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::DeleteView)
+    .payload(RepeatedViewIdPB {
+      items: vec![view_id.to_string()],
+      cascade,
+    })
+    .async_send()
+    .await
+    .parse::<DeleteResultPB>()
+}
+
+#[tokio::test]
+async fn merge_views_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+  let target = test
+    .create_view(&current_workspace.id, "Inbox".to_string())
+    .await;
+  let target_child = test.create_view(&target.id, "My 1 view".to_string()).await;
+  let source = test
+    .create_view(&current_workspace.id, "Inbox".to_string())
+    .await;
+  let source_child_1 = test.create_view(&source.id, "My 2 view".to_string()).await;
+  let source_child_2 = test.create_view(&source.id, "My 3 view".to_string()).await;
+
+  // The source can't be merged into its descendant.
+  let error = merge_views(&test, &source.id, &source_child_1.id)
+    .await
+    .unwrap_err();
+  assert_eq!(error.code, ErrorCode::InvalidParams);
+
+  // Nothing is moved into the locked target.
+  set_view_locked(&test, &target.id, true).await;
+  let error = merge_views(&test, &source.id, &target.id)
+    .await
+    .unwrap_err();
+  assert_eq!(error.code, ErrorCode::ViewIsLocked);
+  assert_eq!(get_child_view_ids(&test, &target.id).await.len(), 1);
+  set_view_locked(&test, &target.id, false).await;
+
+  let target = merge_views(&test, &source.id, &target.id).await.unwrap();
+  assert_eq!(target.child_count, 3);
+  assert_eq!(
+    get_child_view_ids(&test, &target.id).await,
+    vec![target_child.id, source_child_1.id, source_child_2.id]
+  );
+  // The emptied source is moved to the trash.
+  assert_eq!(get_trash_count(&test, &current_workspace.id).await, 1);
+}
+
+async fn merge_views(
+  test: &EventIntegrationTest,
+  source_view_id: &str,
+  target_view_id: &str,
+) -> Result<ViewPB, FlowyError> {
+  EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::MergeViews)
+    .payload(MergeViewsPayloadPB {
+      source_view_id: source_view_id.to_string(),
+      target_view_id: target_view_id.to_string(),
+    })
+    .async_send()
+    .await
+    .try_parse::<ViewPB>()
+}
+
+#[tokio::test]
+async fn view_meta_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+  let view = test
+    .create_view(&current_workspace.id, "My view".to_string())
+    .await;
+  assert_eq!(get_view_meta(&test, &view.id, "plugin.key").await, "");
+
+  set_view_meta(&test, &view.id, "plugin.key", "value").await;
+  assert_eq!(get_view_meta(&test, &view.id, "plugin.key").await, "value");
+  // The metadata is stored in the view of the folder, so it's synced with the folder.
+  assert_eq!(
+    read_view_extra_json(&test, &view.id)["meta"],
+    serde_json::json!({ "plugin.key": "value" })
+  );
+
+  // The empty value deletes the key
+  set_view_meta(&test, &view.id, "plugin.key", "").await;
+  assert_eq!(get_view_meta(&test, &view.id, "plugin.key").await, "");
+  assert!(read_view_extra_json(&test, &view.id)["meta"].is_null());
+}
+
+async fn set_view_meta(test: &EventIntegrationTest, view_id: &str, key: &str, value: &str) {
+  let error = EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::SetViewMeta)
+    .payload(ViewMetaPayloadPB {
+      view_id: view_id.to_string(),
+      key: key.to_string(),
+      value: value.to_string(),
+    })
+    .async_send()
+    .await
+    .error();
+  assert!(error.is_none());
+}
+
+async fn get_view_meta(test: &EventIntegrationTest, view_id: &str, key: &str) -> String {
+  EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::GetViewMeta)
+    .payload(ViewMetaPayloadPB {
+      view_id: view_id.to_string(),
+      key: key.to_string(),
+      value: "".to_string(),
+    })
+    .async_send()
+    .await
+    .parse::<ViewMetaValuePB>()
+    .value
+}
+
+#[tokio::test]
+async fn restore_all_trash_result_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+  let parent = test
+    .create_view(&current_workspace.id, "My view".to_string())
+    .await;
+  let child = test.create_view(&parent.id, "My 1 view".to_string()).await;
+  let other = test
+    .create_view(&current_workspace.id, "My other view".to_string())
+    .await;
+  test.delete_view(&child.id).await;
+  test.delete_view(&other.id).await;
+  test.delete_view(&parent.id).await;
+
+  // Delete the parent permanently, so the child has no parent to go back to
+  EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::DeleteTrash)
+    .payload(RepeatedTrashIdPB {
+      items: vec![TrashIdPB {
+        id: parent.id.clone(),
+      }],
+    })
+    .async_send()
+    .await;
+
+  let result = EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::RestoreAllTrash)
+    .async_send()
+    .await
+    .parse::<RestoreAllResultPB>();
+  assert_eq!(result.restored_count, 2);
+  assert!(result.failed.is_empty());
+
+  // The child is attached to the workspace root because its parent no longer exists
+  let child = test.get_view(&child.id).await;
+  assert_eq!(child.parent_view_id, current_workspace.id);
+  assert_eq!(get_trash_count(&test, &current_workspace.id).await, 0);
+}
+
+#[tokio::test]
+async fn open_workspace_with_preload_depth_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+  let parent = test
+    .create_view(&current_workspace.id, "My view".to_string())
+    .await;
+  let child = test.create_view(&parent.id, "My 1 view".to_string()).await;
+  let grandchild = test.create_view(&child.id, "My 2 view".to_string()).await;
+
+  let get_child = |workspace: WorkspacePB| {
+    let parent = workspace
+      .views
+      .into_iter()
+      .find(|view| view.id == parent.id)
+      .unwrap();
+    assert_eq!(parent.child_views.len(), 1);
+    parent.child_views[0].clone()
+  };
+
+  // Only the direct child views are loaded by default
+  let workspace = open_workspace(&test, &current_workspace.id, None).await;
+  assert!(get_child(workspace).child_views.is_empty());
+
+  let workspace = open_workspace(&test, &current_workspace.id, Some(2)).await;
+  let child_views = get_child(workspace).child_views;
+  assert_eq!(child_views.len(), 1);
+  assert_eq!(child_views[0].id, grandchild.id);
+}
+
+async fn open_workspace(
+  test: &EventIntegrationTest,
+  workspace_id: &str,
+  preload_depth: Option<u32>,
+) -> WorkspacePB {
+  EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::OpenWorkspace)
+    .payload(WorkspaceIdPB {
+      value: workspace_id.to_string(),
+      preload_depth,
+    })
+    .async_send()
+    .await
+    .parse::<WorkspacePB>()
+}
+
+#[tokio::test]
+async fn unique_sibling_names_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+  let parent = test
+    .create_view(&current_workspace.id, "My view".to_string())
+    .await;
+  // The names are not changed by default
+  let view = test.create_view(&parent.id, "Notes".to_string()).await;
+  let view_2 = test.create_view(&parent.id, "Notes".to_string()).await;
+  assert_eq!(view_2.name, "Notes");
+
+  let error = EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::SetUniqueSiblingNames)
+    .payload(UniqueSiblingNamesPayloadPB {
+      workspace_id: current_workspace.id.clone(),
+      enabled: true,
+    })
+    .async_send()
+    .await
+    .error();
+  assert!(error.is_none());
+
+  test.create_view(&parent.id, "Notes (3)".to_string()).await;
+  let view_4 = test.create_view(&parent.id, "Notes".to_string()).await;
+  assert_eq!(view_4.name, "Notes (2)");
+
+  // Renaming the view to its own name doesn't add a suffix
+  let error = test
+    .update_view(UpdateViewPayloadPB {
+      view_id: view.id.clone(),
+      name: Some("Notes".to_string()),
+      ..Default::default()
+    })
+    .await;
+  assert!(error.is_none());
+  assert_eq!(test.get_view(&view.id).await.name, "Notes");
+
+  let error = test
+    .update_view(UpdateViewPayloadPB {
+      view_id: view_2.id.clone(),
+      name: Some("Notes (2)".to_string()),
+      ..Default::default()
+    })
+    .await;
+  assert!(error.is_none());
+  assert_eq!(test.get_view(&view_2.id).await.name, "Notes (2) (2)");
+
+  // The views created earlier in the batch count as the siblings of the later ones
+  let items = (0..2)
+    .map(|_| CreateViewPayloadPB {
+      parent_view_id: parent.id.clone(),
+      name: "Notes".to_string(),
+      ..Default::default()
+    })
+    .collect::<Vec<_>>();
+  let views = EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::CreateViews)
+    .payload(RepeatedCreateViewPayloadPB { items })
+    .async_send()
+    .await
+    .parse::<RepeatedViewPB>()
+    .items;
+  assert_eq!(views[0].name, "Notes (4)");
+  assert_eq!(views[1].name, "Notes (5)");
+}
+
+#[tokio::test]
+async fn export_view_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+  let parent = test
+    .create_view(&current_workspace.id, "My view".to_string())
+    .await;
+  let child = test.create_view(&parent.id, "Child".to_string()).await;
+  test.create_view(&child.id, "Grandchild".to_string()).await;
+  // The grid can't be exported to Markdown, it's skipped without failing the export.
+  test
+    .create_grid(&parent.id, "My grid".to_string(), vec![])
+    .await;
+
+  let data = export_view(&test, &parent.id, true).await;
+  assert_eq!(data.file_name, "My view.md");
+  let headings = data
+    .data
+    .lines()
+    .filter(|line| line.starts_with('#'))
+    .collect::<Vec<_>>();
+  assert_eq!(headings, vec!["# My view", "## Child", "### Grandchild"]);
+
+  let data = export_view(&test, &parent.id, false).await;
+  let headings = data
+    .data
+    .lines()
+    .filter(|line| line.starts_with('#'))
+    .collect::<Vec<_>>();
+  assert_eq!(headings, vec!["# My view"]);
+}
+
+async fn export_view(
+  test: &EventIntegrationTest,
+  view_id: &str,
+  include_children: bool,
+) -> ExportDataPB {
+  EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::ExportView)
+    .payload(ExportViewPayloadPB {
+      view_id: view_id.to_string(),
+      format: ExportFormatPB::Markdown,
+      include_children,
+    })
+    .async_send()
+    .await
+    .parse::<ExportDataPB>()
+}
+
+#[tokio::test]
+async fn export_favorites_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+  let parent = test
+    .create_view(&current_workspace.id, "My view".to_string())
+    .await;
+  let child = test
+    .create_view(&parent.id, "My [1] view".to_string())
+    .await;
+  let trashed_view = test
+    .create_view(&current_workspace.id, "My trashed view".to_string())
+    .await;
+  set_favorites(
+    &test,
+    vec![
+      (child.id.clone(), true),
+      (parent.id.clone(), true),
+      (trashed_view.id.clone(), true),
+    ],
+  )
+  .await;
+  test.delete_view(&trashed_view.id).await;
+
+  let data = EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::ExportFavorites)
+    .payload(ExportFavoritesPayloadPB {
+      format: ExportFormatPB::Markdown,
+    })
+    .async_send()
+    .await
+    .parse::<ExportDataPB>();
+  assert_eq!(data.file_name, "Favorites.md");
+  let lines = data.data.lines().collect::<Vec<_>>();
+  assert_eq!(lines.len(), 2);
+  assert_eq!(
+    lines[0],
+    format!(
+      "- [My \\[1\\] view](<{} / My view / My [1] view>)",
+      current_workspace.name
+    )
+  );
+  assert_eq!(
+    lines[1],
+    format!("- [My view](<{} / My view>)", current_workspace.name)
+  );
+}
+
+#[tokio::test]
+async fn empty_view_children_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+  let parent = test
+    .create_view(&current_workspace.id, "My view".to_string())
+    .await;
+  let child_1 = test.create_view(&parent.id, "My 1 view".to_string()).await;
+  let child_2 = test.create_view(&child_1.id, "My 2 view".to_string()).await;
+  let child_3 = test.create_view(&parent.id, "My 3 view".to_string()).await;
+  // The child view that is already in the trash is not counted
+  test.delete_view(&child_3.id).await;
+
+  // The children can't be trashed while any view under them is locked
+  set_view_locked(&test, &child_2.id, true).await;
+  let error = EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::EmptyViewChildren)
+    .payload(ViewIdPB {
+      value: parent.id.clone(),
+    })
+    .async_send()
+    .await
+    .error()
+    .unwrap();
+  assert_eq!(error.code, ErrorCode::ViewIsLocked);
+  assert_eq!(test.get_view(&parent.id).await.child_views.len(), 1);
+  set_view_locked(&test, &child_2.id, false).await;
+
+  let result = EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::EmptyViewChildren)
+    .payload(ViewIdPB {
+      value: parent.id.clone(),
+    })
+    .async_send()
+    .await
+    .parse::<EmptyViewChildrenResultPB>();
+  assert_eq!(result.trashed_count, 2);
+
+  let parent = test.get_view(&parent.id).await;
+  assert!(parent.child_views.is_empty());
+  assert_eq!(get_trash_count(&test, &current_workspace.id).await, 2);
+}
+
+#[tokio::test]
+async fn search_history_event_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  for query in ["first", "  ", "second", "first "] {
+    search_views(&test, query).await;
+  }
+  assert_eq!(
+    get_search_history(&test).await,
+    vec!["first".to_string(), "second".to_string()]
+  );
+
+  // The search history is stored in the settings of the workspace in the folder.
+  let folder_json = {
+    let folder = test.folder_manager.get_mutex_folder().lock();
+    folder.as_ref().unwrap().to_json_value()
+  };
+  let setting: serde_json::Value =
+    serde_json::from_str(folder_json["extra"]["setting"].as_str().unwrap()).unwrap();
+  assert_eq!(
+    setting["search_history"],
+    serde_json::json!(["first", "second"])
+  );
+
+  EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::ClearSearchHistory)
+    .async_send()
+    .await;
+  assert!(get_search_history(&test).await.is_empty());
+}
+
+async fn search_views(test: &EventIntegrationTest, query: &str) {
+  EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::SearchViews)
+    .payload(SearchViewsPayloadPB {
+      query: query.to_string(),
+      workspace_id: "".to_string(),
+      limit: 0,
+      include_trash: false,
+    })
+    .async_send()
+    .await;
+}
+
+async fn get_search_history(test: &EventIntegrationTest) -> Vec<String> {
+  EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::GetSearchHistory)
+    .async_send()
+    .await
+    .parse::<SearchHistoryPB>()
+    .queries
+}
+
+#[tokio::test]
+async fn update_view_icons_batch_event_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+  let first = test
+    .create_view(&current_workspace.id, "first".to_string())
+    .await;
+  let second = test
+    .create_view(&current_workspace.id, "second".to_string())
+    .await;
+  let icon = ViewIconPB {
+    ty: ViewIconTypePB::Emoji,
+    value: "👍".to_string(),
+  };
+  let items = [first.id.clone(), "not exist".to_string(), second.id.clone()]
+    .into_iter()
+    .map(|view_id| UpdateViewIconPayloadPB {
+      view_id,
+      icon: Some(icon.clone()),
+    })
+    .collect();
+
+  let result = EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::UpdateViewIconsBatch)
+    .payload(RepeatedUpdateViewIconPayloadPB { items })
+    .async_send()
+    .await
+    .parse::<UpdateViewIconsResultPB>();
+  assert_eq!(
+    result.updated_ids,
+    vec![first.id.clone(), second.id.clone()]
+  );
+  assert_eq!(result.failed.len(), 1);
+  assert_eq!(result.failed[0].view_id, "not exist");
+
+  for view_id in [&first.id, &second.id] {
+    let view = test.get_view(view_id).await;
+    assert_eq!(view.icon, Some(icon.clone()));
+  }
+}
+
+#[tokio::test]
+async fn search_views_content_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+  let export_dir = tempdir::TempDir::new("notion_export").unwrap();
+  std::fs::write(
+    export_dir.path().join("Shopping list.md"),
+    "Buy some Pineapples",
+  )
+  .unwrap();
+  let result = EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::ImportData)
+    .payload(ImportPB {
+      file_path: Some(export_dir.path().to_string_lossy().to_string()),
+      ..import_payload(
+        &current_workspace.id,
+        ViewLayoutPB::Document,
+        ImportTypePB::NotionZip,
+      )
+    })
+    .async_send()
+    .await
+    .parse::<ImportResultPB>();
+  assert_eq!(result.created_count, 2);
+  let named = test
+    .create_view(&current_workspace.id, "Pineapple cake".to_string())
+    .await;
+
+  // The name match ranks before the content match
+  let views = EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::SearchViews)
+    .payload(SearchViewsPayloadPB {
+      query: "pineapple".to_string(),
+      workspace_id: "".to_string(),
+      limit: 0,
+      include_trash: false,
+    })
+    .async_send()
+    .await
+    .parse::<RepeatedViewPB>()
+    .items;
+  let names = views
+    .iter()
+    .map(|view| view.name.as_str())
+    .collect::<Vec<_>>();
+  assert_eq!(names, vec![named.name.as_str(), "Shopping list"]);
+}
+
+#[tokio::test]
+async fn flat_views_carry_parent_view_id_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+  let parent = test
+    .create_view(&current_workspace.id, "parent placement".to_string())
+    .await;
+  let child = test
+    .create_view(&parent.id, "child placement".to_string())
+    .await;
+
+  let views = EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::SearchViews)
+    .payload(SearchViewsPayloadPB {
+      query: "placement".to_string(),
+      workspace_id: "".to_string(),
+      limit: 0,
+      include_trash: false,
+    })
+    .async_send()
+    .await
+    .parse::<RepeatedViewPB>()
+    .items;
+  let parent_view_id_of = |view_id: &str| {
+    views
+      .iter()
+      .find(|view| view.id == view_id)
+      .map(|view| view.parent_view_id.clone())
+      .unwrap()
+  };
+  assert_eq!(parent_view_id_of(&parent.id), current_workspace.id);
+  assert_eq!(parent_view_id_of(&child.id), parent.id);
+}
+
+#[tokio::test]
+async fn flatten_subtree_event_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+  let root = test
+    .create_view(&current_workspace.id, "root".to_string())
+    .await;
+  let first = test.create_view(&root.id, "first".to_string()).await;
+  let first_child = test.create_view(&first.id, "first child".to_string()).await;
+  let second = test.create_view(&root.id, "second".to_string()).await;
+  test.delete_view(&second.id).await;
+
+  let flatten = |include_root: bool, include_trash: bool| {
+    let test = test.clone();
+    let view_id = root.id.clone();
+    async move {
+      EventBuilder::new(test)
+        .event(flowy_folder2::event_map::FolderEvent::FlattenSubtree)
+        .payload(FlattenSubtreePayloadPB {
+          view_id,
+          include_root,
+          include_trash,
+        })
+        .async_send()
+        .await
+        .parse::<RepeatedViewPB>()
+        .items
+        .into_iter()
+        .map(|view| (view.id, view.depth))
+        .collect::<Vec<_>>()
+    }
+  };
+
+  assert_eq!(
+    flatten(true, false).await,
+    vec![
+      (root.id.clone(), 0),
+      (first.id.clone(), 1),
+      (first_child.id.clone(), 2)
+    ]
+  );
+  assert_eq!(
+    flatten(false, true).await,
+    vec![
+      (first.id.clone(), 1),
+      (first_child.id.clone(), 2),
+      (second.id.clone(), 1)
+    ]
+  );
+
+  // Pinning a view must not move it away from its place in the depth-first order.
+  let third = test.create_view(&root.id, "third".to_string()).await;
+  toggle_view_pin(&test, vec![third.id.clone()]).await;
+  assert_eq!(
+    flatten(false, false).await,
+    vec![
+      (first.id.clone(), 1),
+      (first_child.id.clone(), 2),
+      (third.id.clone(), 1)
+    ]
+  );
+}
+
+#[tokio::test]
+async fn archive_view_event_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+  let parent = test
+    .create_view(&current_workspace.id, "parent".to_string())
+    .await;
+  let archived = test.create_view(&parent.id, "archived".to_string()).await;
+  let active = test.create_view(&parent.id, "active".to_string()).await;
+
+  set_views_archived(&test, vec![(archived.id.clone(), true)]).await;
+  assert_eq!(
+    get_child_view_ids(&test, &parent.id).await,
+    vec![active.id.clone()]
+  );
+  let archived_views = get_archived_views(&test).await;
+  assert_eq!(archived_views.len(), 1);
+  assert_eq!(archived_views[0].id, archived.id);
+  assert!(archived_views[0].is_archived);
+  // The archived view is not in the trash
+  assert_eq!(get_trash_count(&test, &current_workspace.id).await, 0);
+
+  set_views_archived(&test, vec![(archived.id.clone(), false)]).await;
+  assert_eq!(
+    get_child_view_ids(&test, &parent.id).await,
+    vec![archived.id.clone(), active.id.clone()]
+  );
+  assert!(get_archived_views(&test).await.is_empty());
+}
+
+async fn set_views_archived(test: &EventIntegrationTest, items: Vec<(String, bool)>) {
+  EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::SetViewArchived)
+    .payload(RepeatedSetArchivedPB {
+      items: items
+        .into_iter()
+        .map(|(view_id, is_archived)| SetArchivedPB {
+          view_id,
+          is_archived,
+        })
+        .collect(),
+    })
+    .async_send()
+    .await;
+}
+
+async fn get_archived_views(test: &EventIntegrationTest) -> Vec<ViewPB> {
+  EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::GetArchivedViews)
+    .async_send()
+    .await
+    .parse::<RepeatedViewPB>()
+    .items
+}
+
+#[tokio::test]
+async fn view_last_opened_time_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+  let view = test
+    .create_view(&current_workspace.id, "My view".to_string())
+    .await;
+  assert_eq!(test.get_view(&view.id).await.last_opened_time, 0);
+
+  set_latest_view(&test, &view.id).await.unwrap();
+  let opened_view = test.get_view(&view.id).await;
+  assert!(opened_view.last_opened_time > 0);
+  // Opening the view is not an edit
+  assert_eq!(opened_view.last_edited_time, view.last_edited_time);
+
+  let recent_views = EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::GetRecentViewsSorted)
+    .payload(RecentViewsSortedPayloadPB {
+      sort_type: ViewSortTypePB::LastOpenedTime,
+    })
+    .async_send()
+    .await
+    .parse::<RepeatedViewPB>()
+    .items;
+  assert_eq!(recent_views[0].id, view.id);
+  assert_eq!(
+    recent_views[0].last_opened_time,
+    opened_view.last_opened_time
+  );
+}
+
+#[tokio::test]
+async fn delete_parent_and_child_in_one_batch_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+  let parent = test
+    .create_view(&current_workspace.id, "parent".to_string())
+    .await;
+  let child = test.create_view(&parent.id, "child".to_string()).await;
+  let grandchild = test.create_view(&child.id, "grandchild".to_string()).await;
+
+  let result = EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::DeleteView)
+    .payload(RepeatedViewIdPB {
+      items: vec![child.id.clone(), parent.id.clone(), child.id.clone()],
+      cascade: None,
+    })
+    .async_send()
+    .await
+    .parse::<DeleteResultPB>();
+  assert_eq!(
+    result.trashed_ids.items,
+    vec![parent.id.clone(), child.id.clone(), grandchild.id.clone()]
+  );
+  assert!(result.failures.is_empty());
+
+  // Only the root of the subtree is added to the trash
+  let trash = test.read_trash(&current_workspace.id).await;
+  assert_eq!(trash.items.len(), 1);
+  assert_eq!(trash.items[0].id, parent.id);
+}
+
+#[tokio::test]
+async fn change_view_layout_event_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+  let grid = EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::CreateView)
+    .payload(CreateViewPayloadPB {
+      parent_view_id: current_workspace.id.clone(),
+      name: "My grid".to_string(),
+      layout: Some(ViewLayoutPB::Grid),
+      ..Default::default()
+    })
+    .async_send()
+    .await
+    .parse::<ViewPB>();
+  change_view_layout(&test, &grid.id, ViewLayoutPB::Board)
+    .await
+    .unwrap();
+  assert_eq!(test.get_view(&grid.id).await.layout, ViewLayoutPB::Board);
+
+  // The content of the document would be lost
+  let document = test
+    .create_view(&current_workspace.id, "My document".to_string())
+    .await;
+  let error = change_view_layout(&test, &document.id, ViewLayoutPB::Board)
+    .await
+    .unwrap_err();
+  assert_eq!(error.code, ErrorCode::NotSupportYet);
+  assert_eq!(
+    test.get_view(&document.id).await.layout,
+    ViewLayoutPB::Document
+  );
+}
+
+async fn change_view_layout(
+  test: &EventIntegrationTest,
+  view_id: &str,
+  new_layout: ViewLayoutPB,
+) -> Result<(), FlowyError> {
+  match EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::ChangeViewLayout)
+    .payload(ChangeLayoutPayloadPB {
+      view_id: view_id.to_string(),
+      new_layout,
+    })
+    .async_send()
+    .await
+    .error()
+  {
+    None => Ok(()),
+    Some(error) => Err(error),
+  }
+}
+
+#[tokio::test]
+async fn get_view_siblings_event_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+  let parent = test
+    .create_view(&current_workspace.id, "parent".to_string())
+    .await;
+  let first = test.create_view(&parent.id, "first".to_string()).await;
+  let second = test.create_view(&parent.id, "second".to_string()).await;
+  let third = test.create_view(&parent.id, "third".to_string()).await;
+  test.delete_view(&first.id).await;
+
+  let siblings = EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::GetViewSiblings)
+    .payload(ViewIdPB {
+      value: third.id.clone(),
+    })
+    .async_send()
+    .await
+    .parse::<ViewSiblingsPB>();
+  let sibling_ids = siblings
+    .items
+    .into_iter()
+    .map(|view| view.id)
+    .collect::<Vec<_>>();
+  assert_eq!(sibling_ids, vec![second.id.clone(), third.id.clone()]);
+  assert_eq!(siblings.current_index, 1);
+
+  // The pinned view comes first, and the index follows it.
+  toggle_view_pin(&test, vec![third.id.clone()]).await;
+  let siblings = EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::GetViewSiblings)
+    .payload(ViewIdPB {
+      value: third.id.clone(),
+    })
+    .async_send()
+    .await
+    .parse::<ViewSiblingsPB>();
+  assert_eq!(siblings.items[0].id, third.id);
+  assert_eq!(siblings.current_index, 0);
+}
+
+#[tokio::test]
+async fn find_child_by_name_event_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+  let parent = test
+    .create_view(&current_workspace.id, "parent".to_string())
+    .await;
+  let first = test.create_view(&parent.id, "Notes".to_string()).await;
+  let second = test.create_view(&parent.id, "notes".to_string()).await;
+
+  let result = find_child_by_name(&test, &parent.id, "notes", false)
+    .await
+    .unwrap();
+  assert_eq!(result.view.id, second.id);
+  assert!(!result.is_ambiguous);
+
+  let result = find_child_by_name(&test, &parent.id, "NOTES", true)
+    .await
+    .unwrap();
+  assert_eq!(result.view.id, first.id);
+  assert!(result.is_ambiguous);
+
+  let error = find_child_by_name(&test, &parent.id, "NOTES", false)
+    .await
+    .unwrap_err();
+  assert_eq!(error.code, ErrorCode::RecordNotFound);
+}
+
+async fn find_child_by_name(
+  test: &EventIntegrationTest,
+  parent_id: &str,
+  name: &str,
+  case_insensitive: bool,
+) -> Result<FindChildResultPB, FlowyError> {
+  EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::FindChildByName)
+    .payload(FindChildPayloadPB {
+      parent_id: parent_id.to_string(),
+      name: name.to_string(),
+      case_insensitive,
+    })
+    .async_send()
+    .await
+    .try_parse::<FindChildResultPB>()
+}
+
+#[tokio::test]
+async fn view_limit_event_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+  let view_count = EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::GetWorkspaceStats)
+    .payload(WorkspaceIdPB {
+      value: current_workspace.id.clone(),
+      preload_depth: None,
+    })
+    .async_send()
+    .await
+    .parse::<WorkspaceStatsPB>()
+    .total_views;
+  set_view_limit(&test, &current_workspace.id, view_count as u32 + 1).await;
+  // The limit is stored in the settings of the workspace in the folder, so it applies to all the
+  // devices of the workspace.
+  assert_eq!(
+    read_folder_extra_json(&test, "setting")["view_limit"],
+    view_count + 1
+  );
+
+  test
+    .create_view(&current_workspace.id, "the last view".to_string())
+    .await;
+  let error = EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::CreateView)
+    .payload(CreateViewPayloadPB {
+      parent_view_id: current_workspace.id.clone(),
+      name: "over the limit".to_string(),
+      layout: Some(ViewLayoutPB::Document),
+      ..Default::default()
+    })
+    .async_send()
+    .await
+    .error()
+    .unwrap();
+  assert_eq!(error.code, ErrorCode::ViewLimitReached);
+
+  // Zero means unlimited
+  set_view_limit(&test, &current_workspace.id, 0).await;
+  test
+    .create_view(&current_workspace.id, "unlimited".to_string())
+    .await;
+}
+
+async fn set_view_limit(test: &EventIntegrationTest, workspace_id: &str, limit: u32) {
+  EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::SetViewLimit)
+    .payload(ViewLimitPayloadPB {
+      workspace_id: workspace_id.to_string(),
+      limit,
+    })
+    .async_send()
+    .await;
+}
+
+#[tokio::test]
+async fn get_view_backlinks_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+  let target = test
+    .create_view(&current_workspace.id, "Target".to_string())
+    .await;
+  let source_1 = test
+    .create_view(&current_workspace.id, "Source 1".to_string())
+    .await;
+  let source_2 = test
+    .create_view(&current_workspace.id, "Source 2".to_string())
+    .await;
+  assert!(get_view_backlinks(&test, &target.id).await.is_empty());
+
+  set_view_references(&test, &source_1.id, vec![target.id.clone()]).await;
+  set_view_references(
+    &test,
+    &source_2.id,
+    vec![target.id.clone(), target.id.clone()],
+  )
+  .await;
+  let backlinks = get_view_backlinks(&test, &target.id).await;
+  assert_eq!(backlinks.len(), 2);
+  assert_eq!(backlinks[0].id, source_1.id);
+  assert_eq!(backlinks[1].id, source_2.id);
+
+  // The new references replace the old ones
+  set_view_references(&test, &source_1.id, vec![]).await;
+  let backlinks = get_view_backlinks(&test, &target.id).await;
+  assert_eq!(backlinks.len(), 1);
+  assert_eq!(backlinks[0].id, source_2.id);
+
+  // The trashed views are excluded
+  test.delete_view(&source_2.id).await;
+  assert!(get_view_backlinks(&test, &target.id).await.is_empty());
+}
+
+async fn set_view_references(
+  test: &EventIntegrationTest,
+  view_id: &str,
+  referenced_view_ids: Vec<String>,
+) {
+  let error = EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::SetViewReferences)
+    .payload(ViewReferencesPayloadPB {
+      view_id: view_id.to_string(),
+      referenced_view_ids,
+    })
+    .async_send()
+    .await
+    .error();
+  assert!(error.is_none());
+}
+
+async fn get_view_backlinks(test: &EventIntegrationTest, view_id: &str) -> Vec<ViewPB> {
+  EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::GetViewBacklinks)
+    .payload(ViewIdPB {
+      value: view_id.to_string(),
+    })
+    .async_send()
+    .await
+    .parse::<RepeatedViewPB>()
+    .items
+}
+
+#[tokio::test]
+async fn read_trash_of_workspace_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let workspace = test.get_current_workspace().await;
+  let view = test
+    .create_view(&workspace.id, "My trashed view".to_string())
+    .await;
+  test.delete_view(&view.id).await;
+
+  // The trash is not copied to the duplicated workspace
+  let other_workspace = EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::DuplicateWorkspace)
+    .payload(DuplicateWorkspacePayloadPB {
+      workspace_id: workspace.id.clone(),
+      new_name: "My other workspace".to_string(),
+    })
+    .async_send()
+    .await
+    .parse::<WorkspacePB>();
+  assert!(test.read_trash(&other_workspace.id).await.items.is_empty());
+
+  open_workspace(&test, &other_workspace.id, None).await;
+  let other_view = test
+    .create_view(&other_workspace.id, "My other trashed view".to_string())
+    .await;
+  test.delete_view(&other_view.id).await;
+
+  // Each workspace only sees its own trash
+  let trash = test.read_trash(&workspace.id).await.items;
+  assert_eq!(trash.len(), 1);
+  assert_eq!(trash[0].id, view.id);
+  let trash = test.read_trash(&other_workspace.id).await.items;
+  assert_eq!(trash.len(), 1);
+  assert_eq!(trash[0].id, other_view.id);
+  assert_eq!(get_trash_count(&test, &workspace.id).await, 1);
+  assert_eq!(get_trash_count(&test, &other_workspace.id).await, 1);
+  let page = read_trash_paged(&test, &workspace.id, 0, 10).await;
+  assert_eq!(page.total_count, 1);
+  assert_eq!(page.trash.items[0].id, view.id);
+  let page = read_trash_paged(&test, &other_workspace.id, 0, 10).await;
+  assert_eq!(page.total_count, 1);
+  assert_eq!(page.trash.items[0].id, other_view.id);
+}
+
+#[tokio::test]
+async fn import_error_code_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let workspace_id = test.get_current_workspace().await.id;
+
+  // The csv can't be imported as a document
+  let payload = import_payload(&workspace_id, ViewLayoutPB::Document, ImportTypePB::CSV);
+  let error = import_data(
+    &test,
+    ImportPB {
+      data: Some("a,b".as_bytes().to_vec()),
+      ..payload
+    },
+  )
+  .await;
+  assert_eq!(error.code, ErrorCode::ImportUnsupportedFormat);
+
+  // The csv that isn't valid utf8 can't be parsed
+  let payload = import_payload(&workspace_id, ViewLayoutPB::Grid, ImportTypePB::CSV);
+  let error = import_data(
+    &test,
+    ImportPB {
+      data: Some(vec![0xff, 0xfe, 0xfd]),
+      ..payload
+    },
+  )
+  .await;
+  assert_eq!(error.code, ErrorCode::ImportMalformedFile);
+
+  let payload = import_payload(&workspace_id, ViewLayoutPB::Grid, ImportTypePB::CSV);
+  let error = import_data(
+    &test,
+    ImportPB {
+      file_path: Some("/not/exist/file.csv".to_string()),
+      ..payload
+    },
+  )
+  .await;
+  assert_eq!(error.code, ErrorCode::ImportIOError);
+
+  let payload = import_payload(
+    &workspace_id,
+    ViewLayoutPB::Document,
+    ImportTypePB::NotionZip,
+  );
+  let error = import_data(
+    &test,
+    ImportPB {
+      file_path: Some("/not/exist/notion".to_string()),
+      ..payload
+    },
+  )
+  .await;
+  assert_eq!(error.code, ErrorCode::ImportIOError);
+}
+
+#[tokio::test]
+async fn import_notion_export_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let workspace_id = test.get_current_workspace().await.id;
+
+  // Getting Started.md
+  // Getting Started/
+  //   Sub page.md
+  //   Tasks.csv
+  //   Tasks_all.csv
+  let export_dir = tempdir::TempDir::new("notion_export").unwrap();
+  let page_id = "0a1b2c3d4e5f60718293a4b5c6d7e8f9";
+  let page_dir = export_dir
+    .path()
+    .join(format!("Getting Started {}", page_id));
+  std::fs::create_dir(&page_dir).unwrap();
+  std::fs::write(
+    export_dir
+      .path()
+      .join(format!("Getting Started {}.md", page_id)),
+    "# Getting Started\n\nSee [the removed page](Removed%20page.md)",
+  )
+  .unwrap();
+  std::fs::write(page_dir.join("Sub page.md"), "Hello").unwrap();
+  std::fs::write(page_dir.join("Tasks.csv"), "Name,Done\nWrite,Yes\n").unwrap();
+  std::fs::write(page_dir.join("Tasks_all.csv"), "Name,Done\nWrite,Yes\n").unwrap();
+
+  // The broken link doesn't abort the import
+  let result = EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::ImportData)
+    .payload(ImportPB {
+      file_path: Some(export_dir.path().to_string_lossy().to_string()),
+      ..import_payload(
+        &workspace_id,
+        ViewLayoutPB::Document,
+        ImportTypePB::NotionZip,
+      )
+    })
+    .async_send()
+    .await
+    .parse::<ImportResultPB>();
+  assert_eq!(result.created_count, 4);
+  assert!(result.skipped.is_empty());
+
+  // The hierarchy of the export is kept under the root view of the import
+  let root = test.get_view(&result.view.id).await;
+  assert_eq!(root.name, "Imported");
+  assert_eq!(root.child_views.len(), 1);
+  assert_eq!(root.child_views[0].name, "Getting Started");
+  let page = test.get_view(&root.child_views[0].id).await;
+  let children = page
+    .child_views
+    .iter()
+    .map(|view| (view.name.as_str(), view.layout.clone()))
+    .collect::<Vec<_>>();
+  assert_eq!(
+    children,
+    vec![
+      ("Sub page", ViewLayoutPB::Document),
+      ("Tasks", ViewLayoutPB::Grid)
+    ]
+  );
+}
+
+fn import_payload(
+  parent_view_id: &str,
+  view_layout: ViewLayoutPB,
+  import_type: ImportTypePB,
+) -> ImportPB {
+  ImportPB {
+    parent_view_id: parent_view_id.to_string(),
+    name: "Imported".to_string(),
+    data: None,
+    file_path: None,
+    view_layout,
+    import_type,
+    import_id: None,
+    dry_run: false,
+  }
+}
+
+async fn import_data(test: &EventIntegrationTest, payload: ImportPB) -> FlowyError {
+  EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::ImportData)
+    .payload(payload)
+    .async_send()
+    .await
+    .error()
+    .unwrap()
+}
+
+#[tokio::test]
+async fn update_workspace_settings_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+  let settings = EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::UpdateWorkspaceSettings)
+    .payload(WorkspaceSettingsPayloadPB {
+      workspace_id: current_workspace.id.clone(),
+      trash_retention_days: Some(30),
+      default_view_layout: Some(ViewLayoutPB::Grid),
+      view_limit: None,
+    })
+    .async_send()
+    .await
+    .parse::<WorkspaceSettingsPB>();
+  assert_eq!(settings.trash_retention_days, 30);
+  assert_eq!(settings.default_view_layout, ViewLayoutPB::Grid);
+  assert_eq!(settings.view_limit, 0);
+
+  // The absent settings are left unchanged
+  let settings = EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::UpdateWorkspaceSettings)
+    .payload(WorkspaceSettingsPayloadPB {
+      workspace_id: current_workspace.id.clone(),
+      trash_retention_days: None,
+      default_view_layout: None,
+      view_limit: Some(100),
+    })
+    .async_send()
+    .await
+    .parse::<WorkspaceSettingsPB>();
+  assert_eq!(settings.trash_retention_days, 30);
+  assert_eq!(settings.default_view_layout, ViewLayoutPB::Grid);
+  assert_eq!(settings.view_limit, 100);
+
+  let error = EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::UpdateWorkspaceSettings)
+    .payload(WorkspaceSettingsPayloadPB {
+      workspace_id: "not exist workspace id".to_string(),
+      trash_retention_days: Some(1),
+      default_view_layout: None,
+      view_limit: None,
+    })
+    .async_send()
+    .await
+    .error()
+    .unwrap();
+  assert_eq!(error.code, ErrorCode::RecordNotFound);
+}
+
+#[tokio::test]
+async fn get_subtree_count_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+  let parent = test
+    .create_view(&current_workspace.id, "Parent".to_string())
+    .await;
+  let child_1 = test.create_view(&parent.id, "Child 1".to_string()).await;
+  let child_2 = test.create_view(&parent.id, "Child 2".to_string()).await;
+  let _ = test
+    .create_view(&child_1.id, "Grandchild 1".to_string())
+    .await;
+  let _ = test
+    .create_view(&child_2.id, "Grandchild 2".to_string())
+    .await;
+  assert_eq!(
+    get_subtree_count(&test, &parent.id).await,
+    SubtreeCountPB {
+      direct_children: 2,
+      total_descendants: 4,
+    }
+  );
+
+  // The trashed view is not counted along with its descendants
+  test.delete_view(&child_2.id).await;
+  assert_eq!(
+    get_subtree_count(&test, &parent.id).await,
+    SubtreeCountPB {
+      direct_children: 1,
+      total_descendants: 2,
+    }
+  );
+}
+
+async fn get_subtree_count(test: &EventIntegrationTest, view_id: &str) -> SubtreeCountPB {
+  EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::GetSubtreeCount)
+    .payload(ViewIdPB {
+      value: view_id.to_string(),
+    })
+    .async_send()
+    .await
+    .parse::<SubtreeCountPB>()
+}
+
+#[tokio::test]
+async fn set_workspace_home_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+  let home = test
+    .create_view(&current_workspace.id, "Home".to_string())
+    .await;
+  let other = test
+    .create_view(&current_workspace.id, "Other".to_string())
+    .await;
+  set_latest_view(&test, &other.id).await.unwrap();
+  assert!(get_current_workspace_setting(&test)
+    .await
+    .home_view_id
+    .is_empty());
+
+  set_workspace_home(&test, &current_workspace.id, &home.id).await;
+  assert_eq!(
+    get_current_workspace_setting(&test).await.home_view_id,
+    home.id
+  );
+  let default_view = get_default_open_view(&test, &current_workspace.id).await;
+  assert_eq!(default_view.unwrap().id, home.id);
+  // The home view is stored in the settings of the workspace in the folder.
+  assert_eq!(
+    read_folder_extra_json(&test, "setting")["home_view_id"],
+    home.id
+  );
+
+  // The empty view id reverts to the latest opened view
+  set_workspace_home(&test, &current_workspace.id, "").await;
+  assert!(get_current_workspace_setting(&test)
+    .await
+    .home_view_id
+    .is_empty());
+  let default_view = get_default_open_view(&test, &current_workspace.id).await;
+  assert_eq!(default_view.unwrap().id, other.id);
+  assert!(read_folder_extra_json(&test, "setting")["home_view_id"].is_null());
+}
+
+async fn set_workspace_home(test: &EventIntegrationTest, workspace_id: &str, view_id: &str) {
+  let error = EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::SetWorkspaceHome)
+    .payload(SetHomePayloadPB {
+      workspace_id: workspace_id.to_string(),
+      view_id: view_id.to_string(),
+    })
+    .async_send()
+    .await
+    .error();
+  assert!(error.is_none());
+}
+
+#[tokio::test]
+async fn list_views_cursor_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+  let parent = test
+    .create_view(&current_workspace.id, "Parent".to_string())
+    .await;
+  let mut created_ids = vec![parent.id.clone()];
+  for i in 0..3 {
+    let child = test.create_view(&parent.id, format!("Child {}", i)).await;
+    created_ids.push(child.id.clone());
+    if i == 0 {
+      let grandchild = test.create_view(&child.id, "Grandchild".to_string()).await;
+      created_ids.push(grandchild.id);
+    }
+  }
+  // Pinning a view doesn't change the order of the pages
+  toggle_view_pin(&test, vec![created_ids.last().unwrap().clone()]).await;
+  let trashed_view = test
+    .create_view(&current_workspace.id, "Trashed".to_string())
+    .await;
+  test.delete_view(&trashed_view.id).await;
+
+  let mut listed_ids = vec![];
+  let mut cursor = String::new();
+  loop {
+    let page = EventBuilder::new(test.clone())
+      .event(flowy_folder2::event_map::FolderEvent::ListViewsCursor)
+      .payload(ListViewsCursorPayloadPB {
+        workspace_id: current_workspace.id.clone(),
+        cursor: cursor.clone(),
+        limit: 2,
+      })
+      .async_send()
+      .await
+      .parse::<ViewsPagePB>();
+    assert!(page.items.len() <= 2);
+    listed_ids.extend(page.items.into_iter().map(|view| view.id));
+    if page.next_cursor.is_empty() {
+      break;
+    }
+    cursor = page.next_cursor;
+  }
+
+  // Each view is listed once, and the child views come right after their parent
+  let mut unique_ids = listed_ids.clone();
+  unique_ids.sort();
+  unique_ids.dedup();
+  assert_eq!(unique_ids.len(), listed_ids.len());
+  assert!(!listed_ids.contains(&trashed_view.id));
+  let parent_index = listed_ids.iter().position(|id| id == &parent.id).unwrap();
+  assert_eq!(
+    listed_ids[parent_index..parent_index + created_ids.len()],
+    created_ids[..]
+  );
+}
+
+#[tokio::test]
+async fn check_folder_integrity_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+  let parent = test
+    .create_view(&current_workspace.id, "Parent".to_string())
+    .await;
+  let child = test.create_view(&parent.id, "Child".to_string()).await;
+  // The trashed views are still part of the structure
+  test.delete_view(&parent.id).await;
+
+  let report = EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::CheckFolderIntegrity)
+    .payload(WorkspaceIdPB {
+      value: current_workspace.id.clone(),
+      preload_depth: None,
+    })
+    .async_send()
+    .await
+    .parse::<FolderIntegrityReportPB>();
+  assert!(report.is_healthy(), "{:?}", report);
+
+  let report = EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::RepairFolder)
+    .payload(WorkspaceIdPB {
+      value: current_workspace.id.clone(),
+      preload_depth: None,
+    })
+    .async_send()
+    .await
+    .parse::<FolderIntegrityReportPB>();
+  assert!(report.is_healthy(), "{:?}", report);
+  assert_eq!(test.get_view(&child.id).await.parent_view_id, parent.id);
+}
+
+#[tokio::test]
+async fn check_and_repair_broken_folder_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+  let orphan_parent = test
+    .create_view(&current_workspace.id, "Orphan parent".to_string())
+    .await;
+  let orphan = test
+    .create_view(&orphan_parent.id, "Orphan".to_string())
+    .await;
+  let orphan_child = test
+    .create_view(&orphan.id, "Orphan child".to_string())
+    .await;
+  let cycle_a = test
+    .create_view(&current_workspace.id, "Cycle A".to_string())
+    .await;
+  let cycle_b = test.create_view(&cycle_a.id, "Cycle B".to_string()).await;
+  let duplicate_parent = test
+    .create_view(&current_workspace.id, "Duplicate parent".to_string())
+    .await;
+  let duplicate = test
+    .create_view(&duplicate_parent.id, "Duplicate".to_string())
+    .await;
+  {
+    let folder = test.folder_manager.get_mutex_folder().lock();
+    let folder = folder.as_ref().unwrap();
+    // The orphan is detached along with its child view
+    folder
+      .views
+      .dissociate_parent_child(&orphan_parent.id, &orphan.id);
+    // Cycle A and Cycle B are the parents of each other
+    folder.move_nested_view(&cycle_a.id, &cycle_b.id, None);
+    // The duplicate is listed by two parents
+    folder
+      .views
+      .associate_parent_child(&orphan_parent.id, &duplicate.id, None);
+    folder.add_favorites(vec!["missing_view_id".to_string()]);
+    folder.set_current_view("missing_view_id");
+  }
+
+  let report = EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::CheckFolderIntegrity)
+    .payload(WorkspaceIdPB {
+      value: current_workspace.id.clone(),
+      preload_depth: None,
+    })
+    .async_send()
+    .await
+    .parse::<FolderIntegrityReportPB>();
+  assert_eq!(report.orphans, vec![orphan.id.clone()]);
+  assert_eq!(report.cycles.len(), 1);
+  assert!([&cycle_a.id, &cycle_b.id].contains(&&report.cycles[0]));
+  assert_eq!(report.duplicate_ids, vec![duplicate.id.clone()]);
+  assert_eq!(
+    report.dangling_favorites,
+    vec!["missing_view_id".to_string()]
+  );
+  assert!(report.dangling_current_view);
 
-  // Move My 1-2 view 1 from My view 1 to the current workspace and insert it after My view 1.
-  let move_view_id = parent1_views[0].id.clone();
-  let new_parent_id = current_workspace.id.clone();
-  let prev_id = Some(parent_1.id.clone());
-  move_folder_nested_view(test.clone(), move_view_id, new_parent_id, prev_id).await;
-  let parent1_views = test.get_view(&parent_1.id).await.child_views;
-  let workspace_views = test.get_all_workspace_views().await;
-  let workspace_views_len = workspace_views.len();
-  assert_eq!(parent1_views[0].name, "My 1-3 view 1");
-  assert_eq!(workspace_views[workspace_views_len - 3].name, "My view 1");
+  let report = EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::RepairFolder)
+    .payload(WorkspaceIdPB {
+      value: current_workspace.id.clone(),
+      preload_depth: None,
+    })
+    .async_send()
+    .await
+    .parse::<FolderIntegrityReportPB>();
+  assert!(report.is_healthy(), "{:?}", report);
+
+  let workspace_view_ids = test
+    .get_all_workspace_views()
+    .await
+    .into_iter()
+    .map(|view| view.id)
+    .collect::<Vec<_>>();
+  assert!(workspace_view_ids.contains(&orphan.id));
   assert_eq!(
-    workspace_views[workspace_views_len - 2].name,
-    "My 1-2 view 1"
+    test.get_view(&orphan_child.id).await.parent_view_id,
+    orphan.id
   );
-  assert_eq!(workspace_views[workspace_views_len - 1].name, "My view 2");
+  assert!(workspace_view_ids.contains(&cycle_a.id) || workspace_view_ids.contains(&cycle_b.id));
+  assert_eq!(
+    test.get_view(&duplicate.id).await.parent_view_id,
+    duplicate_parent.id
+  );
+  let orphan_parent_child_ids = test
+    .get_view(&orphan_parent.id)
+    .await
+    .child_views
+    .into_iter()
+    .map(|view| view.id)
+    .collect::<Vec<_>>();
+  assert!(!orphan_parent_child_ids.contains(&duplicate.id));
+  let current_view_id = {
+    let folder = test.folder_manager.get_mutex_folder().lock();
+    folder.as_ref().unwrap().get_current_view()
+  };
+  assert!(current_view_id.map_or(false, |id| id != "missing_view_id"));
+}
+
+#[tokio::test]
+async fn get_current_view_context_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+  let parent = test
+    .create_view(&current_workspace.id, "Parent".to_string())
+    .await;
+  let child = test.create_view(&parent.id, "Child".to_string()).await;
+  let grandchild_1 = test
+    .create_view(&child.id, "Grandchild 1".to_string())
+    .await;
+  let grandchild_2 = test
+    .create_view(&child.id, "Grandchild 2".to_string())
+    .await;
+  set_latest_view(&test, &grandchild_1.id).await.unwrap();
+
+  let context = get_current_view_context(&test, &current_workspace.id).await;
+  assert_eq!(context.view.unwrap().id, grandchild_1.id);
+  assert_eq!(
+    context
+      .ancestors
+      .iter()
+      .map(|view| view.id.clone())
+      .collect::<Vec<_>>(),
+    vec![parent.id.clone(), child.id.clone()]
+  );
+  assert_eq!(
+    context
+      .siblings
+      .iter()
+      .map(|view| view.id.clone())
+      .collect::<Vec<_>>(),
+    vec![grandchild_2.id.clone()]
+  );
+
+  // The default view is returned after the current view is trashed
+  test.delete_view(&grandchild_1.id).await;
+  let context = get_current_view_context(&test, &current_workspace.id).await;
+  let default_view = get_default_open_view(&test, &current_workspace.id).await;
+  assert_eq!(
+    context.view.map(|view| view.id),
+    default_view.map(|view| view.id)
+  );
+}
+
+async fn get_current_view_context(
+  test: &EventIntegrationTest,
+  workspace_id: &str,
+) -> ViewContextPB {
+  EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::GetCurrentViewContext)
+    .payload(WorkspaceIdPB {
+      value: workspace_id.to_string(),
+      preload_depth: None,
+    })
+    .async_send()
+    .await
+    .parse::<ViewContextPB>()
+}
+
+#[tokio::test]
+async fn read_views_sorted_by_last_edited_time_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+  let first_view = test
+    .create_view(&current_workspace.id, "first view".to_string())
+    .await;
+  let second_view = test
+    .create_view(&current_workspace.id, "second view".to_string())
+    .await;
+  assert_eq!(first_view.last_edited_time, first_view.create_time);
+
+  tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+  let error = test
+    .update_view(UpdateViewPayloadPB {
+      view_id: first_view.id.clone(),
+      name: Some("edited view".to_string()),
+      ..Default::default()
+    })
+    .await;
+  assert!(error.is_none());
+
+  let views = EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::ReadViewsSorted)
+    .payload(ReadViewsSortedPayloadPB {
+      parent_view_id: current_workspace.id.clone(),
+      sort_type: ViewSortTypePB::LastEditedTime,
+      ascending: false,
+    })
+    .async_send()
+    .await
+    .parse::<RepeatedViewPB>()
+    .items;
+  assert_eq!(views[0].id, first_view.id);
+  assert!(views[0].last_edited_time > first_view.create_time);
+  let second_view = views.iter().find(|view| view.id == second_view.id).unwrap();
+  assert_eq!(second_view.last_edited_time, second_view.create_time);
+
+  // The last edited time is stored in the view of the folder, so it's synced with the folder.
+  let folder_json = {
+    let folder = test.folder_manager.get_mutex_folder().lock();
+    folder.as_ref().unwrap().to_json_value()
+  };
+  let view_extra = folder_json["views"][&first_view.id]["extra"]
+    .as_str()
+    .unwrap()
+    .to_string();
+  let view_extra: serde_json::Value = serde_json::from_str(&view_extra).unwrap();
+  assert_eq!(view_extra["last_edited_time"], views[0].last_edited_time);
+}
+
+#[tokio::test]
+async fn restore_invalid_folder_snapshot_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+  let view = test
+    .create_view(&current_workspace.id, "My view".to_string())
+    .await;
+  let uid = test.get_user_profile().await.unwrap().id;
+  test
+    .store_preference
+    .set_object(
+      &format!("af_folder_snapshots:{}:{}", uid, current_workspace.id),
+      serde_json::json!([{
+        "snapshot_id": 1,
+        "name": "broken",
+        "created_at": 0,
+        "data": [1, 2, 3],
+      }]),
+    )
+    .unwrap();
+
+  let error = EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::RestoreFolderSnapshot)
+    .payload(RestoreFolderSnapshotPayloadPB {
+      workspace_id: current_workspace.id.clone(),
+      snapshot_id: 1,
+    })
+    .async_send()
+    .await
+    .error();
+  assert!(error.is_some());
+
+  // The current folder is kept as it is.
+  let workspace = test.get_current_workspace().await;
+  assert_eq!(workspace.views.len(), current_workspace.views.len() + 1);
+  assert_eq!(test.get_view(&view.id).await.name, "My view");
+  let view_2 = test
+    .create_view(&current_workspace.id, "My 2 view".to_string())
+    .await;
+  assert_eq!(test.get_view(&view_2.id).await.name, "My 2 view");
+}
+
+#[tokio::test]
+async fn max_nesting_depth_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+  let error = EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::SetMaxNestingDepth)
+    .payload(MaxNestingDepthPayloadPB {
+      workspace_id: current_workspace.id.clone(),
+      depth: 2,
+    })
+    .async_send()
+    .await
+    .error();
+  assert!(error.is_none());
+
+  let parent = test
+    .create_view(&current_workspace.id, "My view".to_string())
+    .await;
+  let child = test.create_view(&parent.id, "My 1 view".to_string()).await;
+  let error = EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::CreateView)
+    .payload(CreateViewPayloadPB {
+      parent_view_id: child.id.clone(),
+      name: "My 1-1 view".to_string(),
+      ..Default::default()
+    })
+    .async_send()
+    .await
+    .error()
+    .unwrap();
+  assert_eq!(error.code, ErrorCode::MaxNestingDepthExceeded);
+
+  // Moving the parent with its child under another top level view exceeds the depth
+  let other = test
+    .create_view(&current_workspace.id, "My other view".to_string())
+    .await;
+  let error = EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::MoveNestedView)
+    .payload(MoveNestedViewPayloadPB {
+      view_id: parent.id.clone(),
+      new_parent_id: other.id.clone(),
+      prev_view_id: None,
+    })
+    .async_send()
+    .await
+    .error()
+    .unwrap();
+  assert_eq!(error.code, ErrorCode::MaxNestingDepthExceeded);
+  assert_eq!(
+    test.get_view(&parent.id).await.parent_view_id,
+    current_workspace.id
+  );
+
+  // Moving the child alone is allowed
+  let error = EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::MoveNestedView)
+    .payload(MoveNestedViewPayloadPB {
+      view_id: child.id.clone(),
+      new_parent_id: other.id.clone(),
+      prev_view_id: None,
+    })
+    .async_send()
+    .await
+    .error();
+  assert!(error.is_none());
+
+  // Duplicating the other view with its child keeps the depth
+  let error = EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::DuplicateViewRecursive)
+    .payload(DuplicateViewPayloadPB {
+      view_id: other.id.clone(),
+      include_children: true,
+      suffix: None,
+    })
+    .async_send()
+    .await
+    .error();
+  assert!(error.is_none());
+
+  // Restoring a trashed view under the child exceeds the depth
+  let trashed = test
+    .create_view(&current_workspace.id, "My trashed view".to_string())
+    .await;
+  test.delete_view(&trashed.id).await;
+  let error = EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::RestoreTrashToParent)
+    .payload(RestoreTrashPayloadPB {
+      trash_id: trashed.id.clone(),
+      target_parent_id: child.id.clone(),
+    })
+    .async_send()
+    .await
+    .error()
+    .unwrap();
+  assert_eq!(error.code, ErrorCode::MaxNestingDepthExceeded);
+}
+
+async fn create_folder_snapshot(
+  test: &EventIntegrationTest,
+  workspace_id: &str,
+) -> FolderSnapshotPB {
+  EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::CreateFolderSnapshot)
+    .payload(CreateSnapshotPayloadPB {
+      workspace_id: workspace_id.to_string(),
+      name: "".to_string(),
+    })
+    .async_send()
+    .await
+    .parse::<FolderSnapshotPB>()
+}
+
+#[tokio::test]
+async fn undo_and_redo_move_view_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+  let parent_1 = test
+    .create_view(&current_workspace.id, "My 1 view".to_string())
+    .await;
+  let parent_2 = test
+    .create_view(&current_workspace.id, "My 2 view".to_string())
+    .await;
+  let child_1 = test
+    .create_view(&parent_1.id, "My 1-1 view".to_string())
+    .await;
+  let child_2 = test
+    .create_view(&parent_1.id, "My 1-2 view".to_string())
+    .await;
+  let child_3 = test
+    .create_view(&parent_1.id, "My 1-3 view".to_string())
+    .await;
+
+  move_folder_nested_view(test.clone(), child_2.id.clone(), parent_2.id.clone(), None).await;
+  assert_eq!(test.get_view(&parent_1.id).await.child_views.len(), 2);
+
+  let operation = undo_folder_operation(&test).await.unwrap();
+  assert_eq!(operation.ty, FolderOperationTypePB::MoveView);
+  assert_eq!(operation.view_id, child_2.id);
+  // The view is moved back to its original position
+  let child_ids = test
+    .get_view(&parent_1.id)
+    .await
+    .child_views
+    .into_iter()
+    .map(|view| view.id)
+    .collect::<Vec<_>>();
+  assert_eq!(child_ids, vec![child_1.id, child_2.id.clone(), child_3.id]);
+  assert!(test.get_view(&parent_2.id).await.child_views.is_empty());
+
+  let operation = redo_folder_operation(&test).await.unwrap();
+  assert_eq!(operation.ty, FolderOperationTypePB::MoveView);
+  let parent_2 = test.get_view(&parent_2.id).await;
+  assert_eq!(parent_2.child_views.len(), 1);
+  assert_eq!(parent_2.child_views[0].id, child_2.id);
+}
+
+#[tokio::test]
+async fn undo_delete_view_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+  let parent = test
+    .create_view(&current_workspace.id, "My view".to_string())
+    .await;
+  let view = test.create_view(&parent.id, "My 1 view".to_string()).await;
+  test.delete_view(&view.id).await;
+
+  let operation = undo_folder_operation(&test).await.unwrap();
+  assert_eq!(operation.ty, FolderOperationTypePB::DeleteView);
+  assert_eq!(operation.view_id, view.id);
+
+  // The view is restored to its original parent
+  let trash = test.read_trash(&current_workspace.id).await.items;
+  assert!(trash.iter().all(|trash| trash.id != view.id));
+  let parent = test.get_view(&parent.id).await;
+  assert_eq!(parent.child_views.len(), 1);
+  assert_eq!(parent.child_views[0].id, view.id);
+}
+
+#[tokio::test]
+async fn undo_move_views_and_restore_trash_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+  let parent = test
+    .create_view(&current_workspace.id, "My view".to_string())
+    .await;
+  let view_1 = test
+    .create_view(&current_workspace.id, "My 1 view".to_string())
+    .await;
+  let view_2 = test
+    .create_view(&current_workspace.id, "My 2 view".to_string())
+    .await;
+
+  // Each moved view is recorded, so undoing them moves them back one by one
+  assert!(move_views(
+    &test,
+    vec![view_1.id.clone(), view_2.id.clone()],
+    &parent.id
+  )
+  .await
+  .is_none());
+  let operation = undo_folder_operation(&test).await.unwrap();
+  assert_eq!(operation.ty, FolderOperationTypePB::MoveView);
+  assert_eq!(operation.view_id, view_2.id);
+  let operation = undo_folder_operation(&test).await.unwrap();
+  assert_eq!(operation.view_id, view_1.id);
+  assert!(test.get_view(&parent.id).await.child_views.is_empty());
+  assert_eq!(
+    test.get_view(&view_1.id).await.parent_view_id,
+    current_workspace.id
+  );
+
+  // Undoing the restoring moves the view back to the trash
+  test.delete_view(&view_1.id).await;
+  EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::PutbackTrash)
+    .payload(TrashIdPB {
+      id: view_1.id.clone(),
+    })
+    .async_send()
+    .await;
+  let operation = undo_folder_operation(&test).await.unwrap();
+  assert_eq!(operation.ty, FolderOperationTypePB::RestoreView);
+  let trash = test.read_trash(&current_workspace.id).await.items;
+  assert!(trash.iter().any(|trash| trash.id == view_1.id));
+
+  // The trash done by the undo is not recorded as a new operation
+  let operation = undo_folder_operation(&test).await.unwrap();
+  assert_eq!(operation.ty, FolderOperationTypePB::DeleteView);
+  assert_eq!(operation.view_id, view_1.id);
+}
+
+#[tokio::test]
+async fn undo_create_views_in_batch_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+  let items = (1..3)
+    .map(|i| CreateViewPayloadPB {
+      parent_view_id: current_workspace.id.clone(),
+      name: format!("My {} view", i),
+      ..Default::default()
+    })
+    .collect::<Vec<_>>();
+  let views = EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::CreateViews)
+    .payload(RepeatedCreateViewPayloadPB { items })
+    .async_send()
+    .await
+    .parse::<RepeatedViewPB>()
+    .items;
+
+  // Each created view is recorded, so undoing them trashes them one by one
+  let operation = undo_folder_operation(&test).await.unwrap();
+  assert_eq!(operation.ty, FolderOperationTypePB::CreateView);
+  assert_eq!(operation.view_id, views[1].id);
+  let operation = undo_folder_operation(&test).await.unwrap();
+  assert_eq!(operation.view_id, views[0].id);
+  let trash = test.read_trash(&current_workspace.id).await.items;
+  assert_eq!(trash.len(), 2);
+}
+
+#[tokio::test]
+async fn new_operation_clears_redo_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+  let view = test
+    .create_view(&current_workspace.id, "My view".to_string())
+    .await;
+  test
+    .update_view(UpdateViewPayloadPB {
+      view_id: view.id.clone(),
+      name: Some("My renamed view".to_string()),
+      ..Default::default()
+    })
+    .await;
+
+  let operation = undo_folder_operation(&test).await.unwrap();
+  assert_eq!(operation.ty, FolderOperationTypePB::RenameView);
+  assert_eq!(test.get_view(&view.id).await.name, "My view");
+
+  let _ = test
+    .create_view(&current_workspace.id, "My 2 view".to_string())
+    .await;
+  let error = redo_folder_operation(&test).await.unwrap_err();
+  assert_eq!(error.code, ErrorCode::RecordNotFound);
+}
+
+async fn undo_folder_operation(
+  test: &EventIntegrationTest,
+) -> Result<FolderOperationPB, FlowyError> {
+  EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::UndoFolderOperation)
+    .async_send()
+    .await
+    .try_parse::<FolderOperationPB>()
+}
+
+async fn redo_folder_operation(
+  test: &EventIntegrationTest,
+) -> Result<FolderOperationPB, FlowyError> {
+  EventBuilder::new(test.clone())
+    .event(flowy_folder2::event_map::FolderEvent::RedoFolderOperation)
+    .async_send()
+    .await
+    .try_parse::<FolderOperationPB>()
 }
 
 async fn move_folder_nested_view(
@@ -8,7 +8,7 @@ use collab::preclude::{merge_updates_v1, JsonValue, Update};
 use collab_folder::FolderData;
 
 use event_integration::event_builder::EventBuilder;
-use flowy_folder2::entities::{FolderSnapshotPB, RepeatedFolderSnapshotPB, WorkspaceIdPB};
+use flowy_folder2::entities::{FolderSnapshotPB, FolderSnapshotQueryPB, RepeatedFolderSnapshotPB};
 use flowy_folder2::event_map::FolderEvent::GetFolderSnapshots;
 
 use crate::util::FlowySupabaseTest;
@@ -38,8 +38,10 @@ impl FlowySupabaseFolderTest {
   pub async fn get_folder_snapshots(&self, workspace_id: &str) -> Vec<FolderSnapshotPB> {
     EventBuilder::new(self.inner.deref().clone())
       .event(GetFolderSnapshots)
-      .payload(WorkspaceIdPB {
-        value: workspace_id.to_string(),
+      .payload(FolderSnapshotQueryPB {
+        workspace_id: workspace_id.to_string(),
+        limit: 10,
+        offset: 0,
       })
       .async_send()
       .await
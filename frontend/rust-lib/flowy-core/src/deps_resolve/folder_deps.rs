@@ -1,9 +1,12 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::convert::TryFrom;
 use std::sync::{Arc, Weak};
+use std::time::Duration;
 
 use bytes::Bytes;
+use tokio::sync::broadcast::error::RecvError;
 use tokio::sync::RwLock;
+use tokio::time::Instant;
 
 use collab_integrate::collab_builder::AppFlowyCollabBuilder;
 use collab_integrate::RocksCollabDB;
@@ -11,11 +14,16 @@ use flowy_database2::entities::DatabaseLayoutPB;
 use flowy_database2::services::share::csv::CSVFormat;
 use flowy_database2::template::{make_default_board, make_default_calendar, make_default_grid};
 use flowy_database2::DatabaseManager;
+use flowy_document2::document_data::get_mentioned_page_ids;
 use flowy_document2::entities::DocumentDataPB;
 use flowy_document2::manager::DocumentManager;
+use flowy_document2::parser::constant::PAGE;
+use flowy_document2::parser::document_data_parser::DocumentDataParser;
+use flowy_document2::parser::external::parser::ExternalDataToNestedJSONParser;
 use flowy_document2::parser::json::parser::JsonToDocumentParser;
-use flowy_error::FlowyError;
-use flowy_folder2::entities::ViewLayoutPB;
+use flowy_document2::parser::parser_entities::{InputType, NestedBlock};
+use flowy_error::{ErrorCode, FlowyError};
+use flowy_folder2::entities::{ViewLayoutPB, ViewReferencesParams, ViewReferencesPayloadPB};
 use flowy_folder2::manager::{FolderManager, FolderUser};
 use flowy_folder2::share::ImportType;
 use flowy_folder2::view_operation::{
@@ -23,8 +31,9 @@ use flowy_folder2::view_operation::{
 };
 use flowy_folder2::ViewLayout;
 use flowy_folder_deps::cloud::FolderCloudService;
+use flowy_sqlite::kv::StorePreferences;
 use flowy_user::manager::UserManager;
-use lib_dispatch::prelude::ToBytes;
+use lib_dispatch::prelude::{af_spawn, ToBytes};
 use lib_infra::future::FutureResult;
 
 pub struct FolderDepsResolver();
@@ -35,18 +44,88 @@ impl FolderDepsResolver {
     database_manager: &Arc<DatabaseManager>,
     collab_builder: Arc<AppFlowyCollabBuilder>,
     folder_cloud: Arc<dyn FolderCloudService>,
+    store_preferences: Weak<StorePreferences>,
   ) -> Arc<FolderManager> {
     let user: Arc<dyn FolderUser> = Arc::new(FolderUserImpl(user_manager.clone()));
 
     let handlers = folder_operation_handlers(document_manager.clone(), database_manager.clone());
-    Arc::new(
-      FolderManager::new(user.clone(), collab_builder, handlers, folder_cloud)
-        .await
-        .unwrap(),
-    )
+    let folder_manager = Arc::new(
+      FolderManager::new(
+        user.clone(),
+        collab_builder,
+        handlers,
+        folder_cloud,
+        store_preferences,
+      )
+      .await
+      .unwrap(),
+    );
+    subscribe_document_references(document_manager, Arc::downgrade(&folder_manager));
+    folder_manager
   }
 }
 
+/// The changes of the same document sent within the window update its references once.
+const DOCUMENT_REFERENCES_DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// Updates the views referenced by the documents whenever their content is changed locally, see
+/// [FolderManager::set_view_references]. The references are synced with the folder, so the
+/// remote changes are not handled here.
+fn subscribe_document_references(
+  document_manager: &Arc<DocumentManager>,
+  weak_folder_manager: Weak<FolderManager>,
+) {
+  let mut rx = document_manager.subscribe_content_changed();
+  let weak_document_manager = Arc::downgrade(document_manager);
+  af_spawn(async move {
+    while let Ok(doc_id) = rx.recv().await {
+      let mut doc_ids = HashSet::from([doc_id]);
+      let deadline = Instant::now() + DOCUMENT_REFERENCES_DEBOUNCE;
+      loop {
+        match tokio::time::timeout_at(deadline, rx.recv()).await {
+          Ok(Ok(doc_id)) => {
+            doc_ids.insert(doc_id);
+          },
+          Ok(Err(RecvError::Lagged(_))) => continue,
+          Ok(Err(RecvError::Closed)) | Err(_) => break,
+        }
+      }
+
+      let (Some(document_manager), Some(folder_manager)) = (
+        weak_document_manager.upgrade(),
+        weak_folder_manager.upgrade(),
+      ) else {
+        break;
+      };
+      for doc_id in doc_ids {
+        let data = match document_manager.get_document(&doc_id).await {
+          Ok(document) => document.lock().get_document_data(),
+          Err(err) => {
+            tracing::warn!("Failed to open the document:{}, {}", doc_id, err);
+            continue;
+          },
+        };
+        let Ok(data) = data else {
+          continue;
+        };
+        let payload = ViewReferencesPayloadPB {
+          view_id: doc_id.clone(),
+          referenced_view_ids: get_mentioned_page_ids(&data),
+        };
+        let params: Result<ViewReferencesParams, ErrorCode> = payload.try_into();
+        let result = match params {
+          Ok(params) => folder_manager.set_view_references(params).await,
+          Err(code) => Err(FlowyError::from(code)),
+        };
+        // The documents of the database rows are not views, so their references are skipped.
+        if let Err(err) = result {
+          tracing::trace!("Skip the references of the document:{}, {}", doc_id, err);
+        }
+      }
+    }
+  });
+}
+
 fn folder_operation_handlers(
   document_manager: Arc<DocumentManager>,
   database_manager: Arc<DatabaseManager>,
@@ -88,6 +167,41 @@ impl FolderUser for FolderUserImpl {
       .ok_or(FlowyError::internal().with_context("Unexpected error: UserSession is None"))?
       .get_collab_db(uid)
   }
+
+  fn workspace_ids(&self, uid: i64) -> Result<Vec<String>, FlowyError> {
+    let user_workspaces = self
+      .0
+      .upgrade()
+      .ok_or(FlowyError::internal().with_context("Unexpected error: UserSession is None"))?
+      .get_local_user_workspaces(uid)?;
+    Ok(
+      user_workspaces
+        .into_iter()
+        .map(|user_workspace| user_workspace.id)
+        .collect(),
+    )
+  }
+
+  fn add_workspace(&self, workspace_id: &str, name: &str) -> Result<(), FlowyError> {
+    self
+      .0
+      .upgrade()
+      .ok_or(FlowyError::internal().with_context("Unexpected error: UserSession is None"))?
+      .add_local_workspace(workspace_id, name)?;
+    Ok(())
+  }
+
+  fn delete_workspace(&self, workspace_id: &str) -> FutureResult<(), FlowyError> {
+    let user_manager = self.0.clone();
+    let workspace_id = workspace_id.to_string();
+    FutureResult::new(async move {
+      user_manager
+        .upgrade()
+        .ok_or(FlowyError::internal().with_context("Unexpected error: UserSession is None"))?
+        .delete_workspace(&workspace_id)
+        .await
+    })
+  }
 }
 
 struct DocumentFolderOperation(Arc<DocumentManager>);
@@ -152,6 +266,16 @@ impl FolderOperationHandler for DocumentFolderOperation {
     })
   }
 
+  fn export_view_to_markdown(&self, view_id: &str) -> FutureResult<String, FlowyError> {
+    let manager = self.0.clone();
+    let view_id = view_id.to_string();
+    FutureResult::new(async move {
+      let data = manager.get_document_data(&view_id).await?;
+      let markdown = DocumentDataParser::new(Arc::new(data), None).to_markdown();
+      Ok(markdown)
+    })
+  }
+
   fn create_view_with_view_data(
     &self,
     user_id: i64,
@@ -195,13 +319,20 @@ impl FolderOperationHandler for DocumentFolderOperation {
     uid: i64,
     view_id: &str,
     _name: &str,
-    _import_type: ImportType,
+    import_type: ImportType,
     bytes: Vec<u8>,
   ) -> FutureResult<(), FlowyError> {
     let view_id = view_id.to_string();
     let manager = self.0.clone();
     FutureResult::new(async move {
-      let data = DocumentDataPB::try_from(Bytes::from(bytes))?;
+      let data = match import_type {
+        ImportType::Markdown => {
+          let markdown =
+            String::from_utf8(bytes).map_err(|err| FlowyError::invalid_data().with_context(err))?;
+          markdown_to_document_data(markdown)?
+        },
+        _ => DocumentDataPB::try_from(Bytes::from(bytes))?,
+      };
       manager
         .create_document(uid, &view_id, Some(data.into()))
         .await?;
@@ -220,6 +351,16 @@ impl FolderOperationHandler for DocumentFolderOperation {
   }
 }
 
+/// Converts the markdown to the [DocumentDataPB]. An empty document will be returned if the
+/// markdown is empty.
+fn markdown_to_document_data(markdown: String) -> Result<DocumentDataPB, FlowyError> {
+  let block = ExternalDataToNestedJSONParser::new(markdown, InputType::Markdown)
+    .to_nested_block()
+    .unwrap_or_else(|| NestedBlock::new(PAGE.to_string(), Default::default(), vec![]));
+  let json_str = serde_json::to_string(&block)?;
+  JsonToDocumentParser::json_str_to_document(&json_str)
+}
+
 struct DatabaseFolderOperation(Arc<DatabaseManager>);
 impl FolderOperationHandler for DatabaseFolderOperation {
   fn close_view(&self, view_id: &str) -> FutureResult<(), FlowyError> {
@@ -338,7 +479,7 @@ impl FolderOperationHandler for DatabaseFolderOperation {
     };
     FutureResult::new(async move {
       let content =
-        String::from_utf8(bytes).map_err(|err| FlowyError::internal().with_context(err))?;
+        String::from_utf8(bytes).map_err(|err| FlowyError::invalid_data().with_context(err))?;
       database_manager
         .import_csv(view_id, content, format)
         .await?;
@@ -171,6 +171,17 @@ impl FolderCloudService for ServerProvider {
     })
   }
 
+  fn get_folder_snapshot_count(&self, workspace_id: &str) -> FutureResult<usize, Error> {
+    let workspace_id = workspace_id.to_string();
+    let server = self.get_server(&self.get_server_type());
+    FutureResult::new(async move {
+      server?
+        .folder_service()
+        .get_folder_snapshot_count(&workspace_id)
+        .await
+    })
+  }
+
   fn get_folder_updates(&self, workspace_id: &str, uid: i64) -> FutureResult<Vec<Vec<u8>>, Error> {
     let workspace_id = workspace_id.to_string();
     let server = self.get_server(&self.get_server_type());
@@ -166,6 +166,7 @@ impl AppFlowyCore {
         &database_manager,
         collab_builder.clone(),
         server_provider.clone(),
+        Arc::downgrade(&store_preference),
       )
       .await;
 
@@ -7,6 +7,7 @@ use collab::core::collab::MutexCollab;
 use collab_document::{blocks::DocumentData, document::Document};
 use futures::StreamExt;
 use parking_lot::Mutex;
+use tokio::sync::broadcast;
 
 use flowy_error::FlowyResult;
 use lib_dispatch::prelude::af_spawn;
@@ -23,11 +24,18 @@ impl MutexDocument {
   /// # Arguments
   /// * `collab` - the identifier of the collaboration instance
   ///
+  /// * `content_changed` - the sender to notify with the document id whenever the content is
+  ///   changed locally
+  ///
   /// # Returns
   /// * `Result<Document, FlowyError>` - a Result containing either a new Document object or an Error if the document creation failed
-  pub fn open(doc_id: &str, collab: Arc<MutexCollab>) -> FlowyResult<Self> {
+  pub fn open(
+    doc_id: &str,
+    collab: Arc<MutexCollab>,
+    content_changed: broadcast::Sender<String>,
+  ) -> FlowyResult<Self> {
     let document = Document::open(collab.clone()).map(|inner| Self(Arc::new(Mutex::new(inner))))?;
-    subscribe_document_changed(doc_id, &document);
+    subscribe_document_changed(doc_id, &document, content_changed);
     subscribe_document_snapshot_state(&collab);
     subscribe_document_sync_state(&collab);
     Ok(document)
@@ -47,7 +55,11 @@ impl MutexDocument {
   }
 }
 
-fn subscribe_document_changed(doc_id: &str, document: &MutexDocument) {
+fn subscribe_document_changed(
+  doc_id: &str,
+  document: &MutexDocument,
+  content_changed: broadcast::Sender<String>,
+) {
   let doc_id = doc_id.to_string();
   document
     .lock()
@@ -56,6 +68,9 @@ fn subscribe_document_changed(doc_id: &str, document: &MutexDocument) {
       send_notification(&doc_id, DocumentNotification::DidReceiveUpdate)
         .payload::<DocEventPB>((events, is_remote).into())
         .send();
+      if !is_remote {
+        let _ = content_changed.send(doc_id.clone());
+      }
     });
 }
 
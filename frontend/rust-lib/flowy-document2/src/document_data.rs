@@ -1,6 +1,39 @@
 use collab_document::blocks::{Block, DocumentData, DocumentMeta};
+use serde_json::Value;
 
 use crate::entities::{BlockPB, ChildrenPB, DocumentDataPB, MetaPB};
+use crate::parser::constant::MENTION;
+
+/// Returns the ids of the pages mentioned in the text of the document without duplicates. The
+/// page is mentioned by the text delta whose `mention` attribute is
+/// `{"type": "page", "page_id": <view id>}`.
+pub fn get_mentioned_page_ids(data: &DocumentData) -> Vec<String> {
+  let mut page_ids: Vec<String> = vec![];
+  let deltas = data
+    .meta
+    .text_map
+    .iter()
+    .flat_map(|text_map| text_map.values());
+  for delta in deltas {
+    let Ok(Value::Array(ops)) = serde_json::from_str::<Value>(delta) else {
+      continue;
+    };
+    for op in ops {
+      let page_id = op
+        .get("attributes")
+        .and_then(|attributes| attributes.get(MENTION))
+        .filter(|mention| mention.get("type").and_then(Value::as_str) == Some("page"))
+        .and_then(|mention| mention.get("page_id"))
+        .and_then(Value::as_str);
+      if let Some(page_id) = page_id {
+        if !page_ids.iter().any(|id| id == page_id) {
+          page_ids.push(page_id.to_string());
+        }
+      }
+    }
+  }
+  page_ids
+}
 
 impl From<DocumentData> for DocumentDataPB {
   fn from(data: DocumentData) -> Self {
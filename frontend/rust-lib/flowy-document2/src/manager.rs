@@ -8,6 +8,7 @@ use collab_document::document_data::default_document_data;
 use collab_document::YrsDocAction;
 use collab_entity::CollabType;
 use parking_lot::RwLock;
+use tokio::sync::broadcast;
 use tracing::instrument;
 
 use collab_integrate::collab_builder::AppFlowyCollabBuilder;
@@ -36,6 +37,7 @@ pub struct DocumentManager {
   #[allow(dead_code)]
   cloud_service: Arc<dyn DocumentCloudService>,
   storage_service: Weak<dyn FileStorageService>,
+  content_changed: broadcast::Sender<String>,
 }
 
 impl DocumentManager {
@@ -45,15 +47,22 @@ impl DocumentManager {
     cloud_service: Arc<dyn DocumentCloudService>,
     storage_service: Weak<dyn FileStorageService>,
   ) -> Self {
+    let (content_changed, _) = broadcast::channel(100);
     Self {
       user,
       collab_builder,
       documents: Default::default(),
       cloud_service,
       storage_service,
+      content_changed,
     }
   }
 
+  /// Returns the receiver of the ids of the opened documents whose content is changed locally.
+  pub fn subscribe_content_changed(&self) -> broadcast::Receiver<String> {
+    self.content_changed.subscribe()
+  }
+
   pub async fn initialize(&self, _uid: i64, _workspace_id: String) -> FlowyResult<()> {
     self.documents.write().clear();
     Ok(())
@@ -117,7 +126,11 @@ impl DocumentManager {
 
     let uid = self.user.user_id()?;
     let collab = self.collab_for_document(uid, doc_id, updates).await?;
-    let document = Arc::new(MutexDocument::open(doc_id, collab)?);
+    let document = Arc::new(MutexDocument::open(
+      doc_id,
+      collab,
+      self.content_changed.clone(),
+    )?);
 
     // save the document to the memory and read it from the memory if we open the same document again.
     // and we don't want to subscribe to the document changes if we open the same document again.
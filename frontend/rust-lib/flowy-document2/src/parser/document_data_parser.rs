@@ -4,7 +4,7 @@ use crate::parser::utils::{get_delta_for_block, get_delta_for_selection};
 use collab_document::blocks::DocumentData;
 use std::sync::Arc;
 
-/// DocumentDataParser is a struct for parsing a document's data and converting it to JSON, HTML, or text.
+/// DocumentDataParser is a struct for parsing a document's data and converting it to JSON, HTML, Markdown, or text.
 pub struct DocumentDataParser {
   /// The document data to parse.
   pub document_data: Arc<DocumentData>,
@@ -43,6 +43,15 @@ impl DocumentDataParser {
     }
   }
 
+  /// Converts the JSON to Markdown.
+  pub fn to_markdown_with_json(&self, json: &Option<NestedBlock>) -> String {
+    if let Some(json) = json {
+      json.convert_to_markdown()
+    } else {
+      String::new()
+    }
+  }
+
   /// Converts the document data to HTML.
   pub fn to_html(&self) -> String {
     let json = self.to_json();
@@ -55,6 +64,12 @@ impl DocumentDataParser {
     self.to_text_with_json(&json)
   }
 
+  /// Converts the document data to Markdown.
+  pub fn to_markdown(&self) -> String {
+    let json = self.to_json();
+    self.to_markdown_with_json(&json)
+  }
+
   /// Converts the document data to a nested JSON structure, considering the optional range.
   pub fn to_json(&self) -> Option<NestedBlock> {
     let root_id = &self.document_data.page_id;
@@ -1,13 +1,15 @@
-use crate::parser::external::utils::{flatten_element_to_block, parse_plaintext_to_nested_block};
+use crate::parser::external::utils::{
+  flatten_element_to_block, parse_markdown_to_nested_block, parse_plaintext_to_nested_block,
+};
 use crate::parser::parser_entities::{InputType, NestedBlock};
 use scraper::Html;
 
 /// External data to nested json parser.
 #[derive(Debug, Clone, Default)]
 pub struct ExternalDataToNestedJSONParser {
-  /// External data. for example: html string, plain text string.
+  /// External data. for example: html string, plain text string, markdown string.
   external_data: String,
-  /// External data type. for example: [InputType]::Html, [InputType]::PlainText.
+  /// External data type. for example: [InputType]::Html, [InputType]::PlainText, [InputType]::Markdown.
   input_type: InputType,
 }
 
@@ -35,6 +37,7 @@ impl ExternalDataToNestedJSONParser {
         flatten_element_to_block(root_element)
       },
       InputType::PlainText => parse_plaintext_to_nested_block(&self.external_data),
+      InputType::Markdown => parse_markdown_to_nested_block(&self.external_data),
     }
   }
 }
@@ -115,6 +115,199 @@ pub fn parse_plaintext_to_nested_block(plaintext: &str) -> Option<NestedBlock> {
   Some(current_block)
 }
 
+/// Parse markdown to nested block. Only the common block syntaxes are supported: headings, lists,
+/// todo lists, quotes, code blocks, dividers and images. The other lines are parsed as paragraphs.
+pub fn parse_markdown_to_nested_block(markdown: &str) -> Option<NestedBlock> {
+  let mut page = NestedBlock {
+    ty: PAGE.to_string(),
+    ..Default::default()
+  };
+
+  let mut lines = markdown.lines();
+  while let Some(line) = lines.next() {
+    let trimmed = line.trim();
+    if trimmed.is_empty() {
+      continue;
+    }
+
+    // Code block. All the lines until the closing fence belong to the code block.
+    if let Some(language) = trimmed.strip_prefix("```") {
+      let mut code_lines = vec![];
+      for code_line in lines.by_ref() {
+        if code_line.trim_start().starts_with("```") {
+          break;
+        }
+        code_lines.push(code_line);
+      }
+      let mut data = delta_data(vec![InsertDelta {
+        insert: code_lines.join("\n"),
+        attributes: None,
+      }]);
+      data.insert(
+        LANGUAGE.to_string(),
+        Value::String(language.trim().to_string()),
+      );
+      page
+        .children
+        .push(NestedBlock::new(CODE.to_string(), data, vec![]));
+      continue;
+    }
+
+    if trimmed == "---" || trimmed == "***" || trimmed == "___" {
+      page.children.push(NestedBlock::new(
+        DIVIDER.to_string(),
+        HashMap::new(),
+        vec![],
+      ));
+      continue;
+    }
+
+    page.children.push(parse_markdown_line(trimmed));
+  }
+
+  if page.children.is_empty() {
+    return None;
+  }
+  Some(page)
+}
+
+fn parse_markdown_line(line: &str) -> NestedBlock {
+  // Heading
+  let level = line.chars().take_while(|c| *c == '#').count();
+  if (1..=6).contains(&level) && line[level..].starts_with(' ') {
+    let mut data = delta_data(parse_markdown_inline(line[level..].trim()));
+    data.insert(LEVEL.to_string(), Value::from(level));
+    return NestedBlock::new(HEADING.to_string(), data, vec![]);
+  }
+
+  // Image
+  if let Some((caption, url)) = line
+    .strip_prefix("![")
+    .and_then(|rest| rest.strip_suffix(')'))
+    .and_then(|rest| rest.split_once("]("))
+  {
+    let mut data = HashMap::new();
+    data.insert(URL.to_string(), Value::String(url.to_string()));
+    data.insert(CAPTION.to_string(), Value::String(caption.to_string()));
+    return NestedBlock::new(IMAGE.to_string(), data, vec![]);
+  }
+
+  // Todo list
+  for (prefix, checked) in [("- [ ] ", false), ("- [x] ", true), ("- [X] ", true)] {
+    if let Some(text) = line.strip_prefix(prefix) {
+      let mut data = delta_data(parse_markdown_inline(text));
+      data.insert(CHECKED.to_string(), Value::Bool(checked));
+      return NestedBlock::new(TODO_LIST.to_string(), data, vec![]);
+    }
+  }
+
+  // Bulleted list
+  for prefix in ["- ", "* ", "+ "] {
+    if let Some(text) = line.strip_prefix(prefix) {
+      let data = delta_data(parse_markdown_inline(text));
+      return NestedBlock::new(BULLETED_LIST.to_string(), data, vec![]);
+    }
+  }
+
+  // Numbered list
+  if let Some((number, text)) = line.split_once(". ") {
+    if !number.is_empty() && number.chars().all(|c| c.is_ascii_digit()) {
+      let data = delta_data(parse_markdown_inline(text));
+      return NestedBlock::new(NUMBERED_LIST.to_string(), data, vec![]);
+    }
+  }
+
+  // Quote
+  if let Some(text) = line.strip_prefix('>') {
+    let data = delta_data(parse_markdown_inline(text.trim_start()));
+    return NestedBlock::new(QUOTE.to_string(), data, vec![]);
+  }
+
+  NestedBlock::new(
+    PARAGRAPH.to_string(),
+    delta_data(parse_markdown_inline(line)),
+    vec![],
+  )
+}
+
+/// Parse the inline markdown syntaxes: **bold**, _italic_, *italic*, ~~strikethrough~~, `code`
+/// and [link](url). The nested syntaxes are not supported.
+fn parse_markdown_inline(text: &str) -> Vec<InsertDelta> {
+  const MARKS: [(&str, &str); 6] = [
+    ("**", BOLD),
+    ("__", BOLD),
+    ("~~", STRIKETHROUGH),
+    ("`", CODE),
+    ("_", ITALIC),
+    ("*", ITALIC),
+  ];
+
+  let mut deltas = vec![];
+  let mut plain = String::new();
+  let mut rest = text;
+  'outer: while !rest.is_empty() {
+    // Link
+    if let Some(link) = rest.strip_prefix('[') {
+      if let Some((link_text, after_text)) = link.split_once("](") {
+        if let Some((href, after_link)) = after_text.split_once(')') {
+          push_plain_delta(&mut deltas, &mut plain);
+          let mut attributes = HashMap::new();
+          attributes.insert(HREF.to_string(), Value::String(href.to_string()));
+          deltas.push(InsertDelta {
+            insert: link_text.to_string(),
+            attributes: Some(attributes),
+          });
+          rest = after_link;
+          continue;
+        }
+      }
+    }
+
+    for (mark, attribute) in MARKS {
+      if let Some(marked) = rest.strip_prefix(mark) {
+        if let Some(end) = marked.find(mark) {
+          if end > 0 {
+            push_plain_delta(&mut deltas, &mut plain);
+            let mut attributes = HashMap::new();
+            attributes.insert(attribute.to_string(), Value::Bool(true));
+            deltas.push(InsertDelta {
+              insert: marked[..end].to_string(),
+              attributes: Some(attributes),
+            });
+            rest = &marked[end + mark.len()..];
+            continue 'outer;
+          }
+        }
+      }
+    }
+
+    let mut chars = rest.chars();
+    if let Some(c) = chars.next() {
+      plain.push(c);
+    }
+    rest = chars.as_str();
+  }
+  push_plain_delta(&mut deltas, &mut plain);
+  deltas
+}
+
+fn push_plain_delta(deltas: &mut Vec<InsertDelta>, plain: &mut String) {
+  if !plain.is_empty() {
+    deltas.push(InsertDelta {
+      insert: std::mem::take(plain),
+      attributes: None,
+    });
+  }
+}
+
+fn delta_data(delta: Vec<InsertDelta>) -> HashMap<String, Value> {
+  let mut data = HashMap::new();
+  if let Ok(delta) = serde_json::to_value(delta) {
+    data.insert(DELTA.to_string(), delta);
+  }
+  data
+}
+
 fn flatten_element_to_json(
   node: ElementRef,
   list_type: &Option<String>,
@@ -2,7 +2,7 @@ use crate::parse::NotEmptyStr;
 use crate::parser::constant::*;
 use crate::parser::utils::{
   convert_insert_delta_from_json, convert_nested_block_children_to_html, delta_to_html,
-  delta_to_text, required_not_empty_str, serialize_color_attribute,
+  delta_to_markdown, delta_to_text, required_not_empty_str, serialize_color_attribute,
 };
 use flowy_derive::{ProtoBuf, ProtoBuf_Enum};
 use flowy_error::ErrorCode;
@@ -163,6 +163,37 @@ impl InsertDelta {
     self.insert.clone()
   }
 
+  pub fn to_markdown(&self) -> String {
+    let mut markdown = self.insert.clone();
+    if markdown.trim().is_empty() {
+      return markdown;
+    }
+    if let Some(attrs) = &self.attributes {
+      let is_enabled = |attr_name: &str| {
+        attrs
+          .get(attr_name)
+          .and_then(|v| v.as_bool())
+          .unwrap_or(false)
+      };
+      if is_enabled(CODE) {
+        markdown = format!("`{}`", markdown);
+      }
+      if is_enabled(STRIKETHROUGH) {
+        markdown = format!("~~{}~~", markdown);
+      }
+      if is_enabled(ITALIC) {
+        markdown = format!("_{}_", markdown);
+      }
+      if is_enabled(BOLD) {
+        markdown = format!("**{}**", markdown);
+      }
+      if let Some(href) = attrs.get(HREF).and_then(|v| v.as_str()) {
+        markdown = format!("[{}]({})", markdown, href);
+      }
+    }
+    markdown
+  }
+
   pub fn to_html(&self) -> String {
     let mut html = String::new();
     let mut style = String::new();
@@ -491,6 +522,106 @@ impl NestedBlock {
     };
     text
   }
+
+  pub fn convert_to_markdown(&self) -> String {
+    let mut markdown = String::new();
+    self.write_markdown(&mut markdown, "");
+    markdown
+  }
+
+  /// Writes the block and its children to the `markdown`. Each line is prefixed with the
+  /// `indent`, so the children of the list blocks are nested under their parent.
+  fn write_markdown(&self, markdown: &mut String, indent: &str) {
+    let delta = self
+      .data
+      .get(DELTA)
+      .and_then(convert_insert_delta_from_json);
+    let delta_markdown = delta.as_ref().map(delta_to_markdown).unwrap_or_default();
+    let get_str = |key: &str| {
+      self
+        .data
+        .get(key)
+        .and_then(|v| v.as_str())
+        .unwrap_or_default()
+        .to_string()
+    };
+
+    let (content, child_indent) = match self.ty.as_str() {
+      PAGE => {
+        if !delta_markdown.is_empty() {
+          write_markdown_lines(markdown, indent, &delta_markdown);
+        }
+        for child in &self.children {
+          child.write_markdown(markdown, indent);
+        }
+        return;
+      },
+      HEADING => {
+        let level = self
+          .data
+          .get(LEVEL)
+          .and_then(|v| v.as_u64())
+          .unwrap_or(1)
+          .clamp(1, 6) as usize;
+        (
+          format!("{} {}", "#".repeat(level), delta_markdown),
+          indent.to_string(),
+        )
+      },
+      BULLETED_LIST | TOGGLE_LIST => (format!("- {}", delta_markdown), format!("{}  ", indent)),
+      NUMBERED_LIST => {
+        let number = self.data.get(NUMBER).and_then(|v| v.as_u64()).unwrap_or(1);
+        let marker = format!("{}. ", number);
+        (
+          format!("{}{}", marker, delta_markdown),
+          format!("{}{}", indent, " ".repeat(marker.len())),
+        )
+      },
+      TODO_LIST => {
+        let checked = self
+          .data
+          .get(CHECKED)
+          .and_then(|v| v.as_bool())
+          .unwrap_or(false);
+        let marker = if checked { "[x]" } else { "[ ]" };
+        (
+          format!("- {} {}", marker, delta_markdown),
+          format!("{}  ", indent),
+        )
+      },
+      QUOTE => (format!("> {}", delta_markdown), format!("{}> ", indent)),
+      CALLOUT => (
+        format!("> {} {}", get_str(ICON), delta_markdown),
+        format!("{}> ", indent),
+      ),
+      CODE => {
+        let code = delta.as_ref().map(delta_to_text).unwrap_or_default();
+        (
+          format!("```{}\n{}\n```", get_str(LANGUAGE), code),
+          indent.to_string(),
+        )
+      },
+      DIVIDER => ("---".to_string(), indent.to_string()),
+      IMAGE => (
+        format!("![{}]({})", get_str(CAPTION), get_str(URL)),
+        indent.to_string(),
+      ),
+      MATH_EQUATION => (format!("$$\n{}\n$$", get_str(FORMULA)), indent.to_string()),
+      _ => (delta_markdown, indent.to_string()),
+    };
+
+    write_markdown_lines(markdown, indent, &content);
+    for child in &self.children {
+      child.write_markdown(markdown, &child_indent);
+    }
+  }
+}
+
+fn write_markdown_lines(markdown: &mut String, indent: &str, content: &str) {
+  for line in content.split('\n') {
+    markdown.push_str(format!("{}{}", indent, line).trim_end());
+    markdown.push('\n');
+  }
 }
 
 pub struct ConvertBlockToHtmlParams {
@@ -503,6 +634,7 @@ pub enum InputType {
   #[default]
   Html = 0,
   PlainText = 1,
+  Markdown = 2,
 }
 
 #[derive(Default, ProtoBuf, Debug, Validate)]
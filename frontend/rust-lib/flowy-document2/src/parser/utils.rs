@@ -71,6 +71,14 @@ pub fn delta_to_html(delta: &Vec<InsertDelta>) -> String {
   result
 }
 
+pub fn delta_to_markdown(delta: &Vec<InsertDelta>) -> String {
+  let mut result = String::new();
+  for d in delta {
+    result.push_str(d.to_markdown().as_str());
+  }
+  result
+}
+
 pub fn convert_nested_block_children_to_html(block: Arc<NestedBlock>) -> String {
   let children = &block.children;
   let mut html = String::new();
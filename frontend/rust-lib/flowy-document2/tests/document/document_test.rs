@@ -2,6 +2,7 @@ use std::{collections::HashMap, vec};
 
 use collab_document::blocks::{Block, BlockAction, BlockActionPayload, BlockActionType};
 use collab_document::document_data::{default_document_data, PARAGRAPH_BLOCK_TYPE};
+use flowy_document2::document_data::get_mentioned_page_ids;
 use serde_json::{json, to_value, Value};
 
 use crate::document::util::{gen_document_id, gen_id, DocumentTest};
@@ -215,3 +216,63 @@ async fn document_apply_update_action() {
   // close a document
   _ = test.close_document(&doc_id);
 }
+
+#[tokio::test]
+async fn notify_local_content_change_test() {
+  let test = DocumentTest::new();
+  let doc_id: String = gen_document_id();
+  let data = default_document_data();
+  let uid = test.user.user_id().unwrap();
+  test
+    .create_document(uid, &doc_id, Some(data.clone()))
+    .await
+    .unwrap();
+  let mut rx = test.subscribe_content_changed();
+
+  let document = test.get_document(&doc_id).await.unwrap();
+  let text_block = Block {
+    id: gen_id(),
+    ty: PARAGRAPH_BLOCK_TYPE.to_string(),
+    parent: data.page_id.clone(),
+    children: gen_id(),
+    external_id: None,
+    external_type: None,
+    data: HashMap::new(),
+  };
+  document.lock().apply_action(vec![BlockAction {
+    action: BlockActionType::Insert,
+    payload: BlockActionPayload {
+      parent_id: None,
+      prev_id: None,
+      block: Some(text_block),
+      delta: None,
+      text_id: None,
+    },
+  }]);
+  assert_eq!(rx.recv().await.unwrap(), doc_id);
+}
+
+#[test]
+fn get_mentioned_page_ids_test() {
+  let mut data = default_document_data();
+  let text_map = data.meta.text_map.get_or_insert_with(HashMap::new);
+  text_map.insert(
+    gen_id(),
+    json!([
+      {"insert": "see "},
+      {"insert": "$", "attributes": {"mention": {"type": "page", "page_id": "page_1"}}},
+      {"insert": "$", "attributes": {"mention": {"type": "date", "date": "2023-01-01"}}},
+    ])
+    .to_string(),
+  );
+  text_map.insert(
+    gen_id(),
+    json!([
+      {"insert": "$", "attributes": {"mention": {"type": "page", "page_id": "page_1"}}},
+      {"insert": "not json"},
+    ])
+    .to_string(),
+  );
+  text_map.insert(gen_id(), "not json".to_string());
+  assert_eq!(get_mentioned_page_ids(&data), vec!["page_1".to_string()]);
+}
@@ -43,3 +43,37 @@ async fn plain_text_to_document_test() {
   let expect_block = serde_json::from_str::<NestedBlock>(expect_json).unwrap();
   assert_eq!(block, expect_block);
 }
+
+macro_rules! generate_markdown_test_cases {
+    ($($ty:ident),*) => {
+        [
+            $(
+                include_str!(concat!("../../assets/markdown/", stringify!($ty), ".md"))
+            ),*
+        ]
+    };
+}
+
+/// test convert markdown to json and back to markdown
+/// - input markdown: # Heading1
+#[tokio::test]
+async fn markdown_to_document_test() {
+  let test_cases = generate_markdown_test_cases!(heading, divider, image, code, quote);
+
+  for markdown in test_cases.iter() {
+    let parser = ExternalDataToNestedJSONParser::new(markdown.to_string(), InputType::Markdown);
+    let block = parser.to_nested_block();
+    assert!(block.is_some());
+    assert_eq!(block.unwrap().convert_to_markdown(), *markdown);
+  }
+}
+
+#[tokio::test]
+async fn markdown_inline_to_document_test() {
+  let markdown = "Hello **AppFlowy**, visit [GitHub](https://github.com/AppFlowy-IO/AppFlowy)\n";
+  let parser = ExternalDataToNestedJSONParser::new(markdown.to_string(), InputType::Markdown);
+  let block = parser.to_nested_block().unwrap();
+  assert_eq!(block.children.len(), 1);
+  assert_eq!(block.convert_to_text(), "Hello AppFlowy, visit GitHub\n");
+  assert_eq!(block.convert_to_markdown(), markdown);
+}
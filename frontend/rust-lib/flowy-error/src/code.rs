@@ -259,6 +259,36 @@ pub enum ErrorCode {
 
   #[error("Not support yet")]
   NotSupportYet = 85,
+
+  #[error("Can't delete the last workspace")]
+  CannotDeleteLastWorkspace = 86,
+
+  #[error("The view is locked")]
+  ViewIsLocked = 87,
+
+  #[error("The import is cancelled")]
+  ImportCancelled = 88,
+
+  #[error("The maximum nesting depth is exceeded")]
+  MaxNestingDepthExceeded = 89,
+
+  #[error("Too many views")]
+  TooManyViews = 90,
+
+  #[error("The trash reason is too long")]
+  TrashReasonTooLong = 91,
+
+  #[error("The maximum number of views of the workspace is reached")]
+  ViewLimitReached = 92,
+
+  #[error("The format of the import is not supported")]
+  ImportUnsupportedFormat = 93,
+
+  #[error("The import file is malformed")]
+  ImportMalformedFile = 94,
+
+  #[error("Failed to read the import file")]
+  ImportIOError = 95,
 }
 
 impl ErrorCode {
@@ -57,6 +57,7 @@ impl FlowyError {
 
   static_flowy_error!(internal, ErrorCode::Internal);
   static_flowy_error!(record_not_found, ErrorCode::RecordNotFound);
+  static_flowy_error!(record_already_exist, ErrorCode::RecordAlreadyExists);
   static_flowy_error!(workspace_name, ErrorCode::WorkspaceNameInvalid);
   static_flowy_error!(workspace_id, ErrorCode::WorkspaceIdInvalid);
   static_flowy_error!(color_style, ErrorCode::AppColorStyleInvalid);
@@ -102,6 +103,17 @@ impl FlowyError {
   static_flowy_error!(collab_not_sync, ErrorCode::CollabDataNotSync);
   static_flowy_error!(server_error, ErrorCode::InternalServerError);
   static_flowy_error!(not_support, ErrorCode::NotSupportYet);
+  static_flowy_error!(view_locked, ErrorCode::ViewIsLocked);
+  static_flowy_error!(import_cancelled, ErrorCode::ImportCancelled);
+  static_flowy_error!(max_depth_exceeded, ErrorCode::MaxNestingDepthExceeded);
+  static_flowy_error!(too_many_views, ErrorCode::TooManyViews);
+  static_flowy_error!(view_limit_reached, ErrorCode::ViewLimitReached);
+  static_flowy_error!(
+    import_unsupported_format,
+    ErrorCode::ImportUnsupportedFormat
+  );
+  static_flowy_error!(import_malformed_file, ErrorCode::ImportMalformedFile);
+  static_flowy_error!(import_io_error, ErrorCode::ImportIOError);
 }
 
 impl std::convert::From<ErrorCode> for FlowyError {
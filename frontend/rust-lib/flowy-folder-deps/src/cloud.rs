@@ -20,6 +20,9 @@ pub trait FolderCloudService: Send + Sync + 'static {
     limit: usize,
   ) -> FutureResult<Vec<FolderSnapshot>, Error>;
 
+  /// Returns the number of all the snapshots of the folder on the server.
+  fn get_folder_snapshot_count(&self, workspace_id: &str) -> FutureResult<usize, Error>;
+
   fn get_folder_updates(&self, workspace_id: &str, uid: i64) -> FutureResult<Vec<Vec<u8>>, Error>;
 
   fn service_name(&self) -> String;
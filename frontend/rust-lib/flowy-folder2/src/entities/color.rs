@@ -0,0 +1,57 @@
+use flowy_derive::{ProtoBuf, ProtoBuf_Enum};
+
+use crate::folder_extra::ViewColor;
+
+/// The color label of the view. [ViewColorPB::None] means the view has no color.
+#[derive(ProtoBuf_Enum, Clone, Debug, PartialEq, Eq, Default)]
+pub enum ViewColorPB {
+  #[default]
+  None = 0,
+  Red = 1,
+  Orange = 2,
+  Yellow = 3,
+  Green = 4,
+  Blue = 5,
+  Purple = 6,
+  Pink = 7,
+  Gray = 8,
+}
+
+impl From<ViewColorPB> for Option<ViewColor> {
+  fn from(pb: ViewColorPB) -> Self {
+    match pb {
+      ViewColorPB::None => None,
+      ViewColorPB::Red => Some(ViewColor::Red),
+      ViewColorPB::Orange => Some(ViewColor::Orange),
+      ViewColorPB::Yellow => Some(ViewColor::Yellow),
+      ViewColorPB::Green => Some(ViewColor::Green),
+      ViewColorPB::Blue => Some(ViewColor::Blue),
+      ViewColorPB::Purple => Some(ViewColor::Purple),
+      ViewColorPB::Pink => Some(ViewColor::Pink),
+      ViewColorPB::Gray => Some(ViewColor::Gray),
+    }
+  }
+}
+
+impl From<Option<ViewColor>> for ViewColorPB {
+  fn from(color: Option<ViewColor>) -> Self {
+    match color {
+      None => ViewColorPB::None,
+      Some(ViewColor::Red) => ViewColorPB::Red,
+      Some(ViewColor::Orange) => ViewColorPB::Orange,
+      Some(ViewColor::Yellow) => ViewColorPB::Yellow,
+      Some(ViewColor::Green) => ViewColorPB::Green,
+      Some(ViewColor::Blue) => ViewColorPB::Blue,
+      Some(ViewColor::Purple) => ViewColorPB::Purple,
+      Some(ViewColor::Pink) => ViewColorPB::Pink,
+      Some(ViewColor::Gray) => ViewColorPB::Gray,
+    }
+  }
+}
+
+/// Get the views with the given color. [ViewColorPB::None] returns the views without a color.
+#[derive(Default, ProtoBuf)]
+pub struct ViewColorFilterPB {
+  #[pb(index = 1)]
+  pub color: ViewColorPB,
+}
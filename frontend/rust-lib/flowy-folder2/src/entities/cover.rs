@@ -0,0 +1,94 @@
+use flowy_derive::{ProtoBuf, ProtoBuf_Enum};
+use flowy_error::ErrorCode;
+
+use crate::entities::parser::view::ViewIdentify;
+use crate::folder_extra::{ViewCover, ViewCoverType};
+
+#[derive(ProtoBuf_Enum, Clone, Debug, PartialEq, Eq, Default)]
+pub enum ViewCoverTypePB {
+  #[default]
+  Color = 0,
+  Gradient = 1,
+  Image = 2,
+}
+
+impl std::convert::From<ViewCoverTypePB> for ViewCoverType {
+  fn from(rev: ViewCoverTypePB) -> Self {
+    match rev {
+      ViewCoverTypePB::Color => ViewCoverType::Color,
+      ViewCoverTypePB::Gradient => ViewCoverType::Gradient,
+      ViewCoverTypePB::Image => ViewCoverType::Image,
+    }
+  }
+}
+
+impl From<ViewCoverType> for ViewCoverTypePB {
+  fn from(val: ViewCoverType) -> Self {
+    match val {
+      ViewCoverType::Color => ViewCoverTypePB::Color,
+      ViewCoverType::Gradient => ViewCoverTypePB::Gradient,
+      ViewCoverType::Image => ViewCoverTypePB::Image,
+    }
+  }
+}
+
+/// The cover of the view. The `value` is the color or the gradient for the [ViewCoverTypePB::Color]
+/// and [ViewCoverTypePB::Gradient] covers, and the url of the uploaded image for the
+/// [ViewCoverTypePB::Image] cover.
+#[derive(Default, ProtoBuf, Debug, Clone, PartialEq, Eq)]
+pub struct ViewCoverPB {
+  #[pb(index = 1)]
+  pub ty: ViewCoverTypePB,
+  #[pb(index = 2)]
+  pub value: String,
+}
+
+impl std::convert::From<ViewCoverPB> for ViewCover {
+  fn from(rev: ViewCoverPB) -> Self {
+    ViewCover {
+      ty: rev.ty.into(),
+      value: rev.value,
+    }
+  }
+}
+
+impl From<ViewCover> for ViewCoverPB {
+  fn from(val: ViewCover) -> Self {
+    ViewCoverPB {
+      ty: val.ty.into(),
+      value: val.value,
+    }
+  }
+}
+
+/// Update the cover of the view. The cover will be removed if the `cover` is `None` or its value
+/// is empty.
+#[derive(Default, ProtoBuf)]
+pub struct UpdateViewCoverPayloadPB {
+  #[pb(index = 1)]
+  pub view_id: String,
+
+  #[pb(index = 2, one_of)]
+  pub cover: Option<ViewCoverPB>,
+}
+
+#[derive(Clone, Debug)]
+pub struct UpdateViewCoverParams {
+  pub view_id: String,
+  pub cover: Option<ViewCover>,
+}
+
+impl TryInto<UpdateViewCoverParams> for UpdateViewCoverPayloadPB {
+  type Error = ErrorCode;
+
+  fn try_into(self) -> Result<UpdateViewCoverParams, Self::Error> {
+    let view_id = ViewIdentify::parse(self.view_id)?.0;
+
+    let cover = self
+      .cover
+      .filter(|cover| !cover.value.trim().is_empty())
+      .map(|cover| cover.into());
+
+    Ok(UpdateViewCoverParams { view_id, cover })
+  }
+}
@@ -0,0 +1,88 @@
+use flowy_derive::{ProtoBuf, ProtoBuf_Enum};
+use flowy_error::ErrorCode;
+
+use crate::entities::parser::view::ViewIdentify;
+use crate::share::{ExportFormat, ExportParams};
+
+#[derive(Clone, Debug, Default, ProtoBuf_Enum)]
+pub enum ExportFormatPB {
+  #[default]
+  Markdown = 0,
+}
+
+impl From<ExportFormatPB> for ExportFormat {
+  fn from(pb: ExportFormatPB) -> Self {
+    match pb {
+      ExportFormatPB::Markdown => ExportFormat::Markdown,
+    }
+  }
+}
+
+#[derive(Clone, Debug, ProtoBuf, Default)]
+pub struct ExportViewPayloadPB {
+  #[pb(index = 1)]
+  pub view_id: String,
+
+  #[pb(index = 2)]
+  pub format: ExportFormatPB,
+
+  /// Export the descendants of the view as well. Each descendant is exported under a heading
+  /// whose level is its depth in the subtree.
+  #[pb(index = 3)]
+  pub include_children: bool,
+}
+
+impl TryInto<ExportParams> for ExportViewPayloadPB {
+  type Error = ErrorCode;
+
+  fn try_into(self) -> Result<ExportParams, Self::Error> {
+    let view_id = ViewIdentify::parse(self.view_id)?.0;
+    Ok(ExportParams {
+      view_id,
+      format: self.format.into(),
+      include_children: self.include_children,
+    })
+  }
+}
+
+#[derive(Clone, Debug, ProtoBuf, Default)]
+pub struct ExportDataPB {
+  #[pb(index = 1)]
+  pub data: String,
+
+  #[pb(index = 2)]
+  pub file_name: String,
+}
+
+/// Export the favorites as a list of links in the order of the favorites. Each link carries the
+/// name of the view and its path in the workspace.
+#[derive(Clone, Debug, ProtoBuf, Default)]
+pub struct ExportFavoritesPayloadPB {
+  #[pb(index = 1)]
+  pub format: ExportFormatPB,
+}
+
+#[derive(Clone, Debug, Default, ProtoBuf_Enum)]
+pub enum ExportWorkspaceFormatPB {
+  /// A zip archive that contains the manifest of the view tree and the data of each view. It can
+  /// be imported without losing the structure of the workspace.
+  #[default]
+  Zip = 0,
+}
+
+#[derive(Clone, Debug, ProtoBuf, Default)]
+pub struct ExportWorkspacePayloadPB {
+  #[pb(index = 1)]
+  pub workspace_id: String,
+
+  #[pb(index = 2)]
+  pub format: ExportWorkspaceFormatPB,
+}
+
+#[derive(Clone, Debug, ProtoBuf, Default)]
+pub struct ExportWorkspaceDataPB {
+  /// The path of the exported file. It's written to the temporary directory, so the caller
+  /// should move it to the place chosen by the user.
+  #[pb(index = 1)]
+  pub file_path: String,
+}
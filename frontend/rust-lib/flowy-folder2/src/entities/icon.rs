@@ -1,4 +1,4 @@
-use crate::entities::parser::view::ViewIdentify;
+use crate::entities::parser::view::{ViewIconUrl, ViewIdentify};
 use collab_folder::{IconType, ViewIcon};
 use flowy_derive::{ProtoBuf, ProtoBuf_Enum};
 use flowy_error::ErrorCode;
@@ -57,6 +57,189 @@ impl From<ViewIcon> for ViewIconPB {
   }
 }
 
+/// Returns the icon with its emoji normalized by [normalize_emoji]. The other icons are returned
+/// as they are.
+pub fn normalize_view_icon(icon: ViewIcon) -> ViewIcon {
+  match icon.ty {
+    IconType::Emoji => ViewIcon {
+      value: normalize_emoji(&icon.value),
+      ..icon
+    },
+    _ => icon,
+  }
+}
+
+/// Returns the fully-qualified form of the emoji defined by the Unicode `emoji-test.txt`, so the
+/// same emoji sent by different clients in different encodings is stored identically.
+///
+/// The variation selectors are dropped first. Then the emoji presentation selector (U+FE0F) is
+/// added after each emoji that is rendered as text by default, which is where the
+/// fully-qualified form requires it, including the components of the ZWJ sequences and the
+/// keycaps. It's not added in front of a skin tone modifier or a tag, where it's not allowed.
+pub fn normalize_emoji(emoji: &str) -> String {
+  let chars = emoji
+    .trim()
+    .chars()
+    .filter(|c| !matches!(c, '\u{FE0E}' | '\u{FE0F}'))
+    .collect::<Vec<_>>();
+  let mut normalized = String::with_capacity(emoji.len() + chars.len());
+  for (index, &c) in chars.iter().enumerate() {
+    normalized.push(c);
+    let next = chars.get(index + 1).copied();
+    let needs_selector = match next {
+      Some('\u{1F3FB}'..='\u{1F3FF}') | Some('\u{E0020}'..='\u{E007F}') => false,
+      // The keycap base is only an emoji when it's followed by the combining keycap.
+      _ if matches!(c, '0'..='9' | '#' | '*') => next == Some('\u{20E3}'),
+      _ => is_text_presentation_emoji(c),
+    };
+    if needs_selector {
+      normalized.push('\u{FE0F}');
+    }
+  }
+  normalized
+}
+
+/// Returns true if the character is an emoji that is rendered as text by default, i.e. it has
+/// the `Emoji` property but not the `Emoji_Presentation` property in the Unicode
+/// `emoji-data.txt`. The keycap bases are handled by [normalize_emoji].
+fn is_text_presentation_emoji(c: char) -> bool {
+  let c = c as u32;
+  TEXT_PRESENTATION_EMOJIS
+    .binary_search_by(|(start, end)| {
+      if *end < c {
+        std::cmp::Ordering::Less
+      } else if *start > c {
+        std::cmp::Ordering::Greater
+      } else {
+        std::cmp::Ordering::Equal
+      }
+    })
+    .is_ok()
+}
+
+/// The sorted ranges of the emojis that are rendered as text by default, see
+/// [is_text_presentation_emoji].
+const TEXT_PRESENTATION_EMOJIS: &[(u32, u32)] = &[
+  (0x00A9, 0x00A9),
+  (0x00AE, 0x00AE),
+  (0x203C, 0x203C),
+  (0x2049, 0x2049),
+  (0x2122, 0x2122),
+  (0x2139, 0x2139),
+  (0x2194, 0x2199),
+  (0x21A9, 0x21AA),
+  (0x2328, 0x2328),
+  (0x23CF, 0x23CF),
+  (0x23ED, 0x23EF),
+  (0x23F1, 0x23F2),
+  (0x23F8, 0x23FA),
+  (0x24C2, 0x24C2),
+  (0x25AA, 0x25AB),
+  (0x25B6, 0x25B6),
+  (0x25C0, 0x25C0),
+  (0x25FB, 0x25FC),
+  (0x2600, 0x2604),
+  (0x260E, 0x260E),
+  (0x2611, 0x2611),
+  (0x2618, 0x2618),
+  (0x261D, 0x261D),
+  (0x2620, 0x2620),
+  (0x2622, 0x2623),
+  (0x2626, 0x2626),
+  (0x262A, 0x262A),
+  (0x262E, 0x262F),
+  (0x2638, 0x263A),
+  (0x2640, 0x2640),
+  (0x2642, 0x2642),
+  (0x265F, 0x2660),
+  (0x2663, 0x2663),
+  (0x2665, 0x2666),
+  (0x2668, 0x2668),
+  (0x267B, 0x267B),
+  (0x267E, 0x267E),
+  (0x2692, 0x2692),
+  (0x2694, 0x2697),
+  (0x2699, 0x2699),
+  (0x269B, 0x269C),
+  (0x26A0, 0x26A0),
+  (0x26A7, 0x26A7),
+  (0x26B0, 0x26B1),
+  (0x26C8, 0x26C8),
+  (0x26CF, 0x26CF),
+  (0x26D1, 0x26D1),
+  (0x26D3, 0x26D3),
+  (0x26E9, 0x26E9),
+  (0x26F0, 0x26F1),
+  (0x26F4, 0x26F4),
+  (0x26F7, 0x26F9),
+  (0x2702, 0x2702),
+  (0x2708, 0x2709),
+  (0x270C, 0x270D),
+  (0x270F, 0x270F),
+  (0x2712, 0x2712),
+  (0x2714, 0x2714),
+  (0x2716, 0x2716),
+  (0x271D, 0x271D),
+  (0x2721, 0x2721),
+  (0x2733, 0x2734),
+  (0x2744, 0x2744),
+  (0x2747, 0x2747),
+  (0x2763, 0x2764),
+  (0x27A1, 0x27A1),
+  (0x2934, 0x2935),
+  (0x2B05, 0x2B07),
+  (0x3030, 0x3030),
+  (0x303D, 0x303D),
+  (0x3297, 0x3297),
+  (0x3299, 0x3299),
+  (0x1F170, 0x1F171),
+  (0x1F17E, 0x1F17F),
+  (0x1F202, 0x1F202),
+  (0x1F237, 0x1F237),
+  (0x1F321, 0x1F321),
+  (0x1F324, 0x1F32C),
+  (0x1F336, 0x1F336),
+  (0x1F37D, 0x1F37D),
+  (0x1F396, 0x1F397),
+  (0x1F399, 0x1F39B),
+  (0x1F39E, 0x1F39F),
+  (0x1F3CB, 0x1F3CE),
+  (0x1F3D4, 0x1F3DF),
+  (0x1F3F3, 0x1F3F3),
+  (0x1F3F5, 0x1F3F5),
+  (0x1F3F7, 0x1F3F7),
+  (0x1F43F, 0x1F43F),
+  (0x1F441, 0x1F441),
+  (0x1F4FD, 0x1F4FD),
+  (0x1F549, 0x1F54A),
+  (0x1F56F, 0x1F570),
+  (0x1F573, 0x1F579),
+  (0x1F587, 0x1F587),
+  (0x1F58A, 0x1F58D),
+  (0x1F590, 0x1F590),
+  (0x1F5A5, 0x1F5A5),
+  (0x1F5A8, 0x1F5A8),
+  (0x1F5B1, 0x1F5B2),
+  (0x1F5BC, 0x1F5BC),
+  (0x1F5C2, 0x1F5C4),
+  (0x1F5D1, 0x1F5D3),
+  (0x1F5DC, 0x1F5DE),
+  (0x1F5E1, 0x1F5E1),
+  (0x1F5E3, 0x1F5E3),
+  (0x1F5E8, 0x1F5E8),
+  (0x1F5EF, 0x1F5EF),
+  (0x1F5F3, 0x1F5F3),
+  (0x1F5FA, 0x1F5FA),
+  (0x1F6CB, 0x1F6CB),
+  (0x1F6CD, 0x1F6CF),
+  (0x1F6E0, 0x1F6E5),
+  (0x1F6E9, 0x1F6E9),
+  (0x1F6F0, 0x1F6F0),
+  (0x1F6F3, 0x1F6F3),
+];
+
+/// Updates the icon of the view. The icon is removed if it's None or its value is empty,
+/// regardless of its type. The value of the [ViewIconTypePB::Url] icon must be a http(s) url.
 #[derive(Default, ProtoBuf)]
 pub struct UpdateViewIconPayloadPB {
   #[pb(index = 1)]
@@ -78,8 +261,48 @@ impl TryInto<UpdateViewIconParams> for UpdateViewIconPayloadPB {
   fn try_into(self) -> Result<UpdateViewIconParams, Self::Error> {
     let view_id = ViewIdentify::parse(self.view_id)?.0;
 
-    let icon = self.icon.map(|icon| icon.into());
+    let icon = match self.icon {
+      Some(icon) if !icon.value.trim().is_empty() => {
+        let value = match icon.ty {
+          ViewIconTypePB::Url => ViewIconUrl::parse(icon.value)?.0,
+          ViewIconTypePB::Emoji | ViewIconTypePB::Icon => icon.value,
+        };
+        Some(ViewIcon {
+          ty: icon.ty.into(),
+          value,
+        })
+      },
+      _ => None,
+    };
 
     Ok(UpdateViewIconParams { view_id, icon })
   }
 }
+
+/// Updates the icons of multiple views at once. The items are validated one by one, and the
+/// invalid ones are returned as failures without aborting the others.
+#[derive(Default, ProtoBuf)]
+pub struct RepeatedUpdateViewIconPayloadPB {
+  #[pb(index = 1)]
+  pub items: Vec<UpdateViewIconPayloadPB>,
+}
+
+#[derive(PartialEq, Eq, ProtoBuf, Default, Debug, Clone)]
+pub struct UpdateViewIconFailurePB {
+  #[pb(index = 1)]
+  pub view_id: String,
+
+  #[pb(index = 2)]
+  pub msg: String,
+}
+
+#[derive(PartialEq, Eq, ProtoBuf, Default, Debug, Clone)]
+pub struct UpdateViewIconsResultPB {
+  /// The ids of the views whose icons were updated.
+  #[pb(index = 1)]
+  pub updated_ids: Vec<String>,
+
+  /// The items that couldn't be applied, e.g. the view doesn't exist or is locked.
+  #[pb(index = 2)]
+  pub failed: Vec<UpdateViewIconFailurePB>,
+}
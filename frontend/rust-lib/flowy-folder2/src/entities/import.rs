@@ -1,8 +1,8 @@
 use flowy_derive::{ProtoBuf, ProtoBuf_Enum};
-use flowy_error::FlowyError;
+use flowy_error::{ErrorCode, FlowyError};
 
 use crate::entities::parser::empty_str::NotEmptyStr;
-use crate::entities::ViewLayoutPB;
+use crate::entities::{ViewLayoutPB, ViewPB};
 use crate::share::{ImportParams, ImportType};
 
 #[derive(Clone, Debug, ProtoBuf_Enum)]
@@ -11,6 +11,7 @@ pub enum ImportTypePB {
   HistoryDatabase = 1,
   RawDatabase = 2,
   CSV = 3,
+  NotionZip = 4,
 }
 
 impl From<ImportTypePB> for ImportType {
@@ -20,6 +21,7 @@ impl From<ImportTypePB> for ImportType {
       ImportTypePB::HistoryDatabase => ImportType::HistoryDatabase,
       ImportTypePB::RawDatabase => ImportType::RawDatabase,
       ImportTypePB::CSV => ImportType::CSV,
+      ImportTypePB::NotionZip => ImportType::NotionZip,
     }
   }
 }
@@ -49,15 +51,27 @@ pub struct ImportPB {
 
   #[pb(index = 6)]
   pub import_type: ImportTypePB,
+
+  /// The id chosen by the client to cancel the import with `CancelImport`.
+  #[pb(index = 7, one_of)]
+  pub import_id: Option<String>,
+
+  /// Reports the views that the import would create without writing anything to the folder.
+  #[pb(index = 8)]
+  pub dry_run: bool,
 }
 
 impl TryInto<ImportParams> for ImportPB {
   type Error = FlowyError;
 
+  /// Returns the [ErrorCode::ImportUnsupportedFormat] error if the layout of the view can't hold
+  /// the imported data, or the Notion export is not given as a directory.
   fn try_into(self) -> Result<ImportParams, Self::Error> {
     let parent_view_id = NotEmptyStr::parse(self.parent_view_id)
       .map_err(|_| FlowyError::invalid_view_id())?
       .0;
+    let import_type: ImportType = self.import_type.into();
+    check_import_format(&import_type, &self.view_layout, self.data.is_some())?;
 
     let name = if self.name.is_empty() {
       "Untitled".to_string()
@@ -80,7 +94,141 @@ impl TryInto<ImportParams> for ImportPB {
       data: self.data,
       file_path,
       view_layout: self.view_layout.into(),
-      import_type: self.import_type.into(),
+      import_type,
+      import_id: self.import_id.filter(|import_id| !import_id.is_empty()),
+      dry_run: self.dry_run,
     })
   }
 }
+
+fn check_import_format(
+  import_type: &ImportType,
+  view_layout: &ViewLayoutPB,
+  has_data: bool,
+) -> Result<(), FlowyError> {
+  let is_supported = match import_type {
+    ImportType::HistoryDocument | ImportType::Markdown => view_layout == &ViewLayoutPB::Document,
+    ImportType::HistoryDatabase | ImportType::RawDatabase | ImportType::CSV => {
+      view_layout.is_database()
+    },
+    // The Notion export is read from its unzipped directory.
+    ImportType::NotionZip => !has_data,
+  };
+  if is_supported {
+    Ok(())
+  } else {
+    Err(FlowyError::new(
+      ErrorCode::ImportUnsupportedFormat,
+      format!("Can't import {:?} as {:?}", import_type, view_layout),
+    ))
+  }
+}
+
+#[derive(Clone, Debug, ProtoBuf, Default)]
+pub struct ImportIdPB {
+  #[pb(index = 1)]
+  pub value: String,
+}
+
+/// The progress of the import. It's sent with the `ImportProgress` notification whose id is the
+/// id of the parent view that the data is imported into.
+#[derive(Clone, Debug, ProtoBuf, Default)]
+pub struct ImportProgressPB {
+  #[pb(index = 1)]
+  pub processed: u32,
+
+  #[pb(index = 2)]
+  pub total: u32,
+
+  /// The name of the item that was imported last.
+  #[pb(index = 3)]
+  pub current_name: String,
+}
+
+/// The result of the import. The import stops when the view limit of the workspace is reached,
+/// so the `created_count` can be less than the `requested_count`.
+///
+/// The import partially succeeds if some of the items fail to import, for example, one of the
+/// pages of the Notion export is malformed. The failed items are listed in the `skipped`, and the
+/// others are imported as usual.
+///
+/// The dry run reports the views that the import would create: the `created_count` and the
+/// `imported_names` are the planned ones, and the `view` is not stored, so it has no id.
+#[derive(Clone, Debug, ProtoBuf, Default)]
+pub struct ImportResultPB {
+  /// The root view of the import.
+  #[pb(index = 1)]
+  pub view: ViewPB,
+
+  /// The number of the views that were created, including the root view.
+  #[pb(index = 2)]
+  pub created_count: u32,
+
+  /// The number of the views that the import would create without the view limit.
+  #[pb(index = 3)]
+  pub requested_count: u32,
+
+  /// The names of the imported views in the order they were imported, the root view first.
+  #[pb(index = 4)]
+  pub imported_names: Vec<String>,
+
+  #[pb(index = 5)]
+  pub skipped: Vec<ImportSkippedItemPB>,
+}
+
+/// The item that was not imported. The sub-pages of a skipped page are skipped with the same
+/// error.
+#[derive(Clone, Debug, ProtoBuf, Default)]
+pub struct ImportSkippedItemPB {
+  #[pb(index = 1)]
+  pub name: String,
+
+  /// The code of the [flowy_error::ErrorCode], for example, the
+  /// [ErrorCode::ImportMalformedFile] if the page can't be parsed.
+  #[pb(index = 2)]
+  pub code: i32,
+
+  #[pb(index = 3)]
+  pub msg: String,
+}
+
+/// The views that the import would create. The views are listed in pre-order, which is the order
+/// they would be imported in, and each view's depth encodes the tree. The root of the import is at
+/// depth one.
+#[derive(Clone, Debug, ProtoBuf, Default)]
+pub struct ImportPreviewPB {
+  #[pb(index = 1)]
+  pub items: Vec<ImportPreviewItemPB>,
+
+  /// The items that would be skipped because the view limit of the workspace is reached.
+  #[pb(index = 2)]
+  pub skipped: Vec<ImportSkippedItemPB>,
+}
+
+#[derive(Clone, Debug, ProtoBuf, Default)]
+pub struct ImportPreviewItemPB {
+  #[pb(index = 1)]
+  pub name: String,
+
+  #[pb(index = 2)]
+  pub layout: ViewLayoutPB,
+
+  #[pb(index = 3)]
+  pub depth: u32,
+
+  /// Whether the view would have the same name as one of its siblings. The `name` is the
+  /// suffixed one if the workspace keeps the names of the sibling views unique.
+  #[pb(index = 4)]
+  pub name_collision: bool,
+}
+
+/// Import the archive exported by `ExportWorkspace` into the target workspace. The current
+/// workspace is used if the `target_workspace_id` is empty.
+#[derive(Clone, Debug, ProtoBuf, Default)]
+pub struct ImportWorkspacePayloadPB {
+  #[pb(index = 1)]
+  pub target_workspace_id: String,
+
+  #[pb(index = 2)]
+  pub data: Vec<u8>,
+}
@@ -0,0 +1,51 @@
+use flowy_derive::ProtoBuf;
+use flowy_error::ErrorCode;
+
+use crate::entities::parser::empty_str::NotEmptyStr;
+use crate::entities::parser::view::ViewIdentify;
+
+/// The metadata attached to the view. The folder stores the value as an opaque string, so the
+/// callers should namespace their keys, for example, `my_plugin.some_key`.
+///
+/// The `value` is ignored when getting the metadata, and setting an empty value deletes the key.
+#[derive(Default, ProtoBuf)]
+pub struct ViewMetaPayloadPB {
+  #[pb(index = 1)]
+  pub view_id: String,
+
+  #[pb(index = 2)]
+  pub key: String,
+
+  #[pb(index = 3)]
+  pub value: String,
+}
+
+#[derive(Clone, Debug)]
+pub struct ViewMetaParams {
+  pub view_id: String,
+  pub key: String,
+  pub value: String,
+}
+
+impl TryInto<ViewMetaParams> for ViewMetaPayloadPB {
+  type Error = ErrorCode;
+
+  fn try_into(self) -> Result<ViewMetaParams, Self::Error> {
+    let view_id = ViewIdentify::parse(self.view_id)?.0;
+    let key = NotEmptyStr::parse(self.key)
+      .map_err(|_| ErrorCode::InvalidParams)?
+      .0;
+    Ok(ViewMetaParams {
+      view_id,
+      key,
+      value: self.value,
+    })
+  }
+}
+
+/// The value of the metadata. Empty if the key isn't set.
+#[derive(Default, ProtoBuf)]
+pub struct ViewMetaValuePB {
+  #[pb(index = 1)]
+  pub value: String,
+}
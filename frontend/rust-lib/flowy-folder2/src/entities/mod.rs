@@ -1,12 +1,24 @@
+pub mod color;
+pub mod cover;
+mod export;
 pub mod icon;
 mod import;
+pub mod meta;
 mod parser;
+pub mod reference;
+pub mod tag;
 pub mod trash;
 pub mod view;
 pub mod workspace;
 
+pub use color::*;
+pub use cover::*;
+pub use export::*;
 pub use icon::*;
 pub use import::*;
+pub use meta::*;
+pub use reference::*;
+pub use tag::*;
 pub use trash::*;
 pub use view::*;
 pub use workspace::*;
@@ -1,3 +1,5 @@
 mod trash_id;
+mod trash_reason;
 
 pub use trash_id::*;
+pub use trash_reason::*;
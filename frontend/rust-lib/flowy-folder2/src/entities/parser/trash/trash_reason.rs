@@ -0,0 +1,25 @@
+use flowy_error::ErrorCode;
+use unicode_segmentation::UnicodeSegmentation;
+
+/// The maximum number of characters of the reason for moving the view to the trash.
+const MAX_TRASH_REASON_LEN: usize = 200;
+
+#[derive(Debug)]
+pub struct TrashReason(pub String);
+
+impl TrashReason {
+  pub fn parse(s: String) -> Result<TrashReason, ErrorCode> {
+    let s = s.trim().to_string();
+    if s.graphemes(true).count() > MAX_TRASH_REASON_LEN {
+      return Err(ErrorCode::TrashReasonTooLong);
+    }
+
+    Ok(Self(s))
+  }
+}
+
+impl AsRef<str> for TrashReason {
+  fn as_ref(&self) -> &str {
+    &self.0
+  }
+}
@@ -1,9 +1,13 @@
 mod view_desc;
+mod view_icon_url;
 mod view_id;
 mod view_name;
+mod view_tag;
 mod view_thumbnail;
 
 pub use view_desc::*;
+pub use view_icon_url::*;
 pub use view_id::*;
 pub use view_name::*;
+pub use view_tag::*;
 pub use view_thumbnail::*;
@@ -0,0 +1,24 @@
+use flowy_error::ErrorCode;
+use url::Url;
+
+/// The url of the custom image icon. Only the http(s) urls are accepted.
+#[derive(Debug)]
+pub struct ViewIconUrl(pub String);
+
+impl ViewIconUrl {
+  pub fn parse(s: String) -> Result<ViewIconUrl, ErrorCode> {
+    let s = s.trim();
+    let url = Url::parse(s).map_err(|_| ErrorCode::InvalidURL)?;
+    if !matches!(url.scheme(), "http" | "https") || url.host_str().is_none() {
+      return Err(ErrorCode::InvalidURL);
+    }
+
+    Ok(Self(s.to_string()))
+  }
+}
+
+impl AsRef<str> for ViewIconUrl {
+  fn as_ref(&self) -> &str {
+    &self.0
+  }
+}
@@ -0,0 +1,22 @@
+use flowy_error::ErrorCode;
+use unicode_segmentation::UnicodeSegmentation;
+
+#[derive(Debug)]
+pub struct ViewTag(pub String);
+
+impl ViewTag {
+  pub fn parse(s: String) -> Result<ViewTag, ErrorCode> {
+    let s = s.trim();
+    if s.is_empty() || s.graphemes(true).count() > 64 {
+      return Err(ErrorCode::InvalidParams);
+    }
+
+    Ok(Self(s.to_string()))
+  }
+}
+
+impl AsRef<str> for ViewTag {
+  fn as_ref(&self) -> &str {
+    &self.0
+  }
+}
@@ -0,0 +1,44 @@
+use flowy_derive::ProtoBuf;
+use flowy_error::ErrorCode;
+
+use crate::entities::parser::view::ViewIdentify;
+
+/// The views referenced by the content of the view, for example, the pages mentioned in a
+/// document. The folder can't read the content of the views, so the plugin that owns the content
+/// reports the references whenever the content changes.
+///
+/// The `referenced_view_ids` replaces the previous references of the view.
+#[derive(Default, ProtoBuf)]
+pub struct ViewReferencesPayloadPB {
+  #[pb(index = 1)]
+  pub view_id: String,
+
+  #[pb(index = 2)]
+  pub referenced_view_ids: Vec<String>,
+}
+
+#[derive(Clone, Debug)]
+pub struct ViewReferencesParams {
+  pub view_id: String,
+  pub referenced_view_ids: Vec<String>,
+}
+
+impl TryInto<ViewReferencesParams> for ViewReferencesPayloadPB {
+  type Error = ErrorCode;
+
+  fn try_into(self) -> Result<ViewReferencesParams, Self::Error> {
+    let view_id = ViewIdentify::parse(self.view_id)?.0;
+    let mut referenced_view_ids: Vec<String> = vec![];
+    for referenced_view_id in self.referenced_view_ids {
+      let referenced_view_id = ViewIdentify::parse(referenced_view_id)?.0;
+      // The view referencing itself is not a backlink.
+      if referenced_view_id != view_id && !referenced_view_ids.contains(&referenced_view_id) {
+        referenced_view_ids.push(referenced_view_id);
+      }
+    }
+    Ok(ViewReferencesParams {
+      view_id,
+      referenced_view_ids,
+    })
+  }
+}
@@ -0,0 +1,41 @@
+use flowy_derive::ProtoBuf;
+use flowy_error::ErrorCode;
+
+use crate::entities::parser::view::{ViewIdentify, ViewTag};
+
+/// Add the tags to the view or remove the tags from the view. The tags are matched
+/// case-insensitively.
+#[derive(Default, ProtoBuf)]
+pub struct ViewTagsPayloadPB {
+  #[pb(index = 1)]
+  pub view_id: String,
+
+  #[pb(index = 2)]
+  pub tags: Vec<String>,
+}
+
+#[derive(Clone, Debug)]
+pub struct ViewTagsParams {
+  pub view_id: String,
+  pub tags: Vec<String>,
+}
+
+impl TryInto<ViewTagsParams> for ViewTagsPayloadPB {
+  type Error = ErrorCode;
+
+  fn try_into(self) -> Result<ViewTagsParams, Self::Error> {
+    let view_id = ViewIdentify::parse(self.view_id)?.0;
+    let tags = self
+      .tags
+      .into_iter()
+      .map(|tag| ViewTag::parse(tag).map(|tag| tag.0))
+      .collect::<Result<Vec<_>, _>>()?;
+    Ok(ViewTagsParams { view_id, tags })
+  }
+}
+
+#[derive(Default, ProtoBuf)]
+pub struct ViewTagPB {
+  #[pb(index = 1)]
+  pub value: String,
+}
@@ -1,5 +1,9 @@
 use collab_folder::TrashInfo;
 use flowy_derive::ProtoBuf;
+use flowy_error::ErrorCode;
+
+use crate::entities::parser::trash::TrashReason;
+use crate::entities::parser::view::ViewIdentify;
 
 #[derive(Eq, PartialEq, ProtoBuf, Default, Debug, Clone)]
 pub struct TrashPB {
@@ -14,6 +18,10 @@ pub struct TrashPB {
 
   #[pb(index = 4)]
   pub create_time: i64,
+
+  /// The reason why the view was moved to the trash. Empty if no reason was given.
+  #[pb(index = 5)]
+  pub reason: String,
 }
 
 impl std::convert::From<TrashInfo> for TrashPB {
@@ -23,6 +31,7 @@ impl std::convert::From<TrashInfo> for TrashPB {
       name: trash_info.name,
       modified_time: trash_info.created_at,
       create_time: trash_info.created_at,
+      reason: "".to_string(),
     }
   }
 }
@@ -71,3 +80,104 @@ pub struct RepeatedTrashIdPB {
   #[pb(index = 1)]
   pub items: Vec<TrashIdPB>,
 }
+
+#[derive(PartialEq, Eq, ProtoBuf, Default, Debug, Clone)]
+pub struct TrashPagePayloadPB {
+  #[pb(index = 1)]
+  pub offset: u32,
+
+  #[pb(index = 2)]
+  pub limit: u32,
+
+  /// Only the trash of the workspace is returned.
+  #[pb(index = 3)]
+  pub workspace_id: String,
+}
+
+#[derive(PartialEq, Eq, ProtoBuf, Default, Debug, Clone)]
+pub struct TrashPagePB {
+  /// The trash items of the page, the newest deleted first.
+  #[pb(index = 1)]
+  pub trash: RepeatedTrashPB,
+
+  /// The number of all the trash items of the workspace.
+  #[pb(index = 2)]
+  pub total_count: u32,
+}
+
+#[derive(PartialEq, Eq, ProtoBuf, Default, Debug, Clone)]
+pub struct TrashCountPB {
+  #[pb(index = 1)]
+  pub count: u32,
+}
+
+#[derive(PartialEq, Eq, ProtoBuf, Default, Debug, Clone)]
+pub struct RestoreTrashPayloadPB {
+  #[pb(index = 1)]
+  pub trash_id: String,
+
+  /// The id of the view or the workspace that the restored view will be attached to.
+  #[pb(index = 2)]
+  pub target_parent_id: String,
+}
+
+#[derive(PartialEq, Eq, ProtoBuf, Default, Debug, Clone)]
+pub struct RestoreTrashBatchPB {
+  /// The ids of the trash items that were restored.
+  #[pb(index = 1)]
+  pub restored_ids: Vec<String>,
+
+  /// The ids that couldn't be restored because they are no longer in the trash.
+  #[pb(index = 2)]
+  pub failed_ids: Vec<String>,
+}
+
+#[derive(PartialEq, Eq, ProtoBuf, Default, Debug, Clone)]
+pub struct RestoreAllResultPB {
+  /// The number of the trash items that were restored, including the ones attached to the root
+  /// of the workspace because their parents no longer exist.
+  #[pb(index = 1)]
+  pub restored_count: u32,
+
+  /// The ids of the trash items that couldn't be restored. They are kept in the trash.
+  #[pb(index = 2)]
+  pub failed: Vec<String>,
+}
+
+#[derive(PartialEq, Eq, ProtoBuf, Default, Debug, Clone)]
+pub struct TrashRetentionPayloadPB {
+  /// The trash items older than the given number of days will be deleted permanently. Zero
+  /// disables the automatic deletion.
+  #[pb(index = 1)]
+  pub days: u32,
+}
+
+/// Move the view to the trash with the reason why it's deleted. The reason is trimmed, and the
+/// empty reason is the same as no reason.
+#[derive(PartialEq, Eq, ProtoBuf, Default, Debug, Clone)]
+pub struct TrashViewPayloadPB {
+  #[pb(index = 1)]
+  pub view_id: String,
+
+  #[pb(index = 2, one_of)]
+  pub reason: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct TrashViewParams {
+  pub view_id: String,
+  pub reason: Option<String>,
+}
+
+impl TryInto<TrashViewParams> for TrashViewPayloadPB {
+  type Error = ErrorCode;
+
+  fn try_into(self) -> Result<TrashViewParams, Self::Error> {
+    let view_id = ViewIdentify::parse(self.view_id)?.0;
+    let reason = match self.reason {
+      None => None,
+      Some(reason) => Some(TrashReason::parse(reason)?.0).filter(|reason| !reason.is_empty()),
+    };
+    Ok(TrashViewParams { view_id, reason })
+  }
+}
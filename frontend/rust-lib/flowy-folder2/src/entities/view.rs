@@ -8,9 +8,16 @@ use collab_folder::{View, ViewLayout};
 use flowy_derive::{ProtoBuf, ProtoBuf_Enum};
 use flowy_error::ErrorCode;
 use flowy_folder_deps::cloud::gen_view_id;
+use lib_infra::util::timestamp;
 
+use crate::entities::color::ViewColorPB;
+use crate::entities::cover::ViewCoverPB;
 use crate::entities::icon::ViewIconPB;
+use crate::entities::parser::empty_str::NotEmptyStr;
 use crate::entities::parser::view::{ViewDesc, ViewIdentify, ViewName, ViewThumbnail};
+use crate::entities::parser::workspace::WorkspaceIdentify;
+use crate::folder_extra::ViewColor;
+use crate::operation_history::FolderOperation;
 
 #[derive(Eq, PartialEq, ProtoBuf, Debug, Default, Clone)]
 pub struct ChildViewUpdatePB {
@@ -25,6 +32,11 @@ pub struct ChildViewUpdatePB {
 
   #[pb(index = 4)]
   pub update_child_views: Vec<ViewPB>,
+
+  /// All the child views of the parent in the new order. Only set when the child views are
+  /// reordered, for example, after a child view is moved in or out of the parent.
+  #[pb(index = 5)]
+  pub child_views: Vec<ViewPB>,
 }
 
 #[derive(Eq, PartialEq, ProtoBuf, Debug, Default, Clone)]
@@ -33,7 +45,9 @@ pub struct ViewPB {
   pub id: String,
 
   /// The parent view id of the view.
-  /// Each view should have a parent view except the orphan view.
+  /// Each view should have a parent view except the orphan view. The views at the root of the
+  /// workspace report the id of the workspace as their parent, so a flat list of views, e.g. the
+  /// search results, is enough to place each view in the tree.
   #[pb(index = 2)]
   pub parent_view_id: String,
 
@@ -57,6 +71,83 @@ pub struct ViewPB {
 
   #[pb(index = 8)]
   pub is_favorite: bool,
+
+  /// The timestamp in seconds of the last time the view was edited. It's the same as the
+  /// `create_time` if the view has never been edited.
+  #[pb(index = 9)]
+  pub last_edited_time: i64,
+
+  /// The description shown under the name of the view. Empty if the view has no description.
+  #[pb(index = 10)]
+  pub description: String,
+
+  /// The locked view can't be updated, moved or deleted until it's unlocked.
+  #[pb(index = 11)]
+  pub locked: bool,
+
+  /// The pinned view comes before its unpinned siblings. It's independent of the favorites.
+  #[pb(index = 12)]
+  pub is_pinned: bool,
+
+  /// Whether the view or any of its ancestors is in the trash. It's only filled for the views
+  /// that may be trashed, for example, the ancestors returned by `GetViewAncestors`.
+  #[pb(index = 13)]
+  pub is_trashed: bool,
+
+  /// The cover of the view.
+  #[pb(index = 14, one_of)]
+  pub cover: Option<ViewCoverPB>,
+
+  /// The tags of the view.
+  #[pb(index = 15)]
+  pub tags: Vec<String>,
+
+  /// The id of the user who created the view. Zero if the creator is unknown.
+  #[pb(index = 16)]
+  pub created_by: i64,
+
+  /// The id of the user who edited the view last time. It's the same as the `created_by` if the
+  /// view has never been edited.
+  #[pb(index = 17)]
+  pub last_edited_by: i64,
+
+  /// The number of the direct child views, not counting the trashed, template and archived ones,
+  /// which are the child views that `GetChildViews` returns.
+  #[pb(index = 18)]
+  pub child_count: u32,
+
+  /// The template view is excluded from the child views of its parent and listed by
+  /// `GetTemplates` instead.
+  #[pb(index = 19)]
+  pub is_template: bool,
+
+  /// Whether the child views of the view are shown in the sidebar. It's kept across restarts.
+  #[pb(index = 20)]
+  pub is_expanded: bool,
+
+  /// The color label of the view.
+  #[pb(index = 21)]
+  pub color: ViewColorPB,
+
+  /// The reason given the last time the view was moved to the trash. It's kept after the view is
+  /// restored, so the restored view can be told apart.
+  #[pb(index = 22, one_of)]
+  pub trash_reason: Option<String>,
+
+  /// The depth of the view relative to the root of the subtree. It's only filled for the views
+  /// returned by `FlattenSubtree`, where the root is at depth zero.
+  #[pb(index = 23)]
+  pub depth: u32,
+
+  /// The archived view is excluded from the child views of its parent and listed by
+  /// `GetArchivedViews` instead. It's still returned by `SearchViews`.
+  #[pb(index = 24)]
+  pub is_archived: bool,
+
+  /// The timestamp in seconds of the last time the view was opened or closed. Zero if the view
+  /// has never been opened. It's independent of the `last_edited_time`.
+  #[pb(index = 25)]
+  pub last_opened_time: i64,
 }
 
 pub fn view_pb_without_child_views(view: Arc<View>) -> ViewPB {
@@ -69,6 +160,23 @@ pub fn view_pb_without_child_views(view: Arc<View>) -> ViewPB {
     layout: view.layout.clone().into(),
     icon: view.icon.clone().map(|icon| icon.into()),
     is_favorite: view.is_favorite,
+    last_edited_time: view.created_at,
+    description: view.desc.clone(),
+    locked: false,
+    is_pinned: false,
+    is_trashed: false,
+    cover: None,
+    tags: vec![],
+    created_by: 0,
+    last_edited_by: 0,
+    child_count: view.children.items.len() as u32,
+    is_template: false,
+    is_expanded: false,
+    color: ViewColorPB::None,
+    trash_reason: None,
+    depth: 0,
+    is_archived: false,
+    last_opened_time: 0,
   }
 }
 
@@ -86,6 +194,23 @@ pub fn view_pb_with_child_views(view: Arc<View>, child_views: Vec<Arc<View>>) ->
     layout: view.layout.clone().into(),
     icon: view.icon.clone().map(|icon| icon.into()),
     is_favorite: view.is_favorite,
+    last_edited_time: view.created_at,
+    description: view.desc.clone(),
+    locked: false,
+    is_pinned: false,
+    is_trashed: false,
+    cover: None,
+    tags: vec![],
+    created_by: 0,
+    last_edited_by: 0,
+    child_count: view.children.items.len() as u32,
+    is_template: false,
+    is_expanded: false,
+    color: ViewColorPB::None,
+    trash_reason: None,
+    depth: 0,
+    is_archived: false,
+    last_opened_time: 0,
   }
 }
 
@@ -148,6 +273,42 @@ impl DerefMut for RepeatedViewPB {
 pub struct RepeatedViewIdPB {
   #[pb(index = 1)]
   pub items: Vec<String>,
+
+  /// Only used by [crate::event_map::FolderEvent::DeleteView]. Whether the child views are moved
+  /// to the trash along with the view. If it's false, the child views are moved to the parent of
+  /// the view before the view is trashed. Defaults to true.
+  #[pb(index = 2, one_of)]
+  pub cascade: Option<bool>,
+}
+
+/// The result of moving the views to the trash.
+#[derive(Default, ProtoBuf)]
+pub struct DeleteResultPB {
+  /// The ids of the views moved to the trash, including their descendants.
+  #[pb(index = 1)]
+  pub trashed_ids: RepeatedViewIdPB,
+
+  /// The views that couldn't be moved to the trash.
+  #[pb(index = 2)]
+  pub failures: Vec<DeleteViewFailurePB>,
+
+  /// The ids of the child views that were moved to the parent of the trashed views instead of
+  /// being trashed. Empty if the deletion cascades.
+  #[pb(index = 3)]
+  pub reparented_ids: Vec<String>,
+}
+
+#[derive(Default, ProtoBuf)]
+pub struct DeleteViewFailurePB {
+  #[pb(index = 1)]
+  pub view_id: String,
+
+  /// The code of the [flowy_error::ErrorCode].
+  #[pb(index = 2)]
+  pub code: i32,
+
+  #[pb(index = 3)]
+  pub msg: String,
 }
 
 #[derive(Default, ProtoBuf)]
@@ -164,8 +325,10 @@ pub struct CreateViewPayloadPB {
   #[pb(index = 4, one_of)]
   pub thumbnail: Option<String>,
 
-  #[pb(index = 5)]
-  pub layout: ViewLayoutPB,
+  // The layout of the view. If the layout is None, the default layout of the workspace will be
+  // used. The document layout is used if the workspace has no default layout.
+  #[pb(index = 5, one_of)]
+  pub layout: Option<ViewLayoutPB>,
 
   #[pb(index = 6)]
   pub initial_data: Vec<u8>,
@@ -181,6 +344,29 @@ pub struct CreateViewPayloadPB {
   // If the index is None or the index is out of range, the view will be appended to the end of the parent view.
   #[pb(index = 9, one_of)]
   pub index: Option<u32>,
+
+  // The id of the view. If the id is None, a new id will be generated. Creating the view fails if
+  // a view with the same id already exists.
+  #[pb(index = 10, one_of)]
+  pub view_id: Option<String>,
+}
+
+/// Creates the view at the `index` among the child views of its parent that are displayed in the
+/// UI. The view is appended to the end if the index is out of range. The `index` of the `view`
+/// payload is ignored.
+#[derive(Default, ProtoBuf)]
+pub struct CreateViewAtPayloadPB {
+  #[pb(index = 1)]
+  pub view: CreateViewPayloadPB,
+
+  #[pb(index = 2)]
+  pub index: u32,
+}
+
+#[derive(Default, ProtoBuf)]
+pub struct RepeatedCreateViewPayloadPB {
+  #[pb(index = 1)]
+  pub items: Vec<CreateViewPayloadPB>,
 }
 
 /// The orphan view is meant to be a view that is not attached to any parent view. By default, this
@@ -209,7 +395,8 @@ pub struct CreateViewParams {
   pub parent_view_id: String,
   pub name: String,
   pub desc: String,
-  pub layout: ViewLayoutPB,
+  // The layout of the view. None means the default layout of the workspace.
+  pub layout: Option<ViewLayoutPB>,
   pub view_id: String,
   pub initial_data: Vec<u8>,
   pub meta: HashMap<String, String>,
@@ -220,13 +407,24 @@ pub struct CreateViewParams {
   pub index: Option<u32>,
 }
 
+impl CreateViewParams {
+  /// Returns the layout of the view. The document layout is the fallback if the layout is not
+  /// specified.
+  pub fn view_layout(&self) -> ViewLayout {
+    self.layout.clone().unwrap_or_default().into()
+  }
+}
+
 impl TryInto<CreateViewParams> for CreateViewPayloadPB {
   type Error = ErrorCode;
 
   fn try_into(self) -> Result<CreateViewParams, Self::Error> {
     let name = ViewName::parse(self.name)?.0;
     let parent_view_id = ViewIdentify::parse(self.parent_view_id)?.0;
-    let view_id = gen_view_id().to_string();
+    let view_id = match self.view_id {
+      Some(view_id) => ViewIdentify::parse(view_id)?.0,
+      None => gen_view_id().to_string(),
+    };
 
     Ok(CreateViewParams {
       parent_view_id,
@@ -253,7 +451,7 @@ impl TryInto<CreateViewParams> for CreateOrphanViewPayloadPB {
       parent_view_id,
       name,
       desc: self.desc,
-      layout: self.layout,
+      layout: Some(self.layout),
       view_id: self.view_id,
       initial_data: self.initial_data,
       meta: Default::default(),
@@ -277,6 +475,320 @@ impl std::convert::From<&str> for ViewIdPB {
   }
 }
 
+#[derive(Default, ProtoBuf, Clone, Debug)]
+pub struct ViewExistsPB {
+  #[pb(index = 1)]
+  pub exists: bool,
+
+  /// Whether the view or any of its ancestors is in the trash. Always false if the view doesn't
+  /// exist.
+  #[pb(index = 2)]
+  pub is_trashed: bool,
+}
+
+/// The path of the view from the workspace down to the view itself.
+#[derive(Default, ProtoBuf, Clone, Debug)]
+pub struct ViewPathPB {
+  /// The raw names along the path. The names are not escaped, so they may contain the separator
+  /// of the `joined` path.
+  #[pb(index = 1)]
+  pub segments: Vec<String>,
+
+  /// The segments joined with " / ".
+  #[pb(index = 2)]
+  pub joined: String,
+}
+
+/// Read the view with its descendants. If the `depth` is `None` or zero, all the descendants will
+/// be included. Otherwise, only the descendants within the given depth will be included.
+#[derive(Default, ProtoBuf, Clone, Debug)]
+pub struct ViewTreePayloadPB {
+  #[pb(index = 1)]
+  pub view_id: String,
+
+  #[pb(index = 2, one_of)]
+  pub depth: Option<u32>,
+}
+
+#[derive(Eq, PartialEq, ProtoBuf, Debug, Default, Clone)]
+pub struct ViewTreePB {
+  /// The view without child views. Check out the `child_views` for its child views.
+  #[pb(index = 1)]
+  pub view: ViewPB,
+
+  #[pb(index = 2)]
+  pub child_views: Vec<ViewTreePB>,
+}
+
+#[derive(Eq, PartialEq, Hash, Debug, ProtoBuf_Enum, Clone, Default)]
+pub enum ViewSortTypePB {
+  #[default]
+  CreatedTime = 0,
+  LastEditedTime = 1,
+  LastOpenedTime = 2,
+}
+
+/// Read the recently opened views of the current workspace sorted by the given timestamp, the
+/// newest first.
+#[derive(Default, ProtoBuf, Clone, Debug)]
+pub struct RecentViewsSortedPayloadPB {
+  #[pb(index = 1)]
+  pub sort_type: ViewSortTypePB,
+}
+
+/// Read the child views of the parent view sorted by the given timestamp. If the
+/// `parent_view_id` is empty, the views of the current workspace will be returned.
+#[derive(Default, ProtoBuf, Clone, Debug)]
+pub struct ReadViewsSortedPayloadPB {
+  #[pb(index = 1)]
+  pub parent_view_id: String,
+
+  #[pb(index = 2)]
+  pub sort_type: ViewSortTypePB,
+
+  /// The newest views come first unless this flag is set.
+  #[pb(index = 3)]
+  pub ascending: bool,
+}
+
+#[derive(Eq, PartialEq, Hash, Debug, ProtoBuf_Enum, Clone, Default)]
+pub enum ViewSortOrderPB {
+  #[default]
+  Ascending = 0,
+  Descending = 1,
+}
+
+/// Sort the child views of the parent by their names and keep the new order. The `parent_id` can
+/// be the id of the current workspace.
+#[derive(Default, ProtoBuf, Clone, Debug)]
+pub struct SortChildViewsPayloadPB {
+  #[pb(index = 1)]
+  pub parent_id: String,
+
+  #[pb(index = 2)]
+  pub order: ViewSortOrderPB,
+}
+
+/// Duplicate the view. If `include_children` is true, all the descendants of the view will be
+/// duplicated too. The `suffix` is appended to the name of the duplicated view, and defaults to
+/// " (copy)".
+#[derive(Default, ProtoBuf, Clone, Debug)]
+pub struct DuplicateViewPayloadPB {
+  #[pb(index = 1)]
+  pub view_id: String,
+
+  #[pb(index = 2)]
+  pub include_children: bool,
+
+  #[pb(index = 3, one_of)]
+  pub suffix: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct DuplicateViewParams {
+  pub view_id: String,
+  pub include_children: bool,
+  pub suffix: String,
+}
+
+impl TryInto<DuplicateViewParams> for DuplicateViewPayloadPB {
+  type Error = ErrorCode;
+
+  fn try_into(self) -> Result<DuplicateViewParams, Self::Error> {
+    let view_id = ViewIdentify::parse(self.view_id)?.0;
+    Ok(DuplicateViewParams {
+      view_id,
+      include_children: self.include_children,
+      suffix: self.suffix.unwrap_or_else(|| " (copy)".to_string()),
+    })
+  }
+}
+
+#[derive(Default, ProtoBuf, Clone, Debug)]
+pub struct SetTemplatePayloadPB {
+  #[pb(index = 1)]
+  pub view_id: String,
+
+  #[pb(index = 2)]
+  pub is_template: bool,
+}
+
+#[derive(Default, ProtoBuf, Clone, Debug)]
+pub struct SetArchivedPB {
+  #[pb(index = 1)]
+  pub view_id: String,
+
+  #[pb(index = 2)]
+  pub is_archived: bool,
+}
+
+#[derive(Default, ProtoBuf, Clone, Debug)]
+pub struct RepeatedSetArchivedPB {
+  #[pb(index = 1)]
+  pub items: Vec<SetArchivedPB>,
+}
+
+/// Creates a new view under the `parent_id` by copying the template view and its descendants.
+#[derive(Default, ProtoBuf, Clone, Debug)]
+pub struct CreateFromTemplatePayloadPB {
+  #[pb(index = 1)]
+  pub template_id: String,
+
+  /// The id of the parent view or the workspace.
+  #[pb(index = 2)]
+  pub parent_id: String,
+
+  /// The name of the new root view.
+  #[pb(index = 3)]
+  pub name: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct CreateFromTemplateParams {
+  pub template_id: String,
+  pub parent_id: String,
+  pub name: String,
+}
+
+impl TryInto<CreateFromTemplateParams> for CreateFromTemplatePayloadPB {
+  type Error = ErrorCode;
+
+  fn try_into(self) -> Result<CreateFromTemplateParams, Self::Error> {
+    let template_id = ViewIdentify::parse(self.template_id)?.0;
+    let parent_id = ViewIdentify::parse(self.parent_id)?.0;
+    let name = ViewName::parse(self.name)?.0;
+    Ok(CreateFromTemplateParams {
+      template_id,
+      parent_id,
+      name,
+    })
+  }
+}
+
+#[derive(Default, ProtoBuf, Clone, Debug)]
+pub struct SetViewLockedPayloadPB {
+  #[pb(index = 1)]
+  pub view_id: String,
+
+  #[pb(index = 2)]
+  pub locked: bool,
+}
+
+#[derive(Default, ProtoBuf, Clone, Debug)]
+pub struct SetFavoritePayloadPB {
+  #[pb(index = 1)]
+  pub items: Vec<FavoriteStatePB>,
+}
+
+#[derive(Default, ProtoBuf, Clone, Debug)]
+pub struct FavoriteStatePB {
+  #[pb(index = 1)]
+  pub view_id: String,
+
+  #[pb(index = 2)]
+  pub is_favorite: bool,
+}
+
+#[derive(Default, ProtoBuf, Clone, Debug)]
+pub struct RepeatedSetExpandedPB {
+  #[pb(index = 1)]
+  pub items: Vec<SetExpandedPB>,
+}
+
+#[derive(Default, ProtoBuf, Clone, Debug)]
+pub struct SetExpandedPB {
+  #[pb(index = 1)]
+  pub view_id: String,
+
+  #[pb(index = 2)]
+  pub is_expanded: bool,
+}
+
+/// Sets the favorite state of the view and all of its descendants.
+#[derive(Default, ProtoBuf, Clone, Debug)]
+pub struct FavoriteSubtreePayloadPB {
+  #[pb(index = 1)]
+  pub view_id: String,
+
+  #[pb(index = 2)]
+  pub is_favorite: bool,
+
+  /// The maximum number of views in the subtree. Nothing is changed if the subtree contains more
+  /// views. The default limit is used if it's None.
+  #[pb(index = 3, one_of)]
+  pub limit: Option<u32>,
+}
+
+#[derive(Default, ProtoBuf, Clone, Debug)]
+pub struct ReorderFavoritePayloadPB {
+  #[pb(index = 1)]
+  pub view_id: String,
+
+  /// The index of the view when the move started. The view is found by its `view_id`, so a
+  /// stale index can't move another favorite.
+  #[pb(index = 2)]
+  pub from_index: u32,
+
+  #[pb(index = 3)]
+  pub to_index: u32,
+}
+
+#[derive(Default, ProtoBuf, Clone, Debug)]
+pub struct SearchViewsPayloadPB {
+  /// Matched against the names, the descriptions and the content of the documents.
+  #[pb(index = 1)]
+  pub query: String,
+
+  /// The workspace to search in. If it's empty, the current workspace will be used.
+  #[pb(index = 2)]
+  pub workspace_id: String,
+
+  /// The maximum number of views to return. Zero means no limit.
+  #[pb(index = 3)]
+  pub limit: u32,
+
+  /// The trashed views are excluded unless this flag is set.
+  #[pb(index = 4)]
+  pub include_trash: bool,
+}
+
+/// The recently searched queries of the workspace, the most recent first.
+#[derive(Default, ProtoBuf, Debug, Clone)]
+pub struct SearchHistoryPB {
+  #[pb(index = 1)]
+  pub queries: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct SearchViewsParams {
+  pub query: String,
+  pub workspace_id: Option<String>,
+  pub limit: usize,
+  pub include_trash: bool,
+}
+
+impl TryInto<SearchViewsParams> for SearchViewsPayloadPB {
+  type Error = ErrorCode;
+
+  fn try_into(self) -> Result<SearchViewsParams, Self::Error> {
+    let query = NotEmptyStr::parse(self.query)
+      .map_err(|_| ErrorCode::InvalidParams)?
+      .0;
+    let workspace_id = if self.workspace_id.is_empty() {
+      None
+    } else {
+      Some(WorkspaceIdentify::parse(self.workspace_id)?.0)
+    };
+
+    Ok(SearchViewsParams {
+      query,
+      workspace_id,
+      limit: self.limit as usize,
+      include_trash: self.include_trash,
+    })
+  }
+}
+
 #[derive(Default, ProtoBuf, Clone, Debug)]
 pub struct DeletedViewPB {
   #[pb(index = 1)]
@@ -313,6 +825,10 @@ pub struct UpdateViewPayloadPB {
 
   #[pb(index = 6, one_of)]
   pub is_favorite: Option<bool>,
+
+  /// The color label of the view. [ViewColorPB::None] clears the color.
+  #[pb(index = 7, one_of)]
+  pub color: Option<ViewColorPB>,
 }
 
 #[derive(Clone, Debug)]
@@ -323,6 +839,8 @@ pub struct UpdateViewParams {
   pub thumbnail: Option<String>,
   pub layout: Option<ViewLayout>,
   pub is_favorite: Option<bool>,
+  /// `Some(None)` clears the color of the view.
+  pub color: Option<Option<ViewColor>>,
 }
 
 impl TryInto<UpdateViewParams> for UpdateViewPayloadPB {
@@ -355,37 +873,216 @@ impl TryInto<UpdateViewParams> for UpdateViewPayloadPB {
       thumbnail,
       is_favorite,
       layout: self.layout.map(|ty| ty.into()),
+      color: self.color.map(|color| color.into()),
     })
   }
 }
 
-#[derive(Default, ProtoBuf)]
-pub struct MoveViewPayloadPB {
+/// The views sharing the same parent with the view, in the order shown in the sidebar. The view
+/// itself is included at the `current_index`.
+#[derive(Eq, PartialEq, ProtoBuf, Debug, Default, Clone)]
+pub struct ViewSiblingsPB {
   #[pb(index = 1)]
-  pub view_id: String,
+  pub items: Vec<ViewPB>,
 
   #[pb(index = 2)]
-  pub from: i32,
+  pub current_index: u32,
+}
 
-  #[pb(index = 3)]
-  pub to: i32,
+/// The number of the views under the view. The trashed views and their descendants are not counted.
+#[derive(Eq, PartialEq, ProtoBuf, Debug, Default, Clone)]
+pub struct SubtreeCountPB {
+  #[pb(index = 1)]
+  pub direct_children: u32,
+
+  /// The number of all the nested views, including the direct children.
+  #[pb(index = 2)]
+  pub total_descendants: u32,
 }
 
-/// * `view_id` - A string slice that holds the id of the view to be moved.
-/// * `new_parent_id` - A string slice that holds the id of the new parent view.
-/// * `prev_view_id` - An `Option<String>` that holds the id of the view after which the `view_id` should be positioned.
-///
-/// If `prev_view_id` is provided, the moved view will be placed right after
-/// the view corresponding to `prev_view_id` under the `new_parent_id`.
-///
-/// If `prev_view_id` is `None`, the moved view will become the first child of the new parent.
+/// Finds the child view of the parent by its name. The parent can be a view or the workspace.
 #[derive(Default, ProtoBuf)]
-pub struct MoveNestedViewPayloadPB {
+pub struct FindChildPayloadPB {
   #[pb(index = 1)]
-  pub view_id: String,
+  pub parent_id: String,
 
   #[pb(index = 2)]
-  pub new_parent_id: String,
+  pub name: String,
+
+  /// The name is matched exactly, including the case, unless this flag is set.
+  #[pb(index = 3)]
+  pub case_insensitive: bool,
+}
+
+#[derive(Clone, Debug)]
+pub struct FindChildParams {
+  pub parent_id: String,
+  pub name: String,
+  pub case_insensitive: bool,
+}
+
+impl TryInto<FindChildParams> for FindChildPayloadPB {
+  type Error = ErrorCode;
+
+  fn try_into(self) -> Result<FindChildParams, Self::Error> {
+    let parent_id = ViewIdentify::parse(self.parent_id)?.0;
+    let name = NotEmptyStr::parse(self.name)
+      .map_err(|_| ErrorCode::InvalidParams)?
+      .0;
+    Ok(FindChildParams {
+      parent_id,
+      name,
+      case_insensitive: self.case_insensitive,
+    })
+  }
+}
+
+#[derive(Eq, PartialEq, ProtoBuf, Debug, Default, Clone)]
+pub struct FindChildResultPB {
+  /// The first matching child view in the order of the siblings.
+  #[pb(index = 1)]
+  pub view: ViewPB,
+
+  /// Whether more than one child view matches the name.
+  #[pb(index = 2)]
+  pub is_ambiguous: bool,
+}
+
+/// Lists the views of the workspace page by page in the depth-first order of the view tree. The
+/// first page is listed with the empty `cursor`, and the next pages with the `next_cursor` of the
+/// previous page. The `limit` is capped, and zero means the default page size.
+#[derive(Default, ProtoBuf)]
+pub struct ListViewsCursorPayloadPB {
+  #[pb(index = 1)]
+  pub workspace_id: String,
+
+  #[pb(index = 2)]
+  pub cursor: String,
+
+  #[pb(index = 3)]
+  pub limit: u32,
+}
+
+#[derive(Clone, Debug)]
+pub struct ListViewsCursorParams {
+  pub workspace_id: String,
+  pub cursor: String,
+  pub limit: usize,
+}
+
+impl TryInto<ListViewsCursorParams> for ListViewsCursorPayloadPB {
+  type Error = ErrorCode;
+
+  fn try_into(self) -> Result<ListViewsCursorParams, Self::Error> {
+    let workspace_id = WorkspaceIdentify::parse(self.workspace_id)?.0;
+    Ok(ListViewsCursorParams {
+      workspace_id,
+      cursor: self.cursor,
+      limit: self.limit as usize,
+    })
+  }
+}
+
+/// A page of the views listed by `ListViewsCursor`. The child views of the views are not
+/// included, because the views are listed one by one.
+#[derive(Eq, PartialEq, ProtoBuf, Debug, Default, Clone)]
+pub struct ViewsPagePB {
+  #[pb(index = 1)]
+  pub items: Vec<ViewPB>,
+
+  /// The cursor to list the next page. It's empty if all the views are listed.
+  #[pb(index = 2)]
+  pub next_cursor: String,
+}
+
+/// Changes the layout of the existing view. Only the conversions between the database layouts,
+/// i.e. the grid, the board and the calendar, are supported. They are lossless, because the
+/// layouts are different views of the same database. The conversions between the document and
+/// the database layouts are rejected, because the content of the view would be lost.
+#[derive(Default, ProtoBuf)]
+pub struct ChangeLayoutPayloadPB {
+  #[pb(index = 1)]
+  pub view_id: String,
+
+  #[pb(index = 2)]
+  pub new_layout: ViewLayoutPB,
+}
+
+#[derive(Clone, Debug)]
+pub struct ChangeLayoutParams {
+  pub view_id: String,
+  pub new_layout: ViewLayout,
+}
+
+impl TryInto<ChangeLayoutParams> for ChangeLayoutPayloadPB {
+  type Error = ErrorCode;
+
+  fn try_into(self) -> Result<ChangeLayoutParams, Self::Error> {
+    let view_id = ViewIdentify::parse(self.view_id)?.0;
+    Ok(ChangeLayoutParams {
+      view_id,
+      new_layout: self.new_layout.into(),
+    })
+  }
+}
+
+/// Renames the view. The surrounding whitespace of the name is trimmed.
+#[derive(Default, ProtoBuf)]
+pub struct RenameViewPayloadPB {
+  #[pb(index = 1)]
+  pub view_id: String,
+
+  #[pb(index = 2)]
+  pub name: String,
+}
+
+#[derive(Clone, Debug)]
+pub struct RenameViewParams {
+  pub view_id: String,
+  pub name: String,
+}
+
+impl TryInto<RenameViewParams> for RenameViewPayloadPB {
+  type Error = ErrorCode;
+
+  fn try_into(self) -> Result<RenameViewParams, Self::Error> {
+    let view_id = ViewIdentify::parse(self.view_id)?.0;
+    let name = self.name.trim();
+    if name.is_empty() {
+      return Err(ErrorCode::ViewNameInvalid);
+    }
+    let name = ViewName::parse(name.to_string())?.0;
+    Ok(RenameViewParams { view_id, name })
+  }
+}
+
+#[derive(Default, ProtoBuf)]
+pub struct MoveViewPayloadPB {
+  #[pb(index = 1)]
+  pub view_id: String,
+
+  #[pb(index = 2)]
+  pub from: i32,
+
+  #[pb(index = 3)]
+  pub to: i32,
+}
+
+/// * `view_id` - A string slice that holds the id of the view to be moved.
+/// * `new_parent_id` - A string slice that holds the id of the new parent view.
+/// * `prev_view_id` - An `Option<String>` that holds the id of the view after which the `view_id` should be positioned.
+///
+/// If `prev_view_id` is provided, the moved view will be placed right after
+/// the view corresponding to `prev_view_id` under the `new_parent_id`.
+///
+/// If `prev_view_id` is `None`, the moved view will become the first child of the new parent.
+#[derive(Default, ProtoBuf)]
+pub struct MoveNestedViewPayloadPB {
+  #[pb(index = 1)]
+  pub view_id: String,
+
+  #[pb(index = 2)]
+  pub new_parent_id: String,
 
   #[pb(index = 3, one_of)]
   pub prev_view_id: Option<String>,
@@ -410,6 +1107,34 @@ impl TryInto<MoveViewParams> for MoveViewPayloadPB {
   }
 }
 
+/// Moves the view to the `new_index` among its siblings. The index is clamped to the range of the
+/// siblings.
+#[derive(Default, ProtoBuf)]
+pub struct ReorderViewPayloadPB {
+  #[pb(index = 1)]
+  pub view_id: String,
+
+  #[pb(index = 2)]
+  pub new_index: u32,
+}
+
+pub struct ReorderViewParams {
+  pub view_id: String,
+  pub new_index: usize,
+}
+
+impl TryInto<ReorderViewParams> for ReorderViewPayloadPB {
+  type Error = ErrorCode;
+
+  fn try_into(self) -> Result<ReorderViewParams, Self::Error> {
+    let view_id = ViewIdentify::parse(self.view_id)?.0;
+    Ok(ReorderViewParams {
+      view_id,
+      new_index: self.new_index as usize,
+    })
+  }
+}
+
 pub struct MoveNestedViewParams {
   pub view_id: String,
   pub new_parent_id: String,
@@ -431,6 +1156,315 @@ impl TryInto<MoveNestedViewParams> for MoveNestedViewPayloadPB {
   }
 }
 
+/// Move the views under the `new_parent_id`. The moved views are placed after the existing child
+/// views of the new parent in the order of `view_ids`.
+#[derive(Default, ProtoBuf)]
+pub struct MoveViewsPayloadPB {
+  #[pb(index = 1)]
+  pub view_ids: Vec<String>,
+
+  #[pb(index = 2)]
+  pub new_parent_id: String,
+}
+
+pub struct MoveViewsParams {
+  pub view_ids: Vec<String>,
+  pub new_parent_id: String,
+}
+
+impl TryInto<MoveViewsParams> for MoveViewsPayloadPB {
+  type Error = ErrorCode;
+
+  fn try_into(self) -> Result<MoveViewsParams, Self::Error> {
+    let view_ids = self
+      .view_ids
+      .into_iter()
+      .map(|view_id| ViewIdentify::parse(view_id).map(|view_id| view_id.0))
+      .collect::<Result<Vec<_>, _>>()?;
+    let new_parent_id = ViewIdentify::parse(self.new_parent_id)?.0;
+    Ok(MoveViewsParams {
+      view_ids,
+      new_parent_id,
+    })
+  }
+}
+
+#[derive(Default, ProtoBuf, Debug, Clone)]
+pub struct EmptyViewChildrenResultPB {
+  /// The number of the descendants that were moved to the trash, including the nested ones.
+  #[pb(index = 1)]
+  pub trashed_count: u32,
+}
+
+/// Move the child views of the `source_view_id` under the `target_view_id`, and then move the
+/// source view to the trash.
+#[derive(Default, ProtoBuf)]
+pub struct MergeViewsPayloadPB {
+  #[pb(index = 1)]
+  pub source_view_id: String,
+
+  #[pb(index = 2)]
+  pub target_view_id: String,
+}
+
+pub struct MergeViewsParams {
+  pub source_view_id: String,
+  pub target_view_id: String,
+}
+
+impl TryInto<MergeViewsParams> for MergeViewsPayloadPB {
+  type Error = ErrorCode;
+
+  fn try_into(self) -> Result<MergeViewsParams, Self::Error> {
+    let source_view_id = ViewIdentify::parse(self.source_view_id)?.0;
+    let target_view_id = ViewIdentify::parse(self.target_view_id)?.0;
+    Ok(MergeViewsParams {
+      source_view_id,
+      target_view_id,
+    })
+  }
+}
+
+/// Move the view and all of its child views to another workspace.
+///
+/// If `parent_view_id` is `None`, the view will be moved to the root of the target workspace.
+#[derive(Default, ProtoBuf)]
+pub struct MoveViewToWorkspacePayloadPB {
+  #[pb(index = 1)]
+  pub view_id: String,
+
+  #[pb(index = 2)]
+  pub target_workspace_id: String,
+
+  #[pb(index = 3, one_of)]
+  pub parent_view_id: Option<String>,
+}
+
+pub struct MoveViewToWorkspaceParams {
+  pub view_id: String,
+  pub target_workspace_id: String,
+  pub parent_view_id: Option<String>,
+}
+
+impl TryInto<MoveViewToWorkspaceParams> for MoveViewToWorkspacePayloadPB {
+  type Error = ErrorCode;
+
+  fn try_into(self) -> Result<MoveViewToWorkspaceParams, Self::Error> {
+    let view_id = ViewIdentify::parse(self.view_id)?.0;
+    let target_workspace_id = WorkspaceIdentify::parse(self.target_workspace_id)?.0;
+    let parent_view_id = match self.parent_view_id {
+      None => None,
+      Some(parent_view_id) => Some(ViewIdentify::parse(parent_view_id)?.0),
+    };
+    Ok(MoveViewToWorkspaceParams {
+      view_id,
+      target_workspace_id,
+      parent_view_id,
+    })
+  }
+}
+
+/// Returns the views of the current workspace that were edited by the user last time. The views
+/// that have never been edited are matched by their creator.
+#[derive(Default, ProtoBuf)]
+pub struct ViewsEditedByPayloadPB {
+  #[pb(index = 1)]
+  pub uid: i64,
+}
+
+/// Copy the view to the root of another workspace. The original view is not changed. If
+/// `include_children` is true, the descendants of the view are copied as well.
+#[derive(Default, ProtoBuf)]
+pub struct CopyViewToWorkspacePayloadPB {
+  #[pb(index = 1)]
+  pub view_id: String,
+
+  #[pb(index = 2)]
+  pub target_workspace_id: String,
+
+  #[pb(index = 3)]
+  pub include_children: bool,
+}
+
+pub struct CopyViewToWorkspaceParams {
+  pub view_id: String,
+  pub target_workspace_id: String,
+  pub include_children: bool,
+}
+
+impl TryInto<CopyViewToWorkspaceParams> for CopyViewToWorkspacePayloadPB {
+  type Error = ErrorCode;
+
+  fn try_into(self) -> Result<CopyViewToWorkspaceParams, Self::Error> {
+    let view_id = ViewIdentify::parse(self.view_id)?.0;
+    let target_workspace_id = WorkspaceIdentify::parse(self.target_workspace_id)?.0;
+    Ok(CopyViewToWorkspaceParams {
+      view_id,
+      target_workspace_id,
+      include_children: self.include_children,
+    })
+  }
+}
+
+/// Flattens the view and all of its descendants into a list in depth-first order.
+#[derive(Default, ProtoBuf)]
+pub struct FlattenSubtreePayloadPB {
+  #[pb(index = 1)]
+  pub view_id: String,
+
+  /// Whether the view itself comes first in the list.
+  #[pb(index = 2)]
+  pub include_root: bool,
+
+  /// The trashed descendants and their child views are excluded unless this flag is set.
+  #[pb(index = 3)]
+  pub include_trash: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct FlattenSubtreeParams {
+  pub view_id: String,
+  pub include_root: bool,
+  pub include_trash: bool,
+}
+
+impl TryInto<FlattenSubtreeParams> for FlattenSubtreePayloadPB {
+  type Error = ErrorCode;
+
+  fn try_into(self) -> Result<FlattenSubtreeParams, Self::Error> {
+    let view_id = ViewIdentify::parse(self.view_id)?.0;
+    Ok(FlattenSubtreeParams {
+      view_id,
+      include_root: self.include_root,
+      include_trash: self.include_trash,
+    })
+  }
+}
+
+/// Returns the views of the workspace whose layout is one of the `layouts`. The views are searched
+/// in the whole view tree of the workspace, and the views in the trash are excluded.
+#[derive(Default, ProtoBuf)]
+pub struct ViewsByLayoutPayloadPB {
+  #[pb(index = 1)]
+  pub workspace_id: String,
+
+  #[pb(index = 2)]
+  pub layouts: Vec<ViewLayoutItemPB>,
+}
+
+#[derive(Default, ProtoBuf, Clone, Debug)]
+pub struct ViewLayoutItemPB {
+  #[pb(index = 1)]
+  pub layout: ViewLayoutPB,
+}
+
+impl std::convert::From<ViewLayoutPB> for ViewLayoutItemPB {
+  fn from(layout: ViewLayoutPB) -> Self {
+    Self { layout }
+  }
+}
+
+pub struct ViewsByLayoutParams {
+  pub workspace_id: String,
+  pub layouts: Vec<ViewLayout>,
+}
+
+impl TryInto<ViewsByLayoutParams> for ViewsByLayoutPayloadPB {
+  type Error = ErrorCode;
+
+  fn try_into(self) -> Result<ViewsByLayoutParams, Self::Error> {
+    let workspace_id = WorkspaceIdentify::parse(self.workspace_id)?.0;
+    let mut layouts: Vec<ViewLayout> = vec![];
+    for item in self.layouts {
+      let layout = item.layout.into();
+      if !layouts.contains(&layout) {
+        layouts.push(layout);
+      }
+    }
+    Ok(ViewsByLayoutParams {
+      workspace_id,
+      layouts,
+    })
+  }
+}
+
+/// Filters the views of the workspace by their last edited time. Both `start` and `end` are
+/// timestamps in seconds and inclusive. The `end` defaults to now.
+#[derive(Default, ProtoBuf, Clone, Debug)]
+pub struct ViewsTimeRangePayloadPB {
+  #[pb(index = 1)]
+  pub workspace_id: String,
+
+  #[pb(index = 2)]
+  pub start: i64,
+
+  #[pb(index = 3, one_of)]
+  pub end: Option<i64>,
+}
+
+pub struct ViewsTimeRangeParams {
+  pub workspace_id: String,
+  pub start: i64,
+  pub end: i64,
+}
+
+impl TryInto<ViewsTimeRangeParams> for ViewsTimeRangePayloadPB {
+  type Error = ErrorCode;
+
+  fn try_into(self) -> Result<ViewsTimeRangeParams, Self::Error> {
+    let workspace_id = WorkspaceIdentify::parse(self.workspace_id)?.0;
+    Ok(ViewsTimeRangeParams {
+      workspace_id,
+      start: self.start,
+      end: self.end.unwrap_or_else(timestamp),
+    })
+  }
+}
+
+#[derive(ProtoBuf_Enum, Clone, Debug, PartialEq, Eq, Default)]
+pub enum FolderOperationTypePB {
+  #[default]
+  CreateView = 0,
+  DeleteView = 1,
+  MoveView = 2,
+  RenameView = 3,
+  RestoreView = 4,
+}
+
+/// Describes the folder operation that was undone or redone.
+#[derive(Default, ProtoBuf, Clone, Debug)]
+pub struct FolderOperationPB {
+  #[pb(index = 1)]
+  pub ty: FolderOperationTypePB,
+
+  #[pb(index = 2)]
+  pub view_id: String,
+}
+
+impl From<&FolderOperation> for FolderOperationPB {
+  fn from(operation: &FolderOperation) -> Self {
+    let ty = match operation {
+      FolderOperation::CreateView { .. } => FolderOperationTypePB::CreateView,
+      FolderOperation::DeleteView { .. } => FolderOperationTypePB::DeleteView,
+      FolderOperation::RestoreView { .. } => FolderOperationTypePB::RestoreView,
+      FolderOperation::MoveView { .. } => FolderOperationTypePB::MoveView,
+      FolderOperation::RenameView { .. } => FolderOperationTypePB::RenameView,
+    };
+    Self {
+      ty,
+      view_id: operation.view_id().to_string(),
+    }
+  }
+}
+
+/// Reattach the orphaned views to the root of the current workspace. If `dry_run` is true, the
+/// orphaned views are returned without being changed.
+#[derive(Default, ProtoBuf)]
+pub struct RepairOrphanedViewsPayloadPB {
+  #[pb(index = 1)]
+  pub dry_run: bool,
+}
+
 // impl<'de> Deserialize<'de> for ViewDataType {
 //     fn deserialize<D>(deserializer: D) -> Result<Self, <D as Deserializer<'de>>::Error>
 //     where
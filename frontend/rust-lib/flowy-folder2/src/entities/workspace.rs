@@ -1,14 +1,17 @@
+use std::collections::HashMap;
 use std::convert::TryInto;
 
 use collab::core::collab_state::SyncState;
-use collab_folder::Workspace;
+use collab_folder::{ViewIcon, Workspace};
 
 use flowy_derive::ProtoBuf;
 use flowy_error::ErrorCode;
 
 use crate::{
+  entities::icon::ViewIconPB,
+  entities::parser::view::ViewIdentify,
   entities::parser::workspace::{WorkspaceDesc, WorkspaceIdentify, WorkspaceName},
-  entities::view::ViewPB,
+  entities::view::{ViewLayoutPB, ViewPB},
 };
 
 #[derive(Eq, PartialEq, ProtoBuf, Default, Debug, Clone)]
@@ -24,8 +27,13 @@ pub struct WorkspacePB {
 
   #[pb(index = 4)]
   pub create_time: i64,
+
+  #[pb(index = 5, one_of)]
+  pub icon: Option<ViewIconPB>,
 }
 
+/// The icon of the workspace is stored in the view of the workspace, so it's filled by the caller
+/// that has access to the folder.
 impl std::convert::From<(Workspace, Vec<ViewPB>)> for WorkspacePB {
   fn from(params: (Workspace, Vec<ViewPB>)) -> Self {
     let (workspace, views) = params;
@@ -34,6 +42,7 @@ impl std::convert::From<(Workspace, Vec<ViewPB>)> for WorkspacePB {
       name: workspace.name,
       views,
       create_time: workspace.created_at,
+      icon: None,
     }
   }
 }
@@ -68,12 +77,17 @@ pub struct CreateWorkspacePayloadPB {
 
   #[pb(index = 2)]
   pub desc: String,
+
+  /// The icon of the workspace. The workspace has no icon if it's None or its value is empty.
+  #[pb(index = 3, one_of)]
+  pub icon: Option<ViewIconPB>,
 }
 
 #[derive(Clone, Debug)]
 pub struct CreateWorkspaceParams {
   pub name: String,
   pub desc: String,
+  pub icon: Option<ViewIcon>,
 }
 
 impl TryInto<CreateWorkspaceParams> for CreateWorkspacePayloadPB {
@@ -86,6 +100,7 @@ impl TryInto<CreateWorkspaceParams> for CreateWorkspacePayloadPB {
     Ok(CreateWorkspaceParams {
       name: name.0,
       desc: desc.0,
+      icon: self.icon.and_then(workspace_icon_from_pb),
     })
   }
 }
@@ -95,6 +110,29 @@ impl TryInto<CreateWorkspaceParams> for CreateWorkspacePayloadPB {
 pub struct WorkspaceIdPB {
   #[pb(index = 1)]
   pub value: String,
+
+  /// The number of the levels of the child views loaded under each top level view when opening
+  /// the workspace. Only the direct child views are loaded if it's `None`.
+  #[pb(index = 2, one_of)]
+  pub preload_depth: Option<u32>,
+}
+
+/// The counts of the views in the workspace. The trashed views are included in the
+/// `total_views` and the `views_by_layout`.
+#[derive(Clone, ProtoBuf, Default, Debug)]
+pub struct WorkspaceStatsPB {
+  #[pb(index = 1)]
+  pub total_views: u64,
+
+  #[pb(index = 2)]
+  pub trashed_views: u64,
+
+  #[pb(index = 3)]
+  pub favorite_views: u64,
+
+  /// The number of views of each layout, keyed by the name of the [ViewLayoutPB].
+  #[pb(index = 4)]
+  pub views_by_layout: HashMap<String, u64>,
 }
 
 #[derive(Default, ProtoBuf, Debug, Clone)]
@@ -104,6 +142,184 @@ pub struct WorkspaceSettingPB {
 
   #[pb(index = 2, one_of)]
   pub latest_view: Option<ViewPB>,
+
+  /// Whether the favorites panel should be shown.
+  #[pb(index = 3)]
+  pub show_favorites: bool,
+
+  /// The id of the home view of the workspace. It's empty if the workspace has no home view, or
+  /// the home view is deleted or in the trash.
+  #[pb(index = 4)]
+  pub home_view_id: String,
+}
+
+/// The view to open when the workspace is loaded. `None` if the workspace has no view to open.
+#[derive(Default, ProtoBuf, Debug, Clone)]
+pub struct DefaultOpenViewPB {
+  #[pb(index = 1, one_of)]
+  pub view: Option<ViewPB>,
+}
+
+/// The view to open when the workspace is loaded, along with the views needed to show it in the
+/// view tree. `view` is `None` if the workspace has no view to open.
+#[derive(Default, ProtoBuf, Debug, Clone)]
+pub struct ViewContextPB {
+  #[pb(index = 1, one_of)]
+  pub view: Option<ViewPB>,
+
+  /// The ancestors of the view, from the top level view down to the parent of the view.
+  #[pb(index = 2)]
+  pub ancestors: Vec<ViewPB>,
+
+  /// The other child views of the view's parent, in their order.
+  #[pb(index = 3)]
+  pub siblings: Vec<ViewPB>,
+}
+
+/// Update the UI settings of the workspace. The settings that are `None` remain unchanged.
+#[derive(Default, ProtoBuf)]
+pub struct WorkspaceUISettingsPayloadPB {
+  #[pb(index = 1)]
+  pub workspace_id: String,
+
+  #[pb(index = 2, one_of)]
+  pub show_favorites: Option<bool>,
+}
+
+/// Set the layout of the views created without specifying the layout in the workspace.
+#[derive(Default, ProtoBuf)]
+pub struct DefaultLayoutPayloadPB {
+  #[pb(index = 1)]
+  pub workspace_id: String,
+
+  #[pb(index = 2)]
+  pub layout: ViewLayoutPB,
+}
+
+/// Set the maximum depth of the views in the workspace. The top level views are at depth one. Zero
+/// means the depth is unlimited.
+#[derive(Default, ProtoBuf)]
+pub struct MaxNestingDepthPayloadPB {
+  #[pb(index = 1)]
+  pub workspace_id: String,
+
+  #[pb(index = 2)]
+  pub depth: u32,
+}
+
+/// Enable or disable keeping the names of the sibling views unique in the workspace. It's
+/// disabled by default.
+#[derive(Default, ProtoBuf)]
+pub struct UniqueSiblingNamesPayloadPB {
+  #[pb(index = 1)]
+  pub workspace_id: String,
+
+  #[pb(index = 2)]
+  pub enabled: bool,
+}
+
+/// Set the maximum number of the views in the workspace, including the trashed ones. Zero means
+/// the number is unlimited.
+#[derive(Default, ProtoBuf)]
+pub struct ViewLimitPayloadPB {
+  #[pb(index = 1)]
+  pub workspace_id: String,
+
+  #[pb(index = 2)]
+  pub limit: u32,
+}
+
+/// Update the settings of the workspace at once. The absent settings are left unchanged, and none
+/// of the settings is applied if any of them is invalid.
+#[derive(Default, ProtoBuf)]
+pub struct WorkspaceSettingsPayloadPB {
+  #[pb(index = 1)]
+  pub workspace_id: String,
+
+  #[pb(index = 2, one_of)]
+  pub trash_retention_days: Option<u32>,
+
+  #[pb(index = 3, one_of)]
+  pub default_view_layout: Option<ViewLayoutPB>,
+
+  #[pb(index = 4, one_of)]
+  pub view_limit: Option<u32>,
+}
+
+#[derive(Clone, Debug)]
+pub struct WorkspaceSettingsParams {
+  pub workspace_id: String,
+  pub trash_retention_days: Option<u32>,
+  pub default_view_layout: Option<ViewLayoutPB>,
+  pub view_limit: Option<u32>,
+}
+
+impl TryInto<WorkspaceSettingsParams> for WorkspaceSettingsPayloadPB {
+  type Error = ErrorCode;
+
+  fn try_into(self) -> Result<WorkspaceSettingsParams, Self::Error> {
+    let workspace_id = WorkspaceIdentify::parse(self.workspace_id)?.0;
+    Ok(WorkspaceSettingsParams {
+      workspace_id,
+      trash_retention_days: self.trash_retention_days,
+      default_view_layout: self.default_view_layout,
+      view_limit: self.view_limit,
+    })
+  }
+}
+
+/// The settings of the workspace after they're updated by `UpdateWorkspaceSettings`. It's also
+/// sent with the `DidUpdateWorkspaceSettings` notification whose id is the workspace id.
+#[derive(Default, ProtoBuf, Debug, Clone)]
+pub struct WorkspaceSettingsPB {
+  #[pb(index = 1)]
+  pub workspace_id: String,
+
+  /// Zero means the trash items are never deleted automatically.
+  #[pb(index = 2)]
+  pub trash_retention_days: u32,
+
+  #[pb(index = 3)]
+  pub default_view_layout: ViewLayoutPB,
+
+  /// Zero means the number of the views is unlimited.
+  #[pb(index = 4)]
+  pub view_limit: u32,
+}
+
+/// Set the view opened when the workspace is loaded. The empty `view_id` clears the home view, so
+/// the latest opened view is opened instead.
+#[derive(Default, ProtoBuf)]
+pub struct SetHomePayloadPB {
+  #[pb(index = 1)]
+  pub workspace_id: String,
+
+  #[pb(index = 2)]
+  pub view_id: String,
+}
+
+#[derive(Clone, Debug)]
+pub struct SetHomeParams {
+  pub workspace_id: String,
+  /// `None` clears the home view.
+  pub view_id: Option<String>,
+}
+
+impl TryInto<SetHomeParams> for SetHomePayloadPB {
+  type Error = ErrorCode;
+
+  fn try_into(self) -> Result<SetHomeParams, Self::Error> {
+    let workspace_id = WorkspaceIdentify::parse(self.workspace_id)?.0;
+    let view_id = if self.view_id.is_empty() {
+      None
+    } else {
+      Some(ViewIdentify::parse(self.view_id)?.0)
+    };
+    Ok(SetHomeParams {
+      workspace_id,
+      view_id,
+    })
+  }
 }
 
 #[derive(ProtoBuf, Default)]
@@ -116,6 +332,10 @@ pub struct UpdateWorkspacePayloadPB {
 
   #[pb(index = 3, one_of)]
   pub desc: Option<String>,
+
+  /// The icon remains unchanged if it's None, and it's removed if its value is empty.
+  #[pb(index = 4, one_of)]
+  pub icon: Option<ViewIconPB>,
 }
 
 #[derive(Clone, Debug)]
@@ -123,6 +343,8 @@ pub struct UpdateWorkspaceParams {
   pub id: String,
   pub name: Option<String>,
   pub desc: Option<String>,
+  /// `Some(None)` removes the icon of the workspace.
+  pub icon: Option<Option<ViewIcon>>,
 }
 
 impl TryInto<UpdateWorkspaceParams> for UpdateWorkspacePayloadPB {
@@ -139,6 +361,45 @@ impl TryInto<UpdateWorkspaceParams> for UpdateWorkspacePayloadPB {
       id: id.0,
       name,
       desc: self.desc,
+      icon: self.icon.map(workspace_icon_from_pb),
+    })
+  }
+}
+
+/// The icon with the empty value means the workspace has no icon.
+fn workspace_icon_from_pb(icon: ViewIconPB) -> Option<ViewIcon> {
+  if icon.value.trim().is_empty() {
+    None
+  } else {
+    Some(icon.into())
+  }
+}
+
+/// Duplicate the workspace into a new workspace named `new_name`.
+#[derive(ProtoBuf, Default)]
+pub struct DuplicateWorkspacePayloadPB {
+  #[pb(index = 1)]
+  pub workspace_id: String,
+
+  #[pb(index = 2)]
+  pub new_name: String,
+}
+
+#[derive(Clone, Debug)]
+pub struct DuplicateWorkspaceParams {
+  pub workspace_id: String,
+  pub new_name: String,
+}
+
+impl TryInto<DuplicateWorkspaceParams> for DuplicateWorkspacePayloadPB {
+  type Error = ErrorCode;
+
+  fn try_into(self) -> Result<DuplicateWorkspaceParams, Self::Error> {
+    let workspace_id = WorkspaceIdentify::parse(self.workspace_id)?.0;
+    let new_name = WorkspaceName::parse(self.new_name)?.0;
+    Ok(DuplicateWorkspaceParams {
+      workspace_id,
+      new_name,
     })
   }
 }
@@ -147,6 +408,24 @@ impl TryInto<UpdateWorkspaceParams> for UpdateWorkspacePayloadPB {
 pub struct RepeatedFolderSnapshotPB {
   #[pb(index = 1)]
   pub items: Vec<FolderSnapshotPB>,
+
+  /// The number of all the available snapshots, not only the ones in `items`.
+  #[pb(index = 2)]
+  pub total_count: u64,
+}
+
+/// Query the snapshots of the workspace in the range of `offset..offset + limit`, the newest
+/// first. Zero `limit` means the default limit.
+#[derive(Clone, ProtoBuf, Default, Debug)]
+pub struct FolderSnapshotQueryPB {
+  #[pb(index = 1)]
+  pub workspace_id: String,
+
+  #[pb(index = 2)]
+  pub limit: u32,
+
+  #[pb(index = 3)]
+  pub offset: u32,
 }
 
 #[derive(Debug, Default, ProtoBuf)]
@@ -162,6 +441,80 @@ pub struct FolderSnapshotPB {
 
   #[pb(index = 4)]
   pub data: Vec<u8>,
+
+  /// The name given by the user. Empty if the snapshot is created automatically.
+  #[pb(index = 5)]
+  pub name: String,
+}
+
+#[derive(Debug, Default, ProtoBuf)]
+pub struct CreateSnapshotPayloadPB {
+  #[pb(index = 1)]
+  pub workspace_id: String,
+
+  #[pb(index = 2)]
+  pub name: String,
+}
+
+#[derive(Debug, Default, ProtoBuf)]
+pub struct RestoreFolderSnapshotPayloadPB {
+  #[pb(index = 1)]
+  pub workspace_id: String,
+
+  #[pb(index = 2)]
+  pub snapshot_id: i64,
+}
+
+#[derive(Debug, Default, ProtoBuf)]
+pub struct DiffSnapshotsPayloadPB {
+  #[pb(index = 1)]
+  pub workspace_id: String,
+
+  #[pb(index = 2)]
+  pub from_snapshot_id: i64,
+
+  #[pb(index = 3)]
+  pub to_snapshot_id: i64,
+}
+
+/// The changes of the views from one snapshot to another
+#[derive(Debug, Default, ProtoBuf)]
+pub struct FolderDiffPB {
+  #[pb(index = 1)]
+  pub added: Vec<ViewPB>,
+
+  #[pb(index = 2)]
+  pub removed: Vec<ViewPB>,
+
+  #[pb(index = 3)]
+  pub renamed: Vec<RenamedViewPB>,
+
+  #[pb(index = 4)]
+  pub moved: Vec<MovedViewPB>,
+}
+
+#[derive(Debug, Default, ProtoBuf)]
+pub struct RenamedViewPB {
+  #[pb(index = 1)]
+  pub view_id: String,
+
+  #[pb(index = 2)]
+  pub old_name: String,
+
+  #[pb(index = 3)]
+  pub new_name: String,
+}
+
+#[derive(Debug, Default, ProtoBuf)]
+pub struct MovedViewPB {
+  #[pb(index = 1)]
+  pub view_id: String,
+
+  #[pb(index = 2)]
+  pub old_parent_view_id: String,
+
+  #[pb(index = 3)]
+  pub new_parent_view_id: String,
 }
 
 #[derive(Debug, Default, ProtoBuf)]
@@ -188,6 +541,19 @@ impl From<SyncState> for FolderSyncStatePB {
   }
 }
 
+/// The sync status of the current workspace's folder.
+#[derive(Debug, Default, Clone, ProtoBuf)]
+pub struct FolderSyncStatusPB {
+  /// Whether the folder has local changes that are not synced yet.
+  #[pb(index = 1)]
+  pub pending_changes: bool,
+
+  /// The timestamp in seconds of the last time the folder finished syncing. Zero if the folder
+  /// has never been synced since it was opened.
+  #[pb(index = 2)]
+  pub last_synced_at: i64,
+}
+
 #[derive(ProtoBuf, Default)]
 pub struct UserFolderPB {
   #[pb(index = 1)]
@@ -196,3 +562,41 @@ pub struct UserFolderPB {
   #[pb(index = 2)]
   pub workspace_id: String,
 }
+
+/// The problems of the folder structure found by `CheckFolderIntegrity`. Checking doesn't change
+/// the folder, `RepairFolder` fixes the problems and returns the report of the problems that are
+/// left.
+#[derive(Default, ProtoBuf, Debug, Clone, PartialEq)]
+pub struct FolderIntegrityReportPB {
+  /// The views that can't be reached from the workspace, because their parent doesn't exist or
+  /// doesn't list them as a child. Only the topmost unreachable view is reported, its child views
+  /// are reattached along with it.
+  #[pb(index = 1)]
+  pub orphans: Vec<String>,
+
+  /// One view of each cycle formed by the parents of the views.
+  #[pb(index = 2)]
+  pub cycles: Vec<String>,
+
+  /// The views that are listed more than once as a child view.
+  #[pb(index = 3)]
+  pub duplicate_ids: Vec<String>,
+
+  /// The favorites whose view doesn't exist.
+  #[pb(index = 4)]
+  pub dangling_favorites: Vec<String>,
+
+  /// Whether the current view doesn't exist or is in the trash.
+  #[pb(index = 5)]
+  pub dangling_current_view: bool,
+}
+
+impl FolderIntegrityReportPB {
+  pub fn is_healthy(&self) -> bool {
+    self.orphans.is_empty()
+      && self.cycles.is_empty()
+      && self.duplicate_ids.is_empty()
+      && self.dangling_favorites.is_empty()
+      && !self.dangling_current_view
+  }
+}
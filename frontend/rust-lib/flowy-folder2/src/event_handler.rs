@@ -5,7 +5,7 @@ use lib_dispatch::prelude::{data_result_ok, AFPluginData, AFPluginState, DataRes
 
 use crate::entities::*;
 use crate::manager::FolderManager;
-use crate::share::ImportParams;
+use crate::share::{ExportParams, ImportParams};
 
 fn upgrade_folder(
   folder_manager: AFPluginState<Weak<FolderManager>>,
@@ -23,6 +23,7 @@ pub(crate) async fn create_workspace_handler(
 ) -> DataResult<WorkspacePB, FlowyError> {
   let folder = upgrade_folder(folder)?;
   let params: CreateWorkspaceParams = data.into_inner().try_into()?;
+  let icon = params.icon.clone().map(|icon| icon.into());
   let workspace = folder.create_workspace(params).await?;
   let views = folder
     .get_views_belong_to(&workspace.id)
@@ -35,6 +36,7 @@ pub(crate) async fn create_workspace_handler(
     name: workspace.name,
     views,
     create_time: workspace.created_at,
+    icon,
   })
 }
 
@@ -48,19 +50,62 @@ pub(crate) async fn get_workspace_views_handler(
   data_result_ok(repeated_view)
 }
 
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn update_workspace_handler(
+  data: AFPluginData<UpdateWorkspacePayloadPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> DataResult<WorkspacePB, FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let params: UpdateWorkspaceParams = data.into_inner().try_into()?;
+  let workspace = folder.update_workspace(params).await?;
+  data_result_ok(workspace)
+}
+
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn delete_workspace_handler(
+  data: AFPluginData<WorkspaceIdPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> Result<(), FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let workspace_id = data.into_inner().value;
+  if workspace_id.is_empty() {
+    return Err(FlowyError::workspace_id().with_context("workspace id should not be empty"));
+  }
+  folder.delete_workspace(&workspace_id).await?;
+  Ok(())
+}
+
+#[tracing::instrument(level = "debug", skip(folder), err)]
+pub(crate) async fn get_all_workspaces_handler(
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> DataResult<RepeatedWorkspacePB, FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let workspaces = folder.get_all_workspaces().await?;
+  data_result_ok(workspaces.into())
+}
+
 #[tracing::instrument(level = "debug", skip(data, folder), err)]
 pub(crate) async fn open_workspace_handler(
   data: AFPluginData<WorkspaceIdPB>,
   folder: AFPluginState<Weak<FolderManager>>,
 ) -> DataResult<WorkspacePB, FlowyError> {
   let folder = upgrade_folder(folder)?;
-  let workspace_id = data.into_inner().value;
+  let params = data.into_inner();
+  let workspace_id = params.value;
   if workspace_id.is_empty() {
     Err(FlowyError::workspace_id().with_context("workspace id should not be empty"))
   } else {
     let workspace = folder.open_workspace(&workspace_id).await?;
-    let views = folder.get_workspace_views(&workspace_id).await?;
-    let workspace_pb: WorkspacePB = (workspace, views).into();
+    let views = match params.preload_depth {
+      None => folder.get_workspace_views(&workspace_id).await?,
+      Some(depth) => {
+        folder
+          .get_workspace_views_with_depth(&workspace_id, depth)
+          .await?
+      },
+    };
+    let mut workspace_pb: WorkspacePB = (workspace, views).into();
+    folder.fill_workspace_extra(&mut workspace_pb);
     data_result_ok(workspace_pb)
   }
 }
@@ -100,7 +145,57 @@ pub(crate) async fn create_view_handler(
   if set_as_current {
     let _ = folder.set_current_view(&view.id).await;
   }
-  data_result_ok(view_pb_without_child_views(Arc::new(view)))
+  let mut view = view_pb_without_child_views(Arc::new(view));
+  folder.fill_flat_view_extras(std::slice::from_mut(&mut view));
+  data_result_ok(view)
+}
+
+pub(crate) async fn create_view_at_handler(
+  data: AFPluginData<CreateViewAtPayloadPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> DataResult<ViewPB, FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let data = data.into_inner();
+  let params: CreateViewParams = data.view.try_into()?;
+  let set_as_current = params.set_as_current;
+  let view = folder.create_view_at(params, data.index as usize).await?;
+  if set_as_current {
+    let _ = folder.set_current_view(&view.id).await;
+  }
+  let mut view = view_pb_without_child_views(Arc::new(view));
+  folder.fill_flat_view_extras(std::slice::from_mut(&mut view));
+  data_result_ok(view)
+}
+
+/// Create all the views in the payload at once. The views are returned in the same order as the
+/// payload. Only the last view's `set_as_current` is honored.
+pub(crate) async fn create_views_batch_handler(
+  data: AFPluginData<RepeatedCreateViewPayloadPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> DataResult<RepeatedViewPB, FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let params = data
+    .into_inner()
+    .items
+    .into_iter()
+    .map(|payload| payload.try_into())
+    .collect::<Result<Vec<CreateViewParams>, _>>()?;
+  let set_as_current = params
+    .last()
+    .map(|params| params.set_as_current)
+    .unwrap_or(false);
+  let views = folder.create_views_with_params(params).await?;
+  if set_as_current {
+    if let Some(view) = views.last() {
+      let _ = folder.set_current_view(&view.id).await;
+    }
+  }
+  let mut views = views
+    .into_iter()
+    .map(|view| view_pb_without_child_views(Arc::new(view)))
+    .collect::<Vec<ViewPB>>();
+  folder.fill_flat_view_extras(&mut views);
+  data_result_ok(RepeatedViewPB { items: views })
 }
 
 pub(crate) async fn create_orphan_view_handler(
@@ -114,7 +209,9 @@ pub(crate) async fn create_orphan_view_handler(
   if set_as_current {
     let _ = folder.set_current_view(&view.id).await;
   }
-  data_result_ok(view_pb_without_child_views(Arc::new(view)))
+  let mut view = view_pb_without_child_views(Arc::new(view));
+  folder.fill_flat_view_extras(std::slice::from_mut(&mut view));
+  data_result_ok(view)
 }
 
 pub(crate) async fn read_view_handler(
@@ -127,6 +224,58 @@ pub(crate) async fn read_view_handler(
   data_result_ok(view_pb)
 }
 
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn get_view_ancestors_handler(
+  data: AFPluginData<ViewIdPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> DataResult<RepeatedViewPB, FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let view_id: ViewIdPB = data.into_inner();
+  let ancestors = folder.get_ancestors(&view_id.value).await?;
+  data_result_ok(RepeatedViewPB { items: ancestors })
+}
+
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn read_views_sorted_handler(
+  data: AFPluginData<ReadViewsSortedPayloadPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> DataResult<RepeatedViewPB, FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let payload = data.into_inner();
+  let views = folder
+    .get_views_sorted(
+      &payload.parent_view_id,
+      payload.sort_type,
+      payload.ascending,
+    )
+    .await?;
+  data_result_ok(RepeatedViewPB { items: views })
+}
+
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn read_view_tree_handler(
+  data: AFPluginData<ViewTreePayloadPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> DataResult<ViewTreePB, FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let payload = data.into_inner();
+  let view_tree = folder
+    .get_view_tree(&payload.view_id, payload.depth.unwrap_or(0))
+    .await?;
+  data_result_ok(view_tree)
+}
+
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn search_views_handler(
+  data: AFPluginData<SearchViewsPayloadPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> DataResult<RepeatedViewPB, FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let params: SearchViewsParams = data.into_inner().try_into()?;
+  let views = folder.search_views(params).await?;
+  data_result_ok(views.into())
+}
+
 #[tracing::instrument(level = "debug", skip(data, folder), err)]
 pub(crate) async fn update_view_handler(
   data: AFPluginData<UpdateViewPayloadPB>,
@@ -149,15 +298,37 @@ pub(crate) async fn update_view_icon_handler(
   Ok(())
 }
 
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn update_view_cover_handler(
+  data: AFPluginData<UpdateViewCoverPayloadPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> Result<(), FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let params: UpdateViewCoverParams = data.into_inner().try_into()?;
+  folder.update_view_cover_with_params(params).await?;
+  Ok(())
+}
+
 pub(crate) async fn delete_view_handler(
   data: AFPluginData<RepeatedViewIdPB>,
   folder: AFPluginState<Weak<FolderManager>>,
+) -> DataResult<DeleteResultPB, FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let params: RepeatedViewIdPB = data.into_inner();
+  let result = folder
+    .move_views_to_trash(params.items, params.cascade.unwrap_or(true))
+    .await?;
+  data_result_ok(result)
+}
+
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn delete_view_permanently_handler(
+  data: AFPluginData<RepeatedViewIdPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
 ) -> Result<(), FlowyError> {
   let folder = upgrade_folder(folder)?;
   let params: RepeatedViewIdPB = data.into_inner();
-  for view_id in &params.items {
-    let _ = folder.move_view_to_trash(view_id).await;
-  }
+  folder.delete_views_permanently(params.items).await?;
   Ok(())
 }
 
@@ -173,13 +344,74 @@ pub(crate) async fn toggle_favorites_handler(
   Ok(())
 }
 
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn set_view_locked_handler(
+  data: AFPluginData<SetViewLockedPayloadPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> Result<(), FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let params = data.into_inner();
+  if params.view_id.is_empty() {
+    return Err(FlowyError::invalid_view_id().with_context("view id should not be empty"));
+  }
+  folder
+    .set_view_locked(&params.view_id, params.locked)
+    .await?;
+  Ok(())
+}
+
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn toggle_view_pin_handler(
+  data: AFPluginData<RepeatedViewIdPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> Result<(), FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let params: RepeatedViewIdPB = data.into_inner();
+  folder.toggle_view_pins(params.items).await?;
+  Ok(())
+}
+
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn set_favorites_handler(
+  data: AFPluginData<SetFavoritePayloadPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> DataResult<RepeatedViewPB, FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let items = data
+    .into_inner()
+    .items
+    .into_iter()
+    .map(|item| (item.view_id, item.is_favorite))
+    .collect::<Vec<_>>();
+  let views = folder.set_favorites(items).await?;
+  data_result_ok(RepeatedViewPB { items: views })
+}
+
 pub(crate) async fn set_latest_view_handler(
   data: AFPluginData<ViewIdPB>,
   folder: AFPluginState<Weak<FolderManager>>,
-) -> Result<(), FlowyError> {
+) -> DataResult<ViewPB, FlowyError> {
   let folder = upgrade_folder(folder)?;
   let view_id: ViewIdPB = data.into_inner();
-  let _ = folder.set_current_view(&view_id.value).await;
+  let view = folder.set_current_view(&view_id.value).await?;
+  data_result_ok(view)
+}
+
+#[tracing::instrument(level = "debug", skip(folder), err)]
+pub(crate) async fn get_recent_views_handler(
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> DataResult<RepeatedViewPB, FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let views = folder.get_recent_views().await?;
+  data_result_ok(RepeatedViewPB { items: views })
+}
+
+#[tracing::instrument(level = "debug", skip(folder), err)]
+pub(crate) async fn clear_recent_views_handler(
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> Result<(), FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  folder.clear_recent_views().await?;
   Ok(())
 }
 
@@ -218,6 +450,36 @@ pub(crate) async fn move_nested_view_handler(
   Ok(())
 }
 
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn move_views_handler(
+  data: AFPluginData<MoveViewsPayloadPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> Result<(), FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let params: MoveViewsParams = data.into_inner().try_into()?;
+  folder
+    .move_views(params.view_ids, params.new_parent_id)
+    .await?;
+  Ok(())
+}
+
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn move_view_to_workspace_handler(
+  data: AFPluginData<MoveViewToWorkspacePayloadPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> Result<(), FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let params: MoveViewToWorkspaceParams = data.into_inner().try_into()?;
+  folder
+    .move_view_to_workspace(
+      &params.view_id,
+      &params.target_workspace_id,
+      params.parent_view_id,
+    )
+    .await?;
+  Ok(())
+}
+
 #[tracing::instrument(level = "debug", skip(data, folder), err)]
 pub(crate) async fn duplicate_view_handler(
   data: AFPluginData<ViewPB>,
@@ -229,6 +491,17 @@ pub(crate) async fn duplicate_view_handler(
   Ok(())
 }
 
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn duplicate_view_recursive_handler(
+  data: AFPluginData<DuplicateViewPayloadPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> DataResult<ViewPB, FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let params: DuplicateViewParams = data.into_inner().try_into()?;
+  let view = folder.duplicate_view_recursive(params).await?;
+  data_result_ok(view)
+}
+
 #[tracing::instrument(level = "debug", skip(folder), err)]
 pub(crate) async fn read_favorites_handler(
   folder: AFPluginState<Weak<FolderManager>>,
@@ -243,13 +516,62 @@ pub(crate) async fn read_favorites_handler(
   }
   data_result_ok(RepeatedViewPB { items: views })
 }
-#[tracing::instrument(level = "debug", skip(folder), err)]
+
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn reorder_favorite_handler(
+  data: AFPluginData<ReorderFavoritePayloadPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> Result<(), FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let payload = data.into_inner();
+  folder
+    .reorder_favorite(&payload.view_id, payload.to_index as usize)
+    .await?;
+  Ok(())
+}
+
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
 pub(crate) async fn read_trash_handler(
+  data: AFPluginData<WorkspaceIdPB>,
   folder: AFPluginState<Weak<FolderManager>>,
 ) -> DataResult<RepeatedTrashPB, FlowyError> {
   let folder = upgrade_folder(folder)?;
-  let trash = folder.get_all_trash().await;
-  data_result_ok(trash.into())
+  let workspace_id = data.into_inner().value;
+  if workspace_id.is_empty() {
+    return Err(FlowyError::workspace_id().with_context("workspace id should not be empty"));
+  }
+  let mut trash: RepeatedTrashPB = folder.get_all_trash(&workspace_id).await?.into();
+  folder
+    .fill_workspace_trash_reasons(&workspace_id, &mut trash.items)
+    .await;
+  data_result_ok(trash)
+}
+
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn read_trash_paged_handler(
+  data: AFPluginData<TrashPagePayloadPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> DataResult<TrashPagePB, FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let payload = data.into_inner();
+  if payload.workspace_id.is_empty() {
+    return Err(FlowyError::workspace_id().with_context("workspace id should not be empty"));
+  }
+  let (trash, total_count) = folder
+    .get_trash_paged(
+      &payload.workspace_id,
+      payload.offset as usize,
+      payload.limit as usize,
+    )
+    .await?;
+  let mut trash: RepeatedTrashPB = trash.into();
+  folder
+    .fill_workspace_trash_reasons(&payload.workspace_id, &mut trash.items)
+    .await;
+  data_result_ok(TrashPagePB {
+    trash,
+    total_count: total_count as u32,
+  })
 }
 
 #[tracing::instrument(level = "debug", skip(identifier, folder), err)]
@@ -262,6 +584,19 @@ pub(crate) async fn putback_trash_handler(
   Ok(())
 }
 
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn restore_trash_to_parent_handler(
+  data: AFPluginData<RestoreTrashPayloadPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> Result<(), FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let payload = data.into_inner();
+  folder
+    .restore_trash_to(&payload.trash_id, &payload.target_parent_id)
+    .await?;
+  Ok(())
+}
+
 #[tracing::instrument(level = "debug", skip(identifiers, folder), err)]
 pub(crate) async fn delete_trash_handler(
   identifiers: AFPluginData<RepeatedTrashIdPB>,
@@ -278,10 +613,10 @@ pub(crate) async fn delete_trash_handler(
 #[tracing::instrument(level = "debug", skip(folder), err)]
 pub(crate) async fn restore_all_trash_handler(
   folder: AFPluginState<Weak<FolderManager>>,
-) -> Result<(), FlowyError> {
+) -> DataResult<RestoreAllResultPB, FlowyError> {
   let folder = upgrade_folder(folder)?;
-  folder.restore_all_trash().await;
-  Ok(())
+  let result = folder.restore_all_trash().await?;
+  data_result_ok(result)
 }
 
 #[tracing::instrument(level = "debug", skip(folder), err)]
@@ -293,24 +628,925 @@ pub(crate) async fn delete_all_trash_handler(
   Ok(())
 }
 
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn set_trash_retention_handler(
+  data: AFPluginData<TrashRetentionPayloadPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> Result<(), FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  folder.set_trash_retention(data.into_inner().days).await?;
+  Ok(())
+}
+
 #[tracing::instrument(level = "debug", skip(data, folder), err)]
 pub(crate) async fn import_data_handler(
   data: AFPluginData<ImportPB>,
   folder: AFPluginState<Weak<FolderManager>>,
-) -> Result<(), FlowyError> {
+) -> DataResult<ImportResultPB, FlowyError> {
   let folder = upgrade_folder(folder)?;
   let params: ImportParams = data.into_inner().try_into()?;
-  folder.import(params).await?;
-  Ok(())
+  let result = folder.import(params).await?;
+  data_result_ok(result)
+}
+
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn export_view_handler(
+  data: AFPluginData<ExportViewPayloadPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> DataResult<ExportDataPB, FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let params: ExportParams = data.into_inner().try_into()?;
+  let data = folder.export_view(params).await?;
+  data_result_ok(data)
+}
+
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn get_workspace_stats_handler(
+  data: AFPluginData<WorkspaceIdPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> DataResult<WorkspaceStatsPB, FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let workspace_id = data.into_inner().value;
+  if workspace_id.is_empty() {
+    return Err(FlowyError::workspace_id().with_context("workspace id should not be empty"));
+  }
+  let stats = folder.get_workspace_stats(&workspace_id).await?;
+  data_result_ok(stats)
+}
+
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn get_views_by_layout_handler(
+  data: AFPluginData<ViewsByLayoutPayloadPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> DataResult<RepeatedViewPB, FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let params: ViewsByLayoutParams = data.into_inner().try_into()?;
+  let views = folder.get_views_by_layout(params).await?;
+  data_result_ok(RepeatedViewPB { items: views })
+}
+
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn export_workspace_handler(
+  data: AFPluginData<ExportWorkspacePayloadPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> DataResult<ExportWorkspaceDataPB, FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let params = data.into_inner();
+  if params.workspace_id.is_empty() {
+    return Err(FlowyError::workspace_id().with_context("workspace id should not be empty"));
+  }
+  let data = match params.format {
+    ExportWorkspaceFormatPB::Zip => folder.export_workspace(&params.workspace_id).await?,
+  };
+  data_result_ok(data)
+}
+
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn import_workspace_handler(
+  data: AFPluginData<ImportWorkspacePayloadPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> DataResult<RepeatedViewPB, FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let params = data.into_inner();
+  let views = folder
+    .import_workspace(&params.target_workspace_id, params.data)
+    .await?;
+  data_result_ok(RepeatedViewPB { items: views })
 }
 
 #[tracing::instrument(level = "debug", skip(folder), err)]
 pub(crate) async fn get_folder_snapshots_handler(
-  data: AFPluginData<WorkspaceIdPB>,
+  data: AFPluginData<FolderSnapshotQueryPB>,
   folder: AFPluginState<Weak<FolderManager>>,
 ) -> DataResult<RepeatedFolderSnapshotPB, FlowyError> {
   let folder = upgrade_folder(folder)?;
   let data = data.into_inner();
-  let snapshots = folder.get_folder_snapshots(&data.value, 10).await?;
-  data_result_ok(RepeatedFolderSnapshotPB { items: snapshots })
+  if data.workspace_id.is_empty() {
+    return Err(FlowyError::workspace_id().with_context("workspace id should not be empty"));
+  }
+  let snapshots = folder
+    .get_folder_snapshots(
+      &data.workspace_id,
+      data.offset as usize,
+      data.limit as usize,
+    )
+    .await?;
+  data_result_ok(snapshots)
+}
+
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn create_folder_snapshot_handler(
+  data: AFPluginData<CreateSnapshotPayloadPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> DataResult<FolderSnapshotPB, FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let data = data.into_inner();
+  if data.workspace_id.is_empty() {
+    return Err(FlowyError::workspace_id().with_context("workspace id should not be empty"));
+  }
+  let snapshot = folder
+    .create_named_snapshot(&data.workspace_id, &data.name)
+    .await?;
+  data_result_ok(snapshot)
+}
+
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn diff_folder_snapshots_handler(
+  data: AFPluginData<DiffSnapshotsPayloadPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> DataResult<FolderDiffPB, FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let data = data.into_inner();
+  if data.workspace_id.is_empty() {
+    return Err(FlowyError::workspace_id().with_context("workspace id should not be empty"));
+  }
+  let diff = folder
+    .diff_snapshots(
+      &data.workspace_id,
+      data.from_snapshot_id,
+      data.to_snapshot_id,
+    )
+    .await?;
+  data_result_ok(diff)
+}
+
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn restore_folder_snapshot_handler(
+  data: AFPluginData<RestoreFolderSnapshotPayloadPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> Result<(), FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let data = data.into_inner();
+  if data.workspace_id.is_empty() {
+    return Err(FlowyError::workspace_id().with_context("workspace id should not be empty"));
+  }
+  folder
+    .restore_snapshot(&data.workspace_id, data.snapshot_id)
+    .await?;
+  Ok(())
+}
+
+#[tracing::instrument(level = "debug", skip(folder), err)]
+pub(crate) async fn undo_folder_operation_handler(
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> DataResult<FolderOperationPB, FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let operation = folder.undo_folder_operation().await?;
+  data_result_ok(operation)
+}
+
+#[tracing::instrument(level = "debug", skip(folder), err)]
+pub(crate) async fn redo_folder_operation_handler(
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> DataResult<FolderOperationPB, FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let operation = folder.redo_folder_operation().await?;
+  data_result_ok(operation)
+}
+
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn add_view_tags_handler(
+  data: AFPluginData<ViewTagsPayloadPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> Result<(), FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let params: ViewTagsParams = data.into_inner().try_into()?;
+  folder.add_view_tags(params).await?;
+  Ok(())
+}
+
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn remove_view_tags_handler(
+  data: AFPluginData<ViewTagsPayloadPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> Result<(), FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let params: ViewTagsParams = data.into_inner().try_into()?;
+  folder.remove_view_tags(params).await?;
+  Ok(())
+}
+
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn get_views_by_tag_handler(
+  data: AFPluginData<ViewTagPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> DataResult<RepeatedViewPB, FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let tag = data.into_inner().value;
+  let views = folder.get_views_by_tag(tag.trim()).await?;
+  data_result_ok(RepeatedViewPB { items: views })
+}
+
+#[tracing::instrument(level = "debug", skip(folder), err)]
+pub(crate) async fn get_orphaned_views_handler(
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> DataResult<RepeatedViewPB, FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let views = folder.get_orphaned_views().await?;
+  data_result_ok(RepeatedViewPB { items: views })
+}
+
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn repair_orphaned_views_handler(
+  data: AFPluginData<RepairOrphanedViewsPayloadPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> DataResult<RepeatedViewPB, FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let views = folder.repair_orphaned_views(data.dry_run).await?;
+  data_result_ok(RepeatedViewPB { items: views })
+}
+
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn set_default_view_layout_handler(
+  data: AFPluginData<DefaultLayoutPayloadPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> Result<(), FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let data = data.into_inner();
+  if data.workspace_id.is_empty() {
+    return Err(FlowyError::workspace_id().with_context("workspace id should not be empty"));
+  }
+  folder
+    .set_default_view_layout(&data.workspace_id, data.layout)
+    .await?;
+  Ok(())
+}
+
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn update_workspace_ui_settings_handler(
+  data: AFPluginData<WorkspaceUISettingsPayloadPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> Result<(), FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let data = data.into_inner();
+  if data.workspace_id.is_empty() {
+    return Err(FlowyError::workspace_id().with_context("workspace id should not be empty"));
+  }
+  folder
+    .update_workspace_ui_settings(&data.workspace_id, data.show_favorites)
+    .await?;
+  Ok(())
+}
+
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn copy_view_to_workspace_handler(
+  data: AFPluginData<CopyViewToWorkspacePayloadPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> DataResult<ViewPB, FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let params: CopyViewToWorkspaceParams = data.into_inner().try_into()?;
+  let view = folder
+    .copy_view_to_workspace(
+      &params.view_id,
+      &params.target_workspace_id,
+      params.include_children,
+    )
+    .await?;
+  data_result_ok(view)
+}
+
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn get_views_edited_by_handler(
+  data: AFPluginData<ViewsEditedByPayloadPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> DataResult<RepeatedViewPB, FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let uid = data.into_inner().uid;
+  let views = folder.get_views_edited_by(uid).await?;
+  data_result_ok(RepeatedViewPB { items: views })
+}
+
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn view_exists_handler(
+  data: AFPluginData<ViewIdPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> DataResult<ViewExistsPB, FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let view_id = data.into_inner().value;
+  data_result_ok(folder.view_exists(&view_id))
+}
+
+#[tracing::instrument(level = "debug", skip(identifiers, folder), err)]
+pub(crate) async fn restore_trash_batch_handler(
+  identifiers: AFPluginData<RepeatedTrashIdPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> DataResult<RestoreTrashBatchPB, FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let trash_ids = identifiers
+    .into_inner()
+    .items
+    .into_iter()
+    .map(|trash_id| trash_id.id)
+    .collect();
+  let result = folder.restore_trash_batch(trash_ids).await?;
+  data_result_ok(result)
+}
+
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn cancel_import_handler(
+  data: AFPluginData<ImportIdPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> Result<(), FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  folder.cancel_import(&data.into_inner().value)?;
+  Ok(())
+}
+
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn duplicate_workspace_handler(
+  data: AFPluginData<DuplicateWorkspacePayloadPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> DataResult<WorkspacePB, FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let params: DuplicateWorkspaceParams = data.into_inner().try_into()?;
+  let workspace = folder
+    .duplicate_workspace(&params.workspace_id, &params.new_name)
+    .await?;
+  data_result_ok(workspace)
+}
+
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn sort_child_views_handler(
+  data: AFPluginData<SortChildViewsPayloadPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> Result<(), FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let payload = data.into_inner();
+  if payload.parent_id.is_empty() {
+    return Err(FlowyError::invalid_view_id().with_context("parent id should not be empty"));
+  }
+  folder
+    .sort_children(&payload.parent_id, payload.order)
+    .await?;
+  Ok(())
+}
+
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn get_view_path_handler(
+  data: AFPluginData<ViewIdPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> DataResult<ViewPathPB, FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let view_id: ViewIdPB = data.into_inner();
+  let path = folder.get_view_path(&view_id.value).await?;
+  data_result_ok(path)
+}
+
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn set_max_nesting_depth_handler(
+  data: AFPluginData<MaxNestingDepthPayloadPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> Result<(), FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let data = data.into_inner();
+  if data.workspace_id.is_empty() {
+    return Err(FlowyError::workspace_id().with_context("workspace id should not be empty"));
+  }
+  folder
+    .set_max_nesting_depth(&data.workspace_id, data.depth)
+    .await?;
+  Ok(())
+}
+
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn get_child_views_handler(
+  data: AFPluginData<ViewIdPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> DataResult<RepeatedViewPB, FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let view_id: ViewIdPB = data.into_inner();
+  let views = folder.get_child_views(&view_id.value).await?;
+  data_result_ok(RepeatedViewPB { items: views })
+}
+
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn favorite_subtree_handler(
+  data: AFPluginData<FavoriteSubtreePayloadPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> DataResult<RepeatedViewIdPB, FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let data = data.into_inner();
+  if data.view_id.is_empty() {
+    return Err(FlowyError::invalid_view_id());
+  }
+  let view_ids = folder
+    .set_favorite_recursive(
+      &data.view_id,
+      data.is_favorite,
+      data.limit.map(|limit| limit as usize),
+    )
+    .await?;
+  data_result_ok(RepeatedViewIdPB {
+    items: view_ids,
+    cascade: None,
+  })
+}
+
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn reorder_view_handler(
+  data: AFPluginData<ReorderViewPayloadPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> Result<(), FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let params: ReorderViewParams = data.into_inner().try_into()?;
+  folder
+    .reorder_view(&params.view_id, params.new_index)
+    .await?;
+  Ok(())
+}
+
+#[tracing::instrument(level = "debug", skip(folder), err)]
+pub(crate) async fn get_folder_sync_status_handler(
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> DataResult<FolderSyncStatusPB, FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  data_result_ok(folder.get_folder_sync_status())
+}
+
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn create_view_from_template_handler(
+  data: AFPluginData<CreateFromTemplatePayloadPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> DataResult<ViewPB, FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let params: CreateFromTemplateParams = data.into_inner().try_into()?;
+  let view = folder.create_view_from_template(params).await?;
+  data_result_ok(view)
+}
+
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn set_view_template_handler(
+  data: AFPluginData<SetTemplatePayloadPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> Result<(), FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let data = data.into_inner();
+  if data.view_id.is_empty() {
+    return Err(FlowyError::invalid_view_id());
+  }
+  folder
+    .set_view_template(&data.view_id, data.is_template)
+    .await?;
+  Ok(())
+}
+
+#[tracing::instrument(level = "debug", skip(folder), err)]
+pub(crate) async fn get_templates_handler(
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> DataResult<RepeatedViewPB, FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let views = folder.get_templates().await?;
+  data_result_ok(RepeatedViewPB { items: views })
+}
+
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn rename_view_handler(
+  data: AFPluginData<RenameViewPayloadPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> Result<(), FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let params: RenameViewParams = data.into_inner().try_into()?;
+  folder.rename_view(params).await?;
+  Ok(())
+}
+
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn get_views_in_range_handler(
+  data: AFPluginData<ViewsTimeRangePayloadPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> DataResult<RepeatedViewPB, FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let params: ViewsTimeRangeParams = data.into_inner().try_into()?;
+  let views = folder.get_views_in_range(params).await?;
+  data_result_ok(RepeatedViewPB { items: views })
+}
+
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn get_trash_count_handler(
+  data: AFPluginData<WorkspaceIdPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> DataResult<TrashCountPB, FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let workspace_id = data.into_inner().value;
+  if workspace_id.is_empty() {
+    return Err(FlowyError::workspace_id().with_context("workspace id should not be empty"));
+  }
+  let count = folder.get_trash_count(&workspace_id).await?;
+  data_result_ok(TrashCountPB {
+    count: count as u32,
+  })
+}
+
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn set_view_expanded_handler(
+  data: AFPluginData<RepeatedSetExpandedPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> Result<(), FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let items = data
+    .into_inner()
+    .items
+    .into_iter()
+    .map(|item| (item.view_id, item.is_expanded))
+    .collect::<Vec<_>>();
+  folder.set_views_expanded(items).await?;
+  Ok(())
+}
+
+/// Returns the views that the import would create. Nothing is written to the folder.
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn import_preview_handler(
+  data: AFPluginData<ImportPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> DataResult<ImportPreviewPB, FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let params: ImportParams = data.into_inner().try_into()?;
+  let preview = folder.preview_import(params).await?;
+  data_result_ok(preview)
+}
+
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn get_views_by_color_handler(
+  data: AFPluginData<ViewColorFilterPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> DataResult<RepeatedViewPB, FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let color = data.into_inner().color;
+  let views = folder.get_views_by_color(color.into()).await?;
+  data_result_ok(RepeatedViewPB { items: views })
+}
+
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn subscribe_view_handler(
+  data: AFPluginData<ViewIdPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> Result<(), FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let view_id = data.into_inner().value;
+  folder.subscribe_view(&view_id)?;
+  Ok(())
+}
+
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn unsubscribe_view_handler(
+  data: AFPluginData<ViewIdPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> Result<(), FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let view_id = data.into_inner().value;
+  folder.unsubscribe_view(&view_id);
+  Ok(())
+}
+
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn get_default_open_view_handler(
+  data: AFPluginData<WorkspaceIdPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> DataResult<DefaultOpenViewPB, FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let workspace_id = data.into_inner().value;
+  if workspace_id.is_empty() {
+    return Err(FlowyError::workspace_id().with_context("workspace id should not be empty"));
+  }
+  let view = folder.resolve_default_view(&workspace_id).await?;
+  data_result_ok(DefaultOpenViewPB { view })
+}
+
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn trash_view_with_reason_handler(
+  data: AFPluginData<TrashViewPayloadPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> DataResult<RepeatedViewIdPB, FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let params: TrashViewParams = data.into_inner().try_into()?;
+  let trashed_ids = folder
+    .move_view_to_trash_with_reason(&params.view_id, params.reason)
+    .await?;
+  data_result_ok(RepeatedViewIdPB {
+    items: trashed_ids,
+    cascade: None,
+  })
+}
+
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn merge_views_handler(
+  data: AFPluginData<MergeViewsPayloadPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> DataResult<ViewPB, FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let params: MergeViewsParams = data.into_inner().try_into()?;
+  let view = folder.merge_views(params).await?;
+  data_result_ok(view)
+}
+
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn set_view_meta_handler(
+  data: AFPluginData<ViewMetaPayloadPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> Result<(), FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let params: ViewMetaParams = data.into_inner().try_into()?;
+  folder.set_view_meta(params).await?;
+  Ok(())
+}
+
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn get_view_meta_handler(
+  data: AFPluginData<ViewMetaPayloadPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> DataResult<ViewMetaValuePB, FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let params: ViewMetaParams = data.into_inner().try_into()?;
+  let value = folder.get_view_meta(&params.view_id, &params.key).await?;
+  data_result_ok(ViewMetaValuePB { value })
+}
+
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn set_unique_sibling_names_handler(
+  data: AFPluginData<UniqueSiblingNamesPayloadPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> Result<(), FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let data = data.into_inner();
+  if data.workspace_id.is_empty() {
+    return Err(FlowyError::workspace_id().with_context("workspace id should not be empty"));
+  }
+  folder
+    .set_unique_sibling_names(&data.workspace_id, data.enabled)
+    .await?;
+  Ok(())
+}
+
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn export_favorites_handler(
+  data: AFPluginData<ExportFavoritesPayloadPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> DataResult<ExportDataPB, FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let format = data.into_inner().format;
+  let data = folder.export_favorites(format.into()).await?;
+  data_result_ok(data)
+}
+
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn empty_view_children_handler(
+  data: AFPluginData<ViewIdPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> DataResult<EmptyViewChildrenResultPB, FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let view_id = data.into_inner().value;
+  if view_id.is_empty() {
+    return Err(FlowyError::invalid_view_id());
+  }
+  let trashed_count = folder.trash_children(&view_id).await?;
+  data_result_ok(EmptyViewChildrenResultPB {
+    trashed_count: trashed_count as u32,
+  })
+}
+
+#[tracing::instrument(level = "debug", skip(folder), err)]
+pub(crate) async fn get_search_history_handler(
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> DataResult<SearchHistoryPB, FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let queries = folder.get_search_history().await?;
+  data_result_ok(SearchHistoryPB { queries })
+}
+
+#[tracing::instrument(level = "debug", skip(folder), err)]
+pub(crate) async fn clear_search_history_handler(
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> Result<(), FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  folder.clear_search_history().await?;
+  Ok(())
+}
+
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn update_view_icons_batch_handler(
+  data: AFPluginData<RepeatedUpdateViewIconPayloadPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> DataResult<UpdateViewIconsResultPB, FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let result = folder.update_view_icons(data.into_inner()).await?;
+  data_result_ok(result)
+}
+
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn flatten_subtree_handler(
+  data: AFPluginData<FlattenSubtreePayloadPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> DataResult<RepeatedViewPB, FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let params: FlattenSubtreeParams = data.into_inner().try_into()?;
+  let views = folder.flatten_subtree(params).await?;
+  data_result_ok(RepeatedViewPB { items: views })
+}
+
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn set_view_archived_handler(
+  data: AFPluginData<RepeatedSetArchivedPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> Result<(), FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let items = data
+    .into_inner()
+    .items
+    .into_iter()
+    .map(|item| {
+      if item.view_id.is_empty() {
+        return Err(FlowyError::invalid_view_id());
+      }
+      Ok((item.view_id, item.is_archived))
+    })
+    .collect::<FlowyResult<Vec<_>>>()?;
+  folder.set_views_archived(items).await?;
+  Ok(())
+}
+
+#[tracing::instrument(level = "debug", skip(folder), err)]
+pub(crate) async fn get_archived_views_handler(
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> DataResult<RepeatedViewPB, FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let views = folder.get_archived_views().await?;
+  data_result_ok(RepeatedViewPB { items: views })
+}
+
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn get_recent_views_sorted_handler(
+  data: AFPluginData<RecentViewsSortedPayloadPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> DataResult<RepeatedViewPB, FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let views = folder
+    .get_recent_views_sorted(data.into_inner().sort_type)
+    .await?;
+  data_result_ok(RepeatedViewPB { items: views })
+}
+
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn change_view_layout_handler(
+  data: AFPluginData<ChangeLayoutPayloadPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> Result<(), FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let params: ChangeLayoutParams = data.into_inner().try_into()?;
+  folder.change_view_layout(params).await?;
+  Ok(())
+}
+
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn get_view_siblings_handler(
+  data: AFPluginData<ViewIdPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> DataResult<ViewSiblingsPB, FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let view_id: ViewIdPB = data.into_inner();
+  if view_id.value.is_empty() {
+    return Err(FlowyError::invalid_view_id());
+  }
+  let siblings = folder.get_view_siblings(&view_id.value).await?;
+  data_result_ok(siblings)
+}
+
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn find_child_by_name_handler(
+  data: AFPluginData<FindChildPayloadPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> DataResult<FindChildResultPB, FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let params: FindChildParams = data.into_inner().try_into()?;
+  let result = folder.find_child_by_name(params).await?;
+  data_result_ok(result)
+}
+
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn set_view_limit_handler(
+  data: AFPluginData<ViewLimitPayloadPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> Result<(), FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let data = data.into_inner();
+  if data.workspace_id.is_empty() {
+    return Err(FlowyError::workspace_id().with_context("workspace id should not be empty"));
+  }
+  folder
+    .set_view_limit(&data.workspace_id, data.limit)
+    .await?;
+  Ok(())
+}
+
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn set_view_references_handler(
+  data: AFPluginData<ViewReferencesPayloadPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> Result<(), FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let params: ViewReferencesParams = data.into_inner().try_into()?;
+  folder.set_view_references(params).await?;
+  Ok(())
+}
+
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn get_view_backlinks_handler(
+  data: AFPluginData<ViewIdPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> DataResult<RepeatedViewPB, FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let view_id = data.into_inner().value;
+  if view_id.is_empty() {
+    return Err(FlowyError::invalid_view_id());
+  }
+  let views = folder.get_view_backlinks(&view_id).await?;
+  data_result_ok(RepeatedViewPB { items: views })
+}
+
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn update_workspace_settings_handler(
+  data: AFPluginData<WorkspaceSettingsPayloadPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> DataResult<WorkspaceSettingsPB, FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let params: WorkspaceSettingsParams = data.into_inner().try_into()?;
+  let settings = folder.update_workspace_settings(params).await?;
+  data_result_ok(settings)
+}
+
+#[tracing::instrument(level = "trace", skip(data, folder), err)]
+pub(crate) async fn get_subtree_count_handler(
+  data: AFPluginData<ViewIdPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> DataResult<SubtreeCountPB, FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let view_id: ViewIdPB = data.into_inner();
+  if view_id.value.is_empty() {
+    return Err(FlowyError::invalid_view_id());
+  }
+  let count = folder.get_subtree_count(&view_id.value).await?;
+  data_result_ok(count)
+}
+
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn set_workspace_home_handler(
+  data: AFPluginData<SetHomePayloadPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> Result<(), FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let params: SetHomeParams = data.into_inner().try_into()?;
+  folder.set_workspace_home(params).await?;
+  Ok(())
+}
+
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn list_views_cursor_handler(
+  data: AFPluginData<ListViewsCursorPayloadPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> DataResult<ViewsPagePB, FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let params: ListViewsCursorParams = data.into_inner().try_into()?;
+  let page = folder.list_views_cursor(params).await?;
+  data_result_ok(page)
+}
+
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn check_folder_integrity_handler(
+  data: AFPluginData<WorkspaceIdPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> DataResult<FolderIntegrityReportPB, FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let workspace_id = data.into_inner().value;
+  if workspace_id.is_empty() {
+    return Err(FlowyError::workspace_id().with_context("workspace id should not be empty"));
+  }
+  let report = folder.check_integrity(&workspace_id).await?;
+  data_result_ok(report)
+}
+
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn repair_folder_handler(
+  data: AFPluginData<WorkspaceIdPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> DataResult<FolderIntegrityReportPB, FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let workspace_id = data.into_inner().value;
+  if workspace_id.is_empty() {
+    return Err(FlowyError::workspace_id().with_context("workspace id should not be empty"));
+  }
+  let report = folder.repair_folder(&workspace_id).await?;
+  data_result_ok(report)
+}
+
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn get_current_view_context_handler(
+  data: AFPluginData<WorkspaceIdPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> DataResult<ViewContextPB, FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let workspace_id = data.into_inner().value;
+  if workspace_id.is_empty() {
+    return Err(FlowyError::workspace_id().with_context("workspace id should not be empty"));
+  }
+  let context = folder.get_current_view_context(&workspace_id).await?;
+  data_result_ok(context)
 }
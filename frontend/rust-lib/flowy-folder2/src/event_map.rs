@@ -15,29 +15,127 @@ pub fn init(folder: Weak<FolderManager>) -> AFPlugin {
       .event(FolderEvent::GetCurrentWorkspaceSetting, read_current_workspace_setting_handler)
     .event(FolderEvent::ReadCurrentWorkspace, read_current_workspace_handler)
     .event(FolderEvent::OpenWorkspace, open_workspace_handler)
+    .event(FolderEvent::UpdateWorkspace, update_workspace_handler)
+    .event(FolderEvent::DeleteWorkspace, delete_workspace_handler)
+    .event(FolderEvent::ReadAllWorkspaces, get_all_workspaces_handler)
     .event(FolderEvent::ReadWorkspaceViews, get_workspace_views_handler)
      // View
     .event(FolderEvent::CreateView, create_view_handler)
+    .event(FolderEvent::CreateViews, create_views_batch_handler)
     .event(FolderEvent::CreateOrphanView, create_orphan_view_handler)
     .event(FolderEvent::ReadView, read_view_handler)
+    .event(FolderEvent::ReadViewTree, read_view_tree_handler)
+    .event(FolderEvent::ReadViewsSorted, read_views_sorted_handler)
+    .event(FolderEvent::SearchViews, search_views_handler)
     .event(FolderEvent::UpdateView, update_view_handler)
     .event(FolderEvent::DeleteView, delete_view_handler)
+    .event(FolderEvent::DeleteViewPermanently, delete_view_permanently_handler)
     .event(FolderEvent::DuplicateView, duplicate_view_handler)
+    .event(
+      FolderEvent::DuplicateViewRecursive,
+      duplicate_view_recursive_handler,
+    )
     .event(FolderEvent::SetLatestView, set_latest_view_handler)
     .event(FolderEvent::CloseView, close_view_handler)
     .event(FolderEvent::MoveView, move_view_handler)
     .event(FolderEvent::MoveNestedView, move_nested_view_handler)
+    .event(FolderEvent::MoveViewToWorkspace, move_view_to_workspace_handler)
     // Trash
     .event(FolderEvent::ReadTrash, read_trash_handler)
+    .event(FolderEvent::ReadTrashPaged, read_trash_paged_handler)
     .event(FolderEvent::PutbackTrash, putback_trash_handler)
+    .event(FolderEvent::RestoreTrashToParent, restore_trash_to_parent_handler)
     .event(FolderEvent::DeleteTrash, delete_trash_handler)
     .event(FolderEvent::RestoreAllTrash, restore_all_trash_handler)
     .event(FolderEvent::DeleteAllTrash, delete_all_trash_handler)
+    .event(FolderEvent::SetTrashRetention, set_trash_retention_handler)
     .event(FolderEvent::ImportData, import_data_handler)
+    .event(FolderEvent::ExportView, export_view_handler)
     .event(FolderEvent::GetFolderSnapshots, get_folder_snapshots_handler)
+    .event(FolderEvent::RestoreFolderSnapshot, restore_folder_snapshot_handler)
+    .event(FolderEvent::CreateFolderSnapshot, create_folder_snapshot_handler)
+    .event(FolderEvent::DiffFolderSnapshots, diff_folder_snapshots_handler)
+    .event(FolderEvent::SetFavorites, set_favorites_handler)
+    .event(FolderEvent::SetViewLocked, set_view_locked_handler)
+    .event(FolderEvent::ToggleViewPin, toggle_view_pin_handler)
+    .event(FolderEvent::GetViewAncestors, get_view_ancestors_handler)
+    .event(FolderEvent::MoveViews, move_views_handler)
+    .event(FolderEvent::UpdateViewCover, update_view_cover_handler)
+    .event(FolderEvent::GetRecentViews, get_recent_views_handler)
+    .event(FolderEvent::ClearRecentViews, clear_recent_views_handler)
+    .event(FolderEvent::ExportWorkspace, export_workspace_handler)
+    .event(FolderEvent::ImportWorkspace, import_workspace_handler)
+    .event(FolderEvent::GetWorkspaceStats, get_workspace_stats_handler)
+    .event(FolderEvent::GetViewsByLayout, get_views_by_layout_handler)
+    .event(FolderEvent::UndoFolderOperation, undo_folder_operation_handler)
+    .event(FolderEvent::RedoFolderOperation, redo_folder_operation_handler)
+    .event(FolderEvent::AddViewTags, add_view_tags_handler)
+    .event(FolderEvent::RemoveViewTags, remove_view_tags_handler)
+    .event(FolderEvent::GetViewsByTag, get_views_by_tag_handler)
+    .event(FolderEvent::GetOrphanedViews, get_orphaned_views_handler)
+    .event(FolderEvent::RepairOrphanedViews, repair_orphaned_views_handler)
+    .event(FolderEvent::SetDefaultViewLayout, set_default_view_layout_handler)
+    .event(
+      FolderEvent::UpdateWorkspaceUISettings,
+      update_workspace_ui_settings_handler,
+    )
+    .event(FolderEvent::CopyViewToWorkspace, copy_view_to_workspace_handler)
+    .event(FolderEvent::GetViewsEditedBy, get_views_edited_by_handler)
+    .event(FolderEvent::ViewExists, view_exists_handler)
+    .event(FolderEvent::RestoreTrashBatch, restore_trash_batch_handler)
+    .event(FolderEvent::CancelImport, cancel_import_handler)
+    .event(FolderEvent::DuplicateWorkspace, duplicate_workspace_handler)
+    .event(FolderEvent::SortChildViews, sort_child_views_handler)
+    .event(FolderEvent::GetViewPath, get_view_path_handler)
+    .event(FolderEvent::SetMaxNestingDepth, set_max_nesting_depth_handler)
+    .event(FolderEvent::GetChildViews, get_child_views_handler)
+    .event(FolderEvent::FavoriteSubtree, favorite_subtree_handler)
+    .event(FolderEvent::ReorderView, reorder_view_handler)
+    .event(FolderEvent::GetFolderSyncStatus, get_folder_sync_status_handler)
+    .event(FolderEvent::CreateViewFromTemplate, create_view_from_template_handler)
+    .event(FolderEvent::SetViewTemplate, set_view_template_handler)
+    .event(FolderEvent::GetTemplates, get_templates_handler)
+    .event(FolderEvent::RenameView, rename_view_handler)
+    .event(FolderEvent::GetViewsInRange, get_views_in_range_handler)
+    .event(FolderEvent::GetTrashCount, get_trash_count_handler)
+    .event(FolderEvent::SetViewExpanded, set_view_expanded_handler)
+    .event(FolderEvent::CreateViewAt, create_view_at_handler)
+    .event(FolderEvent::ImportPreview, import_preview_handler)
+    .event(FolderEvent::GetViewsByColor, get_views_by_color_handler)
+    .event(FolderEvent::SubscribeView, subscribe_view_handler)
+    .event(FolderEvent::UnsubscribeView, unsubscribe_view_handler)
+    .event(FolderEvent::GetDefaultOpenView, get_default_open_view_handler)
+    .event(FolderEvent::TrashViewWithReason, trash_view_with_reason_handler)
+    .event(FolderEvent::MergeViews, merge_views_handler)
+    .event(FolderEvent::SetViewMeta, set_view_meta_handler)
+    .event(FolderEvent::GetViewMeta, get_view_meta_handler)
+    .event(FolderEvent::SetUniqueSiblingNames, set_unique_sibling_names_handler)
+    .event(FolderEvent::ExportFavorites, export_favorites_handler)
+    .event(FolderEvent::EmptyViewChildren, empty_view_children_handler)
+    .event(FolderEvent::GetSearchHistory, get_search_history_handler)
+    .event(FolderEvent::ClearSearchHistory, clear_search_history_handler)
+    .event(FolderEvent::UpdateViewIconsBatch, update_view_icons_batch_handler)
+    .event(FolderEvent::FlattenSubtree, flatten_subtree_handler)
+    .event(FolderEvent::SetViewArchived, set_view_archived_handler)
+    .event(FolderEvent::GetArchivedViews, get_archived_views_handler)
+    .event(FolderEvent::GetRecentViewsSorted, get_recent_views_sorted_handler)
+    .event(FolderEvent::ChangeViewLayout, change_view_layout_handler)
+    .event(FolderEvent::GetViewSiblings, get_view_siblings_handler)
+    .event(FolderEvent::FindChildByName, find_child_by_name_handler)
+    .event(FolderEvent::SetViewLimit, set_view_limit_handler)
+    .event(FolderEvent::SetViewReferences, set_view_references_handler)
+    .event(FolderEvent::GetViewBacklinks, get_view_backlinks_handler)
+    .event(FolderEvent::UpdateWorkspaceSettings, update_workspace_settings_handler)
+    .event(FolderEvent::GetSubtreeCount, get_subtree_count_handler)
+    .event(FolderEvent::SetWorkspaceHome, set_workspace_home_handler)
+    .event(FolderEvent::ListViewsCursor, list_views_cursor_handler)
+    .event(FolderEvent::CheckFolderIntegrity, check_folder_integrity_handler)
+    .event(FolderEvent::RepairFolder, repair_folder_handler)
+    .event(FolderEvent::GetCurrentViewContext, get_current_view_context_handler)
     .event(FolderEvent::UpdateViewIcon, update_view_icon_handler)
     .event(FolderEvent::ReadFavorites, read_favorites_handler)
     .event(FolderEvent::ToggleFavorite, toggle_favorites_handler)
+    .event(FolderEvent::ReorderFavorite, reorder_favorite_handler)
 }
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug, Display, Hash, ProtoBuf_Enum, Flowy_Event)]
@@ -55,7 +153,7 @@ pub enum FolderEvent {
   #[event(output = "WorkspacePB")]
   ReadCurrentWorkspace = 2,
 
-  /// Delete the workspace
+  /// Delete the workspace with all of its views. The last workspace can't be deleted
   #[event(input = "WorkspaceIdPB")]
   DeleteWorkspace = 3,
 
@@ -80,8 +178,10 @@ pub enum FolderEvent {
   #[event(input = "UpdateViewPayloadPB", output = "ViewPB")]
   UpdateView = 12,
 
-  /// Move the view to the trash folder
-  #[event(input = "RepeatedViewIdPB")]
+  /// Move the views to the trash folder. Return the trashed view ids, including the descendants,
+  /// and the views that failed to be trashed. If the deletion doesn't cascade, the child views are
+  /// moved to the parent of the view and kept
+  #[event(input = "RepeatedViewIdPB", output = "DeleteResultPB")]
   DeleteView = 13,
 
   /// Duplicate the view
@@ -100,16 +200,17 @@ pub enum FolderEvent {
   #[event()]
   CopyLink = 20,
 
-  /// Set the current visiting view
-  #[event(input = "ViewIdPB")]
+  /// Set the current visiting view. Return the view, or an error if the view doesn't exist or is
+  /// in the trash
+  #[event(input = "ViewIdPB", output = "ViewPB")]
   SetLatestView = 21,
 
   /// Move the view or app to another place
   #[event(input = "MoveViewPayloadPB")]
   MoveView = 22,
 
-  /// Read the trash that was deleted by the user
-  #[event(output = "RepeatedTrashPB")]
+  /// Read the trash of the workspace that was deleted by the user
+  #[event(input = "WorkspaceIdPB", output = "RepeatedTrashPB")]
   ReadTrash = 23,
 
   /// Put back the trash to the origin folder
@@ -120,18 +221,22 @@ pub enum FolderEvent {
   #[event(input = "RepeatedTrashIdPB")]
   DeleteTrash = 25,
 
-  /// Put back all the trash to its original folder
-  #[event()]
+  /// Put back all the trash to its original folder. Return the number of the restored items and
+  /// the ids of the items that couldn't be restored
+  #[event(output = "RestoreAllResultPB")]
   RestoreAllTrash = 26,
 
   /// Delete all the trash from the disk
   #[event()]
   DeleteAllTrash = 27,
 
-  #[event(input = "ImportPB")]
+  /// Import the data as a new view. The import stops when the view limit of the workspace is
+  /// reached, and the result reports how many of the requested views were created
+  #[event(input = "ImportPB", output = "ImportResultPB")]
   ImportData = 30,
 
-  #[event(input = "WorkspaceIdPB", output = "RepeatedFolderSnapshotPB")]
+  /// Return the snapshots of the workspace in the given range
+  #[event(input = "FolderSnapshotQueryPB", output = "RepeatedFolderSnapshotPB")]
   GetFolderSnapshots = 31,
   /// Moves a nested view to a new location in the hierarchy.
   ///
@@ -150,4 +255,399 @@ pub enum FolderEvent {
 
   #[event(input = "UpdateViewIconPayloadPB")]
   UpdateViewIcon = 35,
+
+  /// Create multiple views at once. The views are returned in the same order as the payload.
+  /// If any of the views fails to be created, none of them will be created.
+  #[event(input = "RepeatedCreateViewPayloadPB", output = "RepeatedViewPB")]
+  CreateViews = 36,
+
+  /// Move the view and all of its child views to another workspace
+  #[event(input = "MoveViewToWorkspacePayloadPB")]
+  MoveViewToWorkspace = 37,
+
+  /// Return the view with its descendants nested up to the given depth.
+  /// The trashed views are excluded.
+  #[event(input = "ViewTreePayloadPB", output = "ViewTreePB")]
+  ReadViewTree = 38,
+
+  /// Search the views whose name matches the query. The views are sorted by the match score and
+  /// then by the creation time.
+  #[event(input = "SearchViewsPayloadPB", output = "RepeatedViewPB")]
+  SearchViews = 39,
+
+  /// Return a page of the trash items of the workspace, the newest deleted first, along with the
+  /// total count.
+  #[event(input = "TrashPagePayloadPB", output = "TrashPagePB")]
+  ReadTrashPaged = 40,
+
+  /// Delete the views and their descendants permanently without moving them to the trash
+  #[event(input = "RepeatedViewIdPB")]
+  DeleteViewPermanently = 41,
+
+  /// Put back the trash and attach the view to the given parent instead of its original parent
+  #[event(input = "RestoreTrashPayloadPB")]
+  RestoreTrashToParent = 42,
+
+  /// Set the number of days that the trash items are kept before being deleted permanently.
+  /// Zero disables the automatic deletion.
+  #[event(input = "TrashRetentionPayloadPB")]
+  SetTrashRetention = 43,
+
+  /// Move the favorite view to another position of the favorites list
+  #[event(input = "ReorderFavoritePayloadPB")]
+  ReorderFavorite = 44,
+
+  /// Export the view, and optionally its descendants, in the given format
+  #[event(input = "ExportViewPayloadPB", output = "ExportDataPB")]
+  ExportView = 45,
+
+  /// Duplicate the view, and optionally all of its descendants
+  #[event(input = "DuplicateViewPayloadPB", output = "ViewPB")]
+  DuplicateViewRecursive = 46,
+
+  /// Return the child views sorted by the created time or the last edited time
+  #[event(input = "ReadViewsSortedPayloadPB", output = "RepeatedViewPB")]
+  ReadViewsSorted = 47,
+
+  /// Update the name or the icon of the workspace
+  #[event(input = "UpdateWorkspacePayloadPB", output = "WorkspacePB")]
+  UpdateWorkspace = 48,
+
+  /// Return all the workspaces of the current user without their views
+  #[event(output = "RepeatedWorkspacePB")]
+  ReadAllWorkspaces = 49,
+
+  /// Replace the current folder with the snapshot. A snapshot of the current folder is taken
+  /// before restoring
+  #[event(input = "RestoreFolderSnapshotPayloadPB")]
+  RestoreFolderSnapshot = 50,
+
+  /// Take a snapshot of the current folder with the given name
+  #[event(input = "CreateSnapshotPayloadPB", output = "FolderSnapshotPB")]
+  CreateFolderSnapshot = 51,
+
+  /// Return the views that are added, removed, renamed or moved between two snapshots
+  #[event(input = "DiffSnapshotsPayloadPB", output = "FolderDiffPB")]
+  DiffFolderSnapshots = 52,
+
+  /// Set the favorite state of the views, and return the views with their final state
+  #[event(input = "SetFavoritePayloadPB", output = "RepeatedViewPB")]
+  SetFavorites = 53,
+
+  /// Lock or unlock the view. The locked view can't be updated, moved or deleted
+  #[event(input = "SetViewLockedPayloadPB")]
+  SetViewLocked = 54,
+
+  /// Pin or unpin the views. The pinned views come before their unpinned siblings
+  #[event(input = "RepeatedViewIdPB")]
+  ToggleViewPin = 55,
+
+  /// Return the ancestors of the view, from the top level view down to the parent of the view
+  #[event(input = "ViewIdPB", output = "RepeatedViewPB")]
+  GetViewAncestors = 56,
+
+  /// Move the views under the new parent at once
+  #[event(input = "MoveViewsPayloadPB")]
+  MoveViews = 57,
+
+  /// Update the cover of the view. The cover is removed if it's empty
+  #[event(input = "UpdateViewCoverPayloadPB")]
+  UpdateViewCover = 58,
+
+  /// Return the recently opened views, the most recent first
+  #[event(output = "RepeatedViewPB")]
+  GetRecentViews = 59,
+
+  /// Clear the recently opened views of the current workspace
+  #[event()]
+  ClearRecentViews = 60,
+
+  /// Export the workspace to an archive that can be imported without losing its structure
+  #[event(input = "ExportWorkspacePayloadPB", output = "ExportWorkspaceDataPB")]
+  ExportWorkspace = 61,
+
+  /// Import the archive exported by `ExportWorkspace`. Return the imported top level views
+  #[event(input = "ImportWorkspacePayloadPB", output = "RepeatedViewPB")]
+  ImportWorkspace = 62,
+
+  /// Return the counts of the views in the workspace
+  #[event(input = "WorkspaceIdPB", output = "WorkspaceStatsPB")]
+  GetWorkspaceStats = 63,
+
+  /// Return the views of the workspace that match any of the given layouts
+  #[event(input = "ViewsByLayoutPayloadPB", output = "RepeatedViewPB")]
+  GetViewsByLayout = 64,
+
+  /// Undo the last create, delete, restore, move or rename of the views. Return the undone
+  /// operation
+  #[event(output = "FolderOperationPB")]
+  UndoFolderOperation = 65,
+
+  /// Redo the last undone operation. Return the redone operation
+  #[event(output = "FolderOperationPB")]
+  RedoFolderOperation = 66,
+
+  /// Add the tags to the view. The tags are matched case-insensitively
+  #[event(input = "ViewTagsPayloadPB")]
+  AddViewTags = 67,
+
+  /// Remove the tags from the view
+  #[event(input = "ViewTagsPayloadPB")]
+  RemoveViewTags = 68,
+
+  /// Return the views that carry the tag
+  #[event(input = "ViewTagPB", output = "RepeatedViewPB")]
+  GetViewsByTag = 69,
+
+  /// Return the views whose parent is missing or in the trash
+  #[event(output = "RepeatedViewPB")]
+  GetOrphanedViews = 70,
+
+  /// Reattach the orphaned views to the workspace root. Return the orphaned views
+  #[event(input = "RepairOrphanedViewsPayloadPB", output = "RepeatedViewPB")]
+  RepairOrphanedViews = 71,
+
+  /// Set the layout used when a view is created without a layout in the workspace
+  #[event(input = "DefaultLayoutPayloadPB")]
+  SetDefaultViewLayout = 72,
+
+  /// Update the UI settings of the workspace, for example, whether the favorites are shown
+  #[event(input = "WorkspaceUISettingsPayloadPB")]
+  UpdateWorkspaceUISettings = 73,
+
+  /// Copy the view to the root of another workspace with new ids. Return the copied view
+  #[event(input = "CopyViewToWorkspacePayloadPB", output = "ViewPB")]
+  CopyViewToWorkspace = 74,
+
+  /// Return the views of the current workspace that were edited by the user last time
+  #[event(input = "ViewsEditedByPayloadPB", output = "RepeatedViewPB")]
+  GetViewsEditedBy = 75,
+
+  /// Check whether the view exists and whether it's in the trash
+  #[event(input = "ViewIdPB", output = "ViewExistsPB")]
+  ViewExists = 76,
+
+  /// Restore the listed trash items. Return the ids that were restored and the ids that failed
+  #[event(input = "RepeatedTrashIdPB", output = "RestoreTrashBatchPB")]
+  RestoreTrashBatch = 77,
+
+  /// Cancel the running import. The views imported so far are deleted
+  #[event(input = "ImportIdPB")]
+  CancelImport = 78,
+
+  /// Duplicate the workspace into a new workspace. Return the new workspace
+  #[event(input = "DuplicateWorkspacePayloadPB", output = "WorkspacePB")]
+  DuplicateWorkspace = 79,
+
+  /// Sort the child views of the parent by their names and keep the new order
+  #[event(input = "SortChildViewsPayloadPB")]
+  SortChildViews = 80,
+
+  /// Return the path of the view from the workspace down to the view
+  #[event(input = "ViewIdPB", output = "ViewPathPB")]
+  GetViewPath = 81,
+
+  /// Set the maximum depth of the views in the workspace. Zero means unlimited
+  #[event(input = "MaxNestingDepthPayloadPB")]
+  SetMaxNestingDepth = 82,
+
+  /// Return the direct child views of the view. Each child view carries the number of its own
+  /// child views instead of the child views themselves
+  #[event(input = "ViewIdPB", output = "RepeatedViewPB")]
+  GetChildViews = 83,
+
+  /// Set the favorite state of the view and all of its descendants. Return the ids of the views
+  /// whose state was changed
+  #[event(input = "FavoriteSubtreePayloadPB", output = "RepeatedViewIdPB")]
+  FavoriteSubtree = 84,
+
+  /// Move the view to the given index among its siblings. The current index of the view is not
+  /// required
+  #[event(input = "ReorderViewPayloadPB")]
+  ReorderView = 85,
+
+  /// Return whether the current folder has unsynced changes and when it was synced last time
+  #[event(output = "FolderSyncStatusPB")]
+  GetFolderSyncStatus = 86,
+
+  /// Create a new view by copying the structure and the content of the template view. Return the
+  /// new root view
+  #[event(input = "CreateFromTemplatePayloadPB", output = "ViewPB")]
+  CreateViewFromTemplate = 87,
+
+  /// Mark the view as a template or not. The template view is excluded from the child views of
+  /// its parent
+  #[event(input = "SetTemplatePayloadPB")]
+  SetViewTemplate = 88,
+
+  /// Return the template views of the current workspace
+  #[event(output = "RepeatedViewPB")]
+  GetTemplates = 89,
+
+  /// Rename the view. The empty name is rejected
+  #[event(input = "RenameViewPayloadPB")]
+  RenameView = 90,
+
+  /// Return the views of the workspace that were last edited within the time range, the most
+  /// recent first
+  #[event(input = "ViewsTimeRangePayloadPB", output = "RepeatedViewPB")]
+  GetViewsInRange = 91,
+
+  /// Return the number of the trash items of the workspace without reading the items
+  #[event(input = "WorkspaceIdPB", output = "TrashCountPB")]
+  GetTrashCount = 92,
+
+  /// Set whether the child views of the views are shown in the sidebar
+  #[event(input = "RepeatedSetExpandedPB")]
+  SetViewExpanded = 93,
+
+  /// Create the view at the given index among the displayed child views of its parent
+  #[event(input = "CreateViewAtPayloadPB", output = "ViewPB")]
+  CreateViewAt = 94,
+
+  /// Return the views that importing the `ImportPB` would create, without importing it
+  #[event(input = "ImportPB", output = "ImportPreviewPB")]
+  ImportPreview = 95,
+
+  /// Return the views with the color label. `None` returns the views without a color
+  #[event(input = "ViewColorFilterPB", output = "RepeatedViewPB")]
+  GetViewsByColor = 96,
+
+  /// Subscribe the changes of the view and its direct child views. The changes are notified with
+  /// `DidUpdateView` until the view is unsubscribed or deleted
+  #[event(input = "ViewIdPB")]
+  SubscribeView = 97,
+
+  /// Drop the subscription added by `SubscribeView`
+  #[event(input = "ViewIdPB")]
+  UnsubscribeView = 98,
+
+  /// Return the view to open when the workspace is loaded. It's the home view if it's set, then
+  /// the latest opened view if it's still valid, otherwise the first top level view
+  #[event(input = "WorkspaceIdPB", output = "DefaultOpenViewPB")]
+  GetDefaultOpenView = 99,
+
+  /// Move the view to the trash with the reason why it's deleted. The reason is shown in the
+  /// trash and kept after the view is restored
+  #[event(input = "TrashViewPayloadPB", output = "RepeatedViewIdPB")]
+  TrashViewWithReason = 100,
+
+  /// Move the child views of the source view under the target view, and then move the source
+  /// view to the trash
+  #[event(input = "MergeViewsPayloadPB", output = "ViewPB")]
+  MergeViews = 101,
+
+  /// Set the metadata of the view. The empty value deletes the key
+  #[event(input = "ViewMetaPayloadPB")]
+  SetViewMeta = 102,
+
+  /// Return the metadata value of the view. It's empty if the key isn't set
+  #[event(input = "ViewMetaPayloadPB", output = "ViewMetaValuePB")]
+  GetViewMeta = 103,
+
+  /// Enable or disable keeping the names of the sibling views unique. The created or renamed view
+  /// gets a numbered suffix if its name is taken
+  #[event(input = "UniqueSiblingNamesPayloadPB")]
+  SetUniqueSiblingNames = 104,
+
+  /// Export the favorites as a list of links to their paths
+  #[event(input = "ExportFavoritesPayloadPB", output = "ExportDataPB")]
+  ExportFavorites = 105,
+
+  /// Move all the descendants of the view to the trash and keep the view itself
+  #[event(input = "ViewIdPB", output = "EmptyViewChildrenResultPB")]
+  EmptyViewChildren = 106,
+
+  /// Return the recently searched queries of the current workspace, the most recent first
+  #[event(output = "SearchHistoryPB")]
+  GetSearchHistory = 107,
+
+  /// Clear the search history of the current workspace
+  #[event()]
+  ClearSearchHistory = 108,
+
+  /// Update the icons of multiple views at once. The items that fail the validation are returned
+  /// instead of aborting the batch
+  #[event(
+    input = "RepeatedUpdateViewIconPayloadPB",
+    output = "UpdateViewIconsResultPB"
+  )]
+  UpdateViewIconsBatch = 109,
+
+  /// Return the view and all of its descendants as a flat list in depth-first order
+  #[event(input = "FlattenSubtreePayloadPB", output = "RepeatedViewPB")]
+  FlattenSubtree = 110,
+
+  /// Archive or unarchive the views. The archived view is excluded from the child views of its
+  /// parent but still searchable
+  #[event(input = "RepeatedSetArchivedPB")]
+  SetViewArchived = 111,
+
+  /// Return the archived views of the current workspace
+  #[event(output = "RepeatedViewPB")]
+  GetArchivedViews = 112,
+
+  /// Return the recently opened views sorted by the last opened or the last edited time
+  #[event(input = "RecentViewsSortedPayloadPB", output = "RepeatedViewPB")]
+  GetRecentViewsSorted = 113,
+
+  /// Change the layout of the view. Only the conversions between the database layouts are
+  /// supported
+  #[event(input = "ChangeLayoutPayloadPB")]
+  ChangeViewLayout = 114,
+
+  /// Return the views sharing the same parent with the view, and the index of the view among them
+  #[event(input = "ViewIdPB", output = "ViewSiblingsPB")]
+  GetViewSiblings = 115,
+
+  /// Find the child view of the parent by its name
+  #[event(input = "FindChildPayloadPB", output = "FindChildResultPB")]
+  FindChildByName = 116,
+
+  /// Set the maximum number of the views in the workspace. Zero means unlimited
+  #[event(input = "ViewLimitPayloadPB")]
+  SetViewLimit = 117,
+
+  /// Replace the views referenced by the content of the view. It's called by the plugin that owns
+  /// the content when the content changes
+  #[event(input = "ViewReferencesPayloadPB")]
+  SetViewReferences = 118,
+
+  /// Return the views whose content references the view, for example, to warn about the links
+  /// before deleting it
+  #[event(input = "ViewIdPB", output = "RepeatedViewPB")]
+  GetViewBacklinks = 119,
+
+  /// Update the trash retention, the default view layout and the view limit of the workspace at
+  /// once. The absent settings are left unchanged
+  #[event(input = "WorkspaceSettingsPayloadPB", output = "WorkspaceSettingsPB")]
+  UpdateWorkspaceSettings = 120,
+
+  /// Return the number of the direct child views and all the nested views of the view. The
+  /// trashed views are not counted
+  #[event(input = "ViewIdPB", output = "SubtreeCountPB")]
+  GetSubtreeCount = 121,
+
+  /// Set the view opened when the workspace is loaded. The empty view id clears the home view
+  #[event(input = "SetHomePayloadPB")]
+  SetWorkspaceHome = 122,
+
+  /// List the views of the workspace page by page in the depth-first order. The trashed views are
+  /// excluded
+  #[event(input = "ListViewsCursorPayloadPB", output = "ViewsPagePB")]
+  ListViewsCursor = 123,
+
+  /// Report the orphan views, the cycles, the duplicate views, the dangling favorites and the
+  /// dangling current view of the current workspace without changing it
+  #[event(input = "WorkspaceIdPB", output = "FolderIntegrityReportPB")]
+  CheckFolderIntegrity = 124,
+
+  /// Fix the problems reported by `CheckFolderIntegrity` and return the problems that are left
+  #[event(input = "WorkspaceIdPB", output = "FolderIntegrityReportPB")]
+  RepairFolder = 125,
+
+  /// Return the current view of the workspace with its ancestors and siblings. The default view
+  /// is returned if the current view can't be opened
+  #[event(input = "WorkspaceIdPB", output = "ViewContextPB")]
+  GetCurrentViewContext = 126,
 }
@@ -0,0 +1,456 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Weak};
+
+use collab::core::collab::MutexCollab;
+use collab::preclude::{Collab, MapRefExtension, MapRefWrapper, ReadTxn, TransactionMut};
+use collab_folder::{ViewIcon, ViewLayout};
+use parking_lot::RwLock;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use flowy_error::{FlowyError, FlowyResult};
+use flowy_sqlite::kv::StorePreferences;
+
+/// The key of the [FolderExtra] that was stored in the [StorePreferences] before it's stored in
+/// the folder collab. It's only read to move the data into the folder collab.
+const LEGACY_FOLDER_EXTRA_KEY: &str = "af_folder_extra";
+
+const FOLDER: &str = "folder";
+const VIEWS: &str = "views";
+/// The map under the root map of the folder that holds the extra data of the workspace.
+const FOLDER_EXTRA: &str = "extra";
+const SETTING: &str = "setting";
+const FAVORITE_ORDER: &str = "favorite_order";
+const RECENT_VIEW_IDS: &str = "recent_view_ids";
+const WORKSPACE: &str = "workspace";
+/// The key in the map of each view that holds the [ViewExtra] of the view.
+const VIEW_EXTRA: &str = "extra";
+
+/// [FolderExtra] holds the data of the workspace that the folder collab doesn't have a field
+/// for, for example, the workspace settings. It's stored in the folder collab next to the data it
+/// belongs to, so it's synced with other devices and included in the snapshots of the folder:
+/// the data of the workspace is stored in the `extra` map of the folder, and the [ViewExtra] of
+/// each view is stored in the map of the view, so it's removed when the view is deleted.
+///
+/// Each part is stored as a JSON string, so the concurrent changes of the same part are resolved
+/// by the last writer.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct FolderExtra {
+  #[serde(default)]
+  pub setting: FolderExtraSetting,
+
+  /// The ids of the favorite views in the order set by the user. The favorites that are not in
+  /// the list come after the ordered ones in the order they were added.
+  #[serde(default)]
+  pub favorite_order: Vec<String>,
+
+  /// The extra data of each view, keyed by the view id. The extra data of the views that don't
+  /// exist in the folder is not stored.
+  #[serde(default)]
+  pub views: HashMap<String, ViewExtra>,
+
+  /// The ids of the recently opened views, the most recent first. Each view appears only once.
+  #[serde(default)]
+  pub recent_view_ids: Vec<String>,
+
+  /// The name and the icon of the workspace set by the user.
+  #[serde(default)]
+  pub workspace: WorkspaceExtra,
+}
+
+impl FolderExtra {
+  /// Reads the [FolderExtra] from the folder collab.
+  pub fn from_collab(collab: &MutexCollab) -> Self {
+    let collab = collab.lock();
+    let txn = collab.transact();
+    Self::from_collab_with_txn(&collab, &txn)
+  }
+
+  fn from_collab_with_txn<T: ReadTxn>(collab: &Collab, txn: &T) -> Self {
+    let mut extra = FolderExtra::default();
+    if let Some(map) = collab.get_map_with_txn(txn, vec![FOLDER, FOLDER_EXTRA]) {
+      extra.setting = get_json_with_txn(txn, &map, SETTING).unwrap_or_default();
+      extra.favorite_order = get_json_with_txn(txn, &map, FAVORITE_ORDER).unwrap_or_default();
+      extra.recent_view_ids = get_json_with_txn(txn, &map, RECENT_VIEW_IDS).unwrap_or_default();
+      extra.workspace = get_json_with_txn(txn, &map, WORKSPACE).unwrap_or_default();
+    }
+    if let Some(views) = collab.get_map_with_txn(txn, vec![FOLDER, VIEWS]) {
+      for view_id in views.keys(txn) {
+        if let Some(view_extra) = views
+          .get_map_with_txn(txn, view_id)
+          .and_then(|view| get_json_with_txn::<ViewExtra, _>(txn, &view, VIEW_EXTRA))
+        {
+          extra.views.insert(view_id.to_string(), view_extra);
+        }
+      }
+    }
+    extra
+  }
+
+  /// Updates the [FolderExtra] in the folder collab with the given function. The collab is
+  /// locked from reading to writing, and only the changed parts are written.
+  pub fn update_collab<F, R>(collab: &MutexCollab, f: F) -> R
+  where
+    F: FnOnce(&mut FolderExtra) -> R,
+  {
+    let collab = collab.lock();
+    let old_extra = {
+      let txn = collab.transact();
+      Self::from_collab_with_txn(&collab, &txn)
+    };
+    let mut extra = old_extra.clone();
+    let output = f(&mut extra);
+    collab.with_origin_transact_mut(|txn| extra.write_with_txn(&old_extra, &collab, txn));
+    output
+  }
+
+  fn write_with_txn(&self, old_extra: &FolderExtra, collab: &Collab, txn: &mut TransactionMut) {
+    let folder = match collab.get_map_with_txn(txn, vec![FOLDER]) {
+      Some(folder) => folder,
+      None => {
+        tracing::warn!("Can't write the folder extra, the folder is not created");
+        return;
+      },
+    };
+    let map = folder
+      .get_map_with_txn(txn, FOLDER_EXTRA)
+      .unwrap_or_else(|| folder.create_map_with_txn(txn, FOLDER_EXTRA));
+    if self.setting != old_extra.setting {
+      insert_json_with_txn(txn, &map, SETTING, &self.setting);
+    }
+    if self.favorite_order != old_extra.favorite_order {
+      insert_json_with_txn(txn, &map, FAVORITE_ORDER, &self.favorite_order);
+    }
+    if self.recent_view_ids != old_extra.recent_view_ids {
+      insert_json_with_txn(txn, &map, RECENT_VIEW_IDS, &self.recent_view_ids);
+    }
+    if self.workspace != old_extra.workspace {
+      insert_json_with_txn(txn, &map, WORKSPACE, &self.workspace);
+    }
+
+    let views = match folder.get_map_with_txn(txn, VIEWS) {
+      Some(views) => views,
+      None => return,
+    };
+    for (view_id, view_extra) in self.views.iter() {
+      if old_extra.views.get(view_id) == Some(view_extra) {
+        continue;
+      }
+      // The extra data of the views that are deleted is dropped.
+      if let Some(view) = views.get_map_with_txn(txn, view_id) {
+        insert_json_with_txn(txn, &view, VIEW_EXTRA, view_extra);
+      }
+    }
+    for view_id in old_extra.views.keys() {
+      if !self.views.contains_key(view_id) {
+        if let Some(view) = views.get_map_with_txn(txn, view_id) {
+          view.delete_with_txn(txn, VIEW_EXTRA);
+        }
+      }
+    }
+  }
+
+  /// Moves the data read from the [StorePreferences] into the extra data. The data that is
+  /// already in the folder collab, for example, moved by another device, is kept.
+  fn merge_legacy(&mut self, legacy: LegacyFolderExtra) {
+    let LegacyFolderExtra {
+      extra: legacy,
+      search_history,
+    } = legacy;
+    if self.setting == FolderExtraSetting::default() {
+      self.setting = legacy.setting;
+    }
+    if self.setting.search_history.is_empty() {
+      self.setting.search_history = search_history;
+    }
+    if self.favorite_order.is_empty() {
+      self.favorite_order = legacy.favorite_order;
+    }
+    if self.recent_view_ids.is_empty() {
+      self.recent_view_ids = legacy.recent_view_ids;
+    }
+    for (view_id, view_extra) in legacy.views {
+      self.views.entry(view_id).or_insert(view_extra);
+    }
+  }
+}
+
+/// The [FolderExtra] stored in the [StorePreferences] by the previous versions, which kept the
+/// search history next to the settings.
+#[derive(Deserialize)]
+struct LegacyFolderExtra {
+  #[serde(flatten)]
+  extra: FolderExtra,
+  #[serde(default)]
+  search_history: Vec<String>,
+}
+
+fn get_json_with_txn<V: DeserializeOwned, T: ReadTxn>(
+  txn: &T,
+  map: &MapRefWrapper,
+  key: &str,
+) -> Option<V> {
+  let value = map.get_str_with_txn(txn, key)?;
+  match serde_json::from_str(&value) {
+    Ok(value) => Some(value),
+    Err(err) => {
+      tracing::error!("Failed to deserialize the folder extra:{}, {:?}", key, err);
+      None
+    },
+  }
+}
+
+fn insert_json_with_txn<V: Serialize>(
+  txn: &mut TransactionMut,
+  map: &MapRefWrapper,
+  key: &str,
+  value: &V,
+) {
+  match serde_json::to_string(value) {
+    Ok(value) => map.insert_str_with_txn(txn, key, value),
+    Err(err) => tracing::error!("Failed to serialize the folder extra:{}, {:?}", key, err),
+  }
+}
+
+/// The workspace isn't a view of the folder, and the folder collab only keeps the name the
+/// workspace was created with. The name and the icon set by the user are stored here instead.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WorkspaceExtra {
+  /// The name that takes the place of the name the workspace was created with. `None` if the
+  /// workspace has never been renamed.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub name: Option<String>,
+
+  /// The icon of the workspace. `None` if the workspace has no icon.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub icon: Option<ViewIcon>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ViewExtra {
+  /// The timestamp in seconds of the last time the view was edited. Zero means the view has never
+  /// been edited since it was created.
+  #[serde(default)]
+  pub last_edited_time: i64,
+
+  /// The locked view can't be updated, moved or deleted until it's unlocked.
+  #[serde(default)]
+  pub locked: bool,
+
+  /// The pinned view comes before its unpinned siblings.
+  #[serde(default)]
+  pub pinned: bool,
+
+  /// The cover shown above the content of the view. `None` if the view has no cover.
+  #[serde(default)]
+  pub cover: Option<ViewCover>,
+
+  /// The tags of the view. The tags keep the case they were added with, and each tag appears
+  /// only once regardless of its case.
+  #[serde(default)]
+  pub tags: Vec<String>,
+
+  /// The id of the user who created the view. Zero means the creator is unknown, for example,
+  /// the view was created before the attribution was recorded.
+  #[serde(default)]
+  pub created_by: i64,
+
+  /// The id of the user who edited the view last time. Zero means the view has never been edited
+  /// since it was created.
+  #[serde(default)]
+  pub last_edited_by: i64,
+
+  /// The timestamp in seconds of the last time the view was opened or closed. Zero if the view
+  /// has never been opened. Opening the view doesn't change its `last_edited_time`.
+  #[serde(default)]
+  pub last_opened_time: i64,
+
+  /// The template view is listed in the templates instead of the view tree.
+  #[serde(default)]
+  pub is_template: bool,
+
+  /// Whether the child views of the view are shown in the sidebar.
+  #[serde(default)]
+  pub expanded: bool,
+
+  /// The archived view is hidden from the view tree but still searchable. Unlike the trash, it's
+  /// never purged automatically.
+  #[serde(default)]
+  pub archived: bool,
+
+  /// The color label of the view. It's not stored if the view has no color.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub color: Option<ViewColor>,
+
+  /// The reason given the last time the view was moved to the trash. It's kept after the view is
+  /// restored.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub trash_reason: Option<String>,
+
+  /// The ids of the views referenced by the content of the view, reported by the plugin that owns
+  /// the content. The backlinks of a view are looked up by scanning this field of all the views.
+  #[serde(default, skip_serializing_if = "Vec::is_empty")]
+  pub referenced_view_ids: Vec<String>,
+
+  /// The metadata attached to the view by the plugins, see `ViewMetaPayloadPB`.
+  #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+  pub meta: HashMap<String, String>,
+}
+
+impl ViewExtra {
+  /// Returns the id of the user who edited the view last time, or the creator if the view has
+  /// never been edited.
+  pub fn last_editor(&self) -> i64 {
+    if self.last_edited_by != 0 {
+      self.last_edited_by
+    } else {
+      self.created_by
+    }
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ViewCover {
+  pub ty: ViewCoverType,
+  pub value: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ViewCoverType {
+  Color,
+  Gradient,
+  Image,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ViewColor {
+  Red,
+  Orange,
+  Yellow,
+  Green,
+  Blue,
+  Purple,
+  Pink,
+  Gray,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FolderExtraSetting {
+  /// The trash items older than the given number of days will be deleted permanently. Zero
+  /// means the trash items will never be deleted automatically.
+  #[serde(default)]
+  pub trash_retention_days: u32,
+
+  /// The layout of the views created without specifying the layout. `None` means the document
+  /// layout.
+  #[serde(default)]
+  pub default_view_layout: Option<ViewLayout>,
+
+  /// Whether the favorites panel is hidden. It's stored as hidden, so that the panel is shown by
+  /// default.
+  #[serde(default)]
+  pub hide_favorites: bool,
+
+  /// The maximum depth of the views. The top level views are at depth one. Zero means the depth
+  /// is unlimited.
+  #[serde(default)]
+  pub max_nesting_depth: u32,
+
+  /// Whether the names of the sibling views are kept unique. The name of the created or renamed
+  /// view gets a " (2)", " (3)", ... suffix if its siblings already have the name.
+  #[serde(default)]
+  pub unique_sibling_names: bool,
+
+  /// The maximum number of the views in the workspace, including the trashed ones. Zero means
+  /// the number is unlimited.
+  #[serde(default)]
+  pub view_limit: u32,
+
+  /// The view opened when the workspace is loaded, in preference to the latest opened view.
+  /// `None` if the workspace has no home view.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub home_view_id: Option<String>,
+
+  /// The recently searched queries, the most recent first. Each query appears only once.
+  #[serde(default, skip_serializing_if = "Vec::is_empty")]
+  pub search_history: Vec<String>,
+}
+
+/// [FolderExtraStore] reads and writes the [FolderExtra] of the current workspace through the
+/// folder collab of the workspace, see [FolderExtraStore::open].
+pub struct FolderExtraStore {
+  store_preferences: Weak<StorePreferences>,
+  folder: RwLock<Option<OpenedFolder>>,
+}
+
+struct OpenedFolder {
+  workspace_id: String,
+  collab: Weak<MutexCollab>,
+}
+
+impl FolderExtraStore {
+  pub fn new(store_preferences: Weak<StorePreferences>) -> Self {
+    Self {
+      store_preferences,
+      folder: Default::default(),
+    }
+  }
+
+  /// Binds the store to the folder collab of the workspace that is opened as the current folder.
+  /// The [FolderExtra] stored in the [StorePreferences] by the previous versions is moved into
+  /// the folder collab.
+  pub fn open(&self, uid: i64, workspace_id: &str, collab: &Arc<MutexCollab>) {
+    if let Some(store_preferences) = self.store_preferences.upgrade() {
+      let key = legacy_key_for_folder_extra(uid, workspace_id);
+      if let Some(legacy) = store_preferences.get_object::<LegacyFolderExtra>(&key) {
+        tracing::info!(
+          "Move the folder extra of workspace:{} to the folder",
+          workspace_id
+        );
+        FolderExtra::update_collab(collab, |extra| extra.merge_legacy(legacy));
+        store_preferences.remove(&key);
+      }
+    }
+    *self.folder.write() = Some(OpenedFolder {
+      workspace_id: workspace_id.to_string(),
+      collab: Arc::downgrade(collab),
+    });
+  }
+
+  /// Returns the [FolderExtra] of the workspace. The default value will be returned if the
+  /// workspace is not the opened one or doesn't have any extra data.
+  pub fn get(&self, workspace_id: &str) -> FolderExtra {
+    match self.get_collab(workspace_id) {
+      Some(collab) => FolderExtra::from_collab(&collab),
+      None => FolderExtra::default(),
+    }
+  }
+
+  /// Updates the [FolderExtra] of the workspace with the given function and saves it. Only the
+  /// opened workspace can be updated.
+  pub fn update<F, R>(&self, workspace_id: &str, f: F) -> FlowyResult<R>
+  where
+    F: FnOnce(&mut FolderExtra) -> R,
+  {
+    let collab = self.get_collab(workspace_id).ok_or_else(|| {
+      FlowyError::internal().with_context(format!(
+        "The folder of workspace:{} is not opened",
+        workspace_id
+      ))
+    })?;
+    Ok(FolderExtra::update_collab(&collab, f))
+  }
+
+  fn get_collab(&self, workspace_id: &str) -> Option<Arc<MutexCollab>> {
+    self
+      .folder
+      .read()
+      .as_ref()
+      .filter(|folder| folder.workspace_id == workspace_id)
+      .and_then(|folder| folder.collab.upgrade())
+  }
+}
+
+fn legacy_key_for_folder_extra(uid: i64, workspace_id: &str) -> String {
+  format!("{}:{}:{}", LEGACY_FOLDER_EXTRA_KEY, uid, workspace_id)
+}
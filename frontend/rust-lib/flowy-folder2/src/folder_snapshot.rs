@@ -0,0 +1,105 @@
+use std::sync::Weak;
+
+use serde::{Deserialize, Serialize};
+
+use flowy_error::{FlowyError, FlowyResult};
+use flowy_sqlite::kv::StorePreferences;
+
+use crate::folder_extra::FolderExtraSetting;
+
+const FOLDER_SNAPSHOT_KEY: &str = "af_folder_snapshots";
+
+/// The maximum number of local snapshots kept for each workspace. The oldest snapshot will be
+/// removed when a new snapshot exceeds the limit.
+const MAX_LOCAL_SNAPSHOTS: usize = 20;
+
+/// [LocalFolderSnapshot] is a snapshot of the folder that is taken on the device, for example, the
+/// snapshot taken before restoring another snapshot. Unlike the snapshots that are created by the
+/// cloud service, it's only stored in the [StorePreferences] of the device.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LocalFolderSnapshot {
+  pub snapshot_id: i64,
+  pub name: String,
+  /// The timestamp in seconds.
+  pub created_at: i64,
+  /// The encoded update of the folder collab, including the [FolderExtra] of the workspace.
+  ///
+  /// [FolderExtra]: crate::folder_extra::FolderExtra
+  pub data: Vec<u8>,
+  /// The settings of the workspace for the snapshots that were taken before the settings were
+  /// stored in the folder collab. `None` for the other snapshots.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub setting: Option<FolderExtraSetting>,
+}
+
+pub struct FolderSnapshotStore {
+  store_preferences: Weak<StorePreferences>,
+}
+
+impl FolderSnapshotStore {
+  pub fn new(store_preferences: Weak<StorePreferences>) -> Self {
+    Self { store_preferences }
+  }
+
+  /// Returns the local snapshots of the workspace, the newest first.
+  pub fn get_snapshots(&self, uid: i64, workspace_id: &str) -> Vec<LocalFolderSnapshot> {
+    let key = cache_key_for_folder_snapshots(uid, workspace_id);
+    self
+      .store_preferences
+      .upgrade()
+      .and_then(|store_preferences| store_preferences.get_object::<Vec<LocalFolderSnapshot>>(&key))
+      .unwrap_or_default()
+  }
+
+  pub fn get_snapshot(
+    &self,
+    uid: i64,
+    workspace_id: &str,
+    snapshot_id: i64,
+  ) -> Option<LocalFolderSnapshot> {
+    self
+      .get_snapshots(uid, workspace_id)
+      .into_iter()
+      .find(|snapshot| snapshot.snapshot_id == snapshot_id)
+  }
+
+  /// Saves the snapshot with the given name and data. The id of the snapshot
+  /// is generated from the current time, and it's always greater than the ids of the existing
+  /// snapshots.
+  pub fn create_snapshot(
+    &self,
+    uid: i64,
+    workspace_id: &str,
+    name: String,
+    data: Vec<u8>,
+  ) -> FlowyResult<LocalFolderSnapshot> {
+    let store_preferences = self
+      .store_preferences
+      .upgrade()
+      .ok_or_else(|| FlowyError::internal().with_context("The store preferences is dropped"))?;
+    let mut snapshots = self.get_snapshots(uid, workspace_id);
+    let now = chrono::Utc::now();
+    let snapshot_id = snapshots
+      .first()
+      .map(|snapshot| snapshot.snapshot_id + 1)
+      .unwrap_or_default()
+      .max(now.timestamp_millis());
+    let snapshot = LocalFolderSnapshot {
+      snapshot_id,
+      name,
+      created_at: now.timestamp(),
+      data,
+      setting: None,
+    };
+    snapshots.insert(0, snapshot.clone());
+    snapshots.truncate(MAX_LOCAL_SNAPSHOTS);
+
+    let key = cache_key_for_folder_snapshots(uid, workspace_id);
+    store_preferences.set_object(&key, snapshots)?;
+    Ok(snapshot)
+  }
+}
+
+fn cache_key_for_folder_snapshots(uid: i64, workspace_id: &str) -> String {
+  format!("{}:{}:{}", FOLDER_SNAPSHOT_KEY, uid, workspace_id)
+}
@@ -3,9 +3,13 @@ pub use collab_folder::ViewLayout;
 pub mod entities;
 pub mod event_handler;
 pub mod event_map;
+mod folder_extra;
+mod folder_snapshot;
 pub mod manager;
 pub mod notification;
+mod operation_history;
 pub mod protobuf;
+mod search;
 mod user_default;
 pub mod view_operation;
 
@@ -1,44 +1,122 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fs;
+use std::io::Cursor;
 use std::ops::Deref;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Weak};
+use std::time::Duration;
 
 use collab::core::collab::{CollabRawData, MutexCollab};
 use collab::core::collab_state::SyncState;
+use collab::core::origin::CollabOrigin;
+use collab::preclude::Collab;
 use collab_entity::CollabType;
 use collab_folder::{
-  Folder, FolderData, FolderNotify, SectionItem, TrashChange, TrashChangeReceiver, TrashInfo,
-  UserId, View, ViewChange, ViewChangeReceiver, ViewLayout, ViewUpdate, Workspace,
+  Folder, FolderData, FolderNotify, RepeatedViewIdentifier, SectionItem, TrashChange,
+  TrashChangeReceiver, TrashInfo, UserId, View, ViewChange, ViewChangeReceiver, ViewIdentifier,
+  ViewLayout, ViewUpdate, Workspace,
 };
 use parking_lot::{Mutex, RwLock};
+use tokio::sync::broadcast::error::RecvError;
+use tokio::task::JoinHandle;
+use tokio::time::Instant;
 use tokio_stream::wrappers::WatchStream;
 use tokio_stream::StreamExt;
 use tracing::{event, info, instrument, Level};
 
 use collab_integrate::collab_builder::AppFlowyCollabBuilder;
 use collab_integrate::{CollabPersistenceConfig, RocksCollabDB, YrsDocAction};
-use flowy_error::{ErrorCode, FlowyError, FlowyResult};
+use flowy_error::{internal_error, ErrorCode, FlowyError, FlowyResult};
 use flowy_folder_deps::cloud::{gen_view_id, FolderCloudService};
+use flowy_sqlite::kv::StorePreferences;
 use lib_dispatch::prelude::af_spawn;
+use lib_infra::future::FutureResult;
+use lib_infra::util::timestamp;
 
-use crate::entities::icon::UpdateViewIconParams;
+use crate::entities::cover::UpdateViewCoverParams;
+use crate::entities::icon::{
+  normalize_view_icon, RepeatedUpdateViewIconPayloadPB, UpdateViewIconFailurePB,
+  UpdateViewIconParams, UpdateViewIconsResultPB,
+};
 use crate::entities::{
-  view_pb_with_child_views, view_pb_without_child_views, ChildViewUpdatePB, CreateViewParams,
-  CreateWorkspaceParams, DeletedViewPB, FolderSnapshotPB, FolderSnapshotStatePB, FolderSyncStatePB,
-  RepeatedTrashPB, RepeatedViewPB, UpdateViewParams, UserFolderPB, ViewPB, WorkspacePB,
-  WorkspaceSettingPB,
+  view_pb_with_child_views, view_pb_without_child_views, ChangeLayoutParams, ChildViewUpdatePB,
+  CreateFromTemplateParams, CreateViewParams, CreateWorkspaceParams, DeleteResultPB,
+  DeleteViewFailurePB, DeletedViewPB, DuplicateViewParams, ExportDataPB, ExportWorkspaceDataPB,
+  FindChildParams, FindChildResultPB, FlattenSubtreeParams, FolderDiffPB, FolderIntegrityReportPB,
+  FolderOperationPB, FolderSnapshotPB, FolderSnapshotStatePB, FolderSyncStatePB,
+  FolderSyncStatusPB, ImportPreviewItemPB, ImportPreviewPB, ImportResultPB, ImportSkippedItemPB,
+  ListViewsCursorParams, MergeViewsParams, MovedViewPB, RenameViewParams, RenamedViewPB,
+  RepeatedFolderSnapshotPB, RepeatedTrashPB, RepeatedViewIdPB, RepeatedViewPB, RestoreAllResultPB,
+  RestoreTrashBatchPB, SearchViewsParams, SetHomeParams, SubtreeCountPB, TrashPB, UpdateViewParams,
+  UpdateWorkspaceParams, UserFolderPB, ViewContextPB, ViewExistsPB, ViewLayoutPB, ViewMetaParams,
+  ViewPB, ViewPathPB, ViewReferencesParams, ViewSiblingsPB, ViewSortOrderPB, ViewSortTypePB,
+  ViewTagsParams, ViewTreePB, ViewsByLayoutParams, ViewsPagePB, ViewsTimeRangeParams, WorkspacePB,
+  WorkspaceSettingPB, WorkspaceSettingsPB, WorkspaceSettingsParams, WorkspaceStatsPB,
 };
+use crate::folder_extra::{FolderExtra, FolderExtraStore, ViewColor};
+use crate::folder_snapshot::{FolderSnapshotStore, LocalFolderSnapshot};
 use crate::notification::{
-  send_notification, send_workspace_setting_notification, FolderNotification,
+  send_coalesced_notification, send_notification, send_workspace_setting_notification,
+  FolderNotification, ImportProgressNotifier,
+};
+use crate::operation_history::{FolderOperation, FolderOperationHistory};
+use crate::search::{match_content_score, match_view_score};
+use crate::share::{
+  find_broken_links, markdown_link_item, plan_notion_pages, read_notion_pages, ExportFormat,
+  ExportParams, ImportParams, ImportType, NotionPageKind, NotionPagePlan, WorkspaceArchiveReader,
+  WorkspaceArchiveWriter, WorkspaceManifest, WORKSPACE_ARCHIVE_VERSION,
 };
-use crate::share::ImportParams;
 use crate::user_default::DefaultFolderBuilder;
 use crate::view_operation::{create_view, FolderOperationHandler, FolderOperationHandlers};
 
+/// The trash changes within this duration are merged into one notification.
+const TRASH_NOTIFICATION_DEBOUNCE: Duration = Duration::from_millis(200);
+
+/// The interval of deleting the expired trash items.
+const TRASH_PURGE_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// The maximum number of recent views kept for each workspace.
+const MAX_RECENT_VIEWS: usize = 20;
+
+/// The maximum number of the searched queries kept in the search history of each workspace.
+const MAX_SEARCH_HISTORY: usize = 20;
+
+/// The number of snapshots returned if the limit is not specified.
+const DEFAULT_FOLDER_SNAPSHOT_LIMIT: usize = 10;
+
+/// The maximum number of snapshots that can be fetched at a time.
+const MAX_FOLDER_SNAPSHOT_LIMIT: usize = 100;
+
+/// The maximum number of characters of the snapshot name. The longer name will be truncated.
+const MAX_FOLDER_SNAPSHOT_NAME_LEN: usize = 255;
+
+/// The maximum number of views that can be favorited or unfavorited as a subtree if the limit is
+/// not specified.
+const DEFAULT_FAVORITE_SUBTREE_LIMIT: usize = 200;
+
+/// The maximum number of the levels of the child views that can be preloaded when opening the
+/// workspace.
+const MAX_PRELOAD_DEPTH: u32 = 5;
+
+/// The number of views listed by [FolderManager::list_views_cursor] if the limit is not specified.
+const DEFAULT_VIEWS_PAGE_SIZE: usize = 100;
+
+/// The maximum number of views that can be listed by [FolderManager::list_views_cursor] at a time.
+const MAX_VIEWS_PAGE_SIZE: usize = 1000;
+
 /// [FolderUser] represents the user for folder.
 pub trait FolderUser: Send + Sync {
   fn user_id(&self) -> Result<i64, FlowyError>;
   fn token(&self) -> Result<Option<String>, FlowyError>;
   fn collab_db(&self, uid: i64) -> Result<Weak<RocksCollabDB>, FlowyError>;
+  /// Returns the ids of all the workspaces of the user.
+  fn workspace_ids(&self, uid: i64) -> Result<Vec<String>, FlowyError>;
+  /// Adds a new workspace to the workspaces of the user.
+  fn add_workspace(&self, workspace_id: &str, name: &str) -> Result<(), FlowyError>;
+  /// Removes the workspace from the workspaces of the user. If the workspace is the current
+  /// workspace, another workspace will be opened.
+  fn delete_workspace(&self, workspace_id: &str) -> FutureResult<(), FlowyError>;
 }
 
 pub struct FolderManager {
@@ -48,6 +126,21 @@ pub struct FolderManager {
   user: Arc<dyn FolderUser>,
   operation_handlers: FolderOperationHandlers,
   cloud_service: Arc<dyn FolderCloudService>,
+  folder_extra: Arc<FolderExtraStore>,
+  folder_snapshots: FolderSnapshotStore,
+  operation_history: FolderOperationHistory,
+  trash_purge_task: Mutex<Option<JoinHandle<()>>>,
+  /// The cancellation flags of the running imports, keyed by the import id.
+  running_imports: Mutex<HashMap<String, Arc<AtomicBool>>>,
+  /// The sync status of the current folder. It's updated by the sync state of the folder's collab
+  /// and reset when the folder is opened.
+  sync_status: Arc<RwLock<FolderSyncStatusPB>>,
+  /// The ids of the views subscribed by [Self::subscribe_view]. The ids of the trashed and the
+  /// deleted views are removed by the trash and the view change listeners.
+  view_subscriptions: Arc<RwLock<HashSet<String>>>,
+  /// The number of the coming updates of each view that the view change listener doesn't notify,
+  /// because the batch that makes them sends its own notifications, see [Self::update_view_icons].
+  muted_view_updates: Arc<Mutex<HashMap<String, usize>>>,
 }
 
 unsafe impl Send for FolderManager {}
@@ -59,14 +152,25 @@ impl FolderManager {
     collab_builder: Arc<AppFlowyCollabBuilder>,
     operation_handlers: FolderOperationHandlers,
     cloud_service: Arc<dyn FolderCloudService>,
+    store_preferences: Weak<StorePreferences>,
   ) -> FlowyResult<Self> {
     let mutex_folder = Arc::new(MutexFolder::default());
+    let folder_extra = Arc::new(FolderExtraStore::new(store_preferences.clone()));
+    let folder_snapshots = FolderSnapshotStore::new(store_preferences);
     let manager = Self {
       user,
       mutex_folder,
       collab_builder,
       operation_handlers,
       cloud_service,
+      folder_extra,
+      folder_snapshots,
+      operation_history: Default::default(),
+      trash_purge_task: Default::default(),
+      running_imports: Default::default(),
+      sync_status: Default::default(),
+      view_subscriptions: Default::default(),
+      muted_view_updates: Default::default(),
       workspace_id: Default::default(),
     };
 
@@ -75,28 +179,35 @@ impl FolderManager {
 
   #[instrument(level = "debug", skip(self), err)]
   pub async fn get_current_workspace(&self) -> FlowyResult<WorkspacePB> {
-    self.with_folder(
-      || {
-        let uid = self.user.user_id()?;
-        let workspace_id = self.workspace_id.read().as_ref().cloned().ok_or(
-          FlowyError::from(ErrorCode::WorkspaceIdInvalid)
-            .with_context("Unexpected empty workspace id"),
-        )?;
-        Err(workspace_data_not_sync_error(uid, &workspace_id))
-      },
-      |folder| {
-        let workspace_pb_from_workspace = |workspace: Workspace, folder: &Folder| {
-          let views = get_workspace_view_pbs(&workspace.id, folder);
-          let workspace: WorkspacePB = (workspace, views).into();
-          Ok::<WorkspacePB, FlowyError>(workspace)
-        };
+    self
+      .with_folder(
+        || {
+          let uid = self.user.user_id()?;
+          let workspace_id = self.workspace_id.read().as_ref().cloned().ok_or(
+            FlowyError::from(ErrorCode::WorkspaceIdInvalid)
+              .with_context("Unexpected empty workspace id"),
+          )?;
+          Err(workspace_data_not_sync_error(uid, &workspace_id))
+        },
+        |folder| {
+          let workspace_pb_from_workspace = |workspace: Workspace, folder: &Folder| {
+            let views = get_workspace_view_pbs(&workspace.id, folder);
+            let extra = self.folder_extra.get(&workspace.id);
+            let mut workspace: WorkspacePB = (workspace, views).into();
+            fill_workspace_extra(&extra, &mut workspace);
+            Ok::<WorkspacePB, FlowyError>(workspace)
+          };
 
-        match folder.get_current_workspace() {
-          None => Err(FlowyError::record_not_found().with_context("Can not find the workspace")),
-          Some(workspace) => workspace_pb_from_workspace(workspace, folder),
-        }
-      },
-    )
+          match folder.get_current_workspace() {
+            None => Err(FlowyError::record_not_found().with_context("Can not find the workspace")),
+            Some(workspace) => workspace_pb_from_workspace(workspace, folder),
+          }
+        },
+      )
+      .map(|mut workspace| {
+        self.remove_hidden_views(&mut workspace.views);
+        workspace
+      })
   }
 
   /// Return a list of views of the current workspace.
@@ -117,10 +228,33 @@ impl FolderManager {
   }
 
   pub async fn get_workspace_views(&self, workspace_id: &str) -> FlowyResult<Vec<ViewPB>> {
-    let views = self.with_folder(Vec::new, |folder| {
+    let mut views = self.with_folder(Vec::new, |folder| {
       get_workspace_view_pbs(workspace_id, folder)
     });
+    self.remove_hidden_views(&mut views);
+    self.fill_view_extras(&mut views);
+    Ok(views)
+  }
 
+  /// Returns the top level views of the workspace like [Self::get_workspace_views], and loads the
+  /// given number of the levels of their child views. The child views in the trash are excluded
+  /// below the first level. The depth is capped at [MAX_PRELOAD_DEPTH].
+  pub async fn get_workspace_views_with_depth(
+    &self,
+    workspace_id: &str,
+    depth: u32,
+  ) -> FlowyResult<Vec<ViewPB>> {
+    let depth = depth.min(MAX_PRELOAD_DEPTH);
+    let mut views = self.with_folder(Vec::new, |folder| {
+      let mut views = get_workspace_view_pbs(workspace_id, folder);
+      let trash_ids = get_trash_ids(folder);
+      for view in views.iter_mut() {
+        preload_child_views(folder, &mut view.child_views, depth, &trash_ids);
+      }
+      views
+    });
+    self.remove_hidden_views(&mut views);
+    self.fill_view_extras(&mut views);
     Ok(views)
   }
 
@@ -133,6 +267,8 @@ impl FolderManager {
     initial_data: FolderInitializeDataSource,
   ) -> FlowyResult<()> {
     *self.workspace_id.write() = Some(workspace_id.to_string());
+    // The operations of the previous workspace can't be undone in the new workspace.
+    self.operation_history.clear();
     let workspace_id = workspace_id.to_string();
     if let Ok(collab_db) = self.user.collab_db(uid) {
       let (view_tx, view_rx) = tokio::sync::broadcast::channel(100);
@@ -142,7 +278,7 @@ impl FolderManager {
         trash_change_tx: trash_tx,
       };
 
-      let folder = match initial_data {
+      let (folder, collab) = match initial_data {
         FolderInitializeDataSource::LocalDisk {
           create_if_not_exist,
         } => {
@@ -152,7 +288,8 @@ impl FolderManager {
             let collab = self
               .collab_for_folder(uid, &workspace_id, collab_db, vec![])
               .await?;
-            Folder::open(UserId::from(uid), collab, Some(folder_notifier))?
+            let folder = Folder::open(UserId::from(uid), collab.clone(), Some(folder_notifier))?;
+            (folder, collab)
           } else if create_if_not_exist {
             event!(Level::INFO, "Create folder with default folder builder");
             let folder_data =
@@ -161,12 +298,13 @@ impl FolderManager {
             let collab = self
               .collab_for_folder(uid, &workspace_id, collab_db, vec![])
               .await?;
-            Folder::create(
+            let folder = Folder::create(
               UserId::from(uid),
-              collab,
+              collab.clone(),
               Some(folder_notifier),
               folder_data,
-            )
+            );
+            (folder, collab)
           } else {
             return Err(FlowyError::new(
               ErrorCode::RecordNotFound,
@@ -182,35 +320,62 @@ impl FolderManager {
           let collab = self
             .collab_for_folder(uid, &workspace_id, collab_db, raw_data)
             .await?;
-          Folder::open(UserId::from(uid), collab, Some(folder_notifier))?
+          let folder = Folder::open(UserId::from(uid), collab.clone(), Some(folder_notifier))?;
+          (folder, collab)
         },
         FolderInitializeDataSource::FolderData(folder_data) => {
           event!(Level::INFO, "Restore folder with passed-in folder data");
           let collab = self
             .collab_for_folder(uid, &workspace_id, collab_db, vec![])
             .await?;
-          Folder::create(
+          let folder = Folder::create(
             UserId::from(uid),
-            collab,
+            collab.clone(),
             Some(folder_notifier),
             folder_data,
-          )
+          );
+          (folder, collab)
         },
       };
 
       tracing::debug!("Current workspace_id: {}", workspace_id);
+      let orphaned_views = get_orphaned_views(&folder);
+      if !orphaned_views.is_empty() {
+        tracing::warn!(
+          "Found {} orphaned views in workspace:{}",
+          orphaned_views.len(),
+          workspace_id
+        );
+      }
       let folder_state_rx = folder.subscribe_sync_state();
+      self.folder_extra.open(uid, &workspace_id, &collab);
       *self.mutex_folder.lock() = Some(folder);
+      *self.sync_status.write() = FolderSyncStatusPB::default();
+      self.view_subscriptions.write().clear();
+      self.muted_view_updates.lock().clear();
 
       let weak_mutex_folder = Arc::downgrade(&self.mutex_folder);
       subscribe_folder_sync_state_changed(
         workspace_id.clone(),
         folder_state_rx,
         &weak_mutex_folder,
+        Arc::downgrade(&self.sync_status),
+      );
+      subscribe_folder_snapshot_state_changed(workspace_id.clone(), &weak_mutex_folder);
+      subscribe_folder_trash_changed(
+        trash_rx,
+        &weak_mutex_folder,
+        Arc::downgrade(&self.folder_extra),
+        Arc::downgrade(&self.view_subscriptions),
       );
-      subscribe_folder_snapshot_state_changed(workspace_id, &weak_mutex_folder);
-      subscribe_folder_trash_changed(trash_rx, &weak_mutex_folder);
-      subscribe_folder_view_changed(view_rx, &weak_mutex_folder);
+      subscribe_folder_view_changed(
+        view_rx,
+        &weak_mutex_folder,
+        Arc::downgrade(&self.folder_extra),
+        Arc::downgrade(&self.view_subscriptions),
+        Arc::downgrade(&self.muted_view_updates),
+      );
+      self.start_trash_purge_task(&workspace_id);
     }
 
     Ok(())
@@ -344,6 +509,178 @@ impl FolderManager {
     )
   }
 
+  /// Duplicates the workspace into a new workspace named `new_name`. The views with their order,
+  /// icons and favorites, and the settings of the workspace are copied with new ids. The trash and
+  /// the snapshots are not copied.
+  ///
+  /// The content of the views is copied as it is, so the references to other views inside the
+  /// content still point to the views of the original workspace.
+  #[tracing::instrument(level = "debug", skip(self), err)]
+  pub async fn duplicate_workspace(
+    &self,
+    workspace_id: &str,
+    new_name: &str,
+  ) -> FlowyResult<WorkspacePB> {
+    let uid = self.user.user_id()?;
+    let read_workspace = |folder: &Folder| {
+      let workspace = folder
+        .get_current_workspace()
+        .filter(|workspace| workspace.id == workspace_id)
+        .ok_or_else(|| {
+          FlowyError::record_not_found()
+            .with_context(format!("Can't find the workspace:{}", workspace_id))
+        })?;
+      let views = get_workspace_all_views(folder, workspace_id, false);
+      let favorite_ids = folder
+        .get_all_favorites()
+        .into_iter()
+        .map(|item| item.id)
+        .collect::<Vec<_>>();
+      Ok::<_, FlowyError>((workspace, views, favorite_ids, folder.get_current_view()))
+    };
+    let (workspace, views, favorite_ids, current_view) =
+      if self.get_current_workspace_id().await? == workspace_id {
+        self.with_folder(|| Err(folder_not_init_error()), read_workspace)?
+      } else {
+        if !is_exist_in_local_disk(&self.user, workspace_id)? {
+          return Err(
+            FlowyError::record_not_found()
+              .with_context(format!("Workspace:{} does not exist", workspace_id)),
+          );
+        }
+        read_workspace(&self.open_folder(uid, workspace_id).await?)?
+      };
+
+    // The new workspace is created through the cloud service, so it's registered on the server
+    // when the user is signed in. The local server only generates the id.
+    let new_workspace = self.cloud_service.create_workspace(uid, new_name).await?;
+    let new_workspace_id = new_workspace.id.clone();
+    let new_view_ids = views
+      .iter()
+      .map(|view| (view.id.clone(), gen_view_id().to_string()))
+      .collect::<HashMap<_, _>>();
+    let remap_ids = |ids: &[String]| {
+      ids
+        .iter()
+        .filter_map(|id| new_view_ids.get(id).cloned())
+        .collect::<Vec<_>>()
+    };
+    let remap_children = |children: &RepeatedViewIdentifier| {
+      let ids = children
+        .items
+        .iter()
+        .map(|child| child.id.clone())
+        .collect::<Vec<_>>();
+      RepeatedViewIdentifier::new(
+        remap_ids(&ids)
+          .into_iter()
+          .map(|id| ViewIdentifier { id })
+          .collect(),
+      )
+    };
+
+    // The data of all the views is created before the folder, and is deleted if any of them fails.
+    let mut created: Vec<(Arc<dyn FolderOperationHandler + Send + Sync>, String)> = vec![];
+    let mut new_views = Vec::with_capacity(views.len());
+    for view in &views {
+      let new_view_id = new_view_ids[&view.id].clone();
+      let parent_view_id = if view.parent_view_id == workspace_id {
+        new_workspace_id.clone()
+      } else {
+        match new_view_ids.get(&view.parent_view_id) {
+          None => continue,
+          Some(parent_view_id) => parent_view_id.clone(),
+        }
+      };
+      let handler = self.get_handler(&view.layout)?;
+      let result = match handler.duplicate_view(&view.id).await {
+        Ok(view_data) => {
+          let params = CreateViewParams {
+            parent_view_id: parent_view_id.clone(),
+            name: view.name.clone(),
+            desc: view.desc.clone(),
+            layout: Some(view.layout.clone().into()),
+            initial_data: view_data.to_vec(),
+            view_id: new_view_id.clone(),
+            meta: Default::default(),
+            set_as_current: false,
+            index: None,
+          };
+          create_view_data(&handler, uid, &params).await
+        },
+        Err(err) => Err(err),
+      };
+      if let Err(err) = result {
+        tracing::error!("Duplicate workspace failed: {}, rollback", err);
+        for (handler, view_id) in created {
+          let _ = handler.delete_view(&view_id).await;
+        }
+        return Err(err);
+      }
+      created.push((handler, new_view_id.clone()));
+      new_views.push(View {
+        id: new_view_id,
+        parent_view_id,
+        name: view.name.clone(),
+        desc: view.desc.clone(),
+        children: remap_children(&view.children),
+        created_at: timestamp(),
+        is_favorite: view.is_favorite,
+        layout: view.layout.clone(),
+        icon: view.icon.clone(),
+      });
+    }
+    tracing::warn!(
+      "Duplicate the workspace:{} to {}, the references to other views inside the content of the \
+       views are kept as they are",
+      workspace_id,
+      new_workspace_id
+    );
+
+    let child_views = remap_children(&workspace.child_views);
+    let current_view = current_view
+      .and_then(|view_id| new_view_ids.get(&view_id).cloned())
+      .or_else(|| child_views.items.first().map(|view| view.id.clone()))
+      .unwrap_or_default();
+    let folder_data = FolderData {
+      workspace: Workspace {
+        child_views,
+        ..new_workspace
+      },
+      current_view,
+      views: new_views,
+      favorites: Default::default(),
+    };
+    let collab_db = self.user.collab_db(uid)?;
+    let collab = self
+      .collab_for_folder(uid, &new_workspace_id, collab_db, vec![])
+      .await?;
+    let folder = Folder::create(UserId::from(uid), collab.clone(), None, folder_data);
+    folder.add_favorites(remap_ids(&favorite_ids));
+
+    // The new workspace is named `new_name`, so only the icon of the workspace is copied.
+    let extra = self.get_folder_extra(workspace_id).await;
+    let new_extra = FolderExtra::update_collab(&collab, |new_extra| {
+      new_extra.workspace.icon = extra.workspace.icon;
+      new_extra.setting = extra.setting;
+      new_extra.favorite_order = remap_ids(&extra.favorite_order);
+      new_extra.views = extra
+        .views
+        .into_iter()
+        .filter_map(|(view_id, view_extra)| {
+          new_view_ids
+            .get(&view_id)
+            .map(|new_view_id| (new_view_id.clone(), view_extra))
+        })
+        .collect();
+      new_extra.clone()
+    });
+    self.user.add_workspace(&new_workspace_id, new_name)?;
+
+    get_workspace_pb_from_folder(&folder, &new_extra)
+      .ok_or_else(|| FlowyError::internal().with_context("Can't find the duplicated workspace"))
+  }
+
   pub async fn get_workspace(&self, _workspace_id: &str) -> Option<Workspace> {
     self.with_folder(|| None, |folder| folder.get_current_workspace())
   }
@@ -351,500 +688,4649 @@ impl FolderManager {
   pub async fn get_workspace_setting_pb(&self) -> Option<WorkspaceSettingPB> {
     let workspace_id = self.get_current_workspace_id().await.ok()?;
     let latest_view = self.get_current_view().await;
+    let show_favorites = self.is_favorites_shown(&workspace_id);
+    let home_view_id = self.get_home_view_id(&workspace_id).unwrap_or_default();
     Some(WorkspaceSettingPB {
       workspace_id,
       latest_view,
+      show_favorites,
+      home_view_id,
     })
   }
 
-  pub async fn get_workspace_pb(&self) -> Option<WorkspacePB> {
-    let workspace_pb = {
-      let guard = self.mutex_folder.lock();
-      let folder = guard.as_ref()?;
-      let workspace = folder.get_current_workspace()?;
-
-      let views = folder
-        .views
-        .get_views_belong_to(&workspace.id)
-        .into_iter()
-        .map(view_pb_without_child_views)
-        .collect::<Vec<ViewPB>>();
-
-      WorkspacePB {
-        id: workspace.id,
-        name: workspace.name,
-        views,
-        create_time: workspace.created_at,
+  /// Returns the view to open when the workspace is loaded. It's the home view if it's set, see
+  /// [Self::set_workspace_home]. Otherwise, it's the latest opened view if it's still in the
+  /// workspace and not in the trash, or the first top level view. Only the current workspace is
+  /// supported.
+  #[tracing::instrument(level = "debug", skip(self), err)]
+  pub async fn resolve_default_view(&self, workspace_id: &str) -> FlowyResult<Option<ViewPB>> {
+    if self.get_current_workspace_id().await? != workspace_id {
+      return Err(
+        FlowyError::not_support()
+          .with_context("Only the default view of the current workspace can be resolved"),
+      );
+    }
+    if let Some(home_view_id) = self.get_home_view_id(workspace_id) {
+      if let Ok(view) = self.get_view_pb(&home_view_id).await {
+        return Ok(Some(view));
       }
-    };
-
-    Some(workspace_pb)
+    }
+    if let Some(view) = self.get_openable_current_view().await {
+      return Ok(Some(view));
+    }
+    let views = self.get_workspace_views(workspace_id).await?;
+    Ok(views.into_iter().next())
   }
 
-  async fn get_current_workspace_id(&self) -> FlowyResult<String> {
-    self
-      .mutex_folder
-      .lock()
-      .as_ref()
-      .map(|folder| folder.get_workspace_id())
-      .ok_or(FlowyError::internal().with_context("Unexpected empty workspace id"))
+  /// Returns the current view if it exists and it's not in the trash or hidden from the view tree.
+  async fn get_openable_current_view(&self) -> Option<ViewPB> {
+    let hidden_ids = self.get_hidden_view_ids();
+    let view_id = self
+      .with_folder(
+        || None,
+        |folder| {
+          let view_id = folder.get_current_view()?;
+          let trash_ids = get_trash_ids(folder);
+          let is_valid =
+            folder.views.get_view(&view_id).is_some() && !is_trashed(folder, &view_id, &trash_ids);
+          is_valid.then_some(view_id)
+        },
+      )
+      .filter(|view_id| !hidden_ids.contains(view_id))?;
+    self.get_view_pb(&view_id).await.ok()
   }
 
-  /// This function acquires a lock on the `mutex_folder` and checks its state.
-  /// If the folder is `None`, it invokes the `none_callback`, otherwise, it passes the folder to the `f2` callback.
-  ///
-  /// # Parameters
-  ///
-  /// * `none_callback`: A callback function that is invoked when `mutex_folder` contains `None`.
-  /// * `f2`: A callback function that is invoked when `mutex_folder` contains a `Some` value. The contained folder is passed as an argument to this callback.
-  fn with_folder<F1, F2, Output>(&self, none_callback: F1, f2: F2) -> Output
-  where
-    F1: FnOnce() -> Output,
-    F2: FnOnce(&Folder) -> Output,
-  {
-    let folder = self.mutex_folder.lock();
-    match &*folder {
-      None => none_callback(),
-      Some(folder) => f2(folder),
+  /// Returns the current view of the workspace with its ancestors and siblings, so the view can be
+  /// opened with its ancestors expanded on start. If the current view can't be opened, the view
+  /// resolved by [FolderManager::resolve_default_view] is returned instead.
+  #[tracing::instrument(level = "debug", skip(self), err)]
+  pub async fn get_current_view_context(&self, workspace_id: &str) -> FlowyResult<ViewContextPB> {
+    if self.get_current_workspace_id().await? != workspace_id {
+      return Err(
+        FlowyError::not_support()
+          .with_context("Only the current view of the current workspace can be returned"),
+      );
     }
-  }
+    let view = match self.get_openable_current_view().await {
+      Some(view) => Some(view),
+      None => self.resolve_default_view(workspace_id).await?,
+    };
+    let Some(view) = view else {
+      return Ok(ViewContextPB::default());
+    };
 
-  pub async fn get_all_workspaces(&self) -> Vec<Workspace> {
-    self.with_folder(Vec::new, |folder| {
-      let mut workspaces = vec![];
-      if let Some(workspace) = folder.get_current_workspace() {
-        workspaces.push(workspace);
-      }
-      workspaces
+    let ancestors = self.get_ancestors(&view.id).await?;
+    let mut siblings = if view.parent_view_id == workspace_id {
+      self.get_workspace_views(workspace_id).await?
+    } else {
+      self
+        .get_view_pb(&view.parent_view_id)
+        .await
+        .map(|parent_view| parent_view.child_views)
+        .unwrap_or_default()
+    };
+    siblings.retain(|sibling| sibling.id != view.id);
+    Ok(ViewContextPB {
+      view: Some(view),
+      ancestors,
+      siblings,
     })
   }
 
-  pub async fn create_view_with_params(&self, params: CreateViewParams) -> FlowyResult<View> {
-    let view_layout: ViewLayout = params.layout.clone().into();
-    let _workspace_id = self.get_current_workspace_id().await?;
-    let handler = self.get_handler(&view_layout)?;
-    let user_id = self.user.user_id()?;
-    let meta = params.meta.clone();
-
-    if meta.is_empty() && params.initial_data.is_empty() {
-      tracing::trace!("Create view with build-in data");
-      handler
-        .create_built_in_view(user_id, &params.view_id, &params.name, view_layout.clone())
-        .await?;
-    } else {
-      tracing::trace!("Create view with view data");
-      handler
-        .create_view_with_view_data(
-          user_id,
-          &params.view_id,
-          &params.name,
-          params.initial_data.clone(),
-          view_layout.clone(),
-          meta,
-        )
-        .await?;
+  /// Sets the view opened when the workspace is loaded. `None` clears the home view, so the
+  /// latest opened view is opened instead. Only the home view of the current workspace can be
+  /// set, and the view can't be in the trash.
+  #[tracing::instrument(level = "debug", skip(self), err)]
+  pub async fn set_workspace_home(&self, params: SetHomeParams) -> FlowyResult<()> {
+    let workspace_id = params.workspace_id;
+    if self.get_current_workspace_id().await? != workspace_id {
+      return Err(
+        FlowyError::not_support()
+          .with_context("Only the home view of the current workspace can be set"),
+      );
     }
-
-    let index = params.index;
-    let view = create_view(params, view_layout);
-    self.with_folder(
-      || (),
-      |folder| {
-        folder.insert_view(view.clone(), index);
-      },
+    if let Some(view_id) = &params.view_id {
+      self.check_view_exists(view_id)?;
+      let is_trashed = self.with_folder(
+        || false,
+        |folder| is_trashed(folder, view_id, &get_trash_ids(folder)),
+      );
+      if is_trashed {
+        return Err(
+          FlowyError::invalid_data().with_context(format!("The view:{} is in the trash", view_id)),
+        );
+      }
+    }
+    self.folder_extra.update(&workspace_id, |extra| {
+      extra.setting.home_view_id = params.view_id;
+    })?;
+    send_workspace_setting_notification(
+      workspace_id.clone(),
+      self.get_current_view().await,
+      self.is_favorites_shown(&workspace_id),
+      self.get_home_view_id(&workspace_id).unwrap_or_default(),
     );
-
-    Ok(view)
+    Ok(())
   }
 
-  /// The orphan view is meant to be a view that is not attached to any parent view. By default, this
-  /// view will not be shown in the view list unless it is attached to a parent view that is shown in
-  /// the view list.
-  pub async fn create_orphan_view_with_params(
-    &self,
-    params: CreateViewParams,
-  ) -> FlowyResult<View> {
-    let view_layout: ViewLayout = params.layout.clone().into();
-    let handler = self.get_handler(&view_layout)?;
-    let user_id = self.user.user_id()?;
-    handler
-      .create_built_in_view(user_id, &params.view_id, &params.name, view_layout.clone())
-      .await?;
-    let view = create_view(params, view_layout);
-    self.with_folder(
-      || (),
-      |folder| {
-        folder.insert_view(view.clone(), None);
-      },
-    );
-    Ok(view)
+  /// Returns the id of the home view of the workspace. `None` if the home view is not set, or it's
+  /// deleted, in the trash or hidden from the view tree.
+  fn get_home_view_id(&self, workspace_id: &str) -> Option<String> {
+    let home_view_id = self.folder_extra.get(workspace_id).setting.home_view_id?;
+    if self.get_hidden_view_ids().contains(&home_view_id) {
+      return None;
+    }
+    self
+      .with_folder(
+        || false,
+        |folder| {
+          folder.get_workspace_id() == workspace_id
+            && folder.views.get_view(&home_view_id).is_some()
+            && !is_trashed(folder, &home_view_id, &get_trash_ids(folder))
+        },
+      )
+      .then_some(home_view_id)
   }
 
+  /// Updates the UI settings of the workspace and notifies the new workspace setting.
   #[tracing::instrument(level = "debug", skip(self), err)]
-  pub(crate) async fn close_view(&self, view_id: &str) -> Result<(), FlowyError> {
-    if let Some(view) = self.with_folder(|| None, |folder| folder.views.get_view(view_id)) {
-      let handler = self.get_handler(&view.layout)?;
-      handler.close_view(view_id).await?;
+  pub async fn update_workspace_ui_settings(
+    &self,
+    workspace_id: &str,
+    show_favorites: Option<bool>,
+  ) -> FlowyResult<()> {
+    if self.get_current_workspace_id().await? != workspace_id {
+      return Err(
+        FlowyError::not_support()
+          .with_context("Only the settings of the current workspace can be updated"),
+      );
     }
+    self.folder_extra.update(workspace_id, |extra| {
+      if let Some(show_favorites) = show_favorites {
+        extra.setting.hide_favorites = !show_favorites;
+      }
+    })?;
+    send_workspace_setting_notification(
+      workspace_id.to_string(),
+      self.get_current_view().await,
+      self.is_favorites_shown(workspace_id),
+      self.get_home_view_id(workspace_id).unwrap_or_default(),
+    );
     Ok(())
   }
 
-  /// Returns the view with the given view id.
-  /// The child views of the view will only access the first. So if you want to get the child view's
-  /// child view, you need to call this method again.
-  #[tracing::instrument(level = "debug", skip(self, view_id), err)]
-  pub async fn get_view_pb(&self, view_id: &str) -> FlowyResult<ViewPB> {
-    let view_id = view_id.to_string();
-    let folder = self.mutex_folder.lock();
-    let folder = folder.as_ref().ok_or_else(folder_not_init_error)?;
-    let trash_ids = folder
-      .get_all_trash()
-      .into_iter()
-      .map(|trash| trash.id)
-      .collect::<Vec<String>>();
+  fn is_favorites_shown(&self, workspace_id: &str) -> bool {
+    !self.folder_extra.get(workspace_id).setting.hide_favorites
+  }
 
-    if trash_ids.contains(&view_id) {
-      return Err(FlowyError::record_not_found());
-    }
+  /// Fills the name and the icon set by [Self::update_workspace] into the workspace. Only the
+  /// current workspace is filled.
+  pub fn fill_workspace_extra(&self, workspace_pb: &mut WorkspacePB) {
+    let extra = self.folder_extra.get(&workspace_pb.id);
+    fill_workspace_extra(&extra, workspace_pb);
+  }
 
-    match folder.views.get_view(&view_id) {
-      None => Err(FlowyError::record_not_found()),
-      Some(view) => {
-        let child_views = folder
-          .views
-          .get_views_belong_to(&view.id)
-          .into_iter()
-          .filter(|view| !trash_ids.contains(&view.id))
-          .collect::<Vec<_>>();
-        let view_pb = view_pb_with_child_views(view, child_views);
-        Ok(view_pb)
-      },
-    }
+  pub async fn get_workspace_pb(&self) -> Option<WorkspacePB> {
+    let workspace_id = self.get_current_workspace_id().await.ok()?;
+    let extra = self.folder_extra.get(&workspace_id);
+    let mut workspace_pb = self.with_folder(
+      || None,
+      |folder| get_workspace_pb_from_folder(folder, &extra),
+    )?;
+    self.fill_view_extras(&mut workspace_pb.views);
+    Some(workspace_pb)
   }
 
-  /// Move the view to trash. If the view is the current view, then set the current view to empty.
-  /// When the view is moved to trash, all the child views will be moved to trash as well.
-  /// All the favorite views being trashed will be unfavorited first to remove it from favorites list as well. The process of unfavoriting concerned view is handled by `unfavorite_view_and_decendants()`
+  /// Returns the counts of the views in the workspace. The counts are computed from the views of
+  /// the folder directly, without building the [ViewPB]s. The workspace that is not the current
+  /// workspace will be opened from the local disk.
   #[tracing::instrument(level = "debug", skip(self), err)]
-  pub async fn move_view_to_trash(&self, view_id: &str) -> FlowyResult<()> {
-    self.with_folder(
-      || (),
-      |folder| {
-        if let Some(view) = folder.views.get_view(view_id) {
-          self.unfavorite_view_and_decendants(view.clone(), folder);
-          folder.add_trash(vec![view_id.to_string()]);
-          // notify the parent view that the view is moved to trash
-          send_notification(view_id, FolderNotification::DidMoveViewToTrash)
-            .payload(DeletedViewPB {
-              view_id: view_id.to_string(),
-              index: None,
-            })
-            .send();
-
-          notify_child_views_changed(
-            view_pb_without_child_views(view),
-            ChildViewChangeReason::DidDeleteView,
-          );
-        }
-      },
-    );
+  pub async fn get_workspace_stats(&self, workspace_id: &str) -> FlowyResult<WorkspaceStatsPB> {
+    if self.get_current_workspace_id().await? == workspace_id {
+      return self.with_folder(
+        || Err(folder_not_init_error()),
+        |folder| Ok(get_workspace_stats_from_folder(folder, workspace_id)),
+      );
+    }
 
-    Ok(())
+    if !is_exist_in_local_disk(&self.user, workspace_id)? {
+      return Err(
+        FlowyError::record_not_found()
+          .with_context(format!("Workspace:{} does not exist", workspace_id)),
+      );
+    }
+    let uid = self.user.user_id()?;
+    let folder = self.open_folder(uid, workspace_id).await?;
+    Ok(get_workspace_stats_from_folder(&folder, workspace_id))
   }
 
-  fn unfavorite_view_and_decendants(&self, view: Arc<View>, folder: &Folder) {
-    let mut all_descendant_views: Vec<Arc<View>> = vec![view.clone()];
-    all_descendant_views.extend(folder.views.get_views_belong_to(&view.id));
+  /// Returns the views of the current workspace whose layout is one of the `layouts`. The views
+  /// keep the order of the view tree, the parent view always comes before its child views.
+  #[tracing::instrument(level = "debug", skip(self, params), err)]
+  pub async fn get_views_by_layout(&self, params: ViewsByLayoutParams) -> FlowyResult<Vec<ViewPB>> {
+    if self.get_current_workspace_id().await? != params.workspace_id {
+      return Err(
+        FlowyError::not_support()
+          .with_context("Only the views of the current workspace can be filtered by layout"),
+      );
+    }
+    let mut views = self.with_folder(Vec::new, |folder| {
+      get_workspace_all_views(folder, &params.workspace_id, false)
+        .into_iter()
+        .filter(|view| params.layouts.contains(&view.layout))
+        .map(view_pb_without_child_views)
+        .collect::<Vec<_>>()
+    });
+    self.fill_flat_view_extras(&mut views);
+    Ok(views)
+  }
 
-    let favorite_descendant_views: Vec<ViewPB> = all_descendant_views
-      .iter()
-      .filter(|view| view.is_favorite)
-      .map(|view| view_pb_without_child_views(view.clone()))
-      .collect();
-
-    if !favorite_descendant_views.is_empty() {
-      folder.delete_favorites(
-        favorite_descendant_views
-          .iter()
-          .map(|v| v.id.clone())
-          .collect(),
+  /// Returns the views of the current workspace that were last edited within the inclusive time
+  /// range, sorted by the last edited time with the most recent first. The trashed views are
+  /// excluded.
+  #[tracing::instrument(level = "debug", skip(self), err)]
+  pub async fn get_views_in_range(&self, params: ViewsTimeRangeParams) -> FlowyResult<Vec<ViewPB>> {
+    if self.get_current_workspace_id().await? != params.workspace_id {
+      return Err(
+        FlowyError::not_support()
+          .with_context("Only the views of the current workspace can be filtered by time"),
       );
-      send_notification("favorite", FolderNotification::DidUnfavoriteView)
-        .payload(RepeatedViewPB {
-          items: favorite_descendant_views,
-        })
-        .send();
     }
+    let mut views = self.with_folder(Vec::new, |folder| {
+      get_workspace_all_views(folder, &params.workspace_id, false)
+        .into_iter()
+        .map(view_pb_without_child_views)
+        .collect::<Vec<_>>()
+    });
+    self.fill_flat_view_extras(&mut views);
+    views
+      .retain(|view| view.last_edited_time >= params.start && view.last_edited_time <= params.end);
+    views.sort_by(|a, b| b.last_edited_time.cmp(&a.last_edited_time));
+    Ok(views)
   }
 
-  /// Moves a nested view to a new location in the hierarchy.
-  ///
-  /// This function takes the `view_id` of the view to be moved,
-  /// `new_parent_id` of the view under which the `view_id` should be moved,
-  /// and an optional `prev_view_id` to position the `view_id` right after
-  /// this specific view.
-  ///
-  /// If `prev_view_id` is provided, the moved view will be placed right after
-  /// the view corresponding to `prev_view_id` under the `new_parent_id`.
-  /// If `prev_view_id` is `None`, the moved view will become the first child of the new parent.
-  ///
-  /// # Arguments
-  ///
-  /// * `view_id` - A string slice that holds the id of the view to be moved.
-  /// * `new_parent_id` - A string slice that holds the id of the new parent view.
-  /// * `prev_view_id` - An `Option<String>` that holds the id of the view after which the `view_id` should be positioned.
+  /// Returns the views of the current workspace whose parent is missing or doesn't list them.
+  /// These views don't show up in the view tree, see [find_orphaned_views] for details.
+  #[tracing::instrument(level = "debug", skip(self), err)]
+  pub async fn get_orphaned_views(&self) -> FlowyResult<Vec<ViewPB>> {
+    let views = self.with_folder(
+      || Err(folder_not_init_error()),
+      |folder| Ok(get_orphaned_views(folder)),
+    )?;
+    let mut views = views
+      .into_iter()
+      .map(view_pb_without_child_views)
+      .collect::<Vec<_>>();
+    self.fill_flat_view_extras(&mut views);
+    Ok(views)
+  }
+
+  /// Reattaches the orphaned views to the root of the current workspace. The reattached views are
+  /// placed after the existing views of the workspace, and their child views move with them.
   ///
-  #[tracing::instrument(level = "trace", skip(self), err)]
-  pub async fn move_nested_view(
-    &self,
-    view_id: String,
-    new_parent_id: String,
-    prev_view_id: Option<String>,
-  ) -> FlowyResult<()> {
-    let view = self.get_view_pb(&view_id).await?;
-    let old_parent_id = view.parent_view_id;
-    self.with_folder(
-      || (),
+  /// Returns the orphaned views. Nothing is changed if `dry_run` is true.
+  #[tracing::instrument(level = "debug", skip(self), err)]
+  pub async fn repair_orphaned_views(&self, dry_run: bool) -> FlowyResult<Vec<ViewPB>> {
+    let (workspace_id, views) = self.with_folder(
+      || Err(folder_not_init_error()),
       |folder| {
-        folder.move_nested_view(&view_id, &new_parent_id, prev_view_id);
-      },
-    );
-    notify_parent_view_did_change(
-      self.mutex_folder.clone(),
-      vec![new_parent_id, old_parent_id],
-    );
-    Ok(())
-  }
+        let workspace_id = folder.get_workspace_id();
+        let orphaned_views = get_orphaned_views(folder);
+        if dry_run || orphaned_views.is_empty() {
+          return Ok((workspace_id, orphaned_views));
+        }
 
-  /// Move the view with given id from one position to another position.
-  /// The view will be moved to the new position in the same parent view.
-  /// The passed in index is the index of the view that displayed in the UI.
-  /// We need to convert the index to the real index of the view in the parent view.
-  #[tracing::instrument(level = "trace", skip(self), err)]
-  pub async fn move_view(&self, view_id: &str, from: usize, to: usize) -> FlowyResult<()> {
-    if let Some((is_workspace, parent_view_id, child_views)) = self.get_view_relation(view_id).await
-    {
-      // The display parent view is the view that is displayed in the UI
-      let display_views = if is_workspace {
-        self
-          .get_current_workspace()
-          .await?
-          .views
-          .into_iter()
-          .map(|view| view.id)
-          .collect::<Vec<_>>()
-      } else {
-        self
-          .get_view_pb(&parent_view_id)
-          .await?
-          .child_views
-          .into_iter()
-          .map(|view| view.id)
-          .collect::<Vec<_>>()
-      };
+        let mut prev_view_id = folder
+          .get_workspace_views(&workspace_id)
+          .last()
+          .map(|view| view.id.clone());
+        for view in orphaned_views.iter() {
+          folder.move_nested_view(&view.id, &workspace_id, prev_view_id.clone());
+          prev_view_id = Some(view.id.clone());
+        }
+        tracing::info!(
+          "Reattached {} orphaned views to workspace:{}",
+          orphaned_views.len(),
+          workspace_id
+        );
+        let repaired_views = orphaned_views
+          .iter()
+          .flat_map(|view| folder.views.get_view(&view.id))
+          .collect();
+        Ok((workspace_id, repaired_views))
+      },
+    )?;
 
-      if display_views.len() > to {
-        let to_view_id = display_views[to].clone();
+    if !dry_run && !views.is_empty() {
+      notify_parent_view_did_change(
+        self.mutex_folder.clone(),
+        &self.folder_extra,
+        vec![workspace_id],
+      );
+    }
+    let mut views = views
+      .into_iter()
+      .map(view_pb_without_child_views)
+      .collect::<Vec<_>>();
+    self.fill_flat_view_extras(&mut views);
+    Ok(views)
+  }
 
-        // Find the actual index of the view in the parent view
-        let actual_from_index = child_views.iter().position(|id| id == view_id);
-        let actual_to_index = child_views.iter().position(|id| id == &to_view_id);
-        if let (Some(actual_from_index), Some(actual_to_index)) =
-          (actual_from_index, actual_to_index)
-        {
-          self.with_folder(
-            || (),
-            |folder| {
-              folder.move_view(view_id, actual_from_index as u32, actual_to_index as u32);
-            },
-          );
-          notify_parent_view_did_change(self.mutex_folder.clone(), vec![parent_view_id]);
+  /// Updates the name and/or the icon of the workspace. The workspace that is not the current
+  /// workspace will be opened from the local disk.
+  #[tracing::instrument(level = "debug", skip(self), err)]
+  pub async fn update_workspace(&self, params: UpdateWorkspaceParams) -> FlowyResult<WorkspacePB> {
+    // The folder doesn't store the description of the workspace, so it's ignored.
+    let UpdateWorkspaceParams {
+      id: workspace_id,
+      name,
+      icon,
+      ..
+    } = params;
+    self
+      .update_folder_extra(&workspace_id, |extra| {
+        if let Some(name) = name {
+          extra.workspace.name = Some(name);
         }
-      }
+        if let Some(icon) = icon {
+          extra.workspace.icon = icon.map(normalize_view_icon);
+        }
+      })
+      .await?;
+
+    let workspace_pb = if workspace_id == self.get_current_workspace_id().await? {
+      let extra = self.folder_extra.get(&workspace_id);
+      self.with_folder(
+        || None,
+        |folder| get_workspace_pb_from_folder(folder, &extra),
+      )
+    } else {
+      let uid = self.user.user_id()?;
+      let extra = self.get_folder_extra(&workspace_id).await;
+      get_workspace_pb_from_folder(&self.open_folder(uid, &workspace_id).await?, &extra)
     }
-    Ok(())
+    .ok_or_else(|| FlowyError::record_not_found().with_context("Can't find the workspace"))?;
+
+    send_coalesced_notification(
+      &workspace_id,
+      FolderNotification::DidUpdateWorkspace,
+      workspace_pb.clone(),
+    );
+    Ok(workspace_pb)
   }
 
-  /// Return a list of views that belong to the given parent view id.
-  #[tracing::instrument(level = "debug", skip(self, parent_view_id), err)]
-  pub async fn get_views_belong_to(&self, parent_view_id: &str) -> FlowyResult<Vec<Arc<View>>> {
-    let views = self.with_folder(Vec::new, |folder| {
-      folder.views.get_views_belong_to(parent_view_id)
-    });
-    Ok(views)
+  /// Sets the layout of the views created without specifying the layout in the workspace. The
+  /// document layout is used if no default layout is set.
+  #[tracing::instrument(level = "debug", skip(self), err)]
+  pub async fn set_default_view_layout(
+    &self,
+    workspace_id: &str,
+    layout: ViewLayoutPB,
+  ) -> FlowyResult<()> {
+    let uid = self.user.user_id()?;
+    self.check_workspace_exists(uid, workspace_id).await?;
+    self
+      .update_folder_extra(workspace_id, |extra| {
+        extra.setting.default_view_layout = Some(layout.into());
+      })
+      .await
   }
 
-  /// Update the view with the given params.
-  #[tracing::instrument(level = "trace", skip(self), err)]
-  pub async fn update_view_with_params(&self, params: UpdateViewParams) -> FlowyResult<()> {
+  /// Sets the maximum depth of the views in the workspace. Zero means the depth is unlimited. The
+  /// existing views deeper than the limit are kept, but no view can be created or moved deeper.
+  #[tracing::instrument(level = "debug", skip(self), err)]
+  pub async fn set_max_nesting_depth(&self, workspace_id: &str, depth: u32) -> FlowyResult<()> {
+    let uid = self.user.user_id()?;
+    self.check_workspace_exists(uid, workspace_id).await?;
     self
-      .update_view(&params.view_id, |update| {
-        update
-          .set_name_if_not_none(params.name)
-          .set_desc_if_not_none(params.desc)
-          .set_layout_if_not_none(params.layout)
-          .set_favorite_if_not_none(params.is_favorite)
-          .done()
+      .update_folder_extra(workspace_id, |extra| {
+        extra.setting.max_nesting_depth = depth;
       })
       .await
   }
 
-  /// Update the icon of the view with the given params.
-  #[tracing::instrument(level = "trace", skip(self), err)]
-  pub async fn update_view_icon_with_params(
+  /// Enables or disables keeping the names of the sibling views unique in the workspace. The
+  /// existing names are kept as they are.
+  #[tracing::instrument(level = "debug", skip(self), err)]
+  pub async fn set_unique_sibling_names(
     &self,
-    params: UpdateViewIconParams,
+    workspace_id: &str,
+    enabled: bool,
   ) -> FlowyResult<()> {
+    let uid = self.user.user_id()?;
+    self.check_workspace_exists(uid, workspace_id).await?;
     self
-      .update_view(&params.view_id, |update| {
-        update.set_icon(params.icon).done()
+      .update_folder_extra(workspace_id, |extra| {
+        extra.setting.unique_sibling_names = enabled;
       })
       .await
   }
 
-  /// Duplicate the view with the given view id.
+  /// Sets the maximum number of the views in the workspace. Zero means the number is unlimited.
+  /// The existing views are kept if there are more views than the limit, but no view can be
+  /// created until the number drops below the limit.
   #[tracing::instrument(level = "debug", skip(self), err)]
-  pub(crate) async fn duplicate_view(&self, view_id: &str) -> Result<(), FlowyError> {
-    let view = self
-      .with_folder(|| None, |folder| folder.views.get_view(view_id))
-      .ok_or_else(|| FlowyError::record_not_found().with_context("Can't duplicate the view"))?;
+  pub async fn set_view_limit(&self, workspace_id: &str, limit: u32) -> FlowyResult<()> {
+    let uid = self.user.user_id()?;
+    self.check_workspace_exists(uid, workspace_id).await?;
+    self
+      .update_folder_extra(workspace_id, |extra| {
+        extra.setting.view_limit = limit;
+      })
+      .await
+  }
 
-    let handler = self.get_handler(&view.layout)?;
-    let view_data = handler.duplicate_view(&view.id).await?;
+  /// Updates the present settings of the workspace at once. All the settings are checked before
+  /// any of them is applied, so either all or none of them are applied, and only one
+  /// [FolderNotification::DidUpdateWorkspaceSettings] is sent.
+  #[tracing::instrument(level = "debug", skip(self), err)]
+  pub async fn update_workspace_settings(
+    &self,
+    params: WorkspaceSettingsParams,
+  ) -> FlowyResult<WorkspaceSettingsPB> {
+    let uid = self.user.user_id()?;
+    let workspace_id = params.workspace_id.clone();
+    self.check_workspace_exists(uid, &workspace_id).await?;
+    if let Some(layout) = &params.default_view_layout {
+      // The views of the default layout can only be created if its handler is registered.
+      self.get_handler(&layout.clone().into())?;
+    }
 
-    // get the current view index in the parent view, because we need to insert the duplicated view below the current view.
-    let index = if let Some((_, __, views)) = self.get_view_relation(&view.parent_view_id).await {
-      views.iter().position(|id| id == view_id).map(|i| i as u32)
-    } else {
-      None
-    };
+    let setting = self
+      .update_folder_extra(&workspace_id, |extra| {
+        if let Some(days) = params.trash_retention_days {
+          extra.setting.trash_retention_days = days;
+        }
+        if let Some(layout) = params.default_view_layout.clone() {
+          extra.setting.default_view_layout = Some(layout.into());
+        }
+        if let Some(limit) = params.view_limit {
+          extra.setting.view_limit = limit;
+        }
+        extra.setting.clone()
+      })
+      .await?;
+    if matches!(params.trash_retention_days, Some(days) if days > 0)
+      && self.get_current_workspace_id().await? == workspace_id
+    {
+      purge_expired_trash(
+        &self.mutex_folder,
+        &self.operation_handlers,
+        setting.trash_retention_days,
+      )
+      .await;
+    }
 
-    let duplicate_params = CreateViewParams {
-      parent_view_id: view.parent_view_id.clone(),
-      name: format!("{} (copy)", &view.name),
-      desc: view.desc.clone(),
-      layout: view.layout.clone().into(),
-      initial_data: view_data.to_vec(),
-      view_id: gen_view_id().to_string(),
-      meta: Default::default(),
-      set_as_current: true,
-      index,
+    let settings = WorkspaceSettingsPB {
+      workspace_id: workspace_id.clone(),
+      trash_retention_days: setting.trash_retention_days,
+      default_view_layout: setting
+        .default_view_layout
+        .map(ViewLayoutPB::from)
+        .unwrap_or_default(),
+      view_limit: setting.view_limit,
     };
+    send_notification(
+      &workspace_id,
+      FolderNotification::DidUpdateWorkspaceSettings,
+    )
+    .payload(settings.clone())
+    .send();
+    Ok(settings)
+  }
 
-    self.create_view_with_params(duplicate_params).await?;
-    Ok(())
+  /// Returns the number of the views that can still be created in the current workspace. `None`
+  /// means the number is unlimited. The trashed views are counted because they still hold their
+  /// data.
+  fn remaining_view_quota(&self) -> Option<usize> {
+    let workspace_id = self.with_folder(|| None, |folder| Some(folder.get_workspace_id()))?;
+    let limit = self.folder_extra.get(&workspace_id).setting.view_limit;
+    if limit == 0 {
+      return None;
+    }
+    let count = self.with_folder(
+      || 0,
+      |folder| get_workspace_all_views(folder, &workspace_id, true).len(),
+    );
+    Some((limit as usize).saturating_sub(count))
   }
 
-  #[tracing::instrument(level = "trace", skip(self), err)]
-  pub(crate) async fn set_current_view(&self, view_id: &str) -> Result<(), FlowyError> {
-    let workspace_id = self.with_folder(
-      || Err(FlowyError::record_not_found()),
-      |folder| {
-        folder.set_current_view(view_id);
-        Ok(folder.get_workspace_id())
+  /// Returns an error if creating the given number of views exceeds the view limit of the
+  /// current workspace.
+  fn check_view_limit(&self, count: usize) -> FlowyResult<()> {
+    match self.remaining_view_quota() {
+      Some(remaining) if count > remaining => {
+        Err(FlowyError::view_limit_reached().with_context(format!(
+          "Only {} more views can be created in the workspace",
+          remaining
+        )))
       },
-    )?;
+      _ => Ok(()),
+    }
+  }
 
-    send_workspace_setting_notification(workspace_id, self.get_current_view().await);
+  async fn check_workspace_exists(&self, uid: i64, workspace_id: &str) -> FlowyResult<()> {
+    if self.get_current_workspace_id().await? != workspace_id
+      && !self
+        .user
+        .workspace_ids(uid)?
+        .iter()
+        .any(|id| id == workspace_id)
+    {
+      return Err(
+        FlowyError::record_not_found()
+          .with_context(format!("Can't find the workspace:{}", workspace_id)),
+      );
+    }
     Ok(())
   }
 
-  #[tracing::instrument(level = "trace", skip(self))]
-  pub(crate) async fn get_current_view(&self) -> Option<ViewPB> {
-    let view_id = self.with_folder(|| None, |folder| folder.get_current_view())?;
-    self.get_view_pb(&view_id).await.ok()
+  /// Returns the name that doesn't collide with the names of the other child views of the parent
+  /// if the unique sibling names are enabled, otherwise the name itself. The views in the trash
+  /// are not counted, and the view of `view_id` is skipped when it's renamed.
+  fn unique_sibling_name(&self, parent_id: &str, name: String, view_id: Option<&str>) -> String {
+    if !self.unique_sibling_names_enabled() {
+      return name;
+    }
+    next_available_name(name, &self.get_sibling_names(parent_id, view_id))
   }
 
-  /// Toggles the favorite status of a view identified by `view_id`If the view is not a favorite, it will be added to the favorites list; otherwise, it will be removed from the list.
-  #[tracing::instrument(level = "debug", skip(self), err)]
-  pub async fn toggle_favorites(&self, view_id: &str) -> FlowyResult<()> {
-    self.with_folder(
-      || (),
+  /// Returns the names of the child views of the parent that are not in the trash, except the
+  /// view of `view_id`.
+  fn get_sibling_names(&self, parent_id: &str, view_id: Option<&str>) -> HashSet<String> {
+    self.with_folder(HashSet::new, |folder| {
+      let trash_ids = get_trash_ids(folder);
+      folder
+        .views
+        .get_views_belong_to(parent_id)
+        .into_iter()
+        .filter(|view| Some(view.id.as_str()) != view_id && !trash_ids.contains(&view.id))
+        .map(|view| view.name.clone())
+        .collect::<HashSet<_>>()
+    })
+  }
+
+  fn unique_sibling_names_enabled(&self) -> bool {
+    let workspace_id = self.with_folder(|| None, |folder| Some(folder.get_workspace_id()));
+    match workspace_id {
+      Some(workspace_id) => {
+        self
+          .folder_extra
+          .get(&workspace_id)
+          .setting
+          .unique_sibling_names
+      },
+      None => false,
+    }
+  }
+
+  /// Returns an error if placing the view with its descendants under the parent exceeds the
+  /// maximum nesting depth of the current workspace. The `view_id` is `None` for a new view.
+  fn check_nesting_depth(&self, parent_id: &str, view_id: Option<&str>) -> FlowyResult<()> {
+    let max_depth = self.current_max_nesting_depth();
+    if max_depth == 0 {
+      return Ok(());
+    }
+
+    let depth = self.with_folder(
+      || 0,
       |folder| {
-        if let Some(old_view) = folder.views.get_view(view_id) {
-          if old_view.is_favorite {
-            folder.delete_favorites(vec![view_id.to_string()]);
-          } else {
-            folder.add_favorites(vec![view_id.to_string()]);
-          }
-        }
+        let subtree_depth = view_id
+          .map(|view_id| get_subtree_depth(folder, view_id))
+          .unwrap_or(1);
+        get_view_depth(folder, parent_id) + subtree_depth
       },
     );
-    self.send_toggle_favorite_notification(view_id).await;
-    Ok(())
+    check_depth(depth, max_depth)
   }
 
-  // Used by toggle_favorites to send notification to frontend, after the favorite status of view has been changed.It sends two distinct notifications: one to correctly update the concerned view's is_favorite status, and another to update the list of favorites that is to be displayed.
-  async fn send_toggle_favorite_notification(&self, view_id: &str) {
-    if let Ok(view) = self.get_view_pb(view_id).await {
-      let notification_type = if view.is_favorite {
-        FolderNotification::DidFavoriteView
-      } else {
-        FolderNotification::DidUnfavoriteView
-      };
-      send_notification("favorite", notification_type)
-        .payload(RepeatedViewPB {
-          items: vec![view.clone()],
-        })
-        .send();
+  /// Same as [Self::check_nesting_depth], but for a batch of new views. The parent of a view in
+  /// the batch can be another view of the batch, so the depth of each view is computed from the
+  /// closest ancestor that already exists in the folder.
+  fn check_batch_nesting_depth(&self, params: &[CreateViewParams]) -> FlowyResult<()> {
+    let max_depth = self.current_max_nesting_depth();
+    if max_depth == 0 {
+      return Ok(());
+    }
+
+    let parent_by_id = params
+      .iter()
+      .map(|params| (params.view_id.as_str(), params.parent_view_id.as_str()))
+      .collect::<HashMap<_, _>>();
+    let depth = self.with_folder(
+      || 0,
+      |folder| {
+        params
+          .iter()
+          .map(|params| {
+            let mut depth = 1;
+            let mut visited = HashSet::new();
+            let mut parent_id = params.parent_view_id.as_str();
+            while let Some(next_parent_id) = parent_by_id.get(parent_id) {
+              if !visited.insert(parent_id) {
+                break;
+              }
+              depth += 1;
+              parent_id = next_parent_id;
+            }
+            get_view_depth(folder, parent_id) + depth
+          })
+          .max()
+          .unwrap_or(0)
+      },
+    );
+    check_depth(depth, max_depth)
+  }
+
+  /// Returns the maximum nesting depth of the current workspace. Zero means unlimited.
+  fn current_max_nesting_depth(&self) -> u32 {
+    match self.with_folder(|| None, |folder| Some(folder.get_workspace_id())) {
+      Some(workspace_id) => {
+        self
+          .folder_extra
+          .get(&workspace_id)
+          .setting
+          .max_nesting_depth
+      },
+      None => 0,
+    }
+  }
+
+  /// Fills the layout of the params with the default layout of the current workspace if the
+  /// layout is not specified.
+  fn fill_default_view_layout(&self, params: &mut CreateViewParams) {
+    if params.layout.is_some() {
+      return;
+    }
+    let workspace_id = self.workspace_id.read().clone();
+    if let Some(workspace_id) = workspace_id {
+      params.layout = self
+        .folder_extra
+        .get(&workspace_id)
+        .setting
+        .default_view_layout
+        .map(|layout| layout.into());
+    }
+  }
+
+  /// Deletes the workspace with all of its views, trash and settings. The last workspace of the
+  /// user can't be deleted. If the workspace is the current workspace, another workspace will be
+  /// opened.
+  #[tracing::instrument(level = "info", skip(self), err)]
+  pub async fn delete_workspace(&self, workspace_id: &str) -> FlowyResult<()> {
+    let uid = self.user.user_id()?;
+    let workspace_ids = self.user.workspace_ids(uid)?;
+    if !workspace_ids.iter().any(|id| id == workspace_id) {
+      return Err(
+        FlowyError::record_not_found()
+          .with_context(format!("Can't find the workspace:{}", workspace_id)),
+      );
+    }
+    if workspace_ids.len() <= 1 {
+      return Err(FlowyError::new(
+        ErrorCode::CannotDeleteLastWorkspace,
+        "Can't delete the last workspace, create another workspace first",
+      ));
+    }
+
+    // Switch to another workspace before deleting the contents, so the views of the deleted
+    // workspace will not be rendered anymore.
+    let is_current_workspace =
+      self.get_current_workspace_id().await.ok().as_deref() == Some(workspace_id);
+    self.user.delete_workspace(workspace_id).await?;
+
+    let collab = self.open_folder_collab(uid, workspace_id).await?;
+    let folder = Folder::open(UserId::from(uid), collab.clone(), None)?;
+    let views = get_workspace_all_views(&folder, workspace_id, true);
+    for view in &views {
+      if let Ok(handler) = self.get_handler(&view.layout) {
+        if let Err(err) = handler.delete_view(&view.id).await {
+          tracing::error!(
+            "Failed to delete the data of view:{}, error: {:?}",
+            view.id,
+            err
+          );
+        }
+      }
+    }
+    let trash_ids = folder
+      .get_all_trash()
+      .into_iter()
+      .map(|trash| trash.id)
+      .collect::<Vec<_>>();
+    folder.delete_trash(trash_ids);
+    folder
+      .views
+      .delete_views(views.iter().map(|view| view.id.as_str()).collect());
+    FolderExtra::update_collab(&collab, |extra| *extra = FolderExtra::default());
+
+    if is_current_workspace {
+      if let Ok(current_workspace_id) = self.get_current_workspace_id().await {
+        let show_favorites = self.is_favorites_shown(&current_workspace_id);
+        let home_view_id = self
+          .get_home_view_id(&current_workspace_id)
+          .unwrap_or_default();
+        send_workspace_setting_notification(
+          current_workspace_id,
+          self.get_current_view().await,
+          show_favorites,
+          home_view_id,
+        );
+      }
+    }
+    Ok(())
+  }
+
+  async fn get_current_workspace_id(&self) -> FlowyResult<String> {
+    self
+      .mutex_folder
+      .lock()
+      .as_ref()
+      .map(|folder| folder.get_workspace_id())
+      .ok_or(FlowyError::internal().with_context("Unexpected empty workspace id"))
+  }
+
+  /// This function acquires a lock on the `mutex_folder` and checks its state.
+  /// If the folder is `None`, it invokes the `none_callback`, otherwise, it passes the folder to the `f2` callback.
+  ///
+  /// # Parameters
+  ///
+  /// * `none_callback`: A callback function that is invoked when `mutex_folder` contains `None`.
+  /// * `f2`: A callback function that is invoked when `mutex_folder` contains a `Some` value. The contained folder is passed as an argument to this callback.
+  fn with_folder<F1, F2, Output>(&self, none_callback: F1, f2: F2) -> Output
+  where
+    F1: FnOnce() -> Output,
+    F2: FnOnce(&Folder) -> Output,
+  {
+    let folder = self.mutex_folder.lock();
+    match &*folder {
+      None => none_callback(),
+      Some(folder) => f2(folder),
+    }
+  }
+
+  /// Returns all the workspaces of the user sorted by the created time, the oldest first. The
+  /// views of the workspaces are not included. The workspaces that are not stored in the local
+  /// disk are skipped.
+  #[tracing::instrument(level = "debug", skip(self), err)]
+  pub async fn get_all_workspaces(&self) -> FlowyResult<Vec<WorkspacePB>> {
+    let uid = self.user.user_id()?;
+    let current_workspace_id = self.get_current_workspace_id().await.ok();
+    let mut workspaces = vec![];
+    for workspace_id in self.user.workspace_ids(uid)? {
+      let workspace = if current_workspace_id.as_ref() == Some(&workspace_id) {
+        let extra = self.folder_extra.get(&workspace_id);
+        self.with_folder(
+          || None,
+          |folder| get_workspace_pb_without_views(folder, &extra),
+        )
+      } else if is_exist_in_local_disk(&self.user, &workspace_id).unwrap_or(false) {
+        match self
+          .open_folder_collab(uid, &workspace_id)
+          .await
+          .and_then(|collab| {
+            let extra = FolderExtra::from_collab(&collab);
+            let folder = Folder::open(UserId::from(uid), collab, None)?;
+            Ok((folder, extra))
+          }) {
+          Ok((folder, extra)) => get_workspace_pb_without_views(&folder, &extra),
+          Err(err) => {
+            tracing::warn!(
+              "Failed to open the workspace:{}, error: {:?}",
+              workspace_id,
+              err
+            );
+            None
+          },
+        }
+      } else {
+        None
+      };
+      workspaces.extend(workspace);
+    }
+    workspaces.sort_by_key(|workspace| workspace.create_time);
+    Ok(workspaces)
+  }
+
+  pub async fn create_view_with_params(&self, mut params: CreateViewParams) -> FlowyResult<View> {
+    let _workspace_id = self.get_current_workspace_id().await?;
+    self.check_view_limit(1)?;
+    self.check_view_id_available(&params.view_id)?;
+    self.check_nesting_depth(&params.parent_view_id, None)?;
+    self.fill_default_view_layout(&mut params);
+    params.name = self.unique_sibling_name(&params.parent_view_id, params.name, None);
+    let view_layout = params.view_layout();
+    let handler = self.get_handler(&view_layout)?;
+    let user_id = self.user.user_id()?;
+    create_view_data(&handler, user_id, &params).await?;
+
+    let index = params.index;
+    let view = create_view(params, view_layout);
+    self.with_folder(
+      || (),
+      |folder| {
+        folder.insert_view(view.clone(), index);
+      },
+    );
+    self.set_views_created_by(&[view.id.as_str()]).await;
+    self.operation_history.record(FolderOperation::CreateView {
+      view_id: view.id.clone(),
+    });
+
+    Ok(view)
+  }
+
+  /// Creates the view at the `display_index` among the child views of its parent that are
+  /// displayed in the UI. Unlike the `index` of the [CreateViewParams], the trashed and the
+  /// template child views are not counted. The view is inserted at the position directly, so only
+  /// one change is notified.
+  #[tracing::instrument(level = "debug", skip(self, params), err)]
+  pub async fn create_view_at(
+    &self,
+    mut params: CreateViewParams,
+    display_index: usize,
+  ) -> FlowyResult<View> {
+    let workspace_id = self.get_current_workspace_id().await?;
+    let is_workspace = params.parent_view_id == workspace_id;
+    let display_view_ids = self
+      .get_display_child_view_ids(is_workspace, &params.parent_view_id)
+      .await?;
+    params.index = match display_view_ids.get(display_index) {
+      None => None,
+      Some(next_view_id) => self.with_folder(
+        || None,
+        |folder| {
+          let child_view_ids = if is_workspace {
+            folder
+              .get_current_workspace()
+              .map(|workspace| workspace.child_views.items)
+          } else {
+            folder
+              .views
+              .get_view(&params.parent_view_id)
+              .map(|view| view.children.items.clone())
+          };
+          child_view_ids?
+            .iter()
+            .position(|child| &child.id == next_view_id)
+            .map(|position| position as u32)
+        },
+      ),
+    };
+    self.create_view_with_params(params).await
+  }
+
+  /// Create a list of views. The returned views keep the same order as the passed-in params.
+  /// The names are made unique among the siblings like [Self::create_view_with_params], and the
+  /// views created earlier in the batch count as the siblings of the later ones.
+  ///
+  /// All the params are validated before creating any view. If creating the data of any view
+  /// fails, the data of the views that were already created will be deleted, and none of the
+  /// views will be inserted into the folder.
+  pub async fn create_views_with_params(
+    &self,
+    mut params: Vec<CreateViewParams>,
+  ) -> FlowyResult<Vec<View>> {
+    let _workspace_id = self.get_current_workspace_id().await?;
+    self.check_view_limit(params.len())?;
+    let user_id = self.user.user_id()?;
+    let mut view_ids = HashSet::new();
+    let mut handlers = Vec::with_capacity(params.len());
+    let unique_sibling_names = self.unique_sibling_names_enabled();
+    let mut sibling_names: HashMap<String, HashSet<String>> = HashMap::new();
+    for params in params.iter_mut() {
+      if !view_ids.insert(params.view_id.clone()) {
+        return Err(
+          FlowyError::record_already_exist()
+            .with_context(format!("The view id:{} is duplicated", params.view_id)),
+        );
+      }
+      self.check_view_id_available(&params.view_id)?;
+      self.fill_default_view_layout(params);
+      if unique_sibling_names {
+        let taken_names = sibling_names
+          .entry(params.parent_view_id.clone())
+          .or_insert_with(|| self.get_sibling_names(&params.parent_view_id, None));
+        params.name = next_available_name(std::mem::take(&mut params.name), taken_names);
+        taken_names.insert(params.name.clone());
+      }
+      handlers.push(self.get_handler(&params.view_layout())?);
+    }
+    self.check_batch_nesting_depth(&params)?;
+
+    let mut created: Vec<(&Arc<dyn FolderOperationHandler + Send + Sync>, &str)> = vec![];
+    for (handler, params) in handlers.iter().zip(params.iter()) {
+      if let Err(err) = create_view_data(handler, user_id, params).await {
+        tracing::error!("Create views in batch failed: {}, rollback", err);
+        for (handler, view_id) in created {
+          let _ = handler.delete_view(view_id).await;
+        }
+        return Err(err);
+      }
+      created.push((handler, &params.view_id));
+    }
+
+    let views = params
+      .into_iter()
+      .map(|params| {
+        let index = params.index;
+        let view_layout = params.view_layout();
+        (create_view(params, view_layout), index)
+      })
+      .collect::<Vec<_>>();
+
+    self.with_folder(
+      || (),
+      |folder| {
+        for (view, index) in &views {
+          folder.insert_view(view.clone(), *index);
+        }
+      },
+    );
+    let view_ids = views
+      .iter()
+      .map(|(view, _)| view.id.as_str())
+      .collect::<Vec<_>>();
+    self.set_views_created_by(&view_ids).await;
+    for view_id in view_ids {
+      self.operation_history.record(FolderOperation::CreateView {
+        view_id: view_id.to_string(),
+      });
+    }
+
+    Ok(views.into_iter().map(|(view, _)| view).collect())
+  }
+
+  /// The orphan view is meant to be a view that is not attached to any parent view. By default, this
+  /// view will not be shown in the view list unless it is attached to a parent view that is shown in
+  /// the view list.
+  pub async fn create_orphan_view_with_params(
+    &self,
+    params: CreateViewParams,
+  ) -> FlowyResult<View> {
+    self.check_view_id_available(&params.view_id)?;
+    let view_layout = params.view_layout();
+    let handler = self.get_handler(&view_layout)?;
+    let user_id = self.user.user_id()?;
+    handler
+      .create_built_in_view(user_id, &params.view_id, &params.name, view_layout.clone())
+      .await?;
+    let view = create_view(params, view_layout);
+    self.with_folder(
+      || (),
+      |folder| {
+        folder.insert_view(view.clone(), None);
+      },
+    );
+    Ok(view)
+  }
+
+  /// Returns an error if a view with the given id already exists. The existing view is never
+  /// overwritten by creating a new view with the same id.
+  fn check_view_id_available(&self, view_id: &str) -> FlowyResult<()> {
+    let exists = self.with_folder(|| false, |folder| folder.views.get_view(view_id).is_some());
+    if exists {
+      return Err(
+        FlowyError::record_already_exist()
+          .with_context(format!("The view with id:{} already exists", view_id)),
+      );
+    }
+    Ok(())
+  }
+
+  #[tracing::instrument(level = "debug", skip(self), err)]
+  pub(crate) async fn close_view(&self, view_id: &str) -> Result<(), FlowyError> {
+    let value = self.with_folder(
+      || None,
+      |folder| {
+        let view = folder.views.get_view(view_id)?;
+        Some((view, folder.get_workspace_id()))
+      },
+    );
+    if let Some((view, workspace_id)) = value {
+      self.set_view_last_opened_time(&workspace_id, view_id);
+      let handler = self.get_handler(&view.layout)?;
+      handler.close_view(view_id).await?;
+    }
+    Ok(())
+  }
+
+  /// Checks whether the view exists without building the [ViewPB]. The view doesn't exist if the
+  /// folder is not initialized.
+  pub fn view_exists(&self, view_id: &str) -> ViewExistsPB {
+    self.with_folder(ViewExistsPB::default, |folder| {
+      if folder.views.get_view(view_id).is_none() {
+        return ViewExistsPB::default();
+      }
+      let trash_ids = get_trash_ids(folder);
+      ViewExistsPB {
+        exists: true,
+        is_trashed: is_trashed(folder, view_id, &trash_ids),
+      }
+    })
+  }
+
+  /// Returns the view with the given view id.
+  /// The child views of the view will only access the first. So if you want to get the child view's
+  /// child view, you need to call this method again.
+  #[tracing::instrument(level = "debug", skip(self, view_id), err)]
+  pub async fn get_view_pb(&self, view_id: &str) -> FlowyResult<ViewPB> {
+    let view_id = view_id.to_string();
+    let mut view_pb = {
+      let folder = self.mutex_folder.lock();
+      let folder = folder.as_ref().ok_or_else(folder_not_init_error)?;
+      let trash_ids = folder
+        .get_all_trash()
+        .into_iter()
+        .map(|trash| trash.id)
+        .collect::<Vec<String>>();
+
+      if trash_ids.contains(&view_id) {
+        return Err(FlowyError::record_not_found());
+      }
+
+      match folder.views.get_view(&view_id) {
+        None => return Err(FlowyError::record_not_found()),
+        Some(view) => {
+          let child_views = folder
+            .views
+            .get_views_belong_to(&view.id)
+            .into_iter()
+            .filter(|view| !trash_ids.contains(&view.id))
+            .collect::<Vec<_>>();
+          view_pb_with_child_views(view, child_views)
+        },
+      }
+    };
+    self.remove_hidden_views(&mut view_pb.child_views);
+    self.fill_view_extras(std::slice::from_mut(&mut view_pb));
+    Ok(view_pb)
+  }
+
+  /// Returns the direct child views of the view without their own child views. Each child view
+  /// carries the number of its child views, see [fill_child_counts], so the client can tell
+  /// whether it can be expanded without fetching the grandchildren.
+  #[tracing::instrument(level = "debug", skip(self), err)]
+  pub async fn get_child_views(&self, view_id: &str) -> FlowyResult<Vec<ViewPB>> {
+    let hidden_ids = self.get_hidden_view_ids();
+    let mut views = self.with_folder(
+      || Err(folder_not_init_error()),
+      |folder| {
+        let trash_ids = get_trash_ids(folder);
+        if folder.views.get_view(view_id).is_none() || is_trashed(folder, view_id, &trash_ids) {
+          return Err(FlowyError::record_not_found());
+        }
+        let views = folder
+          .views
+          .get_views_belong_to(view_id)
+          .into_iter()
+          .filter(|view| !trash_ids.contains(&view.id) && !hidden_ids.contains(&view.id))
+          .map(view_pb_without_child_views)
+          .collect::<Vec<_>>();
+        Ok(views)
+      },
+    )?;
+    self.fill_view_extras(&mut views);
+    Ok(views)
+  }
+
+  /// Returns the first child view of the parent whose name matches the given name, in the order of
+  /// the siblings. The trashed, template and archived child views are not matched.
+  #[tracing::instrument(level = "debug", skip(self), err)]
+  pub async fn find_child_by_name(
+    &self,
+    params: FindChildParams,
+  ) -> FlowyResult<FindChildResultPB> {
+    let trash_ids = self.with_folder(HashSet::new, get_trash_ids);
+    let is_match = |name: &str| {
+      if params.case_insensitive {
+        name.to_lowercase() == params.name.to_lowercase()
+      } else {
+        name == params.name
+      }
+    };
+    let mut matched_views = self
+      .get_views_belong_to(&params.parent_id)
+      .await?
+      .into_iter()
+      .filter(|view| !trash_ids.contains(&view.id) && is_match(&view.name));
+    let view = matched_views.next().ok_or_else(|| {
+      FlowyError::record_not_found().with_context(format!(
+        "Can't find the view named {} in {}",
+        params.name, params.parent_id
+      ))
+    })?;
+    let is_ambiguous = matched_views.next().is_some();
+    let mut view = view_pb_without_child_views(view);
+    self.fill_view_extras(std::slice::from_mut(&mut view));
+    Ok(FindChildResultPB { view, is_ambiguous })
+  }
+
+  /// Returns the views sharing the same parent with the view, including the view itself, in the
+  /// order shown in the sidebar. The trashed, template and archived siblings are excluded.
+  #[tracing::instrument(level = "debug", skip(self), err)]
+  pub async fn get_view_siblings(&self, view_id: &str) -> FlowyResult<ViewSiblingsPB> {
+    let (parent_view_id, trash_ids) = self.with_folder(
+      || Err(folder_not_init_error()),
+      |folder| {
+        let trash_ids = get_trash_ids(folder);
+        match folder.views.get_view(view_id) {
+          Some(view) if !is_trashed(folder, view_id, &trash_ids) => {
+            Ok((view.parent_view_id.clone(), trash_ids))
+          },
+          _ => Err(
+            FlowyError::record_not_found()
+              .with_context(format!("Can't find the view with id: {}", view_id)),
+          ),
+        }
+      },
+    )?;
+    let mut items = self
+      .get_views_belong_to(&parent_view_id)
+      .await?
+      .into_iter()
+      .filter(|view| !trash_ids.contains(&view.id))
+      .map(view_pb_without_child_views)
+      .collect::<Vec<_>>();
+    // The pinned views are moved before the others, so the index is looked up afterward.
+    self.fill_view_extras(&mut items);
+    let current_index = items
+      .iter()
+      .position(|view| view.id == view_id)
+      .ok_or_else(|| {
+        FlowyError::record_not_found()
+          .with_context(format!("The view:{} is hidden from its parent", view_id))
+      })?;
+    Ok(ViewSiblingsPB {
+      items,
+      current_index: current_index as u32,
+    })
+  }
+
+  /// Lists the non-trashed views of the current workspace page by page in the depth-first order.
+  /// The cursor records the last listed view and its position, see [ViewsCursor].
+  ///
+  /// Listing is resumed right after the last listed view, so the pages stay consistent if the
+  /// other views are changed between the pages. If the last listed view itself is deleted, the
+  /// listing is resumed at its old position, which is the best effort and may skip or repeat the
+  /// views moved around it.
+  #[tracing::instrument(level = "debug", skip(self), err)]
+  pub async fn list_views_cursor(&self, params: ListViewsCursorParams) -> FlowyResult<ViewsPagePB> {
+    if self.get_current_workspace_id().await? != params.workspace_id {
+      return Err(
+        FlowyError::not_support()
+          .with_context("Only the views of the current workspace can be listed"),
+      );
+    }
+    let cursor = if params.cursor.is_empty() {
+      None
+    } else {
+      Some(ViewsCursor::decode(&params.cursor)?)
+    };
+    let limit = match params.limit {
+      0 => DEFAULT_VIEWS_PAGE_SIZE,
+      limit => limit.min(MAX_VIEWS_PAGE_SIZE),
+    };
+
+    let (mut items, next_cursor) = self.with_folder(
+      || (vec![], String::new()),
+      |folder| {
+        let trash_ids = get_trash_ids(folder);
+        let workspace_stack = || {
+          let mut stack = folder.get_workspace_views(&params.workspace_id);
+          stack.reverse();
+          stack
+        };
+        // Only the views of the page are walked if the last listed view can still be found.
+        // Otherwise, the walk starts over and skips the views before the old position.
+        let (mut stack, mut skip, start) = match &cursor {
+          None => (workspace_stack(), 0, 0),
+          Some(cursor) => {
+            match views_stack_after(folder, &params.workspace_id, &trash_ids, &cursor.view_id) {
+              Some(stack) => (stack, 0, cursor.index + 1),
+              None => (workspace_stack(), cursor.index, cursor.index),
+            }
+          },
+        };
+
+        // One more view is walked to know whether there is a next page.
+        let mut views = vec![];
+        let mut visited = HashSet::new();
+        while let Some(view) = stack.pop() {
+          if trash_ids.contains(&view.id) || !visited.insert(view.id.clone()) {
+            continue;
+          }
+          let mut child_views = folder.views.get_views_belong_to(&view.id);
+          child_views.reverse();
+          stack.extend(child_views);
+          if skip > 0 {
+            skip -= 1;
+            continue;
+          }
+          views.push(view);
+          if views.len() > limit {
+            break;
+          }
+        }
+
+        let next_cursor = if views.len() > limit {
+          views.truncate(limit);
+          ViewsCursor {
+            index: start + limit - 1,
+            view_id: views[limit - 1].id.clone(),
+          }
+          .encode()
+        } else {
+          String::new()
+        };
+        let items = views
+          .into_iter()
+          .map(view_pb_without_child_views)
+          .collect::<Vec<_>>();
+        (items, next_cursor)
+      },
+    );
+    self.fill_flat_view_extras(&mut items);
+    Ok(ViewsPagePB { items, next_cursor })
+  }
+
+  /// Returns the number of the views under the view, see [count_subtree]. It's cheap enough to be
+  /// called whenever the view is hovered.
+  #[tracing::instrument(level = "trace", skip(self), err)]
+  pub async fn get_subtree_count(&self, view_id: &str) -> FlowyResult<SubtreeCountPB> {
+    self.with_folder(
+      || Err(folder_not_init_error()),
+      |folder| {
+        let trash_ids = get_trash_ids(folder);
+        match folder.views.get_view(view_id) {
+          Some(view) if !is_trashed(folder, view_id, &trash_ids) => {
+            Ok(count_subtree(folder, &view, &trash_ids))
+          },
+          _ => Err(
+            FlowyError::record_not_found()
+              .with_context(format!("Can't find the view with id: {}", view_id)),
+          ),
+        }
+      },
+    )
+  }
+
+  /// Scans the structure of the workspace and reports the problems without changing anything, see
+  /// [check_folder_integrity]. Only the current workspace can be checked.
+  #[tracing::instrument(level = "debug", skip(self), err)]
+  pub async fn check_integrity(&self, workspace_id: &str) -> FlowyResult<FolderIntegrityReportPB> {
+    Ok(self.scan_folder_integrity(workspace_id).await?.report)
+  }
+
+  /// Fixes the problems found by [FolderManager::check_integrity] and returns the report of the
+  /// problems that are left. The orphans and one view of each cycle are moved to the top of the
+  /// workspace, the extra entries of the duplicate views and the dangling favorites are removed,
+  /// and the dangling current view is replaced by the first view of the workspace.
+  #[tracing::instrument(level = "debug", skip(self), err)]
+  pub async fn repair_folder(&self, workspace_id: &str) -> FlowyResult<FolderIntegrityReportPB> {
+    let integrity = self.scan_folder_integrity(workspace_id).await?;
+    if integrity.report.is_healthy() {
+      return Ok(integrity.report);
+    }
+
+    let report = &integrity.report;
+    let (changed_parent_ids, first_view_id) = self.with_folder(
+      || Err(folder_not_init_error()),
+      |folder| {
+        let mut changed_parent_ids = HashSet::from([workspace_id.to_string()]);
+        for (view_id, parent_ids) in &integrity.duplicate_parents {
+          let Some(view) = folder.views.get_view(view_id) else {
+            continue;
+          };
+          // Keep one entry in the view's own parent. If its own parent doesn't list it, all the
+          // entries are removed and the view is moved to the top of the workspace below.
+          let mut kept = !parent_ids.contains(&view.parent_view_id);
+          for parent_id in parent_ids {
+            if !kept && parent_id == &view.parent_view_id {
+              kept = true;
+              continue;
+            }
+            folder.views.dissociate_parent_child(parent_id, view_id);
+            changed_parent_ids.insert(parent_id.clone());
+          }
+          if !parent_ids.contains(&view.parent_view_id) {
+            folder.move_nested_view(view_id, workspace_id, None);
+          }
+        }
+
+        for view_id in report.orphans.iter().chain(report.cycles.iter()) {
+          if let Some(view) = folder.views.get_view(view_id) {
+            changed_parent_ids.insert(view.parent_view_id.clone());
+            folder.move_nested_view(view_id, workspace_id, None);
+          }
+        }
+
+        if !report.dangling_favorites.is_empty() {
+          folder.delete_favorites(report.dangling_favorites.clone());
+        }
+
+        let first_view_id = if report.dangling_current_view {
+          let trash_ids = get_trash_ids(folder);
+          folder
+            .get_workspace_views(workspace_id)
+            .into_iter()
+            .find(|view| !trash_ids.contains(&view.id))
+            .map(|view| view.id.clone())
+        } else {
+          None
+        };
+        Ok::<_, FlowyError>((changed_parent_ids, first_view_id))
+      },
+    )?;
+    tracing::info!(
+      "Repaired the folder of workspace:{}, {:?}",
+      workspace_id,
+      report
+    );
+    notify_parent_view_did_change(
+      self.mutex_folder.clone(),
+      &self.folder_extra,
+      changed_parent_ids.into_iter().collect(),
+    );
+    if let Some(view_id) = first_view_id {
+      self.set_current_view(&view_id).await?;
+    }
+    self.check_integrity(workspace_id).await
+  }
+
+  async fn scan_folder_integrity(&self, workspace_id: &str) -> FlowyResult<FolderIntegrity> {
+    if self.get_current_workspace_id().await? != workspace_id {
+      return Err(
+        FlowyError::not_support().with_context("Only the current workspace can be checked"),
+      );
+    }
+    self.with_folder(
+      || Err(folder_not_init_error()),
+      |folder| Ok(check_folder_integrity(folder, workspace_id)),
+    )
+  }
+
+  /// Returns the child views of the parent view sorted by the given timestamp. If the
+  /// `parent_view_id` is empty, the views of the current workspace will be returned. The trashed
+  /// views are excluded.
+  #[tracing::instrument(level = "debug", skip(self), err)]
+  pub async fn get_views_sorted(
+    &self,
+    parent_view_id: &str,
+    sort_type: ViewSortTypePB,
+    ascending: bool,
+  ) -> FlowyResult<Vec<ViewPB>> {
+    let mut views = if parent_view_id.is_empty() {
+      self.get_current_workspace_views().await?
+    } else {
+      self.get_view_pb(parent_view_id).await?.child_views
+    };
+    views.sort_by(|a, b| {
+      let ordering = match sort_type {
+        ViewSortTypePB::CreatedTime => a.create_time.cmp(&b.create_time),
+        ViewSortTypePB::LastEditedTime => a.last_edited_time.cmp(&b.last_edited_time),
+        ViewSortTypePB::LastOpenedTime => a.last_opened_time.cmp(&b.last_opened_time),
+      };
+      if ascending {
+        ordering
+      } else {
+        ordering.reverse()
+      }
+    });
+    Ok(views)
+  }
+
+  /// Returns the ancestors of the view, ordered from the top level view of the workspace down to
+  /// the parent of the view. The trashed ancestors are included with the `is_trashed` flag set.
+  #[tracing::instrument(level = "debug", skip(self), err)]
+  pub async fn get_ancestors(&self, view_id: &str) -> FlowyResult<Vec<ViewPB>> {
+    let mut ancestors = self.with_folder(
+      || Err(folder_not_init_error()),
+      |folder| {
+        let view = folder.views.get_view(view_id).ok_or_else(|| {
+          FlowyError::record_not_found()
+            .with_context(format!("Can't find the view with id: {}", view_id))
+        })?;
+        let trash_ids = get_trash_ids(folder);
+        let mut visited = HashSet::from([view.id.clone()]);
+        let mut ancestors = vec![];
+        let mut current = folder.views.get_view(&view.parent_view_id);
+        while let Some(parent_view) = current {
+          if !visited.insert(parent_view.id.clone()) {
+            tracing::warn!("The ancestors of the view:{} contain a cycle", view_id);
+            break;
+          }
+          current = folder.views.get_view(&parent_view.parent_view_id);
+          let is_trashed = is_trashed(folder, &parent_view.id, &trash_ids);
+          let mut parent_view_pb = view_pb_without_child_views(parent_view);
+          parent_view_pb.is_trashed = is_trashed;
+          ancestors.push(parent_view_pb);
+        }
+        ancestors.reverse();
+        Ok(ancestors)
+      },
+    )?;
+    self.fill_flat_view_extras(&mut ancestors);
+    Ok(ancestors)
+  }
+
+  /// Returns the path of the view, which is the names of the workspace, the ancestors and the view
+  /// itself. The workspace is omitted if the top of the path is not attached to the workspace.
+  #[tracing::instrument(level = "debug", skip(self), err)]
+  pub async fn get_view_path(&self, view_id: &str) -> FlowyResult<ViewPathPB> {
+    let segments = self.with_folder(
+      || Err(folder_not_init_error()),
+      |folder| {
+        let view = folder.views.get_view(view_id).ok_or_else(|| {
+          FlowyError::record_not_found()
+            .with_context(format!("Can't find the view with id: {}", view_id))
+        })?;
+        let workspace = folder.get_current_workspace();
+        let mut segments = vec![view.name.clone()];
+        let mut visited = HashSet::from([view.id.clone()]);
+        let mut parent_view_id = view.parent_view_id.clone();
+        loop {
+          if let Some(workspace) = workspace
+            .as_ref()
+            .filter(|workspace| workspace.id == parent_view_id)
+          {
+            let extra = self.folder_extra.get(&workspace.id);
+            segments.push(
+              extra
+                .workspace
+                .name
+                .unwrap_or_else(|| workspace.name.clone()),
+            );
+            break;
+          }
+          let parent_view = match folder.views.get_view(&parent_view_id) {
+            None => break,
+            Some(parent_view) => parent_view,
+          };
+          if !visited.insert(parent_view.id.clone()) {
+            tracing::warn!("The ancestors of the view:{} contain a cycle", view_id);
+            break;
+          }
+          segments.push(parent_view.name.clone());
+          parent_view_id = parent_view.parent_view_id.clone();
+        }
+        segments.reverse();
+        Ok(segments)
+      },
+    )?;
+    let joined = segments.join(" / ");
+    Ok(ViewPathPB { segments, joined })
+  }
+
+  /// Returns the view with its descendants nested up to the given `depth`. If the `depth` is zero,
+  /// all the descendants are included. The trashed views are excluded.
+  ///
+  /// The traversal is iterative, so deeply nested folders will not overflow the stack.
+  #[tracing::instrument(level = "debug", skip(self), err)]
+  pub async fn get_view_tree(&self, view_id: &str, depth: u32) -> FlowyResult<ViewTreePB> {
+    let workspace_id = self.get_current_workspace_id().await?;
+    let extra = self.folder_extra.get(&workspace_id);
+    let folder = self.mutex_folder.lock();
+    let folder = folder.as_ref().ok_or_else(folder_not_init_error)?;
+    let trash_ids = get_trash_ids(folder);
+    if trash_ids.contains(view_id) {
+      return Err(FlowyError::record_not_found());
+    }
+    let view = folder
+      .views
+      .get_view(view_id)
+      .ok_or_else(FlowyError::record_not_found)?;
+
+    // Visit the views level by level. Each node records the index of its parent, so the parent
+    // always comes before its children.
+    let mut nodes: Vec<(ViewPB, Option<usize>)> = vec![];
+    let mut visited = HashSet::new();
+    let mut queue = VecDeque::from([(view, None, 0_u32)]);
+    while let Some((view, parent_index, level)) = queue.pop_front() {
+      if !visited.insert(view.id.clone()) {
+        continue;
+      }
+      let index = nodes.len();
+      if depth == 0 || level < depth {
+        for child_view in folder.views.get_views_belong_to(&view.id) {
+          if !trash_ids.contains(&child_view.id) {
+            queue.push_back((child_view, Some(index), level + 1));
+          }
+        }
+      }
+      let mut view_pb = view_pb_without_child_views(view);
+      fill_view_extras(&extra, std::slice::from_mut(&mut view_pb));
+      nodes.push((view_pb, parent_index));
+    }
+
+    // Build the tree from the bottom up.
+    let mut child_views: Vec<Vec<ViewTreePB>> = vec![vec![]; nodes.len()];
+    let mut root = None;
+    for (index, (view, parent_index)) in nodes.into_iter().enumerate().rev() {
+      let mut children = std::mem::take(&mut child_views[index]);
+      children.reverse();
+      let tree = ViewTreePB {
+        view,
+        child_views: children,
+      };
+      match parent_index {
+        None => root = Some(tree),
+        Some(parent_index) => child_views[parent_index].push(tree),
+      }
+    }
+    root.ok_or_else(FlowyError::record_not_found)
+  }
+
+  /// Search the views of the workspace whose name, description or document content matches the
+  /// query. The matching is case-insensitive, and the names support substring and fuzzy matching.
+  /// The views are sorted by the match score and then by the creation time, the newest first.
+  ///
+  /// A content match scores lower than any match of the name or the description, so the
+  /// documents are only read until the limit is filled, the newest first.
+  #[tracing::instrument(level = "debug", skip(self), err)]
+  pub async fn search_views(&self, params: SearchViewsParams) -> FlowyResult<Vec<ViewPB>> {
+    let current_workspace_id = self.get_current_workspace_id().await?;
+    let workspace_id = params
+      .workspace_id
+      .clone()
+      .unwrap_or_else(|| current_workspace_id.clone());
+    self.record_search_query(&workspace_id, &params.query);
+    let views = if workspace_id == current_workspace_id {
+      self.with_folder(Vec::new, |folder| {
+        get_workspace_all_views(folder, &workspace_id, params.include_trash)
+      })
+    } else {
+      let uid = self.user.user_id()?;
+      if !is_exist_in_local_disk(&self.user, &workspace_id)? {
+        return Err(
+          FlowyError::record_not_found()
+            .with_context(format!("Workspace:{} does not exist", workspace_id)),
+        );
+      }
+      let folder = self.open_folder(uid, &workspace_id).await?;
+      get_workspace_all_views(&folder, &workspace_id, params.include_trash)
+    };
+
+    let mut matched_views = vec![];
+    let mut documents = vec![];
+    for view in views {
+      match match_view_score(&view.name, &view.desc, &params.query) {
+        Some(score) => matched_views.push((score, view)),
+        None if view.layout == ViewLayout::Document => documents.push(view),
+        None => {},
+      }
+    }
+    if let Ok(handler) = self.get_handler(&ViewLayout::Document) {
+      documents.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+      for view in documents {
+        if params.limit > 0 && matched_views.len() >= params.limit {
+          break;
+        }
+        match handler.export_view_to_markdown(&view.id).await {
+          Ok(content) => {
+            if let Some(score) = match_content_score(&content, &params.query) {
+              matched_views.push((score, view));
+            }
+          },
+          Err(err) => tracing::trace!("Skip searching the content of view:{}: {}", view.id, err),
+        }
+      }
+    }
+    matched_views.sort_by(|(score_a, view_a), (score_b, view_b)| {
+      score_b
+        .cmp(score_a)
+        .then_with(|| view_b.created_at.cmp(&view_a.created_at))
+    });
+    if params.limit > 0 {
+      matched_views.truncate(params.limit);
+    }
+    let mut views = matched_views
+      .into_iter()
+      .map(|(_, view)| view_pb_without_child_views(view))
+      .collect::<Vec<_>>();
+    fill_view_extras(&self.get_folder_extra(&workspace_id).await, &mut views);
+    Ok(views)
+  }
+
+  /// Move the view to trash. If the view is the current view, then set the current view to empty.
+  /// When the view is moved to trash, all the child views will be moved to trash as well.
+  /// All the favorite views being trashed will be unfavorited first to remove it from favorites list as well. The process of unfavoriting concerned view is handled by `unfavorite_view_and_decendants()`
+  ///
+  /// Returns the ids of the view and its descendants that were not in the trash before.
+  #[tracing::instrument(level = "debug", skip(self), err)]
+  pub async fn move_view_to_trash(&self, view_id: &str) -> FlowyResult<Vec<String>> {
+    self.move_view_to_trash_with_reason(view_id, None).await
+  }
+
+  /// Moves the view to the trash like [Self::move_view_to_trash] and records the reason. The
+  /// reason of the previous deletion is cleared if no reason is given.
+  ///
+  /// The reason is recorded before the view is trashed, so the [FolderNotification::DidUpdateTrash]
+  /// sent for the trashed view already carries it. The previous reason is put back if the view
+  /// can't be trashed.
+  #[tracing::instrument(level = "debug", skip(self), err)]
+  pub async fn move_view_to_trash_with_reason(
+    &self,
+    view_id: &str,
+    reason: Option<String>,
+  ) -> FlowyResult<Vec<String>> {
+    let workspace_id = self.get_current_workspace_id().await?;
+    let previous_reason = self.set_trash_reason(&workspace_id, view_id, reason)?;
+    match self.trash_view(view_id) {
+      Ok(trashed_ids) => Ok(trashed_ids),
+      Err(err) => {
+        self.set_trash_reason(&workspace_id, view_id, previous_reason)?;
+        Err(err)
+      },
+    }
+  }
+
+  /// Sets the trash reason of the view and returns the previous one.
+  fn set_trash_reason(
+    &self,
+    workspace_id: &str,
+    view_id: &str,
+    reason: Option<String>,
+  ) -> FlowyResult<Option<String>> {
+    self
+      .folder_extra
+      .update(workspace_id, |extra| match reason {
+        Some(reason) => extra
+          .views
+          .entry(view_id.to_string())
+          .or_default()
+          .trash_reason
+          .replace(reason),
+        None => extra
+          .views
+          .get_mut(view_id)
+          .and_then(|view_extra| view_extra.trash_reason.take()),
+      })
+  }
+
+  /// Moves the child views of the view that are not in the trash to its parent, right after the
+  /// view and in their order. Each move is recorded, so undoing the trash of the view moves the
+  /// child views back. Returns the ids of the moved child views.
+  fn reparent_child_views_in_folder(
+    &self,
+    folder: &Folder,
+    view_id: &str,
+  ) -> FlowyResult<Vec<String>> {
+    let view = folder.views.get_view(view_id).ok_or_else(|| {
+      FlowyError::record_not_found()
+        .with_context(format!("Can't find the view with id: {}", view_id))
+    })?;
+    let child_view_ids = get_untrashed_child_view_ids(folder, &view);
+    let mut prev_view_id = view.id.clone();
+    for child_view_id in &child_view_ids {
+      let old_prev_view_id = get_prev_view_id(folder, &view.id, child_view_id);
+      folder.move_nested_view(
+        child_view_id,
+        &view.parent_view_id,
+        Some(prev_view_id.clone()),
+      );
+      self.operation_history.record(FolderOperation::MoveView {
+        view_id: child_view_id.clone(),
+        old_parent_id: view.id.clone(),
+        old_prev_view_id,
+        new_parent_id: view.parent_view_id.clone(),
+        new_prev_view_id: Some(prev_view_id),
+      });
+      prev_view_id = child_view_id.clone();
+    }
+    Ok(child_view_ids)
+  }
+
+  /// Moves all the descendants of the view to the trash and keeps the view itself. Only the
+  /// direct child views are added to the trash, the nested ones are trashed with them. Returns
+  /// the number of the descendants that were moved to the trash.
+  ///
+  /// The removed child views are notified in one [FolderNotification::DidUpdateChildViews].
+  #[tracing::instrument(level = "debug", skip(self), err)]
+  pub async fn trash_children(&self, view_id: &str) -> FlowyResult<usize> {
+    self.check_view_unlocked(view_id)?;
+    let (child_view_ids, subtree_view_ids) = self.with_folder(
+      || Err(folder_not_init_error()),
+      |folder| {
+        let view = folder.views.get_view(view_id).ok_or_else(|| {
+          FlowyError::record_not_found()
+            .with_context(format!("Can't find the view with id: {}", view_id))
+        })?;
+        let trash_ids = get_trash_ids(folder);
+        let child_view_ids = view
+          .children
+          .items
+          .iter()
+          .filter(|child| !trash_ids.contains(&child.id))
+          .map(|child| child.id.clone())
+          .collect::<Vec<_>>();
+        // Every view under the children is trashed along with them, so none of them can be locked.
+        let subtree_view_ids = child_view_ids
+          .iter()
+          .flat_map(|child_view_id| get_view_and_descendants(folder, child_view_id))
+          .filter(|view| !is_trashed(folder, &view.id, &trash_ids))
+          .map(|view| view.id.clone())
+          .collect::<Vec<_>>();
+        Ok((child_view_ids, subtree_view_ids))
+      },
+    )?;
+    self.check_views_unlocked(&subtree_view_ids)?;
+    if child_view_ids.is_empty() {
+      return Ok(0);
+    }
+
+    let trashed_count = self.with_folder(
+      || 0,
+      |folder| {
+        let trash_ids = get_trash_ids(folder);
+        let mut trashed_count = 0;
+        for child_view_id in &child_view_ids {
+          trashed_count += get_view_and_descendants(folder, child_view_id)
+            .into_iter()
+            .filter(|view| !is_trashed(folder, &view.id, &trash_ids))
+            .count();
+          if let Some(child_view) = folder.views.get_view(child_view_id) {
+            self.unfavorite_view_and_decendants(child_view, folder);
+          }
+        }
+        folder.add_trash(child_view_ids.clone());
+        trashed_count
+      },
+    );
+    for child_view_id in &child_view_ids {
+      self.operation_history.record(FolderOperation::DeleteView {
+        view_id: child_view_id.clone(),
+      });
+    }
+    send_notification(view_id, FolderNotification::DidUpdateChildViews)
+      .payload(ChildViewUpdatePB {
+        parent_view_id: view_id.to_string(),
+        delete_child_views: child_view_ids,
+        ..Default::default()
+      })
+      .send();
+    Ok(trashed_count)
+  }
+
+  fn trash_view(&self, view_id: &str) -> FlowyResult<Vec<String>> {
+    self.check_view_unlocked(view_id)?;
+    self.with_folder(
+      || Err(folder_not_init_error()),
+      |folder| self.trash_view_in_folder(folder, view_id),
+    )
+  }
+
+  fn trash_view_in_folder(&self, folder: &Folder, view_id: &str) -> FlowyResult<Vec<String>> {
+    let view = folder.views.get_view(view_id).ok_or_else(|| {
+      FlowyError::record_not_found()
+        .with_context(format!("Can't find the view with id: {}", view_id))
+    })?;
+    let trash_ids = get_trash_ids(folder);
+    let trashed_ids = get_view_and_descendants(folder, view_id)
+      .into_iter()
+      .filter(|view| !is_trashed(folder, &view.id, &trash_ids))
+      .map(|view| view.id.clone())
+      .collect::<Vec<_>>();
+    self.unfavorite_view_and_decendants(view.clone(), folder);
+    folder.add_trash(vec![view_id.to_string()]);
+    // notify the parent view that the view is moved to trash
+    send_notification(view_id, FolderNotification::DidMoveViewToTrash)
+      .payload(DeletedViewPB {
+        view_id: view_id.to_string(),
+        index: None,
+      })
+      .send();
+
+    notify_child_views_changed(
+      view_pb_without_child_views(view),
+      ChildViewChangeReason::DidDeleteView,
+    );
+    self.operation_history.record(FolderOperation::DeleteView {
+      view_id: view_id.to_string(),
+    });
+    Ok(trashed_ids)
+  }
+
+  /// Moves the views to the trash in one pass. The view whose ancestor is also in the list is
+  /// trashed together with the ancestor instead of being added to the trash on its own, so each
+  /// subtree is trashed exactly once. Nothing is trashed if any of the views is locked.
+  ///
+  /// If `cascade` is false, the child views that are not in the trash are moved to the parent of
+  /// the view right before the view is trashed, so only the view itself is trashed. The child
+  /// views are locked-checked as well, since they are moved.
+  ///
+  /// The views that can't be found are reported as the failures.
+  #[tracing::instrument(level = "debug", skip(self), err)]
+  pub async fn move_views_to_trash(
+    &self,
+    view_ids: Vec<String>,
+    cascade: bool,
+  ) -> FlowyResult<DeleteResultPB> {
+    self.check_views_unlocked(&view_ids)?;
+    if !cascade {
+      let child_view_ids = self.with_folder(Vec::new, |folder| {
+        view_ids
+          .iter()
+          .filter_map(|view_id| folder.views.get_view(view_id))
+          .flat_map(|view| get_untrashed_child_view_ids(folder, &view))
+          .collect::<Vec<_>>()
+      });
+      self.check_views_unlocked(&child_view_ids)?;
+    }
+    // The reasons of the previous deletions are cleared like [Self::move_view_to_trash], before
+    // the views are trashed. The reasons of the views that fail to be trashed are put back.
+    let workspace_id = self.get_current_workspace_id().await?;
+    let previous_reasons = self.folder_extra.update(&workspace_id, |extra| {
+      view_ids
+        .iter()
+        .filter_map(|view_id| {
+          let reason = extra.views.get_mut(view_id)?.trash_reason.take()?;
+          Some((view_id.clone(), reason))
+        })
+        .collect::<HashMap<_, _>>()
+    })?;
+    let (result, trashed_root_ids, reparented_parent_ids) = self.with_folder(
+      || Err(folder_not_init_error()),
+      |folder| {
+        let view_id_set = view_ids
+          .iter()
+          .map(|view_id| view_id.as_str())
+          .collect::<HashSet<_>>();
+        let mut result = DeleteResultPB::default();
+        let mut trashed_root_ids = vec![];
+        let mut reparented_parent_ids = vec![];
+        let mut visited = HashSet::new();
+        for view_id in &view_ids {
+          if !visited.insert(view_id.as_str()) || has_ancestor_in(folder, view_id, &view_id_set) {
+            continue;
+          }
+          // The child views are moved and the view is trashed under the same lock of the folder,
+          // so no one sees the view trashed without the child views moved out.
+          if !cascade {
+            match self.reparent_child_views_in_folder(folder, view_id) {
+              Ok(reparented_ids) => {
+                if !reparented_ids.is_empty() {
+                  if let Some(view) = folder.views.get_view(view_id) {
+                    reparented_parent_ids.push(view.parent_view_id.clone());
+                  }
+                  reparented_parent_ids.push(view_id.clone());
+                }
+                result.reparented_ids.extend(reparented_ids);
+              },
+              Err(err) => {
+                result.failures.push(DeleteViewFailurePB {
+                  view_id: view_id.clone(),
+                  code: err.code.value(),
+                  msg: err.msg,
+                });
+                continue;
+              },
+            }
+          }
+          match self.trash_view_in_folder(folder, view_id) {
+            Ok(trashed_ids) => {
+              for trashed_id in trashed_ids {
+                if !result.trashed_ids.items.contains(&trashed_id) {
+                  result.trashed_ids.items.push(trashed_id);
+                }
+              }
+              trashed_root_ids.push(view_id.clone());
+            },
+            Err(err) => result.failures.push(DeleteViewFailurePB {
+              view_id: view_id.clone(),
+              code: err.code.value(),
+              msg: err.msg,
+            }),
+          }
+        }
+        Ok((result, trashed_root_ids, reparented_parent_ids))
+      },
+    )?;
+    if !reparented_parent_ids.is_empty() {
+      notify_parent_view_did_change(
+        self.mutex_folder.clone(),
+        &self.folder_extra,
+        reparented_parent_ids,
+      );
+    }
+
+    if previous_reasons
+      .keys()
+      .any(|view_id| !trashed_root_ids.contains(view_id))
+    {
+      self.folder_extra.update(&workspace_id, |extra| {
+        for (view_id, reason) in previous_reasons {
+          if trashed_root_ids.contains(&view_id) {
+            continue;
+          }
+          if let Some(view_extra) = extra.views.get_mut(&view_id) {
+            view_extra.trash_reason = Some(reason);
+          }
+        }
+      })?;
+    }
+    Ok(result)
+  }
+
+  /// Deletes the views and all of their descendants permanently without moving them to the trash.
+  /// The resources that the views hold will be deleted as well.
+  ///
+  /// Nothing is deleted if any of the views can't be found, or any of the views and their
+  /// descendants is locked. The error's payload contains the ids of the views that were not found.
+  #[tracing::instrument(level = "debug", skip(self), err)]
+  pub async fn delete_views_permanently(&self, view_ids: Vec<String>) -> FlowyResult<()> {
+    let subtree_view_ids = self.with_folder(Vec::new, |folder| {
+      view_ids
+        .iter()
+        .flat_map(|view_id| get_view_and_descendants(folder, view_id))
+        .map(|view| view.id.clone())
+        .collect::<Vec<_>>()
+    });
+    self.check_views_unlocked(&subtree_view_ids)?;
+    let (deleted_views, parent_view_ids) = self.with_folder(
+      || Err(folder_not_init_error()),
+      |folder| {
+        let workspace_id = folder.get_workspace_id();
+        if view_ids.iter().any(|view_id| view_id == &workspace_id) {
+          return Err(FlowyError::invalid_data().with_context("Can't delete the workspace"));
+        }
+
+        let not_found_ids = view_ids
+          .iter()
+          .filter(|view_id| folder.views.get_view(view_id).is_none())
+          .cloned()
+          .collect::<Vec<String>>();
+        if !not_found_ids.is_empty() {
+          return Err(
+            FlowyError::record_not_found()
+              .with_context(format!(
+                "Can't find the views: {}",
+                not_found_ids.join(", ")
+              ))
+              .with_payload(RepeatedViewIdPB {
+                items: not_found_ids,
+                cascade: None,
+              }),
+          );
+        }
+
+        let mut visited = HashSet::new();
+        let mut deleted_views = vec![];
+        let mut parent_view_ids = vec![];
+        for view_id in &view_ids {
+          for view in get_view_and_descendants(folder, view_id) {
+            if visited.insert(view.id.clone()) {
+              deleted_views.push(view);
+            }
+          }
+        }
+        for view in &deleted_views {
+          if view_ids.contains(&view.id) && !visited.contains(&view.parent_view_id) {
+            parent_view_ids.push(view.parent_view_id.clone());
+          }
+        }
+
+        let favorite_views = deleted_views
+          .iter()
+          .filter(|view| view.is_favorite)
+          .map(|view| view_pb_without_child_views(view.clone()))
+          .collect::<Vec<ViewPB>>();
+        if !favorite_views.is_empty() {
+          folder.delete_favorites(favorite_views.iter().map(|view| view.id.clone()).collect());
+          send_notification("favorite", FolderNotification::DidUnfavoriteView)
+            .payload(RepeatedViewPB {
+              items: favorite_views,
+            })
+            .send();
+        }
+
+        let deleted_ids = deleted_views
+          .iter()
+          .map(|view| view.id.clone())
+          .collect::<Vec<String>>();
+        folder.delete_trash(deleted_ids.clone());
+        folder
+          .views
+          .delete_views(deleted_ids.iter().map(|id| id.as_str()).collect());
+        Ok((deleted_views, parent_view_ids))
+      },
+    )?;
+
+    for view in &deleted_views {
+      if let Ok(handler) = self.get_handler(&view.layout) {
+        if let Err(err) = handler.delete_view(&view.id).await {
+          tracing::error!(
+            "Failed to delete the data of view:{}, error: {:?}",
+            view.id,
+            err
+          );
+        }
+      }
+    }
+
+    for view in deleted_views {
+      if view_ids.contains(&view.id) {
+        notify_child_views_changed(
+          view_pb_without_child_views(view),
+          ChildViewChangeReason::DidDeleteView,
+        );
+      }
+    }
+    notify_parent_view_did_change(
+      self.mutex_folder.clone(),
+      &self.folder_extra,
+      parent_view_ids.clone(),
+    );
+    self.notify_did_reorder_child_views(parent_view_ids).await;
+    Ok(())
+  }
+
+  fn unfavorite_view_and_decendants(&self, view: Arc<View>, folder: &Folder) {
+    let mut all_descendant_views: Vec<Arc<View>> = vec![view.clone()];
+    all_descendant_views.extend(folder.views.get_views_belong_to(&view.id));
+
+    let favorite_descendant_views: Vec<ViewPB> = all_descendant_views
+      .iter()
+      .filter(|view| view.is_favorite)
+      .map(|view| view_pb_without_child_views(view.clone()))
+      .collect();
+
+    if !favorite_descendant_views.is_empty() {
+      folder.delete_favorites(
+        favorite_descendant_views
+          .iter()
+          .map(|v| v.id.clone())
+          .collect(),
+      );
+      send_notification("favorite", FolderNotification::DidUnfavoriteView)
+        .payload(RepeatedViewPB {
+          items: favorite_descendant_views,
+        })
+        .send();
+    }
+  }
+
+  /// Moves a nested view to a new location in the hierarchy.
+  ///
+  /// This function takes the `view_id` of the view to be moved,
+  /// `new_parent_id` of the view under which the `view_id` should be moved,
+  /// and an optional `prev_view_id` to position the `view_id` right after
+  /// this specific view.
+  ///
+  /// If `prev_view_id` is provided, the moved view will be placed right after
+  /// the view corresponding to `prev_view_id` under the `new_parent_id`.
+  /// If `prev_view_id` is `None`, the moved view will become the first child of the new parent.
+  ///
+  /// # Arguments
+  ///
+  /// * `view_id` - A string slice that holds the id of the view to be moved.
+  /// * `new_parent_id` - A string slice that holds the id of the new parent view.
+  /// * `prev_view_id` - An `Option<String>` that holds the id of the view after which the `view_id` should be positioned.
+  ///
+  #[tracing::instrument(level = "trace", skip(self), err)]
+  pub async fn move_nested_view(
+    &self,
+    view_id: String,
+    new_parent_id: String,
+    prev_view_id: Option<String>,
+  ) -> FlowyResult<()> {
+    self.check_view_unlocked(&view_id)?;
+    self.check_nesting_depth(&new_parent_id, Some(&view_id))?;
+    let view = self.get_view_pb(&view_id).await?;
+    let old_parent_id = view.parent_view_id;
+    let operation = self.with_folder(
+      || None,
+      |folder| {
+        let old_prev_view_id = get_prev_view_id(folder, &old_parent_id, &view_id);
+        folder.move_nested_view(&view_id, &new_parent_id, prev_view_id);
+        Some(FolderOperation::MoveView {
+          view_id: view_id.clone(),
+          old_parent_id: old_parent_id.clone(),
+          old_prev_view_id,
+          new_parent_id: new_parent_id.clone(),
+          new_prev_view_id: get_prev_view_id(folder, &new_parent_id, &view_id),
+        })
+      },
+    );
+    if let Some(operation) = operation {
+      self.operation_history.record(operation);
+    }
+    notify_parent_view_did_change(
+      self.mutex_folder.clone(),
+      &self.folder_extra,
+      vec![new_parent_id.clone(), old_parent_id.clone()],
+    );
+    self
+      .notify_did_reorder_child_views(vec![old_parent_id, new_parent_id])
+      .await;
+    Ok(())
+  }
+
+  /// Moves the views under the new parent at once. The moved views are placed after the existing
+  /// child views of the new parent, and they keep the order of `view_ids`.
+  ///
+  /// None of the views will be moved if any of them is locked, or if the new parent is one of the
+  /// views or their descendants.
+  #[tracing::instrument(level = "debug", skip(self), err)]
+  pub async fn move_views(&self, view_ids: Vec<String>, new_parent_id: String) -> FlowyResult<()> {
+    for view_id in &view_ids {
+      self.check_view_unlocked(view_id)?;
+      self.check_nesting_depth(&new_parent_id, Some(view_id))?;
+    }
+    let (old_parent_ids, operations) = self.with_folder(
+      || Err(folder_not_init_error()),
+      |folder| {
+        if new_parent_id != folder.get_workspace_id()
+          && folder.views.get_view(&new_parent_id).is_none()
+        {
+          return Err(
+            FlowyError::record_not_found()
+              .with_context(format!("Can't find the view with id: {}", new_parent_id)),
+          );
+        }
+
+        let mut old_parent_ids = vec![];
+        for view_id in &view_ids {
+          let view = folder.views.get_view(view_id).ok_or_else(|| {
+            FlowyError::record_not_found()
+              .with_context(format!("Can't find the view with id: {}", view_id))
+          })?;
+          if *view_id == new_parent_id || is_descendant_of(folder, &new_parent_id, view_id) {
+            return Err(
+              FlowyError::invalid_data()
+                .with_context("The view can't be moved into itself or its descendant"),
+            );
+          }
+          if !old_parent_ids.contains(&view.parent_view_id) {
+            old_parent_ids.push(view.parent_view_id.clone());
+          }
+        }
+
+        let mut prev_view_id = folder
+          .views
+          .get_views_belong_to(&new_parent_id)
+          .into_iter()
+          .filter(|view| !view_ids.contains(&view.id))
+          .last()
+          .map(|view| view.id.clone());
+        let mut operations = vec![];
+        for view_id in &view_ids {
+          let old_parent_id = folder
+            .views
+            .get_view(view_id)
+            .map(|view| view.parent_view_id.clone())
+            .unwrap_or_default();
+          let old_prev_view_id = get_prev_view_id(folder, &old_parent_id, view_id);
+          folder.move_nested_view(view_id, &new_parent_id, prev_view_id.clone());
+          operations.push(FolderOperation::MoveView {
+            view_id: view_id.clone(),
+            old_parent_id,
+            old_prev_view_id,
+            new_parent_id: new_parent_id.clone(),
+            new_prev_view_id: prev_view_id,
+          });
+          prev_view_id = Some(view_id.clone());
+        }
+        Ok((old_parent_ids, operations))
+      },
+    )?;
+    for operation in operations {
+      self.operation_history.record(operation);
+    }
+
+    let mut parent_view_ids = vec![new_parent_id];
+    for old_parent_id in old_parent_ids {
+      if !parent_view_ids.contains(&old_parent_id) {
+        parent_view_ids.push(old_parent_id);
+      }
+    }
+    notify_parent_view_did_change(
+      self.mutex_folder.clone(),
+      &self.folder_extra,
+      parent_view_ids,
+    );
+    Ok(())
+  }
+
+  /// Merges the source view into the target view. The child views of the source are moved after
+  /// the existing child views of the target in their original order, and then the source is
+  /// moved to the trash. Returns the target view with its new child views.
+  ///
+  /// Nothing is changed if the source is the target or one of its ancestors, or if the source,
+  /// the target or any of the moved child views is locked.
+  #[tracing::instrument(level = "debug", skip(self), err)]
+  pub async fn merge_views(&self, params: MergeViewsParams) -> FlowyResult<ViewPB> {
+    let child_view_ids = self.with_folder(Vec::new, |folder| {
+      folder
+        .views
+        .get_view(&params.source_view_id)
+        .map(|view| {
+          view
+            .children
+            .items
+            .iter()
+            .map(|child| child.id.clone())
+            .collect::<Vec<_>>()
+        })
+        .unwrap_or_default()
+    });
+    self.check_views_unlocked(&[&params.source_view_id, &params.target_view_id])?;
+    self.check_views_unlocked(&child_view_ids)?;
+    for child_view_id in &child_view_ids {
+      self.check_nesting_depth(&params.target_view_id, Some(child_view_id))?;
+    }
+
+    // The reason of the previous deletion is cleared like [Self::move_view_to_trash].
+    let workspace_id = self.get_current_workspace_id().await?;
+    let previous_reason = self.set_trash_reason(&workspace_id, &params.source_view_id, None)?;
+
+    // The child views are moved and the source is trashed under the same lock of the folder, so
+    // no one sees the child views moved without the source trashed.
+    let result = self.with_folder(
+      || Err(folder_not_init_error()),
+      |folder| {
+        folder
+          .views
+          .get_view(&params.source_view_id)
+          .ok_or_else(|| {
+            FlowyError::record_not_found().with_context(format!(
+              "Can't find the view with id: {}",
+              params.source_view_id
+            ))
+          })?;
+        if folder.views.get_view(&params.target_view_id).is_none() {
+          return Err(FlowyError::record_not_found().with_context(format!(
+            "Can't find the view with id: {}",
+            params.target_view_id
+          )));
+        }
+        if params.source_view_id == params.target_view_id
+          || is_descendant_of(folder, &params.target_view_id, &params.source_view_id)
+        {
+          return Err(
+            FlowyError::invalid_data()
+              .with_context("The view can't be merged into itself or its descendant"),
+          );
+        }
+
+        let mut prev_view_id = folder
+          .views
+          .get_views_belong_to(&params.target_view_id)
+          .last()
+          .map(|view| view.id.clone());
+        for child_view_id in &child_view_ids {
+          let old_prev_view_id = get_prev_view_id(folder, &params.source_view_id, child_view_id);
+          folder.move_nested_view(child_view_id, &params.target_view_id, prev_view_id.clone());
+          self.operation_history.record(FolderOperation::MoveView {
+            view_id: child_view_id.clone(),
+            old_parent_id: params.source_view_id.clone(),
+            old_prev_view_id,
+            new_parent_id: params.target_view_id.clone(),
+            new_prev_view_id: prev_view_id,
+          });
+          prev_view_id = Some(child_view_id.clone());
+        }
+        self.trash_view_in_folder(folder, &params.source_view_id)?;
+        Ok::<_, FlowyError>(())
+      },
+    );
+    if let Err(err) = result {
+      self.set_trash_reason(&workspace_id, &params.source_view_id, previous_reason)?;
+      return Err(err);
+    }
+    notify_parent_view_did_change(
+      self.mutex_folder.clone(),
+      &self.folder_extra,
+      vec![params.target_view_id.clone(), params.source_view_id.clone()],
+    );
+    self.get_view_pb(&params.target_view_id).await
+  }
+
+  /// Moves the view and all of its descendants to another workspace.
+  ///
+  /// The view will be attached to the `parent_view_id` of the target workspace. If the
+  /// `parent_view_id` is `None`, the view will be attached to the root of the target workspace.
+  /// The order of the descendants is preserved, and the extra data of the views moves with them.
+  /// The descendants in the trash are deleted instead of being moved. If the moved view is the
+  /// current view, the current view is cleared.
+  #[tracing::instrument(level = "debug", skip(self), err)]
+  pub async fn move_view_to_workspace(
+    &self,
+    view_id: &str,
+    target_workspace_id: &str,
+    parent_view_id: Option<String>,
+  ) -> FlowyResult<()> {
+    let current_workspace_id = self.get_current_workspace_id().await?;
+    let parent_view_id = parent_view_id.unwrap_or_else(|| target_workspace_id.to_string());
+    if current_workspace_id == target_workspace_id {
+      let is_cycle = self.with_folder(
+        || false,
+        |folder| parent_view_id == view_id || is_descendant_of(folder, &parent_view_id, view_id),
+      );
+      if is_cycle {
+        return Err(
+          FlowyError::invalid_data().with_context("The view can't be moved into its descendant"),
+        );
+      }
+      return self
+        .move_nested_view(view_id.to_string(), parent_view_id, None)
+        .await;
+    }
+
+    let uid = self.user.user_id()?;
+    if !is_exist_in_local_disk(&self.user, target_workspace_id)? {
+      return Err(
+        FlowyError::record_not_found()
+          .with_context(format!("Workspace:{} does not exist", target_workspace_id)),
+      );
+    }
+
+    let (old_parent_id, views, trashed_views) = self.with_folder(
+      || Err(folder_not_init_error()),
+      |folder| {
+        let view = folder
+          .views
+          .get_view(view_id)
+          .ok_or_else(|| FlowyError::record_not_found().with_context("Can't find the view"))?;
+        let trash_ids = get_trash_ids(folder);
+        let (views, trashed_views): (Vec<_>, Vec<_>) = get_view_and_descendants(folder, view_id)
+          .into_iter()
+          .partition(|view| view.id == view_id || !is_trashed(folder, &view.id, &trash_ids));
+        let views = views
+          .into_iter()
+          .map(|view| view.as_ref().clone())
+          .collect::<Vec<View>>();
+        Ok::<_, FlowyError>((view.parent_view_id.clone(), views, trashed_views))
+      },
+    )?;
+    let moved_view_ids = views
+      .iter()
+      .map(|view| view.id.as_str())
+      .chain(trashed_views.iter().map(|view| view.id.as_str()))
+      .collect::<Vec<_>>();
+    self.check_views_unlocked(&moved_view_ids)?;
+    let view_extras = {
+      let mut source_view_extras = self.folder_extra.get(&current_workspace_id).views;
+      views
+        .iter()
+        .filter_map(|view| Some((view.id.clone(), source_view_extras.remove(&view.id)?)))
+        .collect::<HashMap<_, _>>()
+    };
+
+    let max_depth = self
+      .get_folder_extra(target_workspace_id)
+      .await
+      .setting
+      .max_nesting_depth;
+    let subtree_depth = self.with_folder(|| 1, |folder| get_subtree_depth(folder, view_id));
+    // The folder and its extra data are written through the same collab.
+    let target_collab = self.open_folder_collab(uid, target_workspace_id).await?;
+    let target_folder = Folder::open(UserId::from(uid), target_collab.clone(), None)?;
+    if parent_view_id != target_workspace_id
+      && target_folder.views.get_view(&parent_view_id).is_none()
+    {
+      return Err(FlowyError::record_not_found().with_context(format!(
+        "Can't find the parent view:{} in workspace:{}",
+        parent_view_id, target_workspace_id
+      )));
+    }
+    if max_depth > 0 {
+      check_depth(
+        get_view_depth(&target_folder, &parent_view_id) + subtree_depth,
+        max_depth,
+      )?;
+    }
+
+    // Insert the parent view before its children, so that the children will be appended to the
+    // parent in the original order.
+    for mut view in views.clone() {
+      if view.id == view_id {
+        view.parent_view_id = parent_view_id.clone();
+      }
+      view.children = Default::default();
+      target_folder.insert_view(view, None);
+    }
+    if !view_extras.is_empty() {
+      FolderExtra::update_collab(&target_collab, |extra| extra.views.extend(view_extras));
+    }
+
+    self.with_folder(
+      || (),
+      |folder| {
+        if let Some(view) = folder.views.get_view(view_id) {
+          self.unfavorite_view_and_decendants(view, folder);
+        }
+        let is_current_view_moved = folder.get_current_view().map_or(false, |current_view_id| {
+          moved_view_ids.contains(&current_view_id.as_str())
+        });
+        if is_current_view_moved {
+          folder.set_current_view("");
+        }
+        folder.delete_trash(trashed_views.iter().map(|view| view.id.clone()).collect());
+        folder.views.delete_views(moved_view_ids.clone());
+      },
+    );
+    for view in &trashed_views {
+      if let Ok(handler) = self.get_handler(&view.layout) {
+        if let Err(err) = handler.delete_view(&view.id).await {
+          tracing::error!(
+            "Failed to delete the data of the trashed view:{}, error: {:?}",
+            view.id,
+            err
+          );
+        }
+      }
+    }
+    notify_parent_view_did_change(
+      self.mutex_folder.clone(),
+      &self.folder_extra,
+      vec![old_parent_id],
+    );
+    Ok(())
+  }
+
+  /// Copies the view to the root of the target workspace with new ids. The original view is not
+  /// changed. If `include_children` is true, the descendants are copied as well, except the ones
+  /// in the trash. Returns the copied view.
+  ///
+  /// The content of the views is copied as it is, so the references to other views inside the
+  /// content still point to the original views.
+  #[tracing::instrument(level = "debug", skip(self), err)]
+  pub async fn copy_view_to_workspace(
+    &self,
+    view_id: &str,
+    target_workspace_id: &str,
+    include_children: bool,
+  ) -> FlowyResult<ViewPB> {
+    let uid = self.user.user_id()?;
+    let current_workspace_id = self.get_current_workspace_id().await?;
+    let is_current_workspace = current_workspace_id == target_workspace_id;
+    if !is_current_workspace && !is_exist_in_local_disk(&self.user, target_workspace_id)? {
+      return Err(
+        FlowyError::record_not_found()
+          .with_context(format!("Workspace:{} does not exist", target_workspace_id)),
+      );
+    }
+
+    let views = self.with_folder(Vec::new, |folder| {
+      if include_children {
+        get_view_and_descendants(folder, view_id)
+      } else {
+        folder.views.get_view(view_id).into_iter().collect()
+      }
+    });
+    if views.is_empty() {
+      return Err(
+        FlowyError::record_not_found().with_context(format!("Can't find the view:{}", view_id)),
+      );
+    }
+    let trash_ids = self.with_folder(HashSet::new, get_trash_ids);
+
+    let mut new_view_ids: HashMap<String, String> = HashMap::with_capacity(views.len());
+    // The depth of each copy in the target workspace, the copied root is at the top level.
+    let mut depths: HashMap<String, usize> = HashMap::with_capacity(views.len());
+    let mut create_params = Vec::with_capacity(views.len());
+    for view in &views {
+      let parent_view_id = if view.id == view_id {
+        target_workspace_id.to_string()
+      } else if trash_ids.contains(&view.id) {
+        continue;
+      } else {
+        // The parent of a trashed view is skipped, so are its descendants.
+        match new_view_ids.get(&view.parent_view_id) {
+          None => continue,
+          Some(parent_view_id) => parent_view_id.clone(),
+        }
+      };
+
+      let handler = self.get_handler(&view.layout)?;
+      let view_data = handler.duplicate_view(&view.id).await?;
+      let new_view_id = gen_view_id().to_string();
+      new_view_ids.insert(view.id.clone(), new_view_id.clone());
+      let depth = depths.get(&parent_view_id).map_or(1, |depth| depth + 1);
+      depths.insert(new_view_id.clone(), depth);
+      create_params.push(CreateViewParams {
+        parent_view_id,
+        name: view.name.clone(),
+        desc: view.desc.clone(),
+        layout: Some(view.layout.clone().into()),
+        initial_data: view_data.to_vec(),
+        view_id: new_view_id,
+        meta: Default::default(),
+        set_as_current: false,
+        index: None,
+      });
+    }
+    let new_root_id = new_view_ids
+      .get(view_id)
+      .cloned()
+      .ok_or_else(FlowyError::internal)?;
+    tracing::warn!(
+      "Copy {} views to workspace:{}, the references to other views inside their content are kept \
+       as they are",
+      create_params.len(),
+      target_workspace_id
+    );
+
+    if is_current_workspace {
+      self.create_views_with_params(create_params).await?;
+      notify_parent_view_did_change(
+        self.mutex_folder.clone(),
+        &self.folder_extra,
+        vec![target_workspace_id.to_string()],
+      );
+      return self.get_view_pb(&new_root_id).await;
+    }
+
+    // The folder and its extra data are written through the same collab. The settings of the
+    // target workspace apply to the copies like the views created in it.
+    let target_collab = self.open_folder_collab(uid, target_workspace_id).await?;
+    let target_folder = Folder::open(UserId::from(uid), target_collab.clone(), None)?;
+    let target_setting = FolderExtra::from_collab(&target_collab).setting;
+    if target_setting.view_limit > 0 {
+      let count = get_workspace_all_views(&target_folder, target_workspace_id, true).len();
+      let remaining = (target_setting.view_limit as usize).saturating_sub(count);
+      if create_params.len() > remaining {
+        return Err(FlowyError::view_limit_reached().with_context(format!(
+          "Only {} more views can be created in the workspace",
+          remaining
+        )));
+      }
+    }
+    if target_setting.max_nesting_depth > 0 {
+      let copy_depth = depths.values().copied().max().unwrap_or(1);
+      check_depth(copy_depth, target_setting.max_nesting_depth)?;
+    }
+
+    // The views are inserted into the target workspace after the data of all the views is
+    // created, so that a failure doesn't leave the copied views half done.
+    let mut created: Vec<(Arc<dyn FolderOperationHandler + Send + Sync>, String)> = vec![];
+    let mut new_views = Vec::with_capacity(create_params.len());
+    for params in create_params {
+      let view_layout = params.view_layout();
+      let handler = self.get_handler(&view_layout)?;
+      if let Err(err) = create_view_data(&handler, uid, &params).await {
+        tracing::error!("Copy views to workspace failed: {}, rollback", err);
+        for (handler, view_id) in created {
+          let _ = handler.delete_view(&view_id).await;
+        }
+        return Err(err);
+      }
+      created.push((handler, params.view_id.clone()));
+      new_views.push(create_view(params, view_layout));
+    }
+    let new_view_ids = new_views
+      .iter()
+      .map(|view| view.id.clone())
+      .collect::<Vec<_>>();
+    for view in new_views {
+      target_folder.insert_view(view, None);
+    }
+    FolderExtra::update_collab(&target_collab, |extra| {
+      for view_id in new_view_ids {
+        extra.views.entry(view_id).or_default().created_by = uid;
+      }
+    });
+
+    let root_view = target_folder
+      .views
+      .get_view(&new_root_id)
+      .ok_or_else(FlowyError::internal)?;
+    let child_views = target_folder.views.get_views_belong_to(&new_root_id);
+    Ok(view_pb_with_child_views(root_view, child_views))
+  }
+
+  /// Open the folder of the given workspace from the local disk. The returned [Folder] is not
+  /// the current folder, so it will not emit any view or trash changes.
+  async fn open_folder(&self, uid: i64, workspace_id: &str) -> FlowyResult<Folder> {
+    let collab = self.open_folder_collab(uid, workspace_id).await?;
+    let folder = Folder::open(UserId::from(uid), collab, None)?;
+    Ok(folder)
+  }
+
+  /// Open the collab of the folder of the given workspace from the local disk, for example, to
+  /// read the [FolderExtra] that the [Folder] doesn't expose.
+  async fn open_folder_collab(
+    &self,
+    uid: i64,
+    workspace_id: &str,
+  ) -> FlowyResult<Arc<MutexCollab>> {
+    let collab_db = self.user.collab_db(uid)?;
+    self
+      .collab_for_folder(uid, workspace_id, collab_db, vec![])
+      .await
+  }
+
+  /// Updates the [FolderExtra] of the workspace with the given function. The extra of a workspace
+  /// other than the current one is updated on the local disk.
+  async fn update_folder_extra<F, R>(&self, workspace_id: &str, f: F) -> FlowyResult<R>
+  where
+    F: FnOnce(&mut FolderExtra) -> R,
+  {
+    let current_workspace_id = self.get_current_workspace_id().await.ok();
+    if current_workspace_id.as_deref() == Some(workspace_id) {
+      return self.folder_extra.update(workspace_id, f);
+    }
+    let uid = self.user.user_id()?;
+    if !is_exist_in_local_disk(&self.user, workspace_id)? {
+      return Err(
+        FlowyError::record_not_found()
+          .with_context(format!("Workspace:{} does not exist", workspace_id)),
+      );
+    }
+    let collab = self.open_folder_collab(uid, workspace_id).await?;
+    Ok(FolderExtra::update_collab(&collab, f))
+  }
+
+  /// Returns the [FolderExtra] of the workspace. The extra of a workspace other than the current
+  /// one is read from the local disk, and the default value is returned if it can't be read.
+  async fn get_folder_extra(&self, workspace_id: &str) -> FolderExtra {
+    let current_workspace_id = self.get_current_workspace_id().await.ok();
+    if current_workspace_id.as_deref() == Some(workspace_id) {
+      return self.folder_extra.get(workspace_id);
+    }
+    let uid = match self.user.user_id() {
+      Ok(uid) => uid,
+      Err(_) => return FolderExtra::default(),
+    };
+    if !is_exist_in_local_disk(&self.user, workspace_id).unwrap_or(false) {
+      return FolderExtra::default();
+    }
+    match self.open_folder_collab(uid, workspace_id).await {
+      Ok(collab) => FolderExtra::from_collab(&collab),
+      Err(err) => {
+        tracing::error!(
+          "Failed to open the folder of workspace:{}, {:?}",
+          workspace_id,
+          err
+        );
+        FolderExtra::default()
+      },
+    }
+  }
+
+  /// Move the view with given id from one position to another position.
+  /// The view will be moved to the new position in the same parent view.
+  /// The passed in index is the index of the view that displayed in the UI.
+  /// We need to convert the index to the real index of the view in the parent view.
+  #[tracing::instrument(level = "trace", skip(self), err)]
+  pub async fn move_view(&self, view_id: &str, from: usize, to: usize) -> FlowyResult<()> {
+    self.check_view_unlocked(view_id)?;
+    if let Some((is_workspace, parent_view_id, child_views)) = self.get_view_relation(view_id).await
+    {
+      let display_views = self
+        .get_display_child_view_ids(is_workspace, &parent_view_id)
+        .await?;
+      if display_views.len() > to {
+        let to_view_id = display_views[to].clone();
+
+        // Find the actual index of the view in the parent view
+        let actual_from_index = child_views.iter().position(|id| id == view_id);
+        let actual_to_index = child_views.iter().position(|id| id == &to_view_id);
+        if let (Some(actual_from_index), Some(actual_to_index)) =
+          (actual_from_index, actual_to_index)
+        {
+          let operation = self.with_folder(
+            || None,
+            |folder| {
+              let old_prev_view_id = get_prev_view_id(folder, &parent_view_id, view_id);
+              folder.move_view(view_id, actual_from_index as u32, actual_to_index as u32);
+              Some(FolderOperation::MoveView {
+                view_id: view_id.to_string(),
+                old_parent_id: parent_view_id.clone(),
+                old_prev_view_id,
+                new_parent_id: parent_view_id.clone(),
+                new_prev_view_id: get_prev_view_id(folder, &parent_view_id, view_id),
+              })
+            },
+          );
+          if let Some(operation) = operation {
+            self.operation_history.record(operation);
+          }
+          notify_parent_view_did_change(
+            self.mutex_folder.clone(),
+            &self.folder_extra,
+            vec![parent_view_id.clone()],
+          );
+          self
+            .notify_did_reorder_child_views(vec![parent_view_id])
+            .await;
+        }
+      }
+    }
+    Ok(())
+  }
+
+  /// Returns the sync status of the current folder. It only reads the tracked status, so it's cheap
+  /// to call repeatedly.
+  pub fn get_folder_sync_status(&self) -> FolderSyncStatusPB {
+    self.sync_status.read().clone()
+  }
+
+  /// Moves the view to the `new_index` among its siblings that are displayed in the UI. Unlike
+  /// [Self::move_view], the current index of the view is looked up here. The `new_index` is
+  /// clamped to the range of the siblings, so any index beyond the last sibling moves the view to
+  /// the end.
+  #[tracing::instrument(level = "debug", skip(self), err)]
+  pub async fn reorder_view(&self, view_id: &str, new_index: usize) -> FlowyResult<()> {
+    let (is_workspace, parent_view_id, _) =
+      self.get_view_relation(view_id).await.ok_or_else(|| {
+        FlowyError::record_not_found()
+          .with_context(format!("Can't find the view with id: {}", view_id))
+      })?;
+    let display_views = self
+      .get_display_child_view_ids(is_workspace, &parent_view_id)
+      .await?;
+    let from = display_views
+      .iter()
+      .position(|id| id == view_id)
+      .ok_or_else(|| {
+        FlowyError::record_not_found()
+          .with_context(format!("The view with id: {} is not displayed", view_id))
+      })?;
+    let to = new_index.min(display_views.len() - 1);
+    if from == to {
+      return Ok(());
+    }
+    self.move_view(view_id, from, to).await
+  }
+
+  /// Returns the ids of the child views that are displayed in the UI, in the displayed order. The
+  /// trashed views are excluded.
+  async fn get_display_child_view_ids(
+    &self,
+    is_workspace: bool,
+    parent_view_id: &str,
+  ) -> FlowyResult<Vec<String>> {
+    let views = if is_workspace {
+      self.get_current_workspace().await?.views
+    } else {
+      self.get_view_pb(parent_view_id).await?.child_views
+    };
+    Ok(views.into_iter().map(|view| view.id).collect())
+  }
+
+  /// Sorts the child views of the parent by their names, ignoring the case, and persists the new
+  /// order. The pinned views stay before the unpinned ones. The `parent_id` can be the id of the
+  /// current workspace.
+  #[tracing::instrument(level = "debug", skip(self), err)]
+  pub async fn sort_children(&self, parent_id: &str, order: ViewSortOrderPB) -> FlowyResult<()> {
+    let workspace_id = self.get_current_workspace_id().await?;
+    let view_extras = self.folder_extra.get(&workspace_id).views;
+    self.with_folder(
+      || Err(folder_not_init_error()),
+      |folder| {
+        let mut child_views = if parent_id == workspace_id {
+          folder.get_workspace_views(&workspace_id)
+        } else {
+          if folder.views.get_view(parent_id).is_none() {
+            return Err(
+              FlowyError::record_not_found()
+                .with_context(format!("Can't find the parent view:{}", parent_id)),
+            );
+          }
+          folder.views.get_views_belong_to(parent_id)
+        };
+        child_views.sort_by(|a, b| {
+          let ordering = a.name.to_lowercase().cmp(&b.name.to_lowercase());
+          match order {
+            ViewSortOrderPB::Ascending => ordering,
+            ViewSortOrderPB::Descending => ordering.reverse(),
+          }
+        });
+        sort_pinned_views(&mut child_views, |view| {
+          view_extras
+            .get(&view.id)
+            .map(|view_extra| view_extra.pinned)
+            .unwrap_or(false)
+        });
+
+        let mut prev_view_id = None;
+        for view in child_views {
+          folder.move_nested_view(&view.id, parent_id, prev_view_id);
+          prev_view_id = Some(view.id.clone());
+        }
+        Ok(())
+      },
+    )?;
+
+    notify_parent_view_did_change(
+      self.mutex_folder.clone(),
+      &self.folder_extra,
+      vec![parent_id.to_string()],
+    );
+    self
+      .notify_did_reorder_child_views(vec![parent_id.to_string()])
+      .await;
+    Ok(())
+  }
+
+  /// Sends the [FolderNotification::DidUpdateChildViews] with all the child views of each parent
+  /// in the new order. The notification is sent once for each parent even if the parent appears
+  /// more than once.
+  async fn notify_did_reorder_child_views(&self, parent_view_ids: Vec<String>) {
+    let workspace_id = self.with_folder(|| None, |folder| Some(folder.get_workspace_id()));
+    let mut notified_ids = HashSet::new();
+    for parent_view_id in parent_view_ids {
+      if !notified_ids.insert(parent_view_id.clone()) {
+        continue;
+      }
+      let child_views = if workspace_id.as_ref() == Some(&parent_view_id) {
+        self.get_workspace_views(&parent_view_id).await
+      } else {
+        self
+          .get_view_pb(&parent_view_id)
+          .await
+          .map(|view| view.child_views)
+      };
+      match child_views {
+        Ok(child_views) => {
+          send_notification(&parent_view_id, FolderNotification::DidUpdateChildViews)
+            .payload(ChildViewUpdatePB {
+              parent_view_id: parent_view_id.clone(),
+              child_views,
+              ..Default::default()
+            })
+            .send();
+        },
+        Err(err) => tracing::error!(
+          "Failed to get the child views of {}, error: {:?}",
+          parent_view_id,
+          err
+        ),
+      }
+    }
+  }
+
+  /// Return a list of views that belong to the given parent view id. The pinned views come first,
+  /// and the template views are excluded.
+  #[tracing::instrument(level = "debug", skip(self, parent_view_id), err)]
+  pub async fn get_views_belong_to(&self, parent_view_id: &str) -> FlowyResult<Vec<Arc<View>>> {
+    let mut views = self.with_folder(Vec::new, |folder| {
+      folder.views.get_views_belong_to(parent_view_id)
+    });
+    let hidden_ids = self.get_hidden_view_ids();
+    views.retain(|view| !hidden_ids.contains(&view.id));
+    let pinned_view_ids = self.get_pinned_view_ids();
+    sort_pinned_views(&mut views, |view| pinned_view_ids.contains(&view.id));
+    Ok(views)
+  }
+
+  /// Update the view with the given params.
+  #[tracing::instrument(level = "trace", skip(self), err)]
+  pub async fn update_view_with_params(&self, mut params: UpdateViewParams) -> FlowyResult<()> {
+    self.check_view_unlocked(&params.view_id)?;
+    let old_view = self.with_folder(|| None, |folder| folder.views.get_view(&params.view_id));
+    if let (Some(old_view), Some(name)) = (&old_view, params.name.take()) {
+      params.name =
+        Some(self.unique_sibling_name(&old_view.parent_view_id, name, Some(&params.view_id)));
+    }
+    let old_name = old_view.map(|view| view.name.clone());
+    let rename = match (old_name, params.name.clone()) {
+      (Some(old_name), Some(new_name)) if old_name != new_name => {
+        Some(FolderOperation::RenameView {
+          view_id: params.view_id.clone(),
+          old_name,
+          new_name,
+        })
+      },
+      _ => None,
+    };
+    // The color and the last edited time are stored before updating the view, so the
+    // notification sent by the update carries them. They're put back if the update fails.
+    let workspace_id = self.get_current_workspace_id().await?;
+    let previous_extra = self
+      .folder_extra
+      .get(&workspace_id)
+      .views
+      .remove(&params.view_id)
+      .unwrap_or_default();
+    self.set_view_last_edited_time(&params.view_id).await;
+    if let Some(color) = params.color {
+      self.folder_extra.update(&workspace_id, |extra| {
+        extra.views.entry(params.view_id.clone()).or_default().color = color;
+      })?;
+    }
+    let result = self
+      .update_view(&params.view_id, |update| {
+        update
+          .set_name_if_not_none(params.name)
+          .set_desc_if_not_none(params.desc)
+          .set_layout_if_not_none(params.layout)
+          .set_favorite_if_not_none(params.is_favorite)
+          .done()
+      })
+      .await;
+    if let Err(err) = result {
+      let restored = self.folder_extra.update(&workspace_id, |extra| {
+        let view_extra = extra.views.entry(params.view_id.clone()).or_default();
+        view_extra.color = previous_extra.color;
+        view_extra.last_edited_time = previous_extra.last_edited_time;
+        view_extra.last_edited_by = previous_extra.last_edited_by;
+      });
+      if let Err(restore_err) = restored {
+        tracing::error!(
+          "Failed to restore the extra of view:{}, error: {:?}",
+          params.view_id,
+          restore_err
+        );
+      }
+      return Err(err);
+    }
+    if let Some(rename) = rename {
+      self.operation_history.record(rename);
+    }
+    Ok(())
+  }
+
+  /// Changes the layout of the view. The handler of the database layouts updates the layout
+  /// settings of the database, so the conversion between the grid, the board and the calendar
+  /// keeps all the rows and fields. The conversion between the document and the database layouts
+  /// is not supported because the content of the view would be lost.
+  #[tracing::instrument(level = "debug", skip(self), err)]
+  pub async fn change_view_layout(&self, params: ChangeLayoutParams) -> FlowyResult<()> {
+    self.check_view_unlocked(&params.view_id)?;
+    let view = self
+      .with_folder(|| None, |folder| folder.views.get_view(&params.view_id))
+      .ok_or_else(|| {
+        FlowyError::record_not_found()
+          .with_context(format!("Can't find the view with id: {}", params.view_id))
+      })?;
+    if view.layout == params.new_layout {
+      return Ok(());
+    }
+    if !view.layout.is_database() || !params.new_layout.is_database() {
+      return Err(FlowyError::not_support().with_context(format!(
+        "Can't change the layout of the view from {:?} to {:?}, the content would be lost",
+        view.layout, params.new_layout
+      )));
+    }
+
+    self.set_view_last_edited_time(&params.view_id).await;
+    self
+      .update_view(&params.view_id, |update| {
+        update
+          .set_layout_if_not_none(Some(params.new_layout))
+          .done()
+      })
+      .await
+  }
+
+  /// Renames the view. Unlike [Self::update_view_with_params], only the name of the view is
+  /// touched.
+  #[tracing::instrument(level = "trace", skip(self), err)]
+  pub async fn rename_view(&self, params: RenameViewParams) -> FlowyResult<()> {
+    self
+      .update_view_with_params(UpdateViewParams {
+        view_id: params.view_id,
+        name: Some(params.name),
+        desc: None,
+        thumbnail: None,
+        layout: None,
+        is_favorite: None,
+        color: None,
+      })
+      .await
+  }
+
+  /// Update the icon of the view with the given params.
+  #[tracing::instrument(level = "trace", skip(self), err)]
+  pub async fn update_view_icon_with_params(
+    &self,
+    params: UpdateViewIconParams,
+  ) -> FlowyResult<()> {
+    self.check_view_unlocked(&params.view_id)?;
+    self.set_view_last_edited_time(&params.view_id).await;
+    let icon = params.icon.map(normalize_view_icon);
+    self
+      .update_view(&params.view_id, |update| update.set_icon(icon).done())
+      .await
+  }
+
+  /// Updates the icons of the views in one transaction. The invalid items are collected as the
+  /// failures instead of aborting the whole batch. The updated views are notified with one
+  /// [FolderNotification::DidUpdateChildViews] per parent, and the view change listener doesn't
+  /// notify each of them again.
+  #[tracing::instrument(level = "debug", skip(self, payload), err)]
+  pub async fn update_view_icons(
+    &self,
+    payload: RepeatedUpdateViewIconPayloadPB,
+  ) -> FlowyResult<UpdateViewIconsResultPB> {
+    let mut result = UpdateViewIconsResultPB::default();
+    let mut valid_params = vec![];
+    for item in payload.items {
+      let view_id = item.view_id.clone();
+      let params = TryInto::<UpdateViewIconParams>::try_into(item)
+        .map_err(FlowyError::from)
+        .and_then(|params| {
+          self.check_view_exists(&params.view_id)?;
+          self.check_view_unlocked(&params.view_id)?;
+          Ok(params)
+        });
+      match params {
+        Ok(params) => valid_params.push(params),
+        Err(err) => result.failed.push(UpdateViewIconFailurePB {
+          view_id,
+          msg: err.msg,
+        }),
+      }
+    }
+
+    let updated_views = self.with_folder(Vec::new, |folder| {
+      valid_params
+        .into_iter()
+        .flat_map(|params| {
+          let old_view = folder.views.get_view(&params.view_id)?;
+          let icon = params.icon.map(normalize_view_icon);
+          if old_view.icon == icon {
+            return Some((old_view.clone(), old_view.as_ref().clone()));
+          }
+          *self
+            .muted_view_updates
+            .lock()
+            .entry(params.view_id.clone())
+            .or_default() += 1;
+          match folder
+            .views
+            .update_view(&params.view_id, |update| update.set_icon(icon).done())
+          {
+            Some(new_view) => Some((old_view, new_view)),
+            None => {
+              take_muted_view_update(&self.muted_view_updates, &params.view_id);
+              None
+            },
+          }
+        })
+        .collect::<Vec<_>>()
+    });
+
+    let mut updated_child_views: HashMap<String, Vec<ViewPB>> = HashMap::new();
+    for (old_view, new_view) in updated_views {
+      if let Ok(handler) = self.get_handler(&old_view.layout) {
+        if let Err(err) = handler.did_update_view(&old_view, &new_view).await {
+          tracing::error!(
+            "Failed to handle the icon update of view:{}, error: {:?}",
+            new_view.id,
+            err
+          );
+        }
+      }
+      self.set_view_last_edited_time(&new_view.id).await;
+      result.updated_ids.push(new_view.id.clone());
+      if let Ok(view_pb) = self.get_view_pb(&new_view.id).await {
+        updated_child_views
+          .entry(view_pb.parent_view_id.clone())
+          .or_default()
+          .push(view_pb);
+      }
+    }
+    for (parent_view_id, views) in updated_child_views {
+      send_notification(&parent_view_id, FolderNotification::DidUpdateChildViews)
+        .payload(ChildViewUpdatePB {
+          parent_view_id: parent_view_id.clone(),
+          update_child_views: views,
+          ..Default::default()
+        })
+        .send();
+    }
+    Ok(result)
+  }
+
+  /// Update the cover of the view with the given params. The cover is removed if it's `None`.
+  #[tracing::instrument(level = "trace", skip(self), err)]
+  pub async fn update_view_cover_with_params(
+    &self,
+    params: UpdateViewCoverParams,
+  ) -> FlowyResult<()> {
+    // Make sure the view exists
+    let _ = self.get_view_pb(&params.view_id).await?;
+    self.check_view_unlocked(&params.view_id)?;
+    let workspace_id = self.get_current_workspace_id().await?;
+    self.set_view_last_edited_time(&params.view_id).await;
+    self.folder_extra.update(&workspace_id, |extra| {
+      extra.views.entry(params.view_id.clone()).or_default().cover = params.cover;
+    })?;
+
+    let view = self.get_view_pb(&params.view_id).await?;
+    send_coalesced_notification(&params.view_id, FolderNotification::DidUpdateView, view);
+    Ok(())
+  }
+
+  /// Duplicate the view with the given view id.
+  #[tracing::instrument(level = "debug", skip(self), err)]
+  pub(crate) async fn duplicate_view(&self, view_id: &str) -> Result<(), FlowyError> {
+    let view = self
+      .with_folder(|| None, |folder| folder.views.get_view(view_id))
+      .ok_or_else(|| FlowyError::record_not_found().with_context("Can't duplicate the view"))?;
+
+    let handler = self.get_handler(&view.layout)?;
+    let view_data = handler.duplicate_view(&view.id).await?;
+
+    // get the current view index in the parent view, because we need to insert the duplicated view below the current view.
+    let index = if let Some((_, __, views)) = self.get_view_relation(&view.parent_view_id).await {
+      views.iter().position(|id| id == view_id).map(|i| i as u32)
+    } else {
+      None
+    };
+
+    let duplicate_params = CreateViewParams {
+      parent_view_id: view.parent_view_id.clone(),
+      name: format!("{} (copy)", &view.name),
+      desc: view.desc.clone(),
+      layout: Some(view.layout.clone().into()),
+      initial_data: view_data.to_vec(),
+      view_id: gen_view_id().to_string(),
+      meta: Default::default(),
+      set_as_current: true,
+      index,
+    };
+
+    self.create_view_with_params(duplicate_params).await?;
+    Ok(())
+  }
+
+  /// Duplicates the view, and all of its descendants if `include_children` is true. Each
+  /// duplicated view gets a new id, and the duplicated child views are attached to the duplicated
+  /// parent views. Only the name of the duplicated root view gets the suffix. The trashed child
+  /// views are not duplicated.
+  #[tracing::instrument(level = "debug", skip(self), err)]
+  pub(crate) async fn duplicate_view_recursive(
+    &self,
+    params: DuplicateViewParams,
+  ) -> FlowyResult<ViewPB> {
+    let views = self.with_folder(Vec::new, |folder| {
+      if params.include_children {
+        let trash_ids = get_trash_ids(folder);
+        get_view_and_descendants(folder, &params.view_id)
+          .into_iter()
+          .filter(|view| view.id == params.view_id || !trash_ids.contains(&view.id))
+          .collect::<Vec<_>>()
+      } else {
+        folder.views.get_view(&params.view_id).into_iter().collect()
+      }
+    });
+    let root_view = views
+      .first()
+      .cloned()
+      .ok_or_else(|| FlowyError::record_not_found().with_context("Can't duplicate the view"))?;
+
+    // Insert the duplicated view below the original view.
+    let index = self
+      .get_view_relation(&root_view.id)
+      .await
+      .and_then(|(_, _, sibling_ids)| sibling_ids.iter().position(|id| id == &root_view.id))
+      .map(|position| position as u32 + 1);
+
+    let (new_root_id, create_params) = self
+      .copy_views_params(
+        &views,
+        root_view.parent_view_id.clone(),
+        format!("{}{}", root_view.name, params.suffix),
+        index,
+      )
+      .await?;
+    self.create_views_with_params(create_params).await?;
+    notify_parent_view_did_change(
+      self.mutex_folder.clone(),
+      &self.folder_extra,
+      vec![root_view.parent_view_id.clone()],
+    );
+    self.set_current_view(&new_root_id).await?;
+    self.get_view_pb(&new_root_id).await
+  }
+
+  /// Creates a new view under the `parent_id` by copying the structure and the content of the
+  /// template view. The descendants of the template that are not in the trash are copied as well,
+  /// and the new root view is named `name`. The template itself is left untouched.
+  #[tracing::instrument(level = "debug", skip(self), err)]
+  pub async fn create_view_from_template(
+    &self,
+    params: CreateFromTemplateParams,
+  ) -> FlowyResult<ViewPB> {
+    let workspace_id = self.get_current_workspace_id().await?;
+    let views = self.with_folder(
+      || Err(folder_not_init_error()),
+      |folder| {
+        let trash_ids = get_trash_ids(folder);
+        if folder.views.get_view(&params.template_id).is_none()
+          || is_trashed(folder, &params.template_id, &trash_ids)
+        {
+          return Err(FlowyError::record_not_found().with_context(format!(
+            "Can't find the template with id: {}",
+            params.template_id
+          )));
+        }
+        if params.parent_id != workspace_id
+          && (folder.views.get_view(&params.parent_id).is_none()
+            || is_trashed(folder, &params.parent_id, &trash_ids))
+        {
+          return Err(FlowyError::record_not_found().with_context(format!(
+            "Can't find the parent view with id: {}",
+            params.parent_id
+          )));
+        }
+        Ok(
+          get_view_and_descendants(folder, &params.template_id)
+            .into_iter()
+            .filter(|view| !trash_ids.contains(&view.id))
+            .collect::<Vec<_>>(),
+        )
+      },
+    )?;
+    self.check_nesting_depth(&params.parent_id, Some(&params.template_id))?;
+
+    let (new_root_id, create_params) = self
+      .copy_views_params(&views, params.parent_id.clone(), params.name, None)
+      .await?;
+    self.create_views_with_params(create_params).await?;
+    notify_parent_view_did_change(
+      self.mutex_folder.clone(),
+      &self.folder_extra,
+      vec![params.parent_id],
+    );
+    self.get_view_pb(&new_root_id).await
+  }
+
+  /// Builds the params to copy the `views` with their data. The first view is the root, and its
+  /// copy is created under the `root_parent_id` with the `root_name`. Each copy gets a new id, and
+  /// the copied child views are attached to the copied parent views. A view whose parent is not in
+  /// the `views` is skipped, so are its descendants.
+  ///
+  /// Returns the id of the copied root view and the params.
+  async fn copy_views_params(
+    &self,
+    views: &[Arc<View>],
+    root_parent_id: String,
+    root_name: String,
+    root_index: Option<u32>,
+  ) -> FlowyResult<(String, Vec<CreateViewParams>)> {
+    let root_view = views.first().ok_or_else(FlowyError::record_not_found)?;
+    let mut new_view_ids: HashMap<String, String> = HashMap::with_capacity(views.len());
+    let mut create_params = Vec::with_capacity(views.len());
+    for view in views {
+      let (parent_view_id, name, index) = if view.id == root_view.id {
+        (root_parent_id.clone(), root_name.clone(), root_index)
+      } else {
+        match new_view_ids.get(&view.parent_view_id) {
+          None => continue,
+          Some(parent_view_id) => (parent_view_id.clone(), view.name.clone(), None),
+        }
+      };
+
+      let handler = self.get_handler(&view.layout)?;
+      let view_data = handler.duplicate_view(&view.id).await?;
+      let new_view_id = gen_view_id().to_string();
+      new_view_ids.insert(view.id.clone(), new_view_id.clone());
+      create_params.push(CreateViewParams {
+        parent_view_id,
+        name,
+        desc: view.desc.clone(),
+        layout: Some(view.layout.clone().into()),
+        initial_data: view_data.to_vec(),
+        view_id: new_view_id,
+        meta: Default::default(),
+        set_as_current: false,
+        index,
+      });
+    }
+
+    let new_root_id = new_view_ids
+      .get(&root_view.id)
+      .cloned()
+      .ok_or_else(FlowyError::internal)?;
+    Ok((new_root_id, create_params))
+  }
+
+  /// Sets the view as the current view of the workspace and returns the view. The view that
+  /// doesn't exist or is in the trash can't be set as the current view.
+  #[tracing::instrument(level = "trace", skip(self), err)]
+  pub(crate) async fn set_current_view(&self, view_id: &str) -> Result<ViewPB, FlowyError> {
+    let workspace_id = self.with_folder(
+      || Err(FlowyError::record_not_found()),
+      |folder| {
+        if folder.views.get_view(view_id).is_none() {
+          return Err(
+            FlowyError::record_not_found().with_context(format!("Can't find the view:{}", view_id)),
+          );
+        }
+        if is_trashed(folder, view_id, &get_trash_ids(folder)) {
+          return Err(
+            FlowyError::invalid_data()
+              .with_context(format!("The view:{} is in the trash", view_id)),
+          );
+        }
+        folder.set_current_view(view_id);
+        Ok(folder.get_workspace_id())
+      },
+    )?;
+    self.record_recent_view(&workspace_id, view_id);
+    self.set_view_last_opened_time(&workspace_id, view_id);
+
+    let view = self.get_view_pb(view_id).await?;
+    let show_favorites = self.is_favorites_shown(&workspace_id);
+    let home_view_id = self.get_home_view_id(&workspace_id).unwrap_or_default();
+    send_workspace_setting_notification(
+      workspace_id,
+      Some(view.clone()),
+      show_favorites,
+      home_view_id,
+    );
+    Ok(view)
+  }
+
+  /// Moves the view to the front of the recent views, and drops the oldest ones that exceed the
+  /// [MAX_RECENT_VIEWS].
+  fn record_recent_view(&self, workspace_id: &str, view_id: &str) {
+    if view_id.is_empty() {
+      return;
+    }
+    let result = self.folder_extra.update(workspace_id, |extra| {
+      extra.recent_view_ids.retain(|id| id != view_id);
+      extra.recent_view_ids.insert(0, view_id.to_string());
+      extra.recent_view_ids.truncate(MAX_RECENT_VIEWS);
+    });
+    if let Err(err) = result {
+      tracing::error!(
+        "Failed to record the recent view:{}, error: {:?}",
+        view_id,
+        err
+      );
+    }
+  }
+
+  /// Returns the recently opened views, the most recent first. The trashed and deleted views are
+  /// excluded.
+  #[tracing::instrument(level = "debug", skip(self), err)]
+  pub async fn get_recent_views(&self) -> FlowyResult<Vec<ViewPB>> {
+    let workspace_id = self.get_current_workspace_id().await?;
+    let recent_view_ids = self.folder_extra.get(&workspace_id).recent_view_ids;
+    let mut views = self.with_folder(Vec::new, |folder| {
+      let trash_ids = get_trash_ids(folder);
+      recent_view_ids
+        .iter()
+        .filter(|view_id| !is_trashed(folder, view_id, &trash_ids))
+        .filter_map(|view_id| folder.views.get_view(view_id))
+        .map(view_pb_without_child_views)
+        .collect::<Vec<_>>()
+    });
+    self.fill_flat_view_extras(&mut views);
+    Ok(views)
+  }
+
+  /// Returns the recently opened views like [Self::get_recent_views], sorted by the given
+  /// timestamp, the newest first. The views with the same timestamp keep their recent order.
+  #[tracing::instrument(level = "debug", skip(self), err)]
+  pub async fn get_recent_views_sorted(
+    &self,
+    sort_type: ViewSortTypePB,
+  ) -> FlowyResult<Vec<ViewPB>> {
+    let mut views = self.get_recent_views().await?;
+    views.sort_by(|a, b| {
+      let ordering = match sort_type {
+        ViewSortTypePB::CreatedTime => a.create_time.cmp(&b.create_time),
+        ViewSortTypePB::LastEditedTime => a.last_edited_time.cmp(&b.last_edited_time),
+        ViewSortTypePB::LastOpenedTime => a.last_opened_time.cmp(&b.last_opened_time),
+      };
+      ordering.reverse()
+    });
+    Ok(views)
+  }
+
+  /// Clears the recent views of the current workspace.
+  #[tracing::instrument(level = "debug", skip(self), err)]
+  pub async fn clear_recent_views(&self) -> FlowyResult<()> {
+    let workspace_id = self.get_current_workspace_id().await?;
+    self.folder_extra.update(&workspace_id, |extra| {
+      extra.recent_view_ids.clear();
+    })
+  }
+
+  /// Moves the query to the front of the search history, and drops the oldest ones that exceed
+  /// the [MAX_SEARCH_HISTORY]. The empty query is not recorded.
+  fn record_search_query(&self, workspace_id: &str, query: &str) {
+    let query = query.trim();
+    if query.is_empty() {
+      return;
+    }
+    let result = self.folder_extra.update(workspace_id, |extra| {
+      extra.setting.search_history.retain(|item| item != query);
+      extra.setting.search_history.insert(0, query.to_string());
+      extra.setting.search_history.truncate(MAX_SEARCH_HISTORY);
+    });
+    if let Err(err) = result {
+      tracing::error!("Failed to record the search query, error: {:?}", err);
+    }
+  }
+
+  /// Returns the recently searched queries of the current workspace, the most recent first.
+  #[tracing::instrument(level = "debug", skip(self), err)]
+  pub async fn get_search_history(&self) -> FlowyResult<Vec<String>> {
+    let workspace_id = self.get_current_workspace_id().await?;
+    Ok(self.folder_extra.get(&workspace_id).setting.search_history)
+  }
+
+  /// Clears the search history of the current workspace.
+  #[tracing::instrument(level = "debug", skip(self), err)]
+  pub async fn clear_search_history(&self) -> FlowyResult<()> {
+    let workspace_id = self.get_current_workspace_id().await?;
+    self.folder_extra.update(&workspace_id, |extra| {
+      extra.setting.search_history.clear();
+    })
+  }
+
+  /// Undoes the last structural operation (create, delete, restore, move or rename) on the
+  /// current workspace. The undone operation can be redone by [FolderManager::redo_folder_operation].
+  ///
+  /// The operation is dropped if it can't be undone anymore, for example, the view was deleted
+  /// permanently.
+  #[tracing::instrument(level = "debug", skip(self), err)]
+  pub async fn undo_folder_operation(&self) -> FlowyResult<FolderOperationPB> {
+    let operation = self.operation_history.pop_undo().ok_or_else(|| {
+      FlowyError::record_not_found().with_context("There is no operation to undo")
+    })?;
+    self
+      .operation_history
+      .replay(async {
+        match &operation {
+          FolderOperation::CreateView { view_id } | FolderOperation::RestoreView { view_id } => {
+            self.move_view_to_trash(view_id).await?;
+          },
+          FolderOperation::DeleteView { view_id } => self.restore_view_from_trash(view_id)?,
+          FolderOperation::MoveView {
+            view_id,
+            old_parent_id,
+            old_prev_view_id,
+            ..
+          } => {
+            self
+              .move_nested_view(
+                view_id.clone(),
+                old_parent_id.clone(),
+                old_prev_view_id.clone(),
+              )
+              .await?
+          },
+          FolderOperation::RenameView {
+            view_id, old_name, ..
+          } => {
+            self
+              .rename_view(RenameViewParams {
+                view_id: view_id.clone(),
+                name: old_name.clone(),
+              })
+              .await?
+          },
+        }
+        Ok::<(), FlowyError>(())
+      })
+      .await?;
+    let operation_pb = FolderOperationPB::from(&operation);
+    self.operation_history.push_redo(operation);
+    Ok(operation_pb)
+  }
+
+  /// Redoes the operation undone by [FolderManager::undo_folder_operation]. The redo stack is
+  /// cleared once a new operation is performed.
+  #[tracing::instrument(level = "debug", skip(self), err)]
+  pub async fn redo_folder_operation(&self) -> FlowyResult<FolderOperationPB> {
+    let operation = self.operation_history.pop_redo().ok_or_else(|| {
+      FlowyError::record_not_found().with_context("There is no operation to redo")
+    })?;
+    self
+      .operation_history
+      .replay(async {
+        match &operation {
+          FolderOperation::CreateView { view_id } | FolderOperation::RestoreView { view_id } => {
+            self.restore_view_from_trash(view_id)?
+          },
+          FolderOperation::DeleteView { view_id } => {
+            self.move_view_to_trash(view_id).await?;
+          },
+          FolderOperation::MoveView {
+            view_id,
+            new_parent_id,
+            new_prev_view_id,
+            ..
+          } => {
+            self
+              .move_nested_view(
+                view_id.clone(),
+                new_parent_id.clone(),
+                new_prev_view_id.clone(),
+              )
+              .await?
+          },
+          FolderOperation::RenameView {
+            view_id, new_name, ..
+          } => {
+            self
+              .rename_view(RenameViewParams {
+                view_id: view_id.clone(),
+                name: new_name.clone(),
+              })
+              .await?
+          },
+        }
+        Ok::<(), FlowyError>(())
+      })
+      .await?;
+    let operation_pb = FolderOperationPB::from(&operation);
+    self.operation_history.push_undo(operation);
+    Ok(operation_pb)
+  }
+
+  /// Restores the view from the trash. The view is put back to its original parent.
+  fn restore_view_from_trash(&self, view_id: &str) -> FlowyResult<()> {
+    self.with_folder(
+      || Err(folder_not_init_error()),
+      |folder| {
+        if !get_trash_ids(folder).contains(view_id) {
+          return Err(
+            FlowyError::record_not_found()
+              .with_context(format!("The view:{} is not in the trash", view_id)),
+          );
+        }
+        folder.delete_trash(vec![view_id.to_string()]);
+        Ok(())
+      },
+    )
+  }
+
+  #[tracing::instrument(level = "trace", skip(self))]
+  pub(crate) async fn get_current_view(&self) -> Option<ViewPB> {
+    let view_id = self.with_folder(|| None, |folder| folder.get_current_view())?;
+    self.get_view_pb(&view_id).await.ok()
+  }
+
+  /// Toggles the favorite status of a view identified by `view_id`If the view is not a favorite, it will be added to the favorites list; otherwise, it will be removed from the list.
+  #[tracing::instrument(level = "debug", skip(self), err)]
+  pub async fn toggle_favorites(&self, view_id: &str) -> FlowyResult<()> {
+    self.with_folder(
+      || (),
+      |folder| {
+        if let Some(old_view) = folder.views.get_view(view_id) {
+          if old_view.is_favorite {
+            folder.delete_favorites(vec![view_id.to_string()]);
+          } else {
+            folder.add_favorites(vec![view_id.to_string()]);
+          }
+        }
+      },
+    );
+    self.send_toggle_favorite_notification(view_id).await;
+    Ok(())
+  }
+
+  /// Sets the favorite state of the views to the given state, and returns the views with their
+  /// final state. Unlike [Self::toggle_favorites], setting the same state twice is a no-op. If any
+  /// of the views doesn't exist, none of the states will be changed.
+  #[tracing::instrument(level = "debug", skip(self), err)]
+  pub async fn set_favorites(&self, items: Vec<(String, bool)>) -> FlowyResult<Vec<ViewPB>> {
+    let changed_view_ids = self.with_folder(
+      || Err(folder_not_init_error()),
+      |folder| {
+        let mut changed_view_ids = vec![];
+        for (view_id, is_favorite) in &items {
+          match folder.views.get_view(view_id) {
+            None => {
+              return Err(
+                FlowyError::record_not_found()
+                  .with_context(format!("Can't find the view with id: {}", view_id)),
+              )
+            },
+            Some(view) if view.is_favorite != *is_favorite => {
+              changed_view_ids.push((view_id.clone(), *is_favorite))
+            },
+            Some(_) => {},
+          }
+        }
+
+        for (view_id, is_favorite) in &changed_view_ids {
+          if *is_favorite {
+            folder.add_favorites(vec![view_id.clone()]);
+          } else {
+            folder.delete_favorites(vec![view_id.clone()]);
+          }
+        }
+        Ok(changed_view_ids)
+      },
+    )?;
+
+    for (view_id, _) in &changed_view_ids {
+      self.send_toggle_favorite_notification(view_id).await;
+    }
+
+    let mut views = Vec::with_capacity(items.len());
+    for (view_id, _) in &items {
+      views.push(self.get_view_pb(view_id).await?);
+    }
+    Ok(views)
+  }
+
+  /// Sets the expanded state of the views in the sidebar. If any of the views doesn't exist, none
+  /// of the states will be changed.
+  #[tracing::instrument(level = "debug", skip(self), err)]
+  pub async fn set_views_expanded(&self, items: Vec<(String, bool)>) -> FlowyResult<()> {
+    let missing_view_id = self.with_folder(
+      || None,
+      |folder| {
+        items
+          .iter()
+          .find(|(view_id, _)| folder.views.get_view(view_id).is_none())
+          .map(|(view_id, _)| view_id.clone())
+      },
+    );
+    if let Some(view_id) = missing_view_id {
+      return Err(
+        FlowyError::record_not_found()
+          .with_context(format!("Can't find the view with id: {}", view_id)),
+      );
+    }
+
+    let workspace_id = self.get_current_workspace_id().await?;
+    self.folder_extra.update(&workspace_id, |extra| {
+      for (view_id, is_expanded) in items {
+        extra.views.entry(view_id).or_default().expanded = is_expanded;
+      }
+    })
+  }
+
+  /// Sets the metadata of the view. The key is deleted if the value is empty.
+  #[tracing::instrument(level = "debug", skip(self), err)]
+  pub async fn set_view_meta(&self, params: ViewMetaParams) -> FlowyResult<()> {
+    self.check_view_exists(&params.view_id)?;
+    self.check_view_unlocked(&params.view_id)?;
+    let workspace_id = self.get_current_workspace_id().await?;
+    self.folder_extra.update(&workspace_id, |extra| {
+      if params.value.is_empty() {
+        if let Some(view_extra) = extra.views.get_mut(&params.view_id) {
+          view_extra.meta.remove(&params.key);
+        }
+      } else {
+        extra
+          .views
+          .entry(params.view_id.clone())
+          .or_default()
+          .meta
+          .insert(params.key.clone(), params.value.clone());
+      }
+    })
+  }
+
+  /// Returns the metadata value of the view. Empty if the key isn't set.
+  #[tracing::instrument(level = "debug", skip(self), err)]
+  pub async fn get_view_meta(&self, view_id: &str, key: &str) -> FlowyResult<String> {
+    self.check_view_exists(view_id)?;
+    let workspace_id = self.get_current_workspace_id().await?;
+    let value = self
+      .folder_extra
+      .get(&workspace_id)
+      .views
+      .get(view_id)
+      .and_then(|view_extra| view_extra.meta.get(key).cloned())
+      .unwrap_or_default();
+    Ok(value)
+  }
+
+  /// Replaces the views referenced by the content of the view. It should be called by the plugin
+  /// that owns the content whenever the content changes.
+  #[tracing::instrument(level = "debug", skip(self), err)]
+  pub async fn set_view_references(&self, params: ViewReferencesParams) -> FlowyResult<()> {
+    self.check_view_exists(&params.view_id)?;
+    let workspace_id = self.get_current_workspace_id().await?;
+    self.folder_extra.update(&workspace_id, |extra| {
+      if params.referenced_view_ids.is_empty() {
+        if let Some(view_extra) = extra.views.get_mut(&params.view_id) {
+          view_extra.referenced_view_ids.clear();
+        }
+      } else {
+        extra
+          .views
+          .entry(params.view_id.clone())
+          .or_default()
+          .referenced_view_ids = params.referenced_view_ids.clone();
+      }
+    })
+  }
+
+  /// Returns the views whose content references the view. The views in the trash are excluded,
+  /// and the returned views keep their order in the view tree.
+  #[tracing::instrument(level = "debug", skip(self), err)]
+  pub async fn get_view_backlinks(&self, view_id: &str) -> FlowyResult<Vec<ViewPB>> {
+    self.check_view_exists(view_id)?;
+    let workspace_id = self.get_current_workspace_id().await?;
+    let source_ids = self
+      .folder_extra
+      .get(&workspace_id)
+      .views
+      .into_iter()
+      .filter(|(_, view_extra)| {
+        view_extra
+          .referenced_view_ids
+          .iter()
+          .any(|id| id == view_id)
+      })
+      .map(|(source_id, _)| source_id)
+      .collect::<HashSet<_>>();
+    if source_ids.is_empty() {
+      return Ok(vec![]);
+    }
+    let mut views = self.with_folder(Vec::new, |folder| {
+      get_workspace_all_views(folder, &workspace_id, false)
+        .into_iter()
+        .filter(|view| source_ids.contains(&view.id))
+        .map(view_pb_without_child_views)
+        .collect::<Vec<_>>()
+    });
+    self.fill_flat_view_extras(&mut views);
+    Ok(views)
+  }
+
+  fn check_view_exists(&self, view_id: &str) -> FlowyResult<()> {
+    let is_exist = self.with_folder(|| false, |folder| folder.views.get_view(view_id).is_some());
+    if !is_exist {
+      return Err(
+        FlowyError::record_not_found()
+          .with_context(format!("Can't find the view with id: {}", view_id)),
+      );
+    }
+    Ok(())
+  }
+
+  /// Subscribes the changes of the view. The subscribed view is notified with
+  /// [FolderNotification::DidUpdateView] when the view itself or any of its direct child views is
+  /// changed, including the changes synced from other devices. The views that are not subscribed
+  /// are not notified of the synced changes. The subscription is dropped when the view is moved to
+  /// the trash or deleted.
+  #[tracing::instrument(level = "debug", skip(self), err)]
+  pub fn subscribe_view(&self, view_id: &str) -> FlowyResult<()> {
+    self.check_view_exists(view_id)?;
+    self.view_subscriptions.write().insert(view_id.to_string());
+    Ok(())
+  }
+
+  /// Drops the subscription added by [Self::subscribe_view]. It does nothing if the view isn't
+  /// subscribed.
+  pub fn unsubscribe_view(&self, view_id: &str) {
+    self.view_subscriptions.write().remove(view_id);
+  }
+
+  /// Sets the favorite state of the view and all of its descendants that are not in the trash,
+  /// and returns the ids of the views whose state was changed. If the subtree contains more views
+  /// than the `limit`, none of the states will be changed.
+  #[tracing::instrument(level = "debug", skip(self), err)]
+  pub async fn set_favorite_recursive(
+    &self,
+    view_id: &str,
+    is_favorite: bool,
+    limit: Option<usize>,
+  ) -> FlowyResult<Vec<String>> {
+    let limit = limit.unwrap_or(DEFAULT_FAVORITE_SUBTREE_LIMIT);
+    let changed_view_ids = self.with_folder(
+      || Err(folder_not_init_error()),
+      |folder| {
+        let trash_ids = get_trash_ids(folder);
+        if folder.views.get_view(view_id).is_none() || is_trashed(folder, view_id, &trash_ids) {
+          return Err(
+            FlowyError::record_not_found()
+              .with_context(format!("Can't find the view with id: {}", view_id)),
+          );
+        }
+        let views = get_view_and_descendants(folder, view_id)
+          .into_iter()
+          .filter(|view| !trash_ids.contains(&view.id))
+          .collect::<Vec<_>>();
+        if views.len() > limit {
+          return Err(FlowyError::too_many_views().with_context(format!(
+            "The view contains {} views, exceeding the limit:{}",
+            views.len(),
+            limit
+          )));
+        }
+
+        let changed_view_ids = views
+          .into_iter()
+          .filter(|view| view.is_favorite != is_favorite)
+          .map(|view| view.id.clone())
+          .collect::<Vec<_>>();
+        if is_favorite {
+          folder.add_favorites(changed_view_ids.clone());
+        } else {
+          folder.delete_favorites(changed_view_ids.clone());
+        }
+        Ok(changed_view_ids)
+      },
+    )?;
+
+    for view_id in &changed_view_ids {
+      self.send_toggle_favorite_notification(view_id).await;
+    }
+    Ok(changed_view_ids)
+  }
+
+  // Used by toggle_favorites to send notification to frontend, after the favorite status of view has been changed.It sends two distinct notifications: one to correctly update the concerned view's is_favorite status, and another to update the list of favorites that is to be displayed.
+  async fn send_toggle_favorite_notification(&self, view_id: &str) {
+    if let Ok(view) = self.get_view_pb(view_id).await {
+      let notification_type = if view.is_favorite {
+        FolderNotification::DidFavoriteView
+      } else {
+        FolderNotification::DidUnfavoriteView
+      };
+      send_notification("favorite", notification_type)
+        .payload(RepeatedViewPB {
+          items: vec![view.clone()],
+        })
+        .send();
+
+      send_coalesced_notification(&view.id, FolderNotification::DidUpdateView, view);
+    }
+  }
+
+  #[tracing::instrument(level = "trace", skip(self))]
+  pub(crate) async fn get_all_favorites(&self) -> Vec<SectionItem> {
+    let favorite_order = self.get_favorite_order().await;
+    self.with_folder(Vec::new, |folder| {
+      let trash_ids = folder
+        .get_all_trash()
+        .into_iter()
+        .map(|trash| trash.id)
+        .collect::<Vec<String>>();
+
+      let mut views = folder.get_all_favorites();
+      views.retain(|view| !trash_ids.contains(&view.id));
+      sort_favorites(&mut views, &favorite_order);
+      views
+    })
+  }
+
+  /// Moves the favorite view to the `to_index` of the favorites list and saves the order. The
+  /// `to_index` that is out of range is clamped to the bounds of the list. Returns the
+  /// [ErrorCode::RecordNotFound] error if the view is not a favorite.
+  #[tracing::instrument(level = "debug", skip(self), err)]
+  pub async fn reorder_favorite(&self, view_id: &str, to_index: usize) -> FlowyResult<()> {
+    let favorite_order = self.get_favorite_order().await;
+    let mut favorite_ids = self.with_folder(Vec::new, |folder| {
+      let mut favorites = folder.get_all_favorites();
+      sort_favorites(&mut favorites, &favorite_order);
+      favorites
+        .into_iter()
+        .map(|favorite| favorite.id)
+        .collect::<Vec<String>>()
+    });
+    let from_index = favorite_ids
+      .iter()
+      .position(|id| id == view_id)
+      .ok_or_else(|| {
+        FlowyError::record_not_found()
+          .with_context(format!("The view:{} is not a favorite", view_id))
+      })?;
+    let to_index = to_index.min(favorite_ids.len() - 1);
+    let favorite_id = favorite_ids.remove(from_index);
+    favorite_ids.insert(to_index, favorite_id);
+
+    let workspace_id = self.get_current_workspace_id().await?;
+    self.folder_extra.update(&workspace_id, |extra| {
+      extra.favorite_order = favorite_ids;
+    })?;
+    Ok(())
+  }
+
+  /// Sets the last edited time of the view to now and the current user as the last editor. It
+  /// should be called before the view is updated, so that the update notification carries the
+  /// new time.
+  async fn set_view_last_edited_time(&self, view_id: &str) {
+    let result = match (self.user.user_id(), self.get_current_workspace_id().await) {
+      (Ok(uid), Ok(workspace_id)) => self.folder_extra.update(&workspace_id, |extra| {
+        let view_extra = extra.views.entry(view_id.to_string()).or_default();
+        view_extra.last_edited_time = timestamp();
+        view_extra.last_edited_by = uid;
+      }),
+      (Err(err), _) | (_, Err(err)) => Err(err),
+    };
+    if let Err(err) = result {
+      tracing::error!(
+        "Failed to set the last edited time of view:{}, error: {:?}",
+        view_id,
+        err
+      );
+    }
+  }
+
+  /// Records the time the view was opened. It's kept apart from the last edited time, so opening
+  /// a view is not counted as an edit.
+  fn set_view_last_opened_time(&self, workspace_id: &str, view_id: &str) {
+    let result = self.folder_extra.update(workspace_id, |extra| {
+      extra
+        .views
+        .entry(view_id.to_string())
+        .or_default()
+        .last_opened_time = timestamp();
+    });
+    if let Err(err) = result {
+      tracing::error!(
+        "Failed to set the last opened time of view:{}, error: {:?}",
+        view_id,
+        err
+      );
+    }
+  }
+
+  /// Records the current user as the creator of the views.
+  async fn set_views_created_by(&self, view_ids: &[&str]) {
+    let result = match (self.user.user_id(), self.get_current_workspace_id().await) {
+      (Ok(uid), Ok(workspace_id)) => self.folder_extra.update(&workspace_id, |extra| {
+        for view_id in view_ids {
+          extra
+            .views
+            .entry(view_id.to_string())
+            .or_default()
+            .created_by = uid;
+        }
+      }),
+      (Err(err), _) | (_, Err(err)) => Err(err),
+    };
+    if let Err(err) = result {
+      tracing::error!(
+        "Failed to set the creator of views:{:?}, error: {:?}",
+        view_ids,
+        err
+      );
+    }
+  }
+
+  /// Fills the extra data of the views and their child views, see [fill_view_extras].
+  ///
+  /// The pinned views are moved before their unpinned siblings, and the relative order within the
+  /// pinned and the unpinned views is preserved.
+  fn fill_view_extras(&self, views: &mut [ViewPB]) {
+    self.fill_flat_view_extras(views);
+    sort_pinned_view_tree(views);
+  }
+
+  /// Fills the extra data of the views and their child views like [Self::fill_view_extras], but
+  /// keeps the order of the views. It's used for the lists that are not sorted as the view tree,
+  /// for example, the search results.
+  pub(crate) fn fill_flat_view_extras(&self, views: &mut [ViewPB]) {
+    let workspace_id = self
+      .mutex_folder
+      .lock()
+      .as_ref()
+      .map(|folder| folder.get_workspace_id());
+    if let Some(workspace_id) = workspace_id {
+      let extra = self.folder_extra.get(&workspace_id);
+      fill_view_extras(&extra, views);
+      let hidden_ids = get_hidden_view_ids(&extra);
+      self.with_folder(
+        || (),
+        |folder| fill_child_counts(folder, &hidden_ids, views),
+      );
+    }
+  }
+
+  /// Locks or unlocks the view. The locked view can't be updated, moved or deleted until it's
+  /// unlocked.
+  #[tracing::instrument(level = "debug", skip(self), err)]
+  pub async fn set_view_locked(&self, view_id: &str, locked: bool) -> FlowyResult<()> {
+    // Make sure the view exists
+    let _ = self.get_view_pb(view_id).await?;
+    let workspace_id = self.get_current_workspace_id().await?;
+    self.folder_extra.update(&workspace_id, |extra| {
+      extra.views.entry(view_id.to_string()).or_default().locked = locked;
+    })?;
+
+    let view = self.get_view_pb(view_id).await?;
+    send_coalesced_notification(view_id, FolderNotification::DidUpdateView, view);
+    Ok(())
+  }
+
+  /// Toggles the pin state of the views. Each parent of the toggled views is notified with its
+  /// child views in the new order.
+  #[tracing::instrument(level = "debug", skip(self), err)]
+  pub async fn toggle_view_pins(&self, view_ids: Vec<String>) -> FlowyResult<()> {
+    let workspace_id = self.get_current_workspace_id().await?;
+    let mut parent_view_ids = vec![];
+    for view_id in &view_ids {
+      let view = self.get_view_pb(view_id).await?;
+      if !parent_view_ids.contains(&view.parent_view_id) {
+        parent_view_ids.push(view.parent_view_id);
+      }
+    }
+    self.folder_extra.update(&workspace_id, |extra| {
+      for view_id in &view_ids {
+        let view_extra = extra.views.entry(view_id.clone()).or_default();
+        view_extra.pinned = !view_extra.pinned;
+      }
+    })?;
+
+    self
+      .notify_did_update_parent_views(&workspace_id, parent_view_ids)
+      .await?;
+    Ok(())
+  }
+
+  /// Notifies the parent views with their child views, which are built with the view extras.
+  async fn notify_did_update_parent_views(
+    &self,
+    workspace_id: &str,
+    parent_view_ids: Vec<String>,
+  ) -> FlowyResult<()> {
+    for parent_view_id in parent_view_ids {
+      if parent_view_id == workspace_id {
+        let views = self.get_workspace_views(workspace_id).await?;
+        send_coalesced_notification(
+          workspace_id,
+          FolderNotification::DidUpdateWorkspaceViews,
+          RepeatedViewPB::from(views),
+        );
+      } else if let Ok(parent_view) = self.get_view_pb(&parent_view_id).await {
+        send_coalesced_notification(
+          &parent_view_id,
+          FolderNotification::DidUpdateView,
+          parent_view,
+        );
+      }
+    }
+    Ok(())
+  }
+
+  /// Marks the view as a template or not. The template view is moved out of the child views of
+  /// its parent, so the parent is notified with its new child views.
+  #[tracing::instrument(level = "debug", skip(self), err)]
+  pub async fn set_view_template(&self, view_id: &str, is_template: bool) -> FlowyResult<()> {
+    let parent_view_id = self
+      .with_folder(|| None, |folder| folder.views.get_view(view_id))
+      .map(|view| view.parent_view_id.clone())
+      .ok_or_else(|| {
+        FlowyError::record_not_found()
+          .with_context(format!("Can't find the view with id: {}", view_id))
+      })?;
+    self.check_view_unlocked(view_id)?;
+    let workspace_id = self.get_current_workspace_id().await?;
+    self.folder_extra.update(&workspace_id, |extra| {
+      extra
+        .views
+        .entry(view_id.to_string())
+        .or_default()
+        .is_template = is_template;
+    })?;
+
+    self
+      .notify_did_update_parent_views(&workspace_id, vec![parent_view_id])
+      .await?;
+    Ok(())
+  }
+
+  /// Returns the template views of the current workspace that are not in the trash, including
+  /// the document and the database templates. The templates keep their order in the view tree.
+  #[tracing::instrument(level = "debug", skip(self), err)]
+  pub async fn get_templates(&self) -> FlowyResult<Vec<ViewPB>> {
+    let workspace_id = self.get_current_workspace_id().await?;
+    let template_ids = self.get_template_view_ids();
+    if template_ids.is_empty() {
+      return Ok(vec![]);
+    }
+    let mut views = self.with_folder(Vec::new, |folder| {
+      get_workspace_all_views(folder, &workspace_id, false)
+        .into_iter()
+        .filter(|view| template_ids.contains(&view.id))
+        .map(view_pb_without_child_views)
+        .collect::<Vec<_>>()
+    });
+    self.fill_flat_view_extras(&mut views);
+    Ok(views)
+  }
+
+  fn get_template_view_ids(&self) -> HashSet<String> {
+    let workspace_id = self.with_folder(|| None, |folder| Some(folder.get_workspace_id()));
+    match workspace_id {
+      Some(workspace_id) => self
+        .folder_extra
+        .get(&workspace_id)
+        .views
+        .into_iter()
+        .filter(|(_, view_extra)| view_extra.is_template)
+        .map(|(view_id, _)| view_id)
+        .collect(),
+      None => HashSet::new(),
+    }
+  }
+
+  /// Marks the views as archived or not. The parents of the views are notified with their new
+  /// child views, because the archived views are moved out of the view tree.
+  #[tracing::instrument(level = "debug", skip(self), err)]
+  pub async fn set_views_archived(&self, items: Vec<(String, bool)>) -> FlowyResult<()> {
+    let mut parent_view_ids = vec![];
+    for (view_id, _) in &items {
+      let parent_view_id = self
+        .with_folder(|| None, |folder| folder.views.get_view(view_id))
+        .map(|view| view.parent_view_id.clone())
+        .ok_or_else(|| {
+          FlowyError::record_not_found()
+            .with_context(format!("Can't find the view with id: {}", view_id))
+        })?;
+      if !parent_view_ids.contains(&parent_view_id) {
+        parent_view_ids.push(parent_view_id);
+      }
+    }
+    let view_ids = items
+      .iter()
+      .map(|(view_id, _)| view_id.as_str())
+      .collect::<Vec<_>>();
+    self.check_views_unlocked(&view_ids)?;
+    let workspace_id = self.get_current_workspace_id().await?;
+    self.folder_extra.update(&workspace_id, |extra| {
+      for (view_id, is_archived) in items {
+        extra.views.entry(view_id).or_default().archived = is_archived;
+      }
+    })?;
+
+    self
+      .notify_did_update_parent_views(&workspace_id, parent_view_ids)
+      .await?;
+    Ok(())
+  }
+
+  /// Returns the archived views of the current workspace that are not in the trash. The views
+  /// keep their order in the view tree.
+  #[tracing::instrument(level = "debug", skip(self), err)]
+  pub async fn get_archived_views(&self) -> FlowyResult<Vec<ViewPB>> {
+    let workspace_id = self.get_current_workspace_id().await?;
+    let archived_ids = self.get_archived_view_ids();
+    if archived_ids.is_empty() {
+      return Ok(vec![]);
+    }
+    let mut views = self.with_folder(Vec::new, |folder| {
+      get_workspace_all_views(folder, &workspace_id, false)
+        .into_iter()
+        .filter(|view| archived_ids.contains(&view.id))
+        .map(view_pb_without_child_views)
+        .collect::<Vec<_>>()
+    });
+    self.fill_flat_view_extras(&mut views);
+    Ok(views)
+  }
+
+  fn get_archived_view_ids(&self) -> HashSet<String> {
+    let workspace_id = self.with_folder(|| None, |folder| Some(folder.get_workspace_id()));
+    match workspace_id {
+      Some(workspace_id) => self
+        .folder_extra
+        .get(&workspace_id)
+        .views
+        .into_iter()
+        .filter(|(_, view_extra)| view_extra.archived)
+        .map(|(view_id, _)| view_id)
+        .collect(),
+      None => HashSet::new(),
+    }
+  }
+
+  /// Returns the ids of the template and the archived views, which are hidden from the view tree.
+  fn get_hidden_view_ids(&self) -> HashSet<String> {
+    let mut hidden_ids = self.get_template_view_ids();
+    hidden_ids.extend(self.get_archived_view_ids());
+    hidden_ids
+  }
+
+  /// Removes the template and the archived views from the views and from their child views.
+  fn remove_hidden_views(&self, views: &mut Vec<ViewPB>) {
+    remove_hidden_views(&self.get_hidden_view_ids(), views);
+  }
+
+  fn get_pinned_view_ids(&self) -> HashSet<String> {
+    let workspace_id = self.with_folder(|| None, |folder| Some(folder.get_workspace_id()));
+    match workspace_id {
+      Some(workspace_id) => self
+        .folder_extra
+        .get(&workspace_id)
+        .views
+        .into_iter()
+        .filter(|(_, view_extra)| view_extra.pinned)
+        .map(|(view_id, _)| view_id)
+        .collect(),
+      None => HashSet::new(),
+    }
+  }
+
+  /// Adds the tags to the view. The tags that the view already has, regardless of the case, are
+  /// ignored.
+  #[tracing::instrument(level = "debug", skip(self), err)]
+  pub async fn add_view_tags(&self, params: ViewTagsParams) -> FlowyResult<()> {
+    self
+      .update_view_tags(&params.view_id, |tags| {
+        for tag in params.tags {
+          if !contains_tag(tags, &tag) {
+            tags.push(tag);
+          }
+        }
+      })
+      .await
+  }
+
+  /// Removes the tags from the view. The tags are matched case-insensitively.
+  #[tracing::instrument(level = "debug", skip(self), err)]
+  pub async fn remove_view_tags(&self, params: ViewTagsParams) -> FlowyResult<()> {
+    self
+      .update_view_tags(&params.view_id, |tags| {
+        tags.retain(|tag| !contains_tag(&params.tags, tag));
+      })
+      .await
+  }
+
+  async fn update_view_tags<F>(&self, view_id: &str, f: F) -> FlowyResult<()>
+  where
+    F: FnOnce(&mut Vec<String>),
+  {
+    // Make sure the view exists
+    let _ = self.get_view_pb(view_id).await?;
+    self.check_view_unlocked(view_id)?;
+    let workspace_id = self.get_current_workspace_id().await?;
+    self.folder_extra.update(&workspace_id, |extra| {
+      f(&mut extra.views.entry(view_id.to_string()).or_default().tags);
+    })?;
+
+    let view = self.get_view_pb(view_id).await?;
+    send_coalesced_notification(view_id, FolderNotification::DidUpdateView, view);
+    Ok(())
+  }
+
+  /// Returns the views of the current workspace that carry the tag. The views in the trash are
+  /// excluded, and the views keep the order of the view tree.
+  #[tracing::instrument(level = "debug", skip(self), err)]
+  pub async fn get_views_by_tag(&self, tag: &str) -> FlowyResult<Vec<ViewPB>> {
+    let workspace_id = self.get_current_workspace_id().await?;
+    let tagged_view_ids = self
+      .folder_extra
+      .get(&workspace_id)
+      .views
+      .into_iter()
+      .filter(|(_, view_extra)| contains_tag(&view_extra.tags, tag))
+      .map(|(view_id, _)| view_id)
+      .collect::<HashSet<_>>();
+    if tagged_view_ids.is_empty() {
+      return Ok(vec![]);
+    }
+
+    let mut views = self.with_folder(Vec::new, |folder| {
+      get_workspace_all_views(folder, &workspace_id, false)
+        .into_iter()
+        .filter(|view| tagged_view_ids.contains(&view.id))
+        .map(view_pb_without_child_views)
+        .collect::<Vec<_>>()
+    });
+    self.fill_flat_view_extras(&mut views);
+    Ok(views)
+  }
+
+  /// Returns the views of the current workspace with the given color, the views in the trash are
+  /// excluded. `None` returns the views without a color.
+  #[tracing::instrument(level = "debug", skip(self), err)]
+  pub async fn get_views_by_color(&self, color: Option<ViewColor>) -> FlowyResult<Vec<ViewPB>> {
+    let workspace_id = self.get_current_workspace_id().await?;
+    let view_extras = self.folder_extra.get(&workspace_id).views;
+    let mut views = self.with_folder(Vec::new, |folder| {
+      get_workspace_all_views(folder, &workspace_id, false)
+        .into_iter()
+        .filter(|view| {
+          view_extras
+            .get(&view.id)
+            .and_then(|extra| extra.color.clone())
+            == color
+        })
+        .map(view_pb_without_child_views)
+        .collect::<Vec<_>>()
+    });
+    self.fill_flat_view_extras(&mut views);
+    Ok(views)
+  }
+
+  /// Returns the views of the current workspace that were edited by the user last time, the views
+  /// in the trash are excluded. The views that have never been edited are matched by their
+  /// creator.
+  #[tracing::instrument(level = "debug", skip(self), err)]
+  pub async fn get_views_edited_by(&self, editor_uid: i64) -> FlowyResult<Vec<ViewPB>> {
+    let workspace_id = self.get_current_workspace_id().await?;
+    let edited_view_ids = self
+      .folder_extra
+      .get(&workspace_id)
+      .views
+      .into_iter()
+      .filter(|(_, view_extra)| view_extra.last_editor() == editor_uid)
+      .map(|(view_id, _)| view_id)
+      .collect::<HashSet<_>>();
+    if edited_view_ids.is_empty() {
+      return Ok(vec![]);
+    }
+
+    let mut views = self.with_folder(Vec::new, |folder| {
+      get_workspace_all_views(folder, &workspace_id, false)
+        .into_iter()
+        .filter(|view| edited_view_ids.contains(&view.id))
+        .map(view_pb_without_child_views)
+        .collect::<Vec<_>>()
+    });
+    self.fill_flat_view_extras(&mut views);
+    Ok(views)
+  }
+
+  /// Returns the view and its descendants as a flat list in depth-first order. Each view carries
+  /// its depth relative to the view. The trashed descendants are skipped together with their
+  /// child views unless `include_trash` is set.
+  #[tracing::instrument(level = "debug", skip(self), err)]
+  pub async fn flatten_subtree(&self, params: FlattenSubtreeParams) -> FlowyResult<Vec<ViewPB>> {
+    let mut views = self.with_folder(
+      || Err(folder_not_init_error()),
+      |folder| {
+        let root = folder.views.get_view(&params.view_id).ok_or_else(|| {
+          FlowyError::record_not_found()
+            .with_context(format!("Can't find the view with id: {}", params.view_id))
+        })?;
+        let trash_ids = get_trash_ids(folder);
+        let mut views = vec![];
+        let mut visited = HashSet::new();
+        let mut stack = vec![(root, 0)];
+        while let Some((view, depth)) = stack.pop() {
+          if !visited.insert(view.id.clone()) {
+            continue;
+          }
+          let mut child_views = folder
+            .views
+            .get_views_belong_to(&view.id)
+            .into_iter()
+            .filter(|child| params.include_trash || !trash_ids.contains(&child.id))
+            .map(|child| (child, depth + 1))
+            .collect::<Vec<_>>();
+          child_views.reverse();
+          stack.extend(child_views);
+          if depth > 0 || params.include_root {
+            let mut view_pb = view_pb_without_child_views(view);
+            view_pb.depth = depth;
+            views.push(view_pb);
+          }
+        }
+        Ok(views)
+      },
+    )?;
+    self.fill_flat_view_extras(&mut views);
+    Ok(views)
+  }
+
+  /// Returns an error if the view is locked.
+  pub(crate) fn check_view_unlocked(&self, view_id: &str) -> FlowyResult<()> {
+    self.check_views_unlocked(&[view_id])
+  }
+
+  /// Returns an error if any of the views is locked.
+  fn check_views_unlocked<T: AsRef<str>>(&self, view_ids: &[T]) -> FlowyResult<()> {
+    let workspace_id = match self.with_folder(|| None, |folder| Some(folder.get_workspace_id())) {
+      Some(workspace_id) => workspace_id,
+      None => return Ok(()),
+    };
+    let view_extras = self.folder_extra.get(&workspace_id).views;
+    let locked_view_id = view_ids.iter().map(AsRef::as_ref).find(|view_id| {
+      view_extras
+        .get(*view_id)
+        .map(|view_extra| view_extra.locked)
+        .unwrap_or(false)
+    });
+    if let Some(view_id) = locked_view_id {
+      return Err(
+        FlowyError::view_locked().with_context(format!("The view:{} is locked", view_id)),
+      );
+    }
+    Ok(())
+  }
+
+  async fn get_favorite_order(&self) -> Vec<String> {
+    match self.get_current_workspace_id().await {
+      Ok(workspace_id) => self.folder_extra.get(&workspace_id).favorite_order,
+      Err(_) => vec![],
+    }
+  }
+
+  /// Returns the trash items of the workspace. The trash of a workspace other than the current
+  /// one is read from the local disk. Only the items whose views belong to the workspace are
+  /// returned, see [get_workspace_trash].
+  #[tracing::instrument(level = "trace", skip(self), err)]
+  pub(crate) async fn get_all_trash(&self, workspace_id: &str) -> FlowyResult<Vec<TrashInfo>> {
+    let current_workspace_id = self.get_current_workspace_id().await.ok();
+    if current_workspace_id.as_deref() == Some(workspace_id) {
+      return Ok(self.with_folder(Vec::new, |folder| get_workspace_trash(folder, workspace_id)));
+    }
+
+    let uid = self.user.user_id()?;
+    if !is_exist_in_local_disk(&self.user, workspace_id)? {
+      return Err(
+        FlowyError::record_not_found()
+          .with_context(format!("Workspace:{} does not exist", workspace_id)),
+      );
+    }
+    let folder = self.open_folder(uid, workspace_id).await?;
+    Ok(get_workspace_trash(&folder, workspace_id))
+  }
+
+  /// Fills the reasons recorded by [Self::move_view_to_trash_with_reason] into the trash items of
+  /// the current workspace.
+  pub(crate) fn fill_trash_reasons(&self, trash: &mut [TrashPB]) {
+    if let Some(workspace_id) = self.with_folder(|| None, |folder| Some(folder.get_workspace_id()))
+    {
+      fill_trash_reasons(&self.folder_extra.get(&workspace_id), trash);
+    }
+  }
+
+  /// Fills the reasons recorded by [Self::move_view_to_trash_with_reason] into the trash items of
+  /// the workspace.
+  pub(crate) async fn fill_workspace_trash_reasons(
+    &self,
+    workspace_id: &str,
+    trash: &mut [TrashPB],
+  ) {
+    fill_trash_reasons(&self.get_folder_extra(workspace_id).await, trash);
+  }
 
-      send_notification(&view.id, FolderNotification::DidUpdateView)
-        .payload(view)
-        .send()
-    }
+  /// Returns the number of the trash items of the workspace. Like [Self::get_all_trash], only
+  /// the trash of the given workspace is counted.
+  #[tracing::instrument(level = "trace", skip(self), err)]
+  pub(crate) async fn get_trash_count(&self, workspace_id: &str) -> FlowyResult<usize> {
+    Ok(self.get_all_trash(workspace_id).await?.len())
   }
 
-  #[tracing::instrument(level = "trace", skip(self))]
-  pub(crate) async fn get_all_favorites(&self) -> Vec<SectionItem> {
-    self.with_folder(Vec::new, |folder| {
-      let trash_ids = folder
-        .get_all_trash()
+  /// Returns the trash items of the workspace in the range of `offset..offset + limit`, the
+  /// newest deleted first, along with the number of all the trash items of the workspace.
+  #[tracing::instrument(level = "trace", skip(self), err)]
+  pub(crate) async fn get_trash_paged(
+    &self,
+    workspace_id: &str,
+    offset: usize,
+    limit: usize,
+  ) -> FlowyResult<(Vec<TrashInfo>, usize)> {
+    let mut trash = self.get_all_trash(workspace_id).await?;
+    let total_count = trash.len();
+    trash.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    let page = trash.into_iter().skip(offset).take(limit).collect();
+    Ok((page, total_count))
+  }
+
+  /// Restores all the trash items like [Self::restore_trash_batch]. The items that can't be
+  /// restored are kept in the trash and reported as failed instead of stopping the restoring.
+  #[tracing::instrument(level = "trace", skip(self), err)]
+  pub(crate) async fn restore_all_trash(&self) -> FlowyResult<RestoreAllResultPB> {
+    let workspace_id = self.get_current_workspace_id().await?;
+    let trash_ids = self.with_folder(Vec::new, |folder| {
+      get_workspace_trash(folder, &workspace_id)
         .into_iter()
         .map(|trash| trash.id)
-        .collect::<Vec<String>>();
-
-      let mut views = folder.get_all_favorites();
-      views.retain(|view| !trash_ids.contains(&view.id));
-      views
+        .collect::<Vec<_>>()
+    });
+    let result = self.restore_trash_batch(trash_ids).await?;
+    let mut remaining_trash: RepeatedTrashPB = self.get_all_trash(&workspace_id).await?.into();
+    self.fill_trash_reasons(&mut remaining_trash.items);
+    send_notification(&workspace_id, FolderNotification::DidUpdateTrash)
+      .payload(remaining_trash)
+      .send();
+    Ok(RestoreAllResultPB {
+      restored_count: result.restored_ids.len() as u32,
+      failed: result.failed_ids,
     })
   }
 
   #[tracing::instrument(level = "trace", skip(self))]
-  pub(crate) async fn get_all_trash(&self) -> Vec<TrashInfo> {
-    self.with_folder(Vec::new, |folder| folder.get_all_trash())
+  pub(crate) async fn restore_trash(&self, trash_id: &str) {
+    let is_restored = self.with_folder(
+      || false,
+      |folder| {
+        let is_trashed = get_trash_ids(folder).contains(trash_id);
+        folder.delete_trash(vec![trash_id.to_string()]);
+        is_trashed
+      },
+    );
+    if is_restored {
+      self.operation_history.record(FolderOperation::RestoreView {
+        view_id: trash_id.to_string(),
+      });
+    }
   }
 
-  #[tracing::instrument(level = "trace", skip(self))]
-  pub(crate) async fn restore_all_trash(&self) {
-    self.with_folder(
-      || (),
+  /// Restores the listed trash items in one pass. The restored view whose parent no longer exists
+  /// is attached to the root of the workspace. The ids that are not in the trash are returned as
+  /// the failed ids.
+  #[tracing::instrument(level = "debug", skip(self), err)]
+  pub(crate) async fn restore_trash_batch(
+    &self,
+    trash_ids: Vec<String>,
+  ) -> FlowyResult<RestoreTrashBatchPB> {
+    let (result, changed_parent_ids) = self.with_folder(
+      || Err(folder_not_init_error()),
       |folder| {
-        folder.remote_all_trash();
+        let workspace_id = folder.get_workspace_id();
+        let all_trash_ids = get_trash_ids(folder);
+        let mut result = RestoreTrashBatchPB::default();
+        let mut visited = HashSet::new();
+        for trash_id in trash_ids {
+          if !visited.insert(trash_id.clone()) {
+            continue;
+          }
+          if all_trash_ids.contains(&trash_id) && folder.views.get_view(&trash_id).is_some() {
+            result.restored_ids.push(trash_id);
+          } else {
+            result.failed_ids.push(trash_id);
+          }
+        }
+        folder.delete_trash(result.restored_ids.clone());
+
+        let mut changed_parent_ids = vec![];
+        for view_id in &result.restored_ids {
+          let parent_view_id = match folder.views.get_view(view_id) {
+            None => continue,
+            Some(view) => view.parent_view_id.clone(),
+          };
+          if parent_view_id != workspace_id && folder.views.get_view(&parent_view_id).is_none() {
+            folder.move_nested_view(view_id, &workspace_id, None);
+            changed_parent_ids.push(workspace_id.clone());
+          }
+        }
+        Ok((result, changed_parent_ids))
       },
-    );
-    send_notification("trash", FolderNotification::DidUpdateTrash)
-      .payload(RepeatedTrashPB { items: vec![] })
-      .send();
+    )?;
+    // The view attached to the root of the workspace is trashed there when the restoring is
+    // undone, since its original parent no longer exists.
+    for view_id in &result.restored_ids {
+      self.operation_history.record(FolderOperation::RestoreView {
+        view_id: view_id.clone(),
+      });
+    }
+
+    if !changed_parent_ids.is_empty() {
+      notify_parent_view_did_change(
+        self.mutex_folder.clone(),
+        &self.folder_extra,
+        changed_parent_ids,
+      );
+    }
+    Ok(result)
   }
 
-  #[tracing::instrument(level = "trace", skip(self))]
-  pub(crate) async fn restore_trash(&self, trash_id: &str) {
-    self.with_folder(
-      || (),
+  /// Restores the trashed view and attaches it to the `target_parent_id` instead of its original
+  /// parent. The `target_parent_id` can be the id of the current workspace or a view that is not
+  /// in the trash.
+  #[tracing::instrument(level = "debug", skip(self), err)]
+  pub(crate) async fn restore_trash_to(
+    &self,
+    trash_id: &str,
+    target_parent_id: &str,
+  ) -> FlowyResult<()> {
+    self.check_nesting_depth(target_parent_id, Some(trash_id))?;
+    let (old_parent_id, move_operation) = self.with_folder(
+      || Err(folder_not_init_error()),
       |folder| {
+        let trash_ids = get_trash_ids(folder);
+        if !trash_ids.contains(trash_id) {
+          return Err(
+            FlowyError::record_not_found()
+              .with_context(format!("Can't find the trash:{}", trash_id)),
+          );
+        }
+        let view = folder
+          .views
+          .get_view(trash_id)
+          .ok_or_else(|| FlowyError::record_not_found().with_context("Can't find the view"))?;
+
+        if target_parent_id != folder.get_workspace_id() {
+          if folder.views.get_view(target_parent_id).is_none() {
+            return Err(
+              FlowyError::record_not_found()
+                .with_context(format!("Can't find the parent view:{}", target_parent_id)),
+            );
+          }
+          if is_trashed(folder, target_parent_id, &trash_ids) {
+            return Err(FlowyError::invalid_data().with_context("The parent view is in the trash"));
+          }
+        }
+
+        let old_prev_view_id = get_prev_view_id(folder, &view.parent_view_id, trash_id);
         folder.delete_trash(vec![trash_id.to_string()]);
+        folder.move_nested_view(trash_id, target_parent_id, None);
+        let move_operation = FolderOperation::MoveView {
+          view_id: trash_id.to_string(),
+          old_parent_id: view.parent_view_id.clone(),
+          old_prev_view_id,
+          new_parent_id: target_parent_id.to_string(),
+          new_prev_view_id: None,
+        };
+        Ok((view.parent_view_id.clone(), move_operation))
       },
+    )?;
+    // Undoing the restoring moves the view back to its original parent and then trashes it.
+    self.operation_history.record(FolderOperation::RestoreView {
+      view_id: trash_id.to_string(),
+    });
+    self.operation_history.record(move_operation);
+
+    notify_parent_view_did_change(
+      self.mutex_folder.clone(),
+      &self.folder_extra,
+      vec![target_parent_id.to_string(), old_parent_id],
     );
+    Ok(())
   }
 
   /// Delete all the trash permanently.
   #[tracing::instrument(level = "trace", skip(self))]
   pub(crate) async fn delete_all_trash(&self) {
-    let deleted_trash = self.with_folder(Vec::new, |folder| folder.get_all_trash());
+    let workspace_id = match self.get_current_workspace_id().await {
+      Ok(workspace_id) => workspace_id,
+      Err(err) => {
+        tracing::error!("Failed to delete all the trash: {:?}", err);
+        return;
+      },
+    };
+    let deleted_trash = self.with_folder(Vec::new, |folder| {
+      get_workspace_trash(folder, &workspace_id)
+    });
     for trash in deleted_trash {
       let _ = self.delete_trash(&trash.id).await;
     }
-    send_notification("trash", FolderNotification::DidUpdateTrash)
+    send_notification(&workspace_id, FolderNotification::DidUpdateTrash)
       .payload(RepeatedTrashPB { items: vec![] })
       .send();
   }
@@ -870,56 +5356,737 @@ impl FolderManager {
     Ok(())
   }
 
-  pub(crate) async fn import(&self, import_data: ImportParams) -> FlowyResult<View> {
-    if import_data.data.is_none() && import_data.file_path.is_none() {
-      return Err(FlowyError::new(
-        ErrorCode::InvalidParams,
-        "data or file_path is required",
-      ));
+  /// Sets the number of days that the trash items will be kept. The trash items older than the
+  /// given days will be deleted permanently. Zero means the trash items will be kept forever.
+  #[tracing::instrument(level = "debug", skip(self), err)]
+  pub(crate) async fn set_trash_retention(&self, days: u32) -> FlowyResult<()> {
+    let workspace_id = self.get_current_workspace_id().await?;
+    self.folder_extra.update(&workspace_id, |extra| {
+      extra.setting.trash_retention_days = days;
+    })?;
+    if days > 0 {
+      purge_expired_trash(&self.mutex_folder, &self.operation_handlers, days).await;
+    }
+    Ok(())
+  }
+
+  /// Start a background task that deletes the expired trash items of the workspace periodically.
+  /// The previous task will be stopped.
+  fn start_trash_purge_task(&self, workspace_id: &str) {
+    let workspace_id = workspace_id.to_string();
+    let weak_mutex_folder = Arc::downgrade(&self.mutex_folder);
+    let weak_folder_extra = Arc::downgrade(&self.folder_extra);
+    let operation_handlers = self.operation_handlers.clone();
+    let task = af_spawn(async move {
+      let mut interval = tokio::time::interval(TRASH_PURGE_INTERVAL);
+      loop {
+        // The first tick completes immediately, so the purge runs on initialization.
+        interval.tick().await;
+        let (mutex_folder, folder_extra) =
+          match (weak_mutex_folder.upgrade(), weak_folder_extra.upgrade()) {
+            (Some(mutex_folder), Some(folder_extra)) => (mutex_folder, folder_extra),
+            _ => break,
+          };
+        let days = folder_extra.get(&workspace_id).setting.trash_retention_days;
+        if days > 0 {
+          purge_expired_trash(&mutex_folder, &operation_handlers, days).await;
+        }
+      }
+    });
+    if let Some(old_task) = self.trash_purge_task.lock().replace(task) {
+      old_task.abort();
+    }
+  }
+
+  /// Imports the data as a new view. The import with an id can be cancelled by
+  /// [FolderManager::cancel_import], and returns the [ErrorCode::ImportCancelled] error if it's
+  /// cancelled.
+  pub(crate) async fn import(&self, import_data: ImportParams) -> FlowyResult<ImportResultPB> {
+    let import_id = import_data.import_id.clone();
+    let cancelled = Arc::new(AtomicBool::new(false));
+    if let Some(import_id) = &import_id {
+      let mut running_imports = self.running_imports.lock();
+      if running_imports.contains_key(import_id) {
+        return Err(
+          FlowyError::invalid_data()
+            .with_context(format!("The import:{} is already running", import_id)),
+        );
+      }
+      running_imports.insert(import_id.clone(), cancelled.clone());
+    }
+
+    let result = self.import_data(import_data, &cancelled).await;
+    if let Some(import_id) = &import_id {
+      self.running_imports.lock().remove(import_id);
+    }
+    result
+  }
+
+  /// Cancels the running import. The import stops before its next item, and the views imported
+  /// so far are deleted.
+  #[tracing::instrument(level = "debug", skip(self), err)]
+  pub(crate) fn cancel_import(&self, import_id: &str) -> FlowyResult<()> {
+    match self.running_imports.lock().get(import_id) {
+      None => Err(
+        FlowyError::record_not_found()
+          .with_context(format!("The import:{} is not running", import_id)),
+      ),
+      Some(cancelled) => {
+        cancelled.store(true, Ordering::SeqCst);
+        Ok(())
+      },
+    }
+  }
+
+  /// Returns the views that [Self::import] would create, without writing anything. It's the dry
+  /// run of the import, so the preview goes through the same plan as the import, see
+  /// [Self::plan_import].
+  #[tracing::instrument(level = "debug", skip(self), err)]
+  pub(crate) async fn preview_import(
+    &self,
+    import_data: ImportParams,
+  ) -> FlowyResult<ImportPreviewPB> {
+    let plan = self.plan_import(&import_data)?;
+    let mut items = vec![ImportPreviewItemPB {
+      name: plan.root_name.clone(),
+      layout: plan.root_layout.clone().into(),
+      depth: 1,
+      name_collision: plan.root_name_collision,
+    }];
+    items.extend(plan.pages.iter().map(|page| ImportPreviewItemPB {
+      name: page.name.clone(),
+      layout: page.kind.layout().into(),
+      depth: page.depth,
+      name_collision: page.name_collision,
+    }));
+    Ok(ImportPreviewPB {
+      items,
+      skipped: plan.limit_skipped_items(),
+    })
+  }
+
+  /// Plans the views that the import creates. The import and its dry run both go through the
+  /// plan, so the dry run reflects the outcome of the import: the names are suffixed if the
+  /// workspace keeps the names of the sibling views unique, and the pages over the view limit of
+  /// the workspace are skipped. The Notion export is read here, but nothing is written.
+  fn plan_import(&self, import_data: &ImportParams) -> FlowyResult<ImportPlan> {
+    check_import_source(import_data)?;
+    self.check_view_limit(1)?;
+
+    let is_notion = matches!(import_data.import_type, ImportType::NotionZip);
+    let mut pages = if is_notion {
+      plan_notion_pages(read_notion_pages(&notion_export_dir(import_data)?)?, 2)
+    } else {
+      vec![]
+    };
+    if self.unique_sibling_names_enabled() {
+      let mut sibling_names = HashMap::<Option<usize>, HashSet<String>>::new();
+      for page in pages.iter_mut() {
+        let names = sibling_names.entry(page.parent).or_default();
+        page.name = next_available_name(std::mem::take(&mut page.name), names);
+        names.insert(page.name.clone());
+      }
+    }
+    // The root view takes one view of the quota.
+    let skipped_pages = match self.remaining_view_quota() {
+      Some(quota) if pages.len() + 1 > quota => pages.split_off(quota.saturating_sub(1)),
+      _ => vec![],
+    };
+
+    let root_name_collision = self.with_folder(
+      || false,
+      |folder| {
+        let trash_ids = get_trash_ids(folder);
+        folder
+          .views
+          .get_views_belong_to(&import_data.parent_view_id)
+          .iter()
+          .any(|view| view.name == import_data.name && !trash_ids.contains(&view.id))
+      },
+    );
+    Ok(ImportPlan {
+      root_name: self.unique_sibling_name(
+        &import_data.parent_view_id,
+        import_data.name.clone(),
+        None,
+      ),
+      root_layout: if is_notion {
+        ViewLayout::Document
+      } else {
+        import_data.view_layout.clone()
+      },
+      root_name_collision,
+      pages,
+      skipped_pages,
+    })
+  }
+
+  async fn import_data(
+    &self,
+    import_data: ImportParams,
+    cancelled: &AtomicBool,
+  ) -> FlowyResult<ImportResultPB> {
+    let plan = self.plan_import(&import_data)?;
+    if import_data.dry_run {
+      return Ok(plan.into_dry_run_result(import_data.parent_view_id));
+    }
+
+    if matches!(import_data.import_type, ImportType::NotionZip) {
+      return self.import_notion(import_data, plan, cancelled).await;
+    }
+
+    let handler = self.get_handler(&import_data.view_layout)?;
+    let view_id = gen_view_id().to_string();
+    let uid = self.user.user_id()?;
+    if let Some(data) = import_data.data {
+      handler
+        .import_from_bytes(
+          uid,
+          &view_id,
+          &plan.root_name,
+          import_data.import_type,
+          data,
+        )
+        .await
+        .map_err(map_import_error)?;
+    }
+
+    if let Some(file_path) = import_data.file_path {
+      handler
+        .import_from_file_path(&view_id, &plan.root_name, file_path)
+        .await
+        .map_err(map_import_error)?;
+    }
+
+    if cancelled.load(Ordering::SeqCst) {
+      let _ = handler.delete_view(&view_id).await;
+      return Err(FlowyError::import_cancelled());
+    }
+
+    let params = CreateViewParams {
+      parent_view_id: import_data.parent_view_id,
+      name: plan.root_name,
+      desc: "".to_string(),
+      layout: Some(import_data.view_layout.clone().into()),
+      initial_data: vec![],
+      view_id,
+      meta: Default::default(),
+      set_as_current: false,
+      index: None,
+    };
+
+    let view = create_view(params, import_data.view_layout);
+    self.with_folder(
+      || (),
+      |folder| {
+        folder.insert_view(view.clone(), None);
+      },
+    );
+    ImportProgressNotifier::new(&view.parent_view_id, 1).advance(1, &view.name);
+    notify_parent_view_did_change(
+      self.mutex_folder.clone(),
+      &self.folder_extra,
+      vec![view.parent_view_id.clone()],
+    );
+    Ok(ImportResultPB {
+      imported_names: vec![view.name.clone()],
+      view: view_pb_without_child_views(Arc::new(view)),
+      created_count: 1,
+      requested_count: 1,
+      skipped: vec![],
+    })
+  }
+
+  /// Exports the view in the given format. If `include_children` is true, the descendants of the
+  /// view are exported as well, each under a heading whose level is its depth in the subtree.
+  ///
+  /// The views that can't be exported in the given format, for example, the grid or board views
+  /// in Markdown, are skipped. So are the documents that fail to export, the error is logged and
+  /// the rest of the subtree is still exported.
+  #[tracing::instrument(level = "debug", skip(self), err)]
+  pub(crate) async fn export_view(&self, params: ExportParams) -> FlowyResult<ExportDataPB> {
+    let views = self.with_folder(
+      || Err(folder_not_init_error()),
+      |folder| {
+        let trash_ids = get_trash_ids(folder);
+        if trash_ids.contains(&params.view_id) {
+          return Err(FlowyError::record_not_found());
+        }
+        let view = folder
+          .views
+          .get_view(&params.view_id)
+          .ok_or_else(FlowyError::record_not_found)?;
+
+        let mut views = vec![];
+        let mut visited = HashSet::new();
+        let mut stack = vec![(view, 0_usize)];
+        while let Some((view, level)) = stack.pop() {
+          if !visited.insert(view.id.clone()) {
+            continue;
+          }
+          if params.include_children {
+            let mut child_views = folder.views.get_views_belong_to(&view.id);
+            child_views.retain(|child_view| !trash_ids.contains(&child_view.id));
+            child_views.reverse();
+            stack.extend(
+              child_views
+                .into_iter()
+                .map(|child_view| (child_view, level + 1)),
+            );
+          }
+          views.push((view, level));
+        }
+        Ok(views)
+      },
+    )?;
+
+    let file_name = format!(
+      "{}.{}",
+      sanitize_file_name(&views[0].0.name),
+      params.format.file_extension()
+    );
+    let mut data = String::new();
+    for (view, level) in views {
+      if view.layout != ViewLayout::Document {
+        tracing::warn!(
+          "Skip exporting the view:{} with layout:{:?}",
+          view.id,
+          view.layout
+        );
+        continue;
+      }
+
+      let handler = self.get_handler(&view.layout)?;
+      let content = match params.format {
+        ExportFormat::Markdown => handler.export_view_to_markdown(&view.id).await,
+      };
+      let content = match content {
+        Ok(content) => content,
+        Err(err) => {
+          tracing::error!("Skip exporting the view:{}, error: {}", view.id, err);
+          continue;
+        },
+      };
+      if !data.is_empty() {
+        data.push('\n');
+      }
+      data.push_str(&format!(
+        "{} {}\n\n",
+        "#".repeat((level + 1).min(6)),
+        view.name
+      ));
+      data.push_str(&content);
+    }
+
+    Ok(ExportDataPB { data, file_name })
+  }
+
+  /// Exports the favorites as a list in their persisted order. Each item links the name of the
+  /// view to its path, see [Self::get_view_path]. The favorites in the trash or under a trashed
+  /// ancestor are skipped.
+  #[tracing::instrument(level = "debug", skip(self), err)]
+  pub(crate) async fn export_favorites(&self, format: ExportFormat) -> FlowyResult<ExportDataPB> {
+    let favorite_ids = self
+      .get_all_favorites()
+      .await
+      .into_iter()
+      .map(|favorite| favorite.id)
+      .collect::<Vec<_>>();
+    let favorite_ids = self.with_folder(Vec::new, |folder| {
+      let trash_ids = get_trash_ids(folder);
+      favorite_ids
+        .into_iter()
+        .filter(|view_id| !is_trashed(folder, view_id, &trash_ids))
+        .collect::<Vec<_>>()
+    });
+
+    let mut data = String::new();
+    for view_id in favorite_ids {
+      let path = match self.get_view_path(&view_id).await {
+        Ok(path) => path,
+        Err(err) => {
+          tracing::warn!("Skip exporting the favorite:{}: {}", view_id, err);
+          continue;
+        },
+      };
+      let name = path.segments.last().cloned().unwrap_or_default();
+      match format {
+        ExportFormat::Markdown => {
+          data.push_str(&markdown_link_item(&name, &path.joined));
+          data.push('\n');
+        },
+      }
+    }
+    let file_name = format!("Favorites.{}", format.file_extension());
+    Ok(ExportDataPB { data, file_name })
+  }
+
+  /// Exports the current workspace to a zip archive in the temporary directory, and returns the
+  /// path of the archive. The archive contains the manifest of the view tree and the data of each
+  /// view, check out the [WorkspaceManifest] for its layout. The trashed views are skipped.
+  ///
+  /// The data of the views is written to the archive one by one, so the memory usage doesn't grow
+  /// with the size of the workspace.
+  #[tracing::instrument(level = "debug", skip(self), err)]
+  pub async fn export_workspace(&self, workspace_id: &str) -> FlowyResult<ExportWorkspaceDataPB> {
+    if self.get_current_workspace_id().await? != workspace_id {
+      return Err(
+        FlowyError::not_support().with_context("Only the current workspace can be exported"),
+      );
+    }
+    let extra = self.folder_extra.get(workspace_id);
+    let (workspace_name, views, favorite_ids) = self.with_folder(
+      || Err(folder_not_init_error()),
+      |folder| {
+        let workspace_name = folder
+          .get_current_workspace()
+          .map(|workspace| extra.workspace.name.clone().unwrap_or(workspace.name))
+          .unwrap_or_default();
+        let views = get_workspace_all_views(folder, workspace_id, false);
+        let mut favorites = folder.get_all_favorites();
+        sort_favorites(&mut favorites, &extra.favorite_order);
+        let favorite_ids = favorites
+          .into_iter()
+          .map(|favorite| favorite.id)
+          .filter(|id| views.iter().any(|view| &view.id == id))
+          .collect::<Vec<_>>();
+        Ok((workspace_name, views, favorite_ids))
+      },
+    )?;
+
+    let file_path = std::env::temp_dir().join(format!(
+      "{}-{}.zip",
+      sanitize_file_name(&workspace_name),
+      timestamp()
+    ));
+    let mut writer = WorkspaceArchiveWriter::new(&file_path)?;
+    for view in views.iter() {
+      let handler = self.get_handler(&view.layout)?;
+      match handler.duplicate_view(&view.id).await {
+        Ok(data) => writer.add_view_data(&view.id, &data)?,
+        Err(err) => tracing::warn!(
+          "Skip exporting the data of view:{}, error: {:?}",
+          view.id,
+          err
+        ),
+      }
+    }
+    let manifest = WorkspaceManifest {
+      version: WORKSPACE_ARCHIVE_VERSION,
+      workspace_id: workspace_id.to_string(),
+      workspace_name,
+      view_extras: views
+        .iter()
+        .filter_map(|view| {
+          extra
+            .views
+            .get(&view.id)
+            .map(|view_extra| (view.id.clone(), view_extra.clone()))
+        })
+        .collect(),
+      views: views.iter().map(|view| view.as_ref().clone()).collect(),
+      favorite_ids,
+    };
+    writer.finish(&manifest)?;
+
+    Ok(ExportWorkspaceDataPB {
+      file_path: file_path.to_string_lossy().to_string(),
+    })
+  }
+
+  /// Imports the workspace archive written by [Self::export_workspace] into the current workspace,
+  /// and returns the imported top level views. The manifest of the archive drives the import, so
+  /// the hierarchy and the order of the views are the same as the exported ones.
+  ///
+  /// All the views are imported with new ids, so the existing views are never overwritten. The
+  /// parent ids, the favorites and the references between the imported views are remapped to the
+  /// new ids, and the extra data of the views is restored. The references to the views that are
+  /// not in the archive are kept as they are, since the content of the views still points to them.
+  #[tracing::instrument(level = "debug", skip(self, data), err)]
+  pub async fn import_workspace(
+    &self,
+    target_workspace_id: &str,
+    data: Vec<u8>,
+  ) -> FlowyResult<Vec<ViewPB>> {
+    let workspace_id = self.get_current_workspace_id().await?;
+    if !target_workspace_id.is_empty() && target_workspace_id != workspace_id {
+      return Err(
+        FlowyError::not_support()
+          .with_context("Only import into the current workspace is supported"),
+      );
+    }
+
+    let mut reader = WorkspaceArchiveReader::new(Cursor::new(data))?;
+    let manifest = reader.read_manifest()?;
+    let new_view_ids = manifest
+      .views
+      .iter()
+      .map(|view| (view.id.clone(), gen_view_id().to_string()))
+      .collect::<HashMap<_, _>>();
+    let mut params = Vec::with_capacity(manifest.views.len());
+    let mut icons = vec![];
+    for view in manifest.views {
+      let view_id = new_view_ids[&view.id].clone();
+      // The views whose parents are not in the archive are imported as the top level views.
+      let parent_view_id = new_view_ids
+        .get(&view.parent_view_id)
+        .cloned()
+        .unwrap_or_else(|| workspace_id.clone());
+      if view.icon.is_some() {
+        icons.push((view_id.clone(), view.icon.clone().map(normalize_view_icon)));
+      }
+      params.push(CreateViewParams {
+        parent_view_id,
+        name: view.name.clone(),
+        desc: view.desc.clone(),
+        layout: Some(view.layout.clone().into()),
+        view_id,
+        initial_data: reader.read_view_data(&view.id)?.unwrap_or_default(),
+        meta: Default::default(),
+        set_as_current: false,
+        index: None,
+      });
+    }
+
+    let views = self.create_views_with_params(params).await?;
+    let favorite_ids = manifest
+      .favorite_ids
+      .iter()
+      .filter_map(|view_id| new_view_ids.get(view_id).cloned())
+      .collect::<Vec<_>>();
+    self.with_folder(
+      || (),
+      |folder| {
+        for (view_id, icon) in icons {
+          folder
+            .views
+            .update_view(&view_id, |update| update.set_icon(icon).done());
+        }
+        if !favorite_ids.is_empty() {
+          folder.add_favorites(favorite_ids);
+        }
+      },
+    );
+    self.folder_extra.update(&workspace_id, |extra| {
+      for (view_id, mut view_extra) in manifest.view_extras {
+        let new_view_id = match new_view_ids.get(&view_id) {
+          None => continue,
+          Some(new_view_id) => new_view_id.clone(),
+        };
+        view_extra.referenced_view_ids = view_extra
+          .referenced_view_ids
+          .into_iter()
+          .map(|id| new_view_ids.get(&id).cloned().unwrap_or(id))
+          .collect();
+        // The trash of the archive is not imported.
+        view_extra.trash_reason = None;
+        let current_extra = extra.views.entry(new_view_id).or_default();
+        if view_extra.created_by == 0 {
+          view_extra.created_by = current_extra.created_by;
+        }
+        *current_extra = view_extra;
+      }
+    })?;
+    notify_parent_view_did_change(
+      self.mutex_folder.clone(),
+      &self.folder_extra,
+      vec![workspace_id.clone()],
+    );
+
+    let mut top_level_views = vec![];
+    for view in views
+      .iter()
+      .filter(|view| view.parent_view_id == workspace_id)
+    {
+      top_level_views.push(self.get_view_pb(&view.id).await?);
     }
+    Ok(top_level_views)
+  }
 
-    let handler = self.get_handler(&import_data.view_layout)?;
-    let view_id = gen_view_id().to_string();
+  /// Imports the unzipped Notion export. A document view named after the import is created under
+  /// the parent view, and the pages of the export are imported under it, keeping the hierarchy of
+  /// the export. The markdown files are imported as documents and the csv files as grids.
+  ///
+  /// The pages that fail to import are skipped along with their sub-pages, and listed in the
+  /// [ImportResultPB::skipped]. The links to the files that don't exist in the export are logged.
+  /// The pages are imported in the pre-order of the plan. If the view limit of the workspace is
+  /// reached, the rest of the pages are skipped and the views imported so far are kept.
+  async fn import_notion(
+    &self,
+    import_data: ImportParams,
+    plan: ImportPlan,
+    cancelled: &AtomicBool,
+  ) -> FlowyResult<ImportResultPB> {
     let uid = self.user.user_id()?;
-    if let Some(data) = import_data.data {
-      handler
-        .import_from_bytes(
-          uid,
-          &view_id,
-          &import_data.name,
-          import_data.import_type,
-          data,
-        )
-        .await?;
+    let document_handler = self.get_handler(&ViewLayout::Document)?;
+    let total = plan.requested_count();
+    let limit_skipped = plan.limit_skipped_items();
+    let mut progress = ImportProgressNotifier::new(&import_data.parent_view_id, total as u32);
+
+    let root_view_id = gen_view_id().to_string();
+    document_handler
+      .create_built_in_view(uid, &root_view_id, &plan.root_name, ViewLayout::Document)
+      .await?;
+    let root_view_params = notion_view_params(
+      import_data.parent_view_id,
+      plan.root_name,
+      root_view_id.clone(),
+      &ViewLayout::Document,
+    );
+    let root_view = create_view(root_view_params, ViewLayout::Document);
+    progress.advance(1, &root_view.name);
+
+    // The plan lists the parent page before its children, so the parent view is always inserted
+    // before them. The view ids of the imported pages are kept by their indexes in the plan.
+    let mut views = vec![root_view.clone()];
+    let mut skipped = vec![];
+    let mut page_view_ids: Vec<Result<String, FlowyError>> = Vec::with_capacity(plan.pages.len());
+    for page in plan.pages {
+      if cancelled.load(Ordering::SeqCst) {
+        break;
+      }
+      let parent_view_id = match page.parent {
+        None => Ok(root_view_id.clone()),
+        Some(parent) => page_view_ids[parent].clone(),
+      };
+      let parent_view_id = match parent_view_id {
+        Ok(parent_view_id) => parent_view_id,
+        Err(err) => {
+          // The sub-pages of the failed page are skipped along with it.
+          progress.advance(1, &page.name);
+          skipped.push(import_skipped_item(page.name, &err));
+          page_view_ids.push(Err(err));
+          continue;
+        },
+      };
+
+      let view_id = gen_view_id().to_string();
+      let result = match &page.kind {
+        NotionPageKind::Document(path) => {
+          self
+            .import_notion_document(uid, &view_id, &page.name, path)
+            .await
+        },
+        NotionPageKind::Database(path) => {
+          self
+            .import_notion_database(uid, &view_id, &page.name, path)
+            .await
+        },
+        NotionPageKind::Folder => document_handler
+          .create_built_in_view(uid, &view_id, &page.name, ViewLayout::Document)
+          .await
+          .map(|_| ViewLayout::Document),
+      };
+
+      progress.advance(1, &page.name);
+      match result {
+        Ok(layout) => {
+          let params = notion_view_params(parent_view_id, page.name, view_id.clone(), &layout);
+          views.push(create_view(params, layout));
+          page_view_ids.push(Ok(view_id));
+        },
+        Err(err) => {
+          tracing::error!(
+            "Skip importing the Notion page:{}, error: {:?}",
+            page.name,
+            err
+          );
+          skipped.push(import_skipped_item(page.name, &err));
+          page_view_ids.push(Err(err));
+        },
+      }
     }
 
-    if let Some(file_path) = import_data.file_path {
-      handler
-        .import_from_file_path(&view_id, &import_data.name, file_path)
-        .await?;
+    // The views are not inserted into the folder yet, so deleting their data is enough to roll
+    // back the cancelled import.
+    if cancelled.load(Ordering::SeqCst) {
+      tracing::info!(
+        "The Notion import is cancelled, delete the {} imported views",
+        views.len()
+      );
+      for view in views {
+        if let Ok(handler) = self.get_handler(&view.layout) {
+          let _ = handler.delete_view(&view.id).await;
+        }
+      }
+      return Err(FlowyError::import_cancelled());
     }
 
-    let params = CreateViewParams {
-      parent_view_id: import_data.parent_view_id,
-      name: import_data.name,
-      desc: "".to_string(),
-      layout: import_data.view_layout.clone().into(),
-      initial_data: vec![],
-      view_id,
-      meta: Default::default(),
-      set_as_current: false,
-      index: None,
-    };
+    if !limit_skipped.is_empty() {
+      tracing::info!(
+        "The view limit is reached, skip the last {} Notion pages",
+        limit_skipped.len()
+      );
+      progress.advance(limit_skipped.len() as u32, &limit_skipped[0].name);
+      skipped.extend(limit_skipped);
+    }
 
-    let view = create_view(params, import_data.view_layout);
+    let created_count = views.len();
+    let imported_names = views.iter().map(|view| view.name.clone()).collect();
     self.with_folder(
       || (),
       |folder| {
-        folder.insert_view(view.clone(), None);
+        for view in views {
+          folder.insert_view(view, None);
+        }
       },
     );
-    notify_parent_view_did_change(self.mutex_folder.clone(), vec![view.parent_view_id.clone()]);
-    Ok(view)
+    notify_parent_view_did_change(
+      self.mutex_folder.clone(),
+      &self.folder_extra,
+      vec![root_view.parent_view_id.clone()],
+    );
+    Ok(ImportResultPB {
+      view: view_pb_without_child_views(Arc::new(root_view)),
+      created_count: created_count as u32,
+      requested_count: total as u32,
+      imported_names,
+      skipped,
+    })
+  }
+
+  async fn import_notion_document(
+    &self,
+    uid: i64,
+    view_id: &str,
+    name: &str,
+    path: &Path,
+  ) -> FlowyResult<ViewLayout> {
+    let markdown =
+      fs::read_to_string(path).map_err(|err| FlowyError::import_io_error().with_context(err))?;
+    if let Some(page_dir) = path.parent() {
+      for link in find_broken_links(&markdown, page_dir) {
+        tracing::warn!("The Notion page:{} has a broken link: {}", name, link);
+      }
+    }
+    self
+      .get_handler(&ViewLayout::Document)?
+      .import_from_bytes(
+        uid,
+        view_id,
+        name,
+        ImportType::Markdown,
+        markdown.into_bytes(),
+      )
+      .await
+      .map_err(map_import_error)?;
+    Ok(ViewLayout::Document)
+  }
+
+  async fn import_notion_database(
+    &self,
+    uid: i64,
+    view_id: &str,
+    name: &str,
+    path: &Path,
+  ) -> FlowyResult<ViewLayout> {
+    let csv = fs::read(path).map_err(|err| FlowyError::import_io_error().with_context(err))?;
+    self
+      .get_handler(&ViewLayout::Grid)?
+      .import_from_bytes(uid, view_id, name, ImportType::CSV, csv)
+      .await
+      .map_err(map_import_error)?;
+    Ok(ViewLayout::Grid)
   }
 
   /// Update the view with the provided view_id using the specified function.
@@ -944,9 +6111,7 @@ impl FolderManager {
     }
 
     if let Ok(view_pb) = self.get_view_pb(view_id).await {
-      send_notification(&view_pb.id, FolderNotification::DidUpdateView)
-        .payload(view_pb)
-        .send();
+      send_coalesced_notification(&view_pb.id, FolderNotification::DidUpdateView, view_pb);
     }
     Ok(())
   }
@@ -1003,14 +6168,41 @@ impl FolderManager {
     )
   }
 
+  /// Returns the snapshots of the folder in the range of `offset..offset + limit`, the newest
+  /// first. The snapshots taken on the device are included. The `limit` is capped at
+  /// [MAX_FOLDER_SNAPSHOT_LIMIT], and zero means [DEFAULT_FOLDER_SNAPSHOT_LIMIT].
   pub async fn get_folder_snapshots(
     &self,
     workspace_id: &str,
+    offset: usize,
     limit: usize,
-  ) -> FlowyResult<Vec<FolderSnapshotPB>> {
-    let snapshots = self
+  ) -> FlowyResult<RepeatedFolderSnapshotPB> {
+    let limit = match limit {
+      0 => DEFAULT_FOLDER_SNAPSHOT_LIMIT,
+      limit => limit.min(MAX_FOLDER_SNAPSHOT_LIMIT),
+    };
+    let uid = self.user.user_id()?;
+    let mut snapshots = self
+      .folder_snapshots
+      .get_snapshots(uid, workspace_id)
+      .into_iter()
+      .map(|snapshot| FolderSnapshotPB {
+        snapshot_id: snapshot.snapshot_id,
+        snapshot_desc: "".to_string(),
+        created_at: snapshot.created_at,
+        data: snapshot.data,
+        name: snapshot.name,
+      })
+      .collect::<Vec<_>>();
+    // The newest `offset + limit` snapshots of the server are enough to fill the page, and the
+    // server counts all of its snapshots for the total.
+    let remote_count = self
+      .cloud_service
+      .get_folder_snapshot_count(workspace_id)
+      .await?;
+    let remote_snapshots = self
       .cloud_service
-      .get_folder_snapshots(workspace_id, limit)
+      .get_folder_snapshots(workspace_id, offset.saturating_add(limit))
       .await?
       .into_iter()
       .map(|snapshot| FolderSnapshotPB {
@@ -1018,10 +6210,265 @@ impl FolderManager {
         snapshot_desc: "".to_string(),
         created_at: snapshot.created_at,
         data: snapshot.data,
-      })
+        name: "".to_string(),
+      });
+    let total_count = (snapshots.len() + remote_count) as u64;
+    snapshots.extend(remote_snapshots);
+    snapshots.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+
+    let items = snapshots
+      .into_iter()
+      .skip(offset)
+      .take(limit)
       .collect::<Vec<_>>();
+    Ok(RepeatedFolderSnapshotPB { items, total_count })
+  }
+
+  /// Takes a snapshot of the current folder with the given name, so the folder can be restored to
+  /// the current state later. The name is truncated to [MAX_FOLDER_SNAPSHOT_NAME_LEN] characters.
+  #[tracing::instrument(level = "info", skip(self), err)]
+  pub async fn create_named_snapshot(
+    &self,
+    workspace_id: &str,
+    name: &str,
+  ) -> FlowyResult<FolderSnapshotPB> {
+    let uid = self.user.user_id()?;
+    if self.get_current_workspace_id().await? != workspace_id {
+      return Err(
+        FlowyError::not_support()
+          .with_context("Only the snapshot of current workspace can be created"),
+      );
+    }
+    let name = name
+      .chars()
+      .take(MAX_FOLDER_SNAPSHOT_NAME_LEN)
+      .collect::<String>();
+    let snapshot = self.create_local_snapshot(uid, workspace_id, name).await?;
+    Ok(FolderSnapshotPB {
+      snapshot_id: snapshot.snapshot_id,
+      snapshot_desc: "".to_string(),
+      created_at: snapshot.created_at,
+      data: snapshot.data,
+      name: snapshot.name,
+    })
+  }
+
+  /// Replaces the state of the current folder with the state of the snapshot. A snapshot of the
+  /// current state is taken before restoring, so the restoring can be reverted by restoring that
+  /// snapshot.
+  ///
+  /// The current view is kept if it exists in the restored folder. Otherwise, the first view of
+  /// the workspace will be the current view. If the snapshot can't be restored, the current folder
+  /// is kept as it is.
+  #[tracing::instrument(level = "info", skip(self), err)]
+  pub async fn restore_snapshot(&self, workspace_id: &str, snapshot_id: i64) -> FlowyResult<()> {
+    let uid = self.user.user_id()?;
+    if self.get_current_workspace_id().await? != workspace_id {
+      return Err(
+        FlowyError::not_support()
+          .with_context("Only the snapshot of current workspace can be restored"),
+      );
+    }
+
+    let (folder_data, mut folder_extra) = self
+      .get_snapshot_folder_data(uid, workspace_id, snapshot_id)
+      .await?;
+    // The local snapshots taken before the settings were stored in the folder keep them aside.
+    if let Some(setting) = self
+      .folder_snapshots
+      .get_snapshot(uid, workspace_id, snapshot_id)
+      .and_then(|snapshot| snapshot.setting)
+    {
+      folder_extra.setting = setting;
+    }
+    // Build the restored folder in memory first, so the current folder is untouched if the data
+    // of the snapshot can't be restored.
+    let restored_collab =
+      create_restored_folder_collab(uid, workspace_id, folder_data, folder_extra)?;
+
+    self
+      .create_local_snapshot(
+        uid,
+        workspace_id,
+        format!("Before restoring snapshot {}", snapshot_id),
+      )
+      .await?;
+    let current_view_id = self.with_folder(|| None, |folder| folder.get_current_view());
+
+    // The folder can't be rolled back in place, so the local folder is replaced with the restored
+    // one in a single write transaction. The current folder is closed first, so that it doesn't
+    // write to the replaced folder, and it's put back if the folder can't be replaced.
+    let collab_db = self
+      .user
+      .collab_db(uid)?
+      .upgrade()
+      .ok_or_else(|| FlowyError::internal().with_context("The collab db is dropped"))?;
+    let current_folder = self.mutex_folder.lock().take();
+    let result = collab_db
+      .with_write_txn(|txn| {
+        txn.delete_doc(uid, workspace_id)?;
+        let restored_collab = restored_collab.lock();
+        let restored_txn = restored_collab.transact();
+        txn.create_new_doc(uid, workspace_id, &restored_txn)?;
+        Ok(())
+      })
+      .map_err(internal_error);
+    if let Err(err) = result {
+      *self.mutex_folder.lock() = current_folder;
+      return Err(err);
+    }
+    drop(current_folder);
+    self
+      .initialize(
+        uid,
+        workspace_id,
+        FolderInitializeDataSource::LocalDisk {
+          create_if_not_exist: false,
+        },
+      )
+      .await?;
+
+    let current_view_id = self.with_folder(
+      || None,
+      |folder| {
+        notify_did_update_workspace(workspace_id, folder, &self.folder_extra.get(workspace_id));
+        current_view_id
+          .filter(|view_id| folder.views.get_view(view_id).is_some())
+          .or_else(|| {
+            folder
+              .get_workspace_views(workspace_id)
+              .first()
+              .map(|view| view.id.clone())
+          })
+      },
+    );
+    match current_view_id {
+      Some(view_id) => {
+        self.set_current_view(&view_id).await?;
+      },
+      None => {
+        send_workspace_setting_notification(
+          workspace_id.to_string(),
+          None,
+          self.is_favorites_shown(workspace_id),
+          self.get_home_view_id(workspace_id).unwrap_or_default(),
+        );
+      },
+    }
+    Ok(())
+  }
+
+  /// Takes a snapshot of the current folder and saves it on the device.
+  /// Compares the views of the two snapshots by view id. The views that only exist in the
+  /// `to_snapshot_id` snapshot are added, and the views that only exist in the `from_snapshot_id`
+  /// snapshot are removed.
+  #[tracing::instrument(level = "debug", skip(self), err)]
+  pub async fn diff_snapshots(
+    &self,
+    workspace_id: &str,
+    from_snapshot_id: i64,
+    to_snapshot_id: i64,
+  ) -> FlowyResult<FolderDiffPB> {
+    let uid = self.user.user_id()?;
+    let (from, _) = self
+      .get_snapshot_folder_data(uid, workspace_id, from_snapshot_id)
+      .await?;
+    let (to, _) = self
+      .get_snapshot_folder_data(uid, workspace_id, to_snapshot_id)
+      .await?;
+
+    let to_view_ids = to
+      .views
+      .iter()
+      .map(|view| view.id.clone())
+      .collect::<HashSet<_>>();
+    let mut from_views = from
+      .views
+      .into_iter()
+      .map(|view| (view.id.clone(), view))
+      .collect::<HashMap<_, _>>();
+    let mut diff = FolderDiffPB::default();
+    for view in to.views {
+      match from_views.get(&view.id) {
+        None => diff.added.push(view_pb_without_child_views(Arc::new(view))),
+        Some(old_view) => {
+          if old_view.name != view.name {
+            diff.renamed.push(RenamedViewPB {
+              view_id: view.id.clone(),
+              old_name: old_view.name.clone(),
+              new_name: view.name.clone(),
+            });
+          }
+          if old_view.parent_view_id != view.parent_view_id {
+            diff.moved.push(MovedViewPB {
+              view_id: view.id.clone(),
+              old_parent_view_id: old_view.parent_view_id.clone(),
+              new_parent_view_id: view.parent_view_id.clone(),
+            });
+          }
+        },
+      }
+    }
+    from_views.retain(|view_id, _| !to_view_ids.contains(view_id));
+    diff.removed = from_views
+      .into_values()
+      .map(|view| view_pb_without_child_views(Arc::new(view)))
+      .collect();
+    Ok(diff)
+  }
+
+  /// Returns the [FolderData] and the [FolderExtra] of the snapshot. The local snapshots are
+  /// looked up first, then the snapshots of the cloud service.
+  async fn get_snapshot_folder_data(
+    &self,
+    uid: i64,
+    workspace_id: &str,
+    snapshot_id: i64,
+  ) -> FlowyResult<(FolderData, FolderExtra)> {
+    let snapshot_data = match self
+      .folder_snapshots
+      .get_snapshot(uid, workspace_id, snapshot_id)
+    {
+      Some(snapshot) => snapshot.data,
+      None => self
+        .cloud_service
+        .get_folder_snapshots(workspace_id, MAX_FOLDER_SNAPSHOT_LIMIT)
+        .await?
+        .into_iter()
+        .find(|snapshot| snapshot.snapshot_id == snapshot_id)
+        .map(|snapshot| snapshot.data)
+        .ok_or_else(|| {
+          FlowyError::record_not_found()
+            .with_context(format!("Can't find the snapshot:{}", snapshot_id))
+        })?,
+    };
+    let collab = Collab::new_with_raw_data(
+      CollabOrigin::Empty,
+      workspace_id,
+      vec![snapshot_data],
+      vec![],
+    )
+    .map_err(internal_error)?;
+    let collab = Arc::new(MutexCollab::from_collab(collab));
+    let folder_extra = FolderExtra::from_collab(&collab);
+    let folder_data = Folder::open(UserId::from(uid), collab, None)?
+      .get_folder_data()
+      .ok_or_else(|| FlowyError::invalid_data().with_context("The snapshot data is invalid"))?;
+    Ok((folder_data, folder_extra))
+  }
 
-    Ok(snapshots)
+  async fn create_local_snapshot(
+    &self,
+    uid: i64,
+    workspace_id: &str,
+    name: String,
+  ) -> FlowyResult<LocalFolderSnapshot> {
+    let data = self
+      .with_folder(|| None, |folder| Some(folder.encode_as_update_v1().0))
+      .ok_or_else(folder_not_init_error)?;
+    self
+      .folder_snapshots
+      .create_snapshot(uid, workspace_id, name, data)
   }
 
   /// Only expose this method for testing
@@ -1041,34 +6488,91 @@ impl FolderManager {
 fn subscribe_folder_view_changed(
   mut rx: ViewChangeReceiver,
   weak_mutex_folder: &Weak<MutexFolder>,
+  weak_folder_extra: Weak<FolderExtraStore>,
+  weak_view_subscriptions: Weak<RwLock<HashSet<String>>>,
+  weak_muted_view_updates: Weak<Mutex<HashMap<String, usize>>>,
 ) {
   let weak_mutex_folder = weak_mutex_folder.clone();
   af_spawn(async move {
     while let Ok(value) = rx.recv().await {
-      if let Some(folder) = weak_mutex_folder.upgrade() {
+      if let ViewChange::DidUpdate { view } = &value {
+        let is_muted = weak_muted_view_updates
+          .upgrade()
+          .map_or(false, |muted_view_updates| {
+            take_muted_view_update(&muted_view_updates, &view.id)
+          });
+        if is_muted {
+          continue;
+        }
+      }
+      if let (Some(folder), Some(folder_extra)) =
+        (weak_mutex_folder.upgrade(), weak_folder_extra.upgrade())
+      {
         tracing::trace!("Did receive view change: {:?}", value);
+        let view_subscriptions = weak_view_subscriptions.upgrade();
         match value {
           ViewChange::DidCreateView { view } => {
             notify_child_views_changed(
               view_pb_without_child_views(Arc::new(view.clone())),
               ChildViewChangeReason::DidCreateView,
             );
-            notify_parent_view_did_change(folder.clone(), vec![view.parent_view_id]);
+            if let Some(view_subscriptions) = &view_subscriptions {
+              notify_subscribed_views(
+                folder.clone(),
+                &folder_extra,
+                view_subscriptions,
+                vec![view.parent_view_id],
+              );
+            }
           },
           ViewChange::DidDeleteView { views } => {
+            let deleted_ids = views
+              .iter()
+              .map(|view| view.id.clone())
+              .collect::<HashSet<_>>();
+            remove_view_references(&folder, &folder_extra, &deleted_ids);
+            let mut parent_view_ids = vec![];
             for view in views {
+              if let Some(view_subscriptions) = &view_subscriptions {
+                view_subscriptions.write().remove(&view.id);
+              }
+              parent_view_ids.push(view.parent_view_id.clone());
               notify_child_views_changed(
                 view_pb_without_child_views(view),
                 ChildViewChangeReason::DidDeleteView,
               );
             }
+            if let Some(view_subscriptions) = &view_subscriptions {
+              notify_subscribed_views(
+                folder.clone(),
+                &folder_extra,
+                view_subscriptions,
+                parent_view_ids,
+              );
+            }
           },
           ViewChange::DidUpdate { view } => {
-            notify_child_views_changed(
-              view_pb_without_child_views(Arc::new(view.clone())),
-              ChildViewChangeReason::DidUpdateView,
-            );
-            notify_parent_view_did_change(folder.clone(), vec![view.parent_view_id]);
+            // The template and the archived views are not in the child views of their parent.
+            let workspace_id = folder
+              .lock()
+              .as_ref()
+              .map(|folder| folder.get_workspace_id());
+            let extra = workspace_id
+              .map(|workspace_id| folder_extra.get(&workspace_id))
+              .unwrap_or_default();
+            if !get_hidden_view_ids(&extra).contains(&view.id) {
+              let mut view_pb = view_pb_without_child_views(Arc::new(view.clone()));
+              fill_view_extras(&extra, std::slice::from_mut(&mut view_pb));
+              notify_child_views_changed(view_pb, ChildViewChangeReason::DidUpdateView);
+            }
+            if let Some(view_subscriptions) = &view_subscriptions {
+              notify_subscribed_views(
+                folder.clone(),
+                &folder_extra,
+                view_subscriptions,
+                vec![view.id.clone(), view.parent_view_id],
+              );
+            }
           },
         };
       }
@@ -1076,6 +6580,24 @@ fn subscribe_folder_view_changed(
   });
 }
 
+/// Returns true if the update of the view is muted, and consumes one of its muted updates.
+fn take_muted_view_update(
+  muted_view_updates: &Mutex<HashMap<String, usize>>,
+  view_id: &str,
+) -> bool {
+  let mut muted_view_updates = muted_view_updates.lock();
+  match muted_view_updates.get_mut(view_id) {
+    None => false,
+    Some(count) => {
+      *count -= 1;
+      if *count == 0 {
+        muted_view_updates.remove(view_id);
+      }
+      true
+    },
+  }
+}
+
 fn subscribe_folder_snapshot_state_changed(
   workspace_id: String,
   weak_mutex_folder: &Weak<MutexFolder>,
@@ -1108,9 +6630,17 @@ fn subscribe_folder_sync_state_changed(
   workspace_id: String,
   mut folder_sync_state_rx: WatchStream<SyncState>,
   _weak_mutex_folder: &Weak<MutexFolder>,
+  weak_sync_status: Weak<RwLock<FolderSyncStatusPB>>,
 ) {
   af_spawn(async move {
     while let Some(state) = folder_sync_state_rx.next().await {
+      if let Some(sync_status) = weak_sync_status.upgrade() {
+        let mut sync_status = sync_status.write();
+        sync_status.pending_changes = state.is_syncing();
+        if state.is_sync_finished() {
+          sync_status.last_synced_at = timestamp();
+        }
+      }
       send_notification(&workspace_id, FolderNotification::DidUpdateFolderSyncUpdate)
         .payload(FolderSyncStatePB::from(state))
         .send();
@@ -1122,79 +6652,289 @@ fn subscribe_folder_sync_state_changed(
 fn subscribe_folder_trash_changed(
   mut rx: TrashChangeReceiver,
   weak_mutex_folder: &Weak<MutexFolder>,
+  weak_folder_extra: Weak<FolderExtraStore>,
+  weak_view_subscriptions: Weak<RwLock<HashSet<String>>>,
 ) {
   let weak_mutex_folder = weak_mutex_folder.clone();
   af_spawn(async move {
     while let Ok(value) = rx.recv().await {
-      if let Some(folder) = weak_mutex_folder.upgrade() {
+      // Collect the changes that arrive within the debounce window. So that moving many views to
+      // the trash in a loop only sends a few notifications.
+      let mut changes = vec![value];
+      let deadline = Instant::now() + TRASH_NOTIFICATION_DEBOUNCE;
+      loop {
+        match tokio::time::timeout_at(deadline, rx.recv()).await {
+          Ok(Ok(value)) => changes.push(value),
+          Ok(Err(RecvError::Lagged(_))) => continue,
+          Ok(Err(RecvError::Closed)) | Err(_) => break,
+        }
+      }
+
+      if let (Some(folder), Some(folder_extra)) =
+        (weak_mutex_folder.upgrade(), weak_folder_extra.upgrade())
+      {
         let mut unique_ids = HashSet::new();
-        tracing::trace!("Did receive trash change: {:?}", value);
-        let ids = match value {
-          TrashChange::DidCreateTrash { ids } => ids,
-          TrashChange::DidDeleteTrash { ids } => ids,
-        };
+        tracing::trace!("Did receive trash changes: {:?}", changes);
+        let mut trashed_ids = vec![];
+        let ids = changes
+          .into_iter()
+          .flat_map(|change| match change {
+            TrashChange::DidCreateTrash { ids } => {
+              trashed_ids.extend(ids.clone());
+              ids
+            },
+            TrashChange::DidDeleteTrash { ids } => ids,
+          })
+          .collect::<Vec<String>>();
 
         if let Some(folder) = folder.lock().as_ref() {
+          // The views in the trash are not notified, so their subscriptions are dropped.
+          if let Some(view_subscriptions) = weak_view_subscriptions.upgrade() {
+            let mut view_subscriptions = view_subscriptions.write();
+            for trashed_id in &trashed_ids {
+              for view in get_view_and_descendants(folder, trashed_id) {
+                view_subscriptions.remove(&view.id);
+              }
+            }
+          }
+
           let views = folder.views.get_views(&ids);
           for view in views {
             unique_ids.insert(view.parent_view_id.clone());
           }
 
-          let repeated_trash: RepeatedTrashPB = folder.get_all_trash().into();
-          send_notification("trash", FolderNotification::DidUpdateTrash)
+          // The trash is notified per workspace, see [FolderManager::get_all_trash].
+          let workspace_id = folder.get_workspace_id();
+          let mut repeated_trash: RepeatedTrashPB =
+            get_workspace_trash(folder, &workspace_id).into();
+          fill_trash_reasons(&folder_extra.get(&workspace_id), &mut repeated_trash.items);
+          send_notification(&workspace_id, FolderNotification::DidUpdateTrash)
             .payload(repeated_trash)
             .send();
         }
 
         let parent_view_ids = unique_ids.into_iter().collect();
-        notify_parent_view_did_change(folder.clone(), parent_view_ids);
+        notify_parent_view_did_change(folder.clone(), &folder_extra, parent_view_ids);
+      }
+    }
+  });
+}
+
+/// Return the views that belong to the workspace. The views are filtered by the trash.
+fn get_workspace_view_pbs(workspace_id: &str, folder: &Folder) -> Vec<ViewPB> {
+  let trash_ids = folder
+    .get_all_trash()
+    .into_iter()
+    .map(|trash| trash.id)
+    .collect::<Vec<String>>();
+
+  let mut views = folder.get_workspace_views(workspace_id);
+  views.retain(|view| !trash_ids.contains(&view.id));
+
+  views
+    .into_iter()
+    .map(|view| {
+      // Get child views
+      let child_views = folder
+        .views
+        .get_views_belong_to(&view.id)
+        .into_iter()
+        .collect();
+      view_pb_with_child_views(view, child_views)
+    })
+    .collect()
+}
+
+/// Returns the name itself if it's not taken, otherwise the name with the lowest " (n)" suffix
+/// that is not taken, starting from two.
+fn next_available_name(name: String, taken_names: &HashSet<String>) -> String {
+  if !taken_names.contains(&name) {
+    return name;
+  }
+  (2..)
+    .map(|n| format!("{} ({})", name, n))
+    .find(|candidate| !taken_names.contains(candidate))
+    .unwrap_or(name)
+}
+
+/// Loads the child views of the views recursively until the views are at the given depth. The
+/// views are at depth one.
+fn preload_child_views(
+  folder: &Folder,
+  views: &mut [ViewPB],
+  depth: u32,
+  trash_ids: &HashSet<String>,
+) {
+  if depth <= 1 {
+    return;
+  }
+  for view in views.iter_mut() {
+    view.child_views = folder
+      .views
+      .get_views_belong_to(&view.id)
+      .into_iter()
+      .filter(|child_view| !trash_ids.contains(&child_view.id))
+      .map(view_pb_without_child_views)
+      .collect();
+    preload_child_views(folder, &mut view.child_views, depth - 1, trash_ids);
+  }
+}
+
+/// Returns the current workspace of the folder. Only the first level of child views are
+/// included.
+fn get_workspace_pb_from_folder(folder: &Folder, extra: &FolderExtra) -> Option<WorkspacePB> {
+  let mut workspace_pb = get_workspace_pb_without_views(folder, extra)?;
+  workspace_pb.views = folder
+    .views
+    .get_views_belong_to(&workspace_pb.id)
+    .into_iter()
+    .map(view_pb_without_child_views)
+    .collect::<Vec<ViewPB>>();
+  Some(workspace_pb)
+}
+
+/// Returns the current workspace of the folder without its views.
+fn get_workspace_pb_without_views(folder: &Folder, extra: &FolderExtra) -> Option<WorkspacePB> {
+  let workspace = folder.get_current_workspace()?;
+  let mut workspace_pb = WorkspacePB {
+    id: workspace.id,
+    name: workspace.name,
+    views: vec![],
+    create_time: workspace.created_at,
+    icon: None,
+  };
+  fill_workspace_extra(extra, &mut workspace_pb);
+  Some(workspace_pb)
+}
+
+/// Fills the name and the icon stored in the [FolderExtra] into the workspace. The name the
+/// workspace was created with is kept if the workspace has never been renamed.
+fn fill_workspace_extra(extra: &FolderExtra, workspace_pb: &mut WorkspacePB) {
+  if let Some(name) = &extra.workspace.name {
+    workspace_pb.name = name.clone();
+  }
+  workspace_pb.icon = extra.workspace.icon.clone().map(|icon| icon.into());
+}
+
+/// Fills the `last_edited_time`, `locked`, `is_pinned`, `cover`, `tags` and the attribution of
+/// the views and their child views. The order of the views is kept.
+/// The views that have never been edited keep their creation time as the last edited time, and
+/// their creator as the last editor.
+fn fill_view_extras(extra: &FolderExtra, views: &mut [ViewPB]) {
+  let mut stack = views.iter_mut().collect::<Vec<_>>();
+  while let Some(view) = stack.pop() {
+    if let Some(view_extra) = extra.views.get(&view.id) {
+      if view_extra.last_edited_time > 0 {
+        view.last_edited_time = view_extra.last_edited_time;
       }
+      view.locked = view_extra.locked;
+      view.is_pinned = view_extra.pinned;
+      view.cover = view_extra.cover.clone().map(|cover| cover.into());
+      view.tags = view_extra.tags.clone();
+      view.is_template = view_extra.is_template;
+      view.is_archived = view_extra.archived;
+      view.last_opened_time = view_extra.last_opened_time;
+      view.is_expanded = view_extra.expanded;
+      view.color = view_extra.color.clone().into();
+      view.trash_reason = view_extra.trash_reason.clone();
+      view.created_by = view_extra.created_by;
+      view.last_edited_by = view_extra.last_editor();
+    }
+    stack.extend(view.child_views.iter_mut());
+  }
+}
+
+/// Sets the child count of the views and their child views to the number of the child views that
+/// `GetChildViews` would return, so the trashed and the hidden child views are not counted.
+fn fill_child_counts(folder: &Folder, hidden_ids: &HashSet<String>, views: &mut [ViewPB]) {
+  let trash_ids = get_trash_ids(folder);
+  let mut stack = views.iter_mut().collect::<Vec<_>>();
+  while let Some(view) = stack.pop() {
+    if let Some(folder_view) = folder.views.get_view(&view.id) {
+      view.child_count = folder_view
+        .children
+        .items
+        .iter()
+        .filter(|child| !trash_ids.contains(&child.id) && !hidden_ids.contains(&child.id))
+        .count() as u32;
     }
-  });
+    stack.extend(view.child_views.iter_mut());
+  }
 }
 
-/// Return the views that belong to the workspace. The views are filtered by the trash.
-fn get_workspace_view_pbs(workspace_id: &str, folder: &Folder) -> Vec<ViewPB> {
-  let trash_ids = folder
-    .get_all_trash()
-    .into_iter()
-    .map(|trash| trash.id)
-    .collect::<Vec<String>>();
+/// Moves the pinned views before the unpinned ones. The sort is stable, so the relative order
+/// within the pinned and the unpinned views is preserved.
+fn sort_pinned_views<T>(views: &mut [T], is_pinned: impl Fn(&T) -> bool) {
+  views.sort_by_key(|view| !is_pinned(view));
+}
 
-  let mut views = folder.get_workspace_views(workspace_id);
-  views.retain(|view| !trash_ids.contains(&view.id));
+/// Sorts the pinned views of each level with [sort_pinned_views].
+fn sort_pinned_view_tree(views: &mut [ViewPB]) {
+  sort_pinned_views(views, |view| view.is_pinned);
+  let mut stack = views.iter_mut().collect::<Vec<_>>();
+  while let Some(view) = stack.pop() {
+    sort_pinned_views(&mut view.child_views, |view| view.is_pinned);
+    stack.extend(view.child_views.iter_mut());
+  }
+}
 
-  views
-    .into_iter()
-    .map(|view| {
-      // Get child views
-      let child_views = folder
-        .views
-        .get_views_belong_to(&view.id)
-        .into_iter()
-        .collect();
-      view_pb_with_child_views(view, child_views)
-    })
+/// Returns the ids of the template and the archived views, which are hidden from the view tree.
+fn get_hidden_view_ids(extra: &FolderExtra) -> HashSet<String> {
+  extra
+    .views
+    .iter()
+    .filter(|(_, view_extra)| view_extra.is_template || view_extra.archived)
+    .map(|(view_id, _)| view_id.clone())
     .collect()
 }
 
-fn notify_did_update_workspace(workspace_id: &str, folder: &Folder) {
-  let repeated_view: RepeatedViewPB = get_workspace_view_pbs(workspace_id, folder).into();
+/// Removes the hidden views from the views and from their child views.
+fn remove_hidden_views(hidden_ids: &HashSet<String>, views: &mut Vec<ViewPB>) {
+  if hidden_ids.is_empty() {
+    return;
+  }
+  views.retain(|view| !hidden_ids.contains(&view.id));
+  let mut stack = views.iter_mut().collect::<Vec<_>>();
+  while let Some(view) = stack.pop() {
+    view
+      .child_views
+      .retain(|child_view| !hidden_ids.contains(&child_view.id));
+    stack.extend(view.child_views.iter_mut());
+  }
+}
+
+/// Prepares the views of the notifications the same way as the views that are queried: the
+/// template and the archived views are removed, the extras are filled and the pinned views are
+/// moved before their siblings.
+fn prepare_notified_views(extra: &FolderExtra, views: &mut Vec<ViewPB>) {
+  remove_hidden_views(&get_hidden_view_ids(extra), views);
+  fill_view_extras(extra, views);
+  sort_pinned_view_tree(views);
+}
+
+fn notify_did_update_workspace(workspace_id: &str, folder: &Folder, extra: &FolderExtra) {
+  let mut views = get_workspace_view_pbs(workspace_id, folder);
+  prepare_notified_views(extra, &mut views);
+  let repeated_view: RepeatedViewPB = views.into();
   tracing::trace!("Did update workspace views: {:?}", repeated_view);
-  send_notification(workspace_id, FolderNotification::DidUpdateWorkspaceViews)
-    .payload(repeated_view)
-    .send();
+  send_coalesced_notification(
+    workspace_id,
+    FolderNotification::DidUpdateWorkspaceViews,
+    repeated_view,
+  );
 }
 
 /// Notify the the list of parent view ids that its child views were changed.
 #[tracing::instrument(level = "debug", skip(folder, parent_view_ids))]
 fn notify_parent_view_did_change<T: AsRef<str>>(
   folder: Arc<MutexFolder>,
+  folder_extra: &FolderExtraStore,
   parent_view_ids: Vec<T>,
 ) -> Option<()> {
   let folder = folder.lock();
   let folder = folder.as_ref()?;
   let workspace_id = folder.get_workspace_id();
+  let extra = folder_extra.get(&workspace_id);
   let trash_ids = folder
     .get_all_trash()
     .into_iter()
@@ -1207,7 +6947,7 @@ fn notify_parent_view_did_change<T: AsRef<str>>(
     // if the view's parent id equal to workspace id. Then it will fetch the current
     // workspace views. Because the the workspace is not a view stored in the views map.
     if parent_view_id == workspace_id {
-      notify_did_update_workspace(&workspace_id, folder)
+      notify_did_update_workspace(&workspace_id, folder, &extra)
     } else {
       // Parent view can contain a list of child views. Currently, only get the first level
       // child views.
@@ -1217,16 +6957,71 @@ fn notify_parent_view_did_change<T: AsRef<str>>(
       event!(Level::DEBUG, child_views_count = child_views.len());
 
       // Post the notification
-      let parent_view_pb = view_pb_with_child_views(parent_view, child_views);
-      send_notification(parent_view_id, FolderNotification::DidUpdateView)
-        .payload(parent_view_pb)
-        .send();
+      let mut parent_view_pb = view_pb_with_child_views(parent_view, child_views);
+      remove_hidden_views(
+        &get_hidden_view_ids(&extra),
+        &mut parent_view_pb.child_views,
+      );
+      fill_view_extras(&extra, std::slice::from_mut(&mut parent_view_pb));
+      sort_pinned_view_tree(&mut parent_view_pb.child_views);
+      send_coalesced_notification(
+        parent_view_id,
+        FolderNotification::DidUpdateView,
+        parent_view_pb,
+      );
     }
   }
 
   None
 }
 
+/// Removes the deleted views from the views referenced by the other views, so they are not left
+/// behind in [FolderManager::set_view_references]. The references of the deleted views themselves
+/// are removed along with the views.
+fn remove_view_references(
+  folder: &MutexFolder,
+  folder_extra: &FolderExtraStore,
+  deleted_ids: &HashSet<String>,
+) {
+  let workspace_id = folder
+    .lock()
+    .as_ref()
+    .map(|folder| folder.get_workspace_id());
+  if let Some(workspace_id) = workspace_id {
+    let _ = folder_extra.update(&workspace_id, |extra| {
+      for view_extra in extra.views.values_mut() {
+        view_extra
+          .referenced_view_ids
+          .retain(|view_id| !deleted_ids.contains(view_id));
+      }
+    });
+  }
+}
+
+/// Notifies the views that are subscribed by [FolderManager::subscribe_view] among the given
+/// views.
+fn notify_subscribed_views(
+  folder: Arc<MutexFolder>,
+  folder_extra: &FolderExtraStore,
+  view_subscriptions: &RwLock<HashSet<String>>,
+  view_ids: Vec<String>,
+) {
+  let subscribed_view_ids = {
+    let view_subscriptions = view_subscriptions.read();
+    view_ids
+      .into_iter()
+      .filter(|view_id| view_subscriptions.contains(view_id))
+      .collect::<HashSet<_>>()
+  };
+  if !subscribed_view_ids.is_empty() {
+    notify_parent_view_did_change(
+      folder,
+      folder_extra,
+      subscribed_view_ids.into_iter().collect(),
+    );
+  }
+}
+
 pub enum ChildViewChangeReason {
   DidCreateView,
   DidDeleteView,
@@ -1259,6 +7054,734 @@ fn notify_child_views_changed(view_pb: ViewPB, reason: ChildViewChangeReason) {
     .send();
 }
 
+/// Sorts the favorites by the order set by the user. The favorites that are not in the
+/// `favorite_order` keep their original order and come after the ordered ones.
+fn sort_favorites(favorites: &mut [SectionItem], favorite_order: &[String]) {
+  favorites.sort_by_key(|favorite| {
+    favorite_order
+      .iter()
+      .position(|id| id == &favorite.id)
+      .unwrap_or(usize::MAX)
+  });
+}
+
+fn notion_view_params(
+  parent_view_id: String,
+  name: String,
+  view_id: String,
+  layout: &ViewLayout,
+) -> CreateViewParams {
+  CreateViewParams {
+    parent_view_id,
+    name,
+    desc: "".to_string(),
+    layout: Some(layout.clone().into()),
+    initial_data: vec![],
+    view_id,
+    meta: Default::default(),
+    set_as_current: false,
+    index: None,
+  }
+}
+
+/// Replaces the characters that are not allowed in file names.
+fn sanitize_file_name(name: &str) -> String {
+  let file_name = name
+    .trim()
+    .chars()
+    .map(|c| match c {
+      '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+      _ => c,
+    })
+    .collect::<String>();
+  if file_name.is_empty() {
+    "Untitled".to_string()
+  } else {
+    file_name
+  }
+}
+
+/// Returns the ids of all the trashed views.
+fn get_trash_ids(folder: &Folder) -> HashSet<String> {
+  folder
+    .get_all_trash()
+    .into_iter()
+    .map(|trash| trash.id)
+    .collect()
+}
+
+/// The position of the last view listed by [FolderManager::list_views_cursor]. It's encoded as
+/// `<index>:<view_id>`, where the index is the position of the view in the depth-first order at
+/// the time it was listed.
+struct ViewsCursor {
+  index: usize,
+  view_id: String,
+}
+
+impl ViewsCursor {
+  fn encode(&self) -> String {
+    format!("{}:{}", self.index, self.view_id)
+  }
+
+  fn decode(cursor: &str) -> FlowyResult<Self> {
+    cursor
+      .split_once(':')
+      .and_then(|(index, view_id)| {
+        let index = index.parse::<usize>().ok()?;
+        (!view_id.is_empty()).then(|| ViewsCursor {
+          index,
+          view_id: view_id.to_string(),
+        })
+      })
+      .ok_or_else(|| {
+        FlowyError::new(
+          ErrorCode::InvalidParams,
+          format!("Invalid cursor: {}", cursor),
+        )
+      })
+  }
+}
+
+/// The result of [check_folder_integrity].
+struct FolderIntegrity {
+  report: FolderIntegrityReportPB,
+  /// The ids of the parents that list each duplicate view, the parent that lists the view more
+  /// than once appears more than once.
+  duplicate_parents: HashMap<String, Vec<String>>,
+}
+
+/// Scans the structure of the workspace without changing it. All the views stored in the folder
+/// are scanned, including the ones that can't be reached from the workspace.
+fn check_folder_integrity(folder: &Folder, workspace_id: &str) -> FolderIntegrity {
+  let views = get_folder_views(folder);
+
+  // Record the parents that list each view.
+  let mut parents_of = HashMap::<String, Vec<String>>::new();
+  for view in folder.get_workspace_views(workspace_id) {
+    parents_of
+      .entry(view.id.clone())
+      .or_default()
+      .push(workspace_id.to_string());
+  }
+  for view in views.iter() {
+    for child in view.children.items.iter() {
+      parents_of
+        .entry(child.id.clone())
+        .or_default()
+        .push(view.id.clone());
+    }
+  }
+
+  let mut report = FolderIntegrityReportPB::default();
+  // Walk up from each view. Each view is walked once, and the cycle is reported at the first
+  // view that is met twice on the same walk.
+  let mut checked = HashSet::new();
+  for view_id in views.iter().map(|view| &view.id) {
+    let mut path = vec![];
+    let mut current = folder.views.get_view(view_id);
+    while let Some(view) = current {
+      if path.contains(&view.id) {
+        report.cycles.push(view.id.clone());
+        break;
+      }
+      if !checked.insert(view.id.clone()) {
+        break;
+      }
+      path.push(view.id.clone());
+      current = folder.views.get_view(&view.parent_view_id);
+    }
+  }
+
+  report.orphans = find_orphaned_views(folder, workspace_id, &views)
+    .into_iter()
+    .map(|view| view.id.clone())
+    .collect();
+
+  let duplicate_parents = parents_of
+    .into_iter()
+    .filter(|(_, parent_ids)| parent_ids.len() > 1)
+    .collect::<HashMap<_, _>>();
+  report.duplicate_ids = duplicate_parents.keys().cloned().collect();
+  let current_view_id = folder.get_current_view().filter(|id| !id.is_empty());
+  report.dangling_favorites = folder
+    .get_all_favorites()
+    .into_iter()
+    .map(|favorite| favorite.id)
+    .filter(|id| folder.views.get_view(id).is_none())
+    .collect();
+  report.dangling_current_view = current_view_id.map_or(false, |id| {
+    folder.views.get_view(&id).is_none() || is_trashed(folder, &id, &get_trash_ids(folder))
+  });
+
+  report.orphans.sort();
+  report.cycles.sort();
+  report.duplicate_ids.sort();
+  report.dangling_favorites.sort();
+  FolderIntegrity {
+    report,
+    duplicate_parents,
+  }
+}
+
+/// Counts the child views and the nested views of the view by walking the child ids, without
+/// building the [ViewPB]s. The trashed views are skipped along with their descendants, and each
+/// view is counted once even if the folder has a cycle.
+fn count_subtree(folder: &Folder, view: &View, trash_ids: &HashSet<String>) -> SubtreeCountPB {
+  let mut visited = HashSet::new();
+  visited.insert(view.id.clone());
+  let mut visit = |child_id: &str| {
+    if trash_ids.contains(child_id) || !visited.insert(child_id.to_string()) {
+      return None;
+    }
+    folder.views.get_view(child_id)
+  };
+
+  let mut stack = view
+    .children
+    .items
+    .iter()
+    .filter_map(|child| visit(&child.id))
+    .collect::<Vec<_>>();
+  let mut count = SubtreeCountPB {
+    direct_children: stack.len() as u32,
+    total_descendants: 0,
+  };
+  while let Some(view) = stack.pop() {
+    count.total_descendants += 1;
+    stack.extend(
+      view
+        .children
+        .items
+        .iter()
+        .filter_map(|child| visit(&child.id)),
+    );
+  }
+  count
+}
+
+/// Returns true if the view or any of its ancestors is in the trash.
+fn is_trashed(folder: &Folder, view_id: &str, trash_ids: &HashSet<String>) -> bool {
+  let mut visited = HashSet::new();
+  let mut current = folder.views.get_view(view_id);
+  while let Some(view) = current {
+    if trash_ids.contains(&view.id) {
+      return true;
+    }
+    if !visited.insert(view.id.clone()) {
+      return false;
+    }
+    current = folder.views.get_view(&view.parent_view_id);
+  }
+  false
+}
+
+/// Returns true if any ancestor of the view is one of the `view_ids`. The walk stops when the
+/// parents form a cycle.
+fn has_ancestor_in(folder: &Folder, view_id: &str, view_ids: &HashSet<&str>) -> bool {
+  let mut visited = HashSet::new();
+  let mut current = folder
+    .views
+    .get_view(view_id)
+    .map(|view| view.parent_view_id.clone());
+  while let Some(parent_view_id) = current {
+    if parent_view_id == view_id || !visited.insert(parent_view_id.clone()) {
+      return false;
+    }
+    if view_ids.contains(parent_view_id.as_str()) {
+      return true;
+    }
+    current = folder
+      .views
+      .get_view(&parent_view_id)
+      .map(|view| view.parent_view_id.clone());
+  }
+  false
+}
+
+/// Returns the view and all of its descendants. The parent view always comes before its child
+/// views, and the child views keep the order in their parent.
+fn get_view_and_descendants(folder: &Folder, view_id: &str) -> Vec<Arc<View>> {
+  let mut views = vec![];
+  let mut visited = HashSet::new();
+  let mut stack = folder
+    .views
+    .get_view(view_id)
+    .into_iter()
+    .collect::<Vec<_>>();
+  while let Some(view) = stack.pop() {
+    if !visited.insert(view.id.clone()) {
+      continue;
+    }
+    let mut child_views = folder.views.get_views_belong_to(&view.id);
+    child_views.reverse();
+    stack.extend(child_views);
+    views.push(view);
+  }
+  views
+}
+
+/// Counts the views of the workspace, including the trashed ones.
+fn get_workspace_stats_from_folder(folder: &Folder, workspace_id: &str) -> WorkspaceStatsPB {
+  let trash_ids = get_trash_ids(folder);
+  let mut stats = WorkspaceStatsPB::default();
+  for view in get_workspace_all_views(folder, workspace_id, true) {
+    stats.total_views += 1;
+    if is_trashed(folder, &view.id, &trash_ids) {
+      stats.trashed_views += 1;
+    } else if view.is_favorite {
+      stats.favorite_views += 1;
+    }
+    let layout: ViewLayoutPB = view.layout.clone().into();
+    *stats
+      .views_by_layout
+      .entry(format!("{:?}", layout))
+      .or_default() += 1;
+  }
+  stats
+}
+
+/// Returns all the views stored in the folder.
+fn get_folder_views(folder: &Folder) -> Vec<Arc<View>> {
+  folder
+    .get_folder_data()
+    .map(|folder_data| folder_data.views)
+    .unwrap_or_default()
+    .into_iter()
+    .map(Arc::new)
+    .collect()
+}
+
+/// Returns the views that can't be reached from the workspace, see [find_orphaned_views].
+fn get_orphaned_views(folder: &Folder) -> Vec<Arc<View>> {
+  let views = get_folder_views(folder);
+  find_orphaned_views(folder, &folder.get_workspace_id(), &views)
+}
+
+/// Returns the orphaned views among the `views`. A view is orphaned if its parent doesn't exist,
+/// or if its parent doesn't list it as a child view. The child views of an orphaned view are
+/// still listed by their parent, so only the topmost view of the detached branch is returned.
+/// The trashed views keep their parent, so neither they nor their child views are orphaned. The
+/// views are sorted by their creation time.
+fn find_orphaned_views(folder: &Folder, workspace_id: &str, views: &[Arc<View>]) -> Vec<Arc<View>> {
+  let workspace_view_ids = folder
+    .get_workspace_views(workspace_id)
+    .into_iter()
+    .map(|view| view.id.clone())
+    .collect::<HashSet<_>>();
+  let mut orphaned_views = views
+    .iter()
+    .filter(|view| {
+      if view.parent_view_id == workspace_id {
+        return !workspace_view_ids.contains(&view.id);
+      }
+      match folder.views.get_view(&view.parent_view_id) {
+        None => true,
+        Some(parent_view) => !parent_view
+          .children
+          .items
+          .iter()
+          .any(|child| child.id == view.id),
+      }
+    })
+    .cloned()
+    .collect::<Vec<_>>();
+  orphaned_views.sort_by_key(|view| view.created_at);
+  orphaned_views
+}
+
+/// Creates the collab of the folder with the data of the snapshot in memory. Returns an error if
+/// the folder can't be read back from the collab.
+fn create_restored_folder_collab(
+  uid: i64,
+  workspace_id: &str,
+  folder_data: FolderData,
+  folder_extra: FolderExtra,
+) -> FlowyResult<Arc<MutexCollab>> {
+  let collab = Collab::new_with_raw_data(CollabOrigin::Empty, workspace_id, vec![], vec![])
+    .map_err(internal_error)?;
+  let collab = Arc::new(MutexCollab::from_collab(collab));
+  let folder = Folder::create(UserId::from(uid), collab.clone(), None, folder_data);
+  if folder.get_folder_data().is_none() {
+    return Err(FlowyError::invalid_data().with_context("The snapshot data can't be restored"));
+  }
+  FolderExtra::update_collab(&collab, |extra| *extra = folder_extra);
+  Ok(collab)
+}
+
+/// Fills the reasons recorded by [FolderManager::move_view_to_trash_with_reason] into the trash
+/// items.
+fn fill_trash_reasons(extra: &FolderExtra, trash: &mut [TrashPB]) {
+  for item in trash.iter_mut() {
+    if let Some(reason) = extra
+      .views
+      .get(&item.id)
+      .and_then(|view_extra| view_extra.trash_reason.clone())
+    {
+      item.reason = reason;
+    }
+  }
+}
+
+fn contains_tag(tags: &[String], tag: &str) -> bool {
+  let tag = tag.to_lowercase();
+  tags.iter().any(|other| other.to_lowercase() == tag)
+}
+
+/// Returns the ids of the child views of the view that are not in the trash, in their order.
+fn get_untrashed_child_view_ids(folder: &Folder, view: &View) -> Vec<String> {
+  let trash_ids = get_trash_ids(folder);
+  view
+    .children
+    .items
+    .iter()
+    .filter(|child| !trash_ids.contains(&child.id))
+    .map(|child| child.id.clone())
+    .collect()
+}
+
+/// Returns the id of the view in front of the view in its parent. `None` if the view is the first
+/// child of the parent.
+fn get_prev_view_id(folder: &Folder, parent_view_id: &str, view_id: &str) -> Option<String> {
+  let child_views = folder.views.get_views_belong_to(parent_view_id);
+  let index = child_views.iter().position(|view| view.id == view_id)?;
+  index
+    .checked_sub(1)
+    .map(|prev_index| child_views[prev_index].id.clone())
+}
+
+/// Returns the trash items whose views belong to the workspace, that is, the workspace is the root
+/// of the ancestors of the view. The items whose views can't be found are excluded, because it
+/// can't be told which workspace they belong to.
+fn get_workspace_trash(folder: &Folder, workspace_id: &str) -> Vec<TrashInfo> {
+  folder
+    .get_all_trash()
+    .into_iter()
+    .filter(|trash| get_view_root_id(folder, &trash.id).as_deref() == Some(workspace_id))
+    .collect()
+}
+
+/// Returns the id of the workspace that the view belongs to by walking up its ancestors. `None`
+/// if the view can't be found or its ancestors form a cycle.
+fn get_view_root_id(folder: &Folder, view_id: &str) -> Option<String> {
+  let mut visited = HashSet::new();
+  let mut view = folder.views.get_view(view_id)?;
+  loop {
+    if !visited.insert(view.id.clone()) {
+      return None;
+    }
+    match folder.views.get_view(&view.parent_view_id) {
+      Some(parent_view) => view = parent_view,
+      None => return Some(view.parent_view_id.clone()),
+    }
+  }
+}
+
+/// Returns all the views of the workspace, including the nested views. If `include_trash` is
+/// false, the trashed views and their descendants are excluded.
+fn get_workspace_all_views(
+  folder: &Folder,
+  workspace_id: &str,
+  include_trash: bool,
+) -> Vec<Arc<View>> {
+  let trash_ids = if include_trash {
+    HashSet::new()
+  } else {
+    get_trash_ids(folder)
+  };
+  let mut views = vec![];
+  let mut visited = HashSet::new();
+  let mut stack = folder.get_workspace_views(workspace_id);
+  stack.reverse();
+  while let Some(view) = stack.pop() {
+    if trash_ids.contains(&view.id) || !visited.insert(view.id.clone()) {
+      continue;
+    }
+    let mut child_views = folder.views.get_views_belong_to(&view.id);
+    child_views.reverse();
+    stack.extend(child_views);
+    views.push(view);
+  }
+  views
+}
+
+/// Returns the stack of [get_workspace_all_views] right after the view is walked, which holds the
+/// child views of the view and the views following it and its ancestors. Returns `None` if the
+/// view isn't in the workspace, or it or one of its ancestors is trashed.
+fn views_stack_after(
+  folder: &Folder,
+  workspace_id: &str,
+  trash_ids: &HashSet<String>,
+  view_id: &str,
+) -> Option<Vec<Arc<View>>> {
+  let mut stack = folder.views.get_views_belong_to(view_id);
+  stack.reverse();
+  let mut visited = HashSet::new();
+  let mut current_id = view_id.to_string();
+  loop {
+    if trash_ids.contains(&current_id) || !visited.insert(current_id.clone()) {
+      return None;
+    }
+    let view = folder.views.get_view(&current_id)?;
+    let siblings = if view.parent_view_id == workspace_id {
+      folder.get_workspace_views(workspace_id)
+    } else {
+      folder.views.get_views_belong_to(&view.parent_view_id)
+    };
+    let index = siblings
+      .iter()
+      .position(|sibling| sibling.id == current_id)?;
+    let mut following = siblings[index + 1..].to_vec();
+    following.reverse();
+    // The views following the ancestors are walked after the views following the view.
+    stack.splice(0..0, following);
+    if view.parent_view_id == workspace_id {
+      return Some(stack);
+    }
+    current_id = view.parent_view_id.clone();
+  }
+}
+
+fn check_import_source(import_data: &ImportParams) -> FlowyResult<()> {
+  if import_data.data.is_none() && import_data.file_path.is_none() {
+    return Err(FlowyError::new(
+      ErrorCode::InvalidParams,
+      "data or file_path is required",
+    ));
+  }
+  if let Some(file_path) = &import_data.file_path {
+    if !Path::new(file_path).exists() {
+      return Err(FlowyError::new(
+        ErrorCode::ImportIOError,
+        format!("The import file:{} doesn't exist", file_path),
+      ));
+    }
+  }
+  Ok(())
+}
+
+/// Maps the error returned by the [FolderOperationHandler] when importing the data to the import
+/// error codes. Only the errors of parsing the data are reported as the
+/// [ErrorCode::ImportMalformedFile], the other errors, for example, the storage errors, are kept
+/// as they are.
+fn map_import_error(err: FlowyError) -> FlowyError {
+  match err.code {
+    ErrorCode::InvalidParams
+    | ErrorCode::Serde
+    | ErrorCode::ProtobufSerde
+    | ErrorCode::DocumentDataInvalid
+    | ErrorCode::ViewDataInvalid
+    | ErrorCode::InvalidDateTimeFormat => FlowyError::new(ErrorCode::ImportMalformedFile, err.msg),
+    ErrorCode::NotSupportYet => FlowyError::new(ErrorCode::ImportUnsupportedFormat, err.msg),
+    _ => err,
+  }
+}
+
+fn import_skipped_item(name: String, err: &FlowyError) -> ImportSkippedItemPB {
+  ImportSkippedItemPB {
+    name,
+    code: err.code.value(),
+    msg: err.msg.clone(),
+  }
+}
+
+/// The views that an import creates, see [FolderManager::plan_import].
+struct ImportPlan {
+  root_name: String,
+  root_layout: ViewLayout,
+  /// Whether the name of the import collides with one of the existing child views of the parent.
+  root_name_collision: bool,
+  /// The Notion pages that are imported under the root view. It's empty for the other imports.
+  pages: Vec<NotionPagePlan>,
+  /// The Notion pages that are skipped because the view limit of the workspace is reached.
+  skipped_pages: Vec<NotionPagePlan>,
+}
+
+impl ImportPlan {
+  /// Returns the number of the views that the import would create without the view limit,
+  /// including the root view.
+  fn requested_count(&self) -> usize {
+    1 + self.pages.len() + self.skipped_pages.len()
+  }
+
+  fn limit_skipped_items(&self) -> Vec<ImportSkippedItemPB> {
+    let err = FlowyError::view_limit_reached();
+    self
+      .skipped_pages
+      .iter()
+      .map(|page| import_skipped_item(page.name.clone(), &err))
+      .collect()
+  }
+
+  fn into_dry_run_result(self, parent_view_id: String) -> ImportResultPB {
+    let requested_count = self.requested_count() as u32;
+    let skipped = self.limit_skipped_items();
+    let mut imported_names = vec![self.root_name.clone()];
+    imported_names.extend(self.pages.into_iter().map(|page| page.name));
+    ImportResultPB {
+      view: ViewPB {
+        parent_view_id,
+        name: self.root_name,
+        layout: self.root_layout.into(),
+        ..Default::default()
+      },
+      created_count: imported_names.len() as u32,
+      requested_count,
+      imported_names,
+      skipped,
+    }
+  }
+}
+
+fn notion_export_dir(import_data: &ImportParams) -> FlowyResult<PathBuf> {
+  import_data
+    .file_path
+    .as_ref()
+    .map(PathBuf::from)
+    .ok_or_else(|| {
+      FlowyError::invalid_data().with_context("The Notion export directory is required")
+    })
+}
+
+/// Returns the depth of the view. The workspace is at depth zero and the top level views are at
+/// depth one. The orphaned view is counted from the top of its detached branch.
+fn get_view_depth(folder: &Folder, view_id: &str) -> usize {
+  let workspace_id = folder.get_workspace_id();
+  let mut depth = 0;
+  let mut visited = HashSet::new();
+  let mut current_id = view_id.to_string();
+  while current_id != workspace_id && visited.insert(current_id.clone()) {
+    match folder.views.get_view(&current_id) {
+      None => break,
+      Some(view) => {
+        depth += 1;
+        current_id = view.parent_view_id.clone();
+      },
+    }
+  }
+  depth
+}
+
+/// Returns an error if the depth exceeds the maximum nesting depth.
+fn check_depth(depth: usize, max_depth: u32) -> FlowyResult<()> {
+  if depth > max_depth as usize {
+    return Err(FlowyError::max_depth_exceeded().with_context(format!(
+      "The depth:{} exceeds the maximum nesting depth:{}",
+      depth, max_depth
+    )));
+  }
+  Ok(())
+}
+
+/// Returns the number of levels of the view and its descendants. A view without child views has
+/// one level.
+fn get_subtree_depth(folder: &Folder, view_id: &str) -> usize {
+  let mut max_depth = 0;
+  let mut visited = HashSet::new();
+  let mut stack = vec![(view_id.to_string(), 1)];
+  while let Some((view_id, depth)) = stack.pop() {
+    if !visited.insert(view_id.clone()) {
+      continue;
+    }
+    max_depth = max_depth.max(depth);
+    for child_view in folder.views.get_views_belong_to(&view_id) {
+      stack.push((child_view.id.clone(), depth + 1));
+    }
+  }
+  max_depth
+}
+
+/// Returns true if the view with `view_id` is a descendant of the view with `ancestor_id`.
+/// Walks up the parent chain of the view and stops if a cycle is detected.
+fn is_descendant_of(folder: &Folder, view_id: &str, ancestor_id: &str) -> bool {
+  let mut visited = HashSet::new();
+  let mut current = folder.views.get_view(view_id);
+  while let Some(view) = current {
+    if view.parent_view_id == ancestor_id {
+      return true;
+    }
+    if !visited.insert(view.id.clone()) {
+      return false;
+    }
+    current = folder.views.get_view(&view.parent_view_id);
+  }
+  false
+}
+
+/// Deletes the trash items that were moved to the trash more than `retention_days` days ago,
+/// along with the resources that the views hold.
+async fn purge_expired_trash(
+  mutex_folder: &MutexFolder,
+  operation_handlers: &FolderOperationHandlers,
+  retention_days: u32,
+) {
+  let expired_before = timestamp() - retention_days as i64 * 24 * 60 * 60;
+  let expired_views = {
+    let folder = mutex_folder.lock();
+    let folder = match folder.as_ref() {
+      None => return,
+      Some(folder) => folder,
+    };
+    let expired_ids = folder
+      .get_all_trash()
+      .into_iter()
+      .filter(|trash| trash.created_at <= expired_before)
+      .map(|trash| trash.id)
+      .collect::<Vec<String>>();
+    if expired_ids.is_empty() {
+      return;
+    }
+
+    let expired_views = folder.views.get_views(&expired_ids);
+    folder.delete_trash(expired_ids.clone());
+    folder
+      .views
+      .delete_views(expired_ids.iter().map(|id| id.as_str()).collect());
+    expired_views
+  };
+
+  info!("Purge {} expired trash items", expired_views.len());
+  for view in expired_views {
+    if let Some(handler) = operation_handlers.get(&view.layout) {
+      if let Err(err) = handler.delete_view(&view.id).await {
+        tracing::error!(
+          "Failed to delete the data of view:{}, error: {:?}",
+          view.id,
+          err
+        );
+      }
+    }
+  }
+}
+
+/// Create the data of the view with the handler that matches the view's layout.
+async fn create_view_data(
+  handler: &Arc<dyn FolderOperationHandler + Send + Sync>,
+  user_id: i64,
+  params: &CreateViewParams,
+) -> FlowyResult<()> {
+  let view_layout = params.view_layout();
+  let meta = params.meta.clone();
+  if meta.is_empty() && params.initial_data.is_empty() {
+    tracing::trace!("Create view with build-in data");
+    handler
+      .create_built_in_view(user_id, &params.view_id, &params.name, view_layout)
+      .await?;
+  } else {
+    tracing::trace!("Create view with view data");
+    handler
+      .create_view_with_view_data(
+        user_id,
+        &params.view_id,
+        &params.name,
+        params.initial_data.clone(),
+        view_layout,
+        meta,
+      )
+      .await?;
+  }
+  Ok(())
+}
+
 fn folder_not_init_error() -> FlowyError {
   FlowyError::internal().with_context("Folder not initialized")
 }
@@ -1,8 +1,14 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
 use flowy_derive::ProtoBuf_Enum;
 use flowy_notification::NotificationBuilder;
+use lazy_static::lazy_static;
 use lib_dispatch::prelude::ToBytes;
+use parking_lot::Mutex;
 
-use crate::entities::{ViewPB, WorkspaceSettingPB};
+use crate::entities::{ImportProgressPB, ViewPB, WorkspaceSettingPB};
 
 const FOLDER_OBSERVABLE_SOURCE: &str = "Workspace";
 
@@ -26,13 +32,34 @@ pub enum FolderNotification {
   DidRestoreView = 13,
   /// Trigger after moving the view to trash
   DidMoveViewToTrash = 14,
-  /// Trigger when the number of trash is changed
+  /// Trigger when the number of trash is changed. The id of the notification is the id of the
+  /// workspace, and the payload only contains the trash of that workspace.
   DidUpdateTrash = 15,
   DidUpdateFolderSnapshotState = 16,
   DidUpdateFolderSyncUpdate = 17,
 
   DidFavoriteView = 36,
   DidUnfavoriteView = 37,
+  /// Trigger while importing. The id of the notification is the id of the parent view that the
+  /// data is imported into.
+  ImportProgress = 38,
+  /// Trigger once after the settings of the workspace are updated together. The id of the
+  /// notification is the workspace id.
+  DidUpdateWorkspaceSettings = 39,
+}
+
+impl FolderNotification {
+  /// Whether the notifications of this type are coalesced by [send_coalesced_notification]. Only
+  /// the notifications whose payload carries the whole latest state opt in. The ordering-sensitive
+  /// ones, e.g. creating or deleting views, are always sent one by one.
+  pub fn is_coalesced(&self) -> bool {
+    matches!(
+      self,
+      FolderNotification::DidUpdateView
+        | FolderNotification::DidUpdateWorkspace
+        | FolderNotification::DidUpdateWorkspaceViews
+    )
+  }
 }
 
 impl std::convert::From<FolderNotification> for i32 {
@@ -58,16 +85,142 @@ impl std::convert::From<i32> for FolderNotification {
       17 => FolderNotification::DidUpdateFolderSyncUpdate,
       36 => FolderNotification::DidFavoriteView,
       37 => FolderNotification::DidUnfavoriteView,
+      38 => FolderNotification::ImportProgress,
+      39 => FolderNotification::DidUpdateWorkspaceSettings,
       _ => FolderNotification::Unknown,
     }
   }
 }
 
+/// Returns the builder of the notification. The pending coalesced notifications of the same id
+/// are sent first, so they are never delivered after a notification that is sent later.
 #[tracing::instrument(level = "trace")]
 pub(crate) fn send_notification(id: &str, ty: FolderNotification) -> NotificationBuilder {
+  flush_pending_notifications(id);
   NotificationBuilder::new(id, ty, FOLDER_OBSERVABLE_SOURCE)
 }
 
+/// The notifications of the same id and type sent within the window are merged into one.
+const COALESCE_WINDOW: Duration = Duration::from_millis(50);
+
+lazy_static! {
+  /// The latest notification of each id and type that is waiting for the end of its window, along
+  /// with the sequence number of the first notification merged into it.
+  static ref PENDING_NOTIFICATIONS: Mutex<HashMap<(String, i32), (u64, NotificationBuilder)>> =
+    Mutex::new(HashMap::new());
+}
+
+static PENDING_SEQUENCE: AtomicU64 = AtomicU64::new(0);
+
+/// Sends the pending coalesced notifications of the id right away, in the order they were first
+/// sent.
+fn flush_pending_notifications(id: &str) {
+  let mut builders = {
+    let mut pending = PENDING_NOTIFICATIONS.lock();
+    let keys = pending
+      .keys()
+      .filter(|(pending_id, _)| pending_id == id)
+      .cloned()
+      .collect::<Vec<_>>();
+    keys
+      .into_iter()
+      .flat_map(|key| pending.remove(&key))
+      .collect::<Vec<_>>()
+  };
+  builders.sort_by_key(|(sequence, _)| *sequence);
+  for (_, builder) in builders {
+    builder.send();
+  }
+}
+
+/// Sends the notification like [send_notification]. If the type is
+/// [FolderNotification::is_coalesced], the notifications of the same id and type sent within the
+/// [COALESCE_WINDOW] are merged into one, which carries the payload of the last one and keeps the
+/// place of the first one. It's sent immediately if there is no runtime to wait for the window.
+pub(crate) fn send_coalesced_notification<T: ToBytes>(
+  id: &str,
+  ty: FolderNotification,
+  payload: T,
+) {
+  let is_coalesced = ty.is_coalesced();
+  let ty: i32 = ty.into();
+  let builder = NotificationBuilder::new(id, ty, FOLDER_OBSERVABLE_SOURCE).payload(payload);
+  let runtime = match tokio::runtime::Handle::try_current() {
+    Ok(runtime) if is_coalesced => runtime,
+    _ => {
+      flush_pending_notifications(id);
+      return builder.send();
+    },
+  };
+
+  let key = (id.to_string(), ty);
+  let first_sequence = {
+    let mut pending = PENDING_NOTIFICATIONS.lock();
+    match pending.get_mut(&key) {
+      Some((_, pending_builder)) => {
+        *pending_builder = builder;
+        None
+      },
+      None => {
+        let sequence = PENDING_SEQUENCE.fetch_add(1, Ordering::SeqCst);
+        pending.insert(key.clone(), (sequence, builder));
+        Some(sequence)
+      },
+    }
+  };
+  if let Some(first_sequence) = first_sequence {
+    runtime.spawn(async move {
+      tokio::time::sleep(COALESCE_WINDOW).await;
+      // The notification might be flushed already, and the one pending now has its own window.
+      let builder = {
+        let mut pending = PENDING_NOTIFICATIONS.lock();
+        match pending.get(&key) {
+          Some((sequence, _)) if *sequence == first_sequence => pending.remove(&key),
+          _ => None,
+        }
+      };
+      if let Some((_, builder)) = builder {
+        builder.send();
+      }
+    });
+  }
+}
+
+/// Sends the [ImportProgressPB] every [ImportProgressNotifier::BATCH_SIZE] items, so that a large
+/// import doesn't flood the notification channel. The last item is always notified.
+pub(crate) struct ImportProgressNotifier {
+  id: String,
+  processed: u32,
+  total: u32,
+}
+
+impl ImportProgressNotifier {
+  const BATCH_SIZE: u32 = 10;
+
+  pub(crate) fn new(id: &str, total: u32) -> Self {
+    Self {
+      id: id.to_string(),
+      processed: 0,
+      total,
+    }
+  }
+
+  /// Marks the given number of items, ending with the item named `current_name`, as processed.
+  pub(crate) fn advance(&mut self, count: u32, current_name: &str) {
+    let old_batch = self.processed / Self::BATCH_SIZE;
+    self.processed = (self.processed + count).min(self.total);
+    if self.processed == self.total || self.processed / Self::BATCH_SIZE > old_batch {
+      send_notification(&self.id, FolderNotification::ImportProgress)
+        .payload(ImportProgressPB {
+          processed: self.processed,
+          total: self.total,
+          current_name: current_name.to_string(),
+        })
+        .send();
+    }
+  }
+}
+
 /// The [CURRENT_WORKSPACE] represents as the current workspace that opened by the
 /// user. Only one workspace can be opened at a time.
 const CURRENT_WORKSPACE: &str = "current-workspace";
@@ -80,11 +233,121 @@ pub(crate) fn send_workspace_notification<T: ToBytes>(ty: FolderNotification, pa
 pub(crate) fn send_workspace_setting_notification(
   workspace_id: String,
   latest_view: Option<ViewPB>,
+  show_favorites: bool,
+  home_view_id: String,
 ) -> Option<()> {
   let setting = WorkspaceSettingPB {
     workspace_id,
     latest_view,
+    show_favorites,
+    home_view_id,
   };
   send_workspace_notification(FolderNotification::DidUpdateWorkspaceSetting, setting);
   None
 }
+
+#[cfg(test)]
+mod tests {
+  use std::sync::Once;
+
+  use bytes::Bytes;
+  use flowy_notification::entities::SubscribeObject;
+  use flowy_notification::{register_notification_sender, NotificationSender};
+  use lazy_static::lazy_static;
+  use parking_lot::Mutex;
+
+  use crate::entities::ViewPB;
+  use crate::notification::{
+    send_coalesced_notification, send_notification, FolderNotification, COALESCE_WINDOW,
+  };
+
+  lazy_static! {
+    static ref SENT_NOTIFICATIONS: Mutex<Vec<SubscribeObject>> = Mutex::new(vec![]);
+  }
+  static REGISTER: Once = Once::new();
+
+  struct RecordingSender;
+
+  impl NotificationSender for RecordingSender {
+    fn send_subject(&self, subject: SubscribeObject) -> Result<(), String> {
+      SENT_NOTIFICATIONS.lock().push(subject);
+      Ok(())
+    }
+  }
+
+  /// Returns the type and the name in the payload of the notifications sent for the id.
+  fn sent_notifications(id: &str) -> Vec<(i32, String)> {
+    SENT_NOTIFICATIONS
+      .lock()
+      .iter()
+      .filter(|subject| subject.id == id)
+      .map(|subject| {
+        let name = subject
+          .payload
+          .clone()
+          .and_then(|payload| ViewPB::try_from(Bytes::from(payload)).ok())
+          .map(|view| view.name)
+          .unwrap_or_default();
+        (subject.ty, name)
+      })
+      .collect()
+  }
+
+  fn view(name: &str) -> ViewPB {
+    ViewPB {
+      name: name.to_string(),
+      ..Default::default()
+    }
+  }
+
+  #[tokio::test]
+  async fn coalesce_notifications_test() {
+    REGISTER.call_once(|| register_notification_sender(RecordingSender));
+    for name in ["first", "second", "third"] {
+      send_coalesced_notification(
+        "coalesce_view",
+        FolderNotification::DidUpdateView,
+        view(name),
+      );
+    }
+    assert!(sent_notifications("coalesce_view").is_empty());
+
+    tokio::time::sleep(COALESCE_WINDOW * 2).await;
+    assert_eq!(
+      sent_notifications("coalesce_view"),
+      vec![(
+        FolderNotification::DidUpdateView as i32,
+        "third".to_string()
+      )]
+    );
+  }
+
+  #[tokio::test]
+  async fn flush_coalesced_notifications_before_others_test() {
+    REGISTER.call_once(|| register_notification_sender(RecordingSender));
+    send_coalesced_notification(
+      "ordering_view",
+      FolderNotification::DidUpdateView,
+      view("updated"),
+    );
+    send_notification("ordering_view", FolderNotification::DidDeleteView)
+      .payload(view("deleted"))
+      .send();
+
+    // The pending update is sent before the deletion and isn't sent again after the window.
+    tokio::time::sleep(COALESCE_WINDOW * 2).await;
+    assert_eq!(
+      sent_notifications("ordering_view"),
+      vec![
+        (
+          FolderNotification::DidUpdateView as i32,
+          "updated".to_string()
+        ),
+        (
+          FolderNotification::DidDeleteView as i32,
+          "deleted".to_string()
+        ),
+      ]
+    );
+  }
+}
@@ -0,0 +1,108 @@
+use std::collections::VecDeque;
+use std::future::Future;
+
+use parking_lot::Mutex;
+
+/// The maximum number of operations that can be undone. The oldest operation will be dropped
+/// when a new operation exceeds the limit.
+const MAX_FOLDER_OPERATIONS: usize = 50;
+
+/// The structural operation on the folder. Each operation keeps enough information to undo and
+/// redo itself.
+#[derive(Debug, Clone)]
+pub enum FolderOperation {
+  CreateView {
+    view_id: String,
+  },
+  /// The view is moved to the trash.
+  DeleteView {
+    view_id: String,
+  },
+  /// The view is restored from the trash.
+  RestoreView {
+    view_id: String,
+  },
+  /// The position of the view is described by its parent and the view in front of it. `None`
+  /// means the view is the first child of the parent.
+  MoveView {
+    view_id: String,
+    old_parent_id: String,
+    old_prev_view_id: Option<String>,
+    new_parent_id: String,
+    new_prev_view_id: Option<String>,
+  },
+  RenameView {
+    view_id: String,
+    old_name: String,
+    new_name: String,
+  },
+}
+
+impl FolderOperation {
+  pub fn view_id(&self) -> &str {
+    match self {
+      FolderOperation::CreateView { view_id }
+      | FolderOperation::DeleteView { view_id }
+      | FolderOperation::RestoreView { view_id }
+      | FolderOperation::MoveView { view_id, .. }
+      | FolderOperation::RenameView { view_id, .. } => view_id,
+    }
+  }
+}
+
+tokio::task_local! {
+  /// Set while the task is undoing or redoing an operation, so that the operations performed by
+  /// the undo or redo are not recorded as new operations. It's scoped to the task, so the
+  /// operations performed by other tasks in the meantime are still recorded.
+  static REPLAYING: ();
+}
+
+/// [FolderOperationHistory] keeps the undo and redo stacks of the structural operations in
+/// memory. The history is not persisted, and it's cleared when the workspace is switched.
+#[derive(Default)]
+pub struct FolderOperationHistory {
+  undo_stack: Mutex<VecDeque<FolderOperation>>,
+  redo_stack: Mutex<Vec<FolderOperation>>,
+}
+
+impl FolderOperationHistory {
+  /// Records the operation performed by the user. The redo stack is cleared because the undone
+  /// operations can't be redone on top of the new operation.
+  pub fn record(&self, operation: FolderOperation) {
+    if REPLAYING.try_with(|_| ()).is_ok() {
+      return;
+    }
+    self.push_undo(operation);
+    self.redo_stack.lock().clear();
+  }
+
+  pub fn pop_undo(&self) -> Option<FolderOperation> {
+    self.undo_stack.lock().pop_back()
+  }
+
+  pub fn push_undo(&self, operation: FolderOperation) {
+    let mut undo_stack = self.undo_stack.lock();
+    undo_stack.push_back(operation);
+    while undo_stack.len() > MAX_FOLDER_OPERATIONS {
+      undo_stack.pop_front();
+    }
+  }
+
+  pub fn pop_redo(&self) -> Option<FolderOperation> {
+    self.redo_stack.lock().pop()
+  }
+
+  pub fn push_redo(&self, operation: FolderOperation) {
+    self.redo_stack.lock().push(operation);
+  }
+
+  pub fn clear(&self) {
+    self.undo_stack.lock().clear();
+    self.redo_stack.lock().clear();
+  }
+
+  /// Runs the future without recording the operations it performs.
+  pub async fn replay<F: Future>(&self, f: F) -> F::Output {
+    REPLAYING.scope((), f).await
+  }
+}
@@ -0,0 +1,124 @@
+/// Returns the match score of the `name` against the `query`. The higher the score, the better
+/// the match. Returns `None` if the `name` does not match the `query`.
+///
+/// The matching is case-insensitive. In descending order of score, a name matches if it equals
+/// the query, starts with the query, contains a word that starts with the query, contains the
+/// query, or contains all the characters of the query in order.
+pub(crate) fn match_score(name: &str, query: &str) -> Option<u32> {
+  let name = name.to_lowercase();
+  let query = query.trim().to_lowercase();
+  if query.is_empty() {
+    return None;
+  }
+
+  if name == query {
+    return Some(1000);
+  }
+  if name.starts_with(&query) {
+    return Some(800);
+  }
+  if name
+    .split(|c: char| !c.is_alphanumeric())
+    .any(|word| word.starts_with(&query))
+  {
+    return Some(600);
+  }
+  if name.contains(&query) {
+    return Some(400);
+  }
+  fuzzy_score(&name, &query)
+}
+
+/// Returns the match score of the view against the `query`. The name is matched by
+/// [match_score], and a view whose description contains the query scores lower than any match of
+/// the name.
+pub(crate) fn match_view_score(name: &str, desc: &str, query: &str) -> Option<u32> {
+  match_score(name, query).or_else(|| {
+    let query = query.trim().to_lowercase();
+    if !query.is_empty() && desc.to_lowercase().contains(&query) {
+      Some(50)
+    } else {
+      None
+    }
+  })
+}
+
+/// Returns the match score of the content of a document against the `query`. The content matches
+/// if it contains the query case-insensitively, and scores lower than any match of
+/// [match_view_score].
+pub(crate) fn match_content_score(content: &str, query: &str) -> Option<u32> {
+  let query = query.trim().to_lowercase();
+  if !query.is_empty() && content.to_lowercase().contains(&query) {
+    Some(10)
+  } else {
+    None
+  }
+}
+
+/// Matches the characters of the `query` in order. Consecutive matched characters get a bonus,
+/// so `"mtg notes"` scores higher than `"my tiny garden"` for the query `"mtg"`.
+fn fuzzy_score(name: &str, query: &str) -> Option<u32> {
+  let mut name_chars = name.chars();
+  let mut score = 100_u32;
+  let mut last_matched = false;
+  for query_char in query.chars() {
+    let mut matched = false;
+    for name_char in name_chars.by_ref() {
+      if name_char == query_char {
+        matched = true;
+        break;
+      }
+      last_matched = false;
+    }
+    if !matched {
+      return None;
+    }
+    if last_matched {
+      score += 10;
+    }
+    last_matched = true;
+  }
+  Some(score.min(399))
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::search::{match_content_score, match_score, match_view_score};
+
+  #[test]
+  fn match_score_order_test() {
+    let exact = match_score("Meeting", "meeting").unwrap();
+    let prefix = match_score("Meeting notes", "meeting").unwrap();
+    let word_prefix = match_score("Weekly meeting", "meet").unwrap();
+    let substring = match_score("Weekly", "eek").unwrap();
+    let fuzzy = match_score("My tiny garden", "mtg").unwrap();
+    assert!(exact > prefix);
+    assert!(prefix > word_prefix);
+    assert!(word_prefix > substring);
+    assert!(substring > fuzzy);
+  }
+
+  #[test]
+  fn match_score_not_match_test() {
+    assert!(match_score("Meeting", "").is_none());
+    assert!(match_score("Meeting", "gm").is_none());
+    assert!(match_score("Meeting", "meetings").is_none());
+  }
+
+  #[test]
+  fn match_view_description_test() {
+    let name = match_view_score("My tiny garden", "", "mtg").unwrap();
+    let desc = match_view_score("Notes", "Weekly meeting notes", "meeting").unwrap();
+    assert!(name > desc);
+    assert!(match_view_score("Notes", "Weekly meeting notes", "garden").is_none());
+  }
+
+  #[test]
+  fn match_content_test() {
+    let desc = match_view_score("Notes", "Weekly meeting notes", "meeting").unwrap();
+    let content = match_content_score("# Agenda\n\nThe weekly Meeting", "meeting").unwrap();
+    assert!(desc > content);
+    assert!(match_content_score("# Agenda", "meeting").is_none());
+    assert!(match_content_score("# Agenda", " ").is_none());
+  }
+}
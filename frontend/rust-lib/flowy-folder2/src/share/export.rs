@@ -0,0 +1,27 @@
+#[derive(Clone, Debug)]
+pub enum ExportFormat {
+  Markdown = 0,
+}
+
+impl ExportFormat {
+  pub fn file_extension(&self) -> &'static str {
+    match self {
+      ExportFormat::Markdown => "md",
+    }
+  }
+}
+
+#[derive(Clone, Debug)]
+pub struct ExportParams {
+  pub view_id: String,
+  pub format: ExportFormat,
+  pub include_children: bool,
+}
+
+/// Returns the markdown list item that links the `text` to the `destination`. The destination is
+/// wrapped in angle brackets, so it can contain spaces.
+pub fn markdown_link_item(text: &str, destination: &str) -> String {
+  let text = text.replace('[', "\\[").replace(']', "\\]");
+  let destination = destination.replace('<', "\\<").replace('>', "\\>");
+  format!("- [{}](<{}>)", text, destination)
+}
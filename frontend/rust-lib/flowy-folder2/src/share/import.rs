@@ -6,6 +6,10 @@ pub enum ImportType {
   HistoryDatabase = 1,
   RawDatabase = 2,
   CSV = 3,
+  /// The unzipped directory of the Notion export. Check out the [NotionPage] for its layout.
+  NotionZip = 4,
+  /// The markdown file. It's used to import the pages of the Notion export.
+  Markdown = 5,
 }
 
 #[derive(Clone, Debug)]
@@ -16,4 +20,8 @@ pub struct ImportParams {
   pub file_path: Option<String>,
   pub view_layout: ViewLayout,
   pub import_type: ImportType,
+  /// The id used to cancel the import. The import can't be cancelled if it's `None`.
+  pub import_id: Option<String>,
+  /// Plans the import without writing anything if it's true.
+  pub dry_run: bool,
 }
@@ -1,3 +1,9 @@
+mod export;
 mod import;
+mod notion;
+mod workspace_archive;
 
+pub use export::*;
 pub use import::*;
+pub use notion::*;
+pub use workspace_archive::*;
@@ -0,0 +1,323 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use collab_folder::ViewLayout;
+use flowy_error::{ErrorCode, FlowyError, FlowyResult};
+
+/// A page of the Notion export. Notion exports each page as a markdown file and each database as
+/// a csv file. The sub-pages of a page are stored in the directory that has the same name as the
+/// page's file, next to it.
+#[derive(Debug, Clone)]
+pub struct NotionPage {
+  pub name: String,
+  pub kind: NotionPageKind,
+  pub children: Vec<NotionPage>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NotionPageKind {
+  /// The page is a markdown file.
+  Document(PathBuf),
+  /// The page is a csv file.
+  Database(PathBuf),
+  /// The page is a directory without a markdown or csv file. It's imported as an empty document.
+  Folder,
+}
+
+impl NotionPageKind {
+  /// Returns the layout of the view that the page is imported as.
+  pub fn layout(&self) -> ViewLayout {
+    match self {
+      NotionPageKind::Document(_) | NotionPageKind::Folder => ViewLayout::Document,
+      NotionPageKind::Database(_) => ViewLayout::Grid,
+    }
+  }
+}
+
+/// The page that is imported, see [plan_notion_pages].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NotionPagePlan {
+  pub name: String,
+  pub kind: NotionPageKind,
+  pub depth: u32,
+  /// The index of the parent page in the plan, `None` for the top level pages.
+  pub parent: Option<usize>,
+  /// Whether any sibling of the page has the same name in the export.
+  pub name_collision: bool,
+}
+
+/// Flattens the pages in pre-order, which is the order they are imported in, so the parent page
+/// always comes before its children. The top level pages are at the given `depth`.
+pub fn plan_notion_pages(pages: Vec<NotionPage>, depth: u32) -> Vec<NotionPagePlan> {
+  let mut plans = vec![];
+  plan_sibling_pages(pages, depth, None, &mut plans);
+  plans
+}
+
+fn plan_sibling_pages(
+  pages: Vec<NotionPage>,
+  depth: u32,
+  parent: Option<usize>,
+  plans: &mut Vec<NotionPagePlan>,
+) {
+  let mut names = HashSet::new();
+  let duplicated_names = pages
+    .iter()
+    .filter(|page| !names.insert(page.name.as_str()))
+    .map(|page| page.name.clone())
+    .collect::<HashSet<_>>();
+  for page in pages {
+    let index = plans.len();
+    plans.push(NotionPagePlan {
+      name_collision: duplicated_names.contains(&page.name),
+      name: page.name,
+      kind: page.kind,
+      depth,
+      parent,
+    });
+    plan_sibling_pages(page.children, depth + 1, Some(index), plans);
+  }
+}
+
+/// Reads the unzipped Notion export in the `dir` and returns the pages in the hierarchy of the
+/// export. The pages are sorted by their names.
+pub fn read_notion_pages(dir: &Path) -> FlowyResult<Vec<NotionPage>> {
+  if !dir.exists() {
+    return Err(FlowyError::new(
+      ErrorCode::ImportIOError,
+      format!("{} doesn't exist", dir.display()),
+    ));
+  }
+  if !dir.is_dir() {
+    return Err(FlowyError::new(
+      ErrorCode::ImportUnsupportedFormat,
+      format!(
+        "{} is not a directory. Please unzip the Notion export first",
+        dir.display()
+      ),
+    ));
+  }
+
+  // The pages are read level by level. Each entry of the stack is the directory to read and the
+  // path of the page's position in the tree, so the children can be attached to their parent.
+  let mut root = vec![];
+  let mut stack: Vec<(PathBuf, Vec<usize>)> = vec![(dir.to_path_buf(), vec![])];
+  while let Some((dir, position)) = stack.pop() {
+    let pages = read_notion_dir(&dir)?;
+    let siblings = page_at_mut(&mut root, &position);
+    for (index, (page, sub_dir)) in pages.into_iter().enumerate() {
+      siblings.push(page);
+      if let Some(sub_dir) = sub_dir {
+        let mut child_position = position.clone();
+        child_position.push(index);
+        stack.push((sub_dir, child_position));
+      }
+    }
+  }
+  Ok(root)
+}
+
+/// Returns the links in the markdown that point to the files that don't exist in the export.
+/// The links to the web pages are ignored.
+pub fn find_broken_links(markdown: &str, dir: &Path) -> Vec<String> {
+  let mut broken_links = vec![];
+  let mut rest = markdown;
+  while let Some(start) = rest.find("](") {
+    let after = &rest[start + 2..];
+    let end = match after.find(')') {
+      None => break,
+      Some(end) => end,
+    };
+    let link = &after[..end];
+    rest = &after[end..];
+
+    if link.is_empty() || link.contains("://") || link.starts_with('#') {
+      continue;
+    }
+    let path = decode_url_path(link);
+    if !dir.join(&path).exists() {
+      broken_links.push(link.to_string());
+    }
+  }
+  broken_links
+}
+
+fn page_at_mut<'a>(pages: &'a mut Vec<NotionPage>, position: &[usize]) -> &'a mut Vec<NotionPage> {
+  let mut pages = pages;
+  for index in position {
+    pages = &mut pages[*index].children;
+  }
+  pages
+}
+
+/// Reads the pages in the directory. Returns each page with the directory that holds its
+/// sub-pages.
+fn read_notion_dir(dir: &Path) -> FlowyResult<Vec<(NotionPage, Option<PathBuf>)>> {
+  let mut entries = fs::read_dir(dir)
+    .map_err(|err| FlowyError::import_io_error().with_context(err))?
+    .filter_map(|entry| entry.ok())
+    .map(|entry| entry.path())
+    .collect::<Vec<PathBuf>>();
+  entries.sort();
+
+  let file_stems = entries
+    .iter()
+    .filter(|path| path.is_file())
+    .filter_map(|path| path.file_stem())
+    .map(|stem| stem.to_string_lossy().to_string())
+    .collect::<HashSet<String>>();
+
+  let mut pages = vec![];
+  for path in &entries {
+    let stem = match path.file_stem() {
+      None => continue,
+      Some(stem) => stem.to_string_lossy().to_string(),
+    };
+    let sub_dir = Some(path.with_extension("")).filter(|sub_dir| sub_dir.is_dir());
+
+    if path.is_dir() {
+      // The directory that belongs to a page is read along with the page.
+      if !file_stems.contains(&stem) {
+        pages.push((new_page(&stem, NotionPageKind::Folder), Some(path.clone())));
+      }
+      continue;
+    }
+
+    match path.extension().and_then(|ext| ext.to_str()) {
+      Some("md") => {
+        pages.push((
+          new_page(&stem, NotionPageKind::Document(path.clone())),
+          sub_dir,
+        ));
+      },
+      Some("csv") => {
+        // Notion exports both `name.csv` and `name_all.csv` for a database. Only import one of
+        // them. The rows of the database are already in the csv file, so the sub-pages of the
+        // database are not imported.
+        if let Some(name) = stem.strip_suffix("_all") {
+          if file_stems.contains(name) {
+            continue;
+          }
+        }
+        pages.push((
+          new_page(&stem, NotionPageKind::Database(path.clone())),
+          None,
+        ));
+      },
+      _ => {},
+    }
+  }
+  Ok(pages)
+}
+
+fn new_page(file_stem: &str, kind: NotionPageKind) -> NotionPage {
+  NotionPage {
+    name: page_name_from_file_stem(file_stem),
+    kind,
+    children: vec![],
+  }
+}
+
+/// Notion appends the id of the page to its file name, for example, `Getting Started
+/// 0a1b2c3d4e5f60718293a4b5c6d7e8f9`. Removes the id from the name.
+fn page_name_from_file_stem(file_stem: &str) -> String {
+  let stem = file_stem.strip_suffix("_all").unwrap_or(file_stem);
+  match stem.rsplit_once(' ') {
+    Some((name, id)) if id.len() == 32 && id.chars().all(|c| c.is_ascii_hexdigit()) => {
+      name.trim().to_string()
+    },
+    _ => stem.trim().to_string(),
+  }
+}
+
+/// Decodes the percent-encoded characters of the link, for example, `%20` to a space.
+fn decode_url_path(link: &str) -> String {
+  let bytes = link.as_bytes();
+  let mut decoded = Vec::with_capacity(bytes.len());
+  let mut i = 0;
+  while i < bytes.len() {
+    if bytes[i] == b'%' && i + 2 < bytes.len() {
+      let byte = std::str::from_utf8(&bytes[i + 1..i + 3])
+        .ok()
+        .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+      if let Some(byte) = byte {
+        decoded.push(byte);
+        i += 3;
+        continue;
+      }
+    }
+    decoded.push(bytes[i]);
+    i += 1;
+  }
+  String::from_utf8_lossy(&decoded).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+  use std::path::PathBuf;
+
+  use collab_folder::ViewLayout;
+
+  use crate::share::notion::{
+    decode_url_path, page_name_from_file_stem, plan_notion_pages, NotionPage, NotionPageKind,
+  };
+
+  #[test]
+  fn page_name_test() {
+    assert_eq!(
+      page_name_from_file_stem("Getting Started 0a1b2c3d4e5f60718293a4b5c6d7e8f9"),
+      "Getting Started"
+    );
+    assert_eq!(
+      page_name_from_file_stem("Tasks 0a1b2c3d4e5f60718293a4b5c6d7e8f9_all"),
+      "Tasks"
+    );
+    assert_eq!(page_name_from_file_stem("Notes"), "Notes");
+  }
+
+  #[test]
+  fn plan_notion_pages_test() {
+    let page = |name: &str, kind: NotionPageKind, children: Vec<NotionPage>| NotionPage {
+      name: name.to_string(),
+      kind,
+      children,
+    };
+    let pages = vec![
+      page(
+        "Notes",
+        NotionPageKind::Folder,
+        vec![page(
+          "Tasks",
+          NotionPageKind::Database(PathBuf::from("Tasks.csv")),
+          vec![],
+        )],
+      ),
+      page(
+        "Notes",
+        NotionPageKind::Document(PathBuf::from("Notes.md")),
+        vec![],
+      ),
+    ];
+    let plans = plan_notion_pages(pages, 2);
+    assert_eq!(plans.len(), 3);
+    assert_eq!(plans[0].name, "Notes");
+    assert_eq!(plans[0].depth, 2);
+    assert_eq!(plans[0].parent, None);
+    assert!(plans[0].name_collision);
+    assert_eq!(plans[1].name, "Tasks");
+    assert_eq!(plans[1].kind.layout(), ViewLayout::Grid);
+    assert_eq!(plans[1].depth, 3);
+    assert_eq!(plans[1].parent, Some(0));
+    assert!(!plans[1].name_collision);
+    assert_eq!(plans[2].kind.layout(), ViewLayout::Document);
+    assert_eq!(plans[2].parent, None);
+    assert!(plans[2].name_collision);
+  }
+
+  #[test]
+  fn decode_url_path_test() {
+    assert_eq!(decode_url_path("My%20Page%20abc.md"), "My Page abc.md");
+    assert_eq!(decode_url_path("100%"), "100%");
+  }
+}
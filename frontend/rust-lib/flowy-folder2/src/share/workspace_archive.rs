@@ -0,0 +1,123 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{Read, Seek, Write};
+use std::path::Path;
+
+use collab_folder::View;
+use serde::{Deserialize, Serialize};
+use zip::result::ZipError;
+use zip::write::FileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+use flowy_error::{internal_error, FlowyError, FlowyResult};
+
+use crate::folder_extra::ViewExtra;
+
+const MANIFEST_FILE_NAME: &str = "manifest.json";
+const VIEW_DATA_DIR: &str = "views";
+pub const WORKSPACE_ARCHIVE_VERSION: u32 = 1;
+
+/// The manifest of the workspace archive. It describes the view tree of the exported workspace,
+/// and the data of each view is stored in the `views/{view_id}` file of the archive.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkspaceManifest {
+  pub version: u32,
+  pub workspace_id: String,
+  pub workspace_name: String,
+  /// The views of the workspace. The parent view always comes before its child views, and the
+  /// child views keep the order in their parent.
+  pub views: Vec<View>,
+  /// The extra data of the views, keyed by the view id. The archives written before it was added
+  /// don't have it.
+  #[serde(default)]
+  pub view_extras: HashMap<String, ViewExtra>,
+  /// The ids of the favorite views in the order of the favorites.
+  #[serde(default)]
+  pub favorite_ids: Vec<String>,
+}
+
+/// Writes the workspace archive to a zip file. The data of each view is written to the file
+/// as soon as it's added, so the whole archive is never held in memory.
+pub struct WorkspaceArchiveWriter {
+  zip: ZipWriter<File>,
+}
+
+impl WorkspaceArchiveWriter {
+  pub fn new(path: &Path) -> FlowyResult<Self> {
+    let file = File::create(path)?;
+    Ok(Self {
+      zip: ZipWriter::new(file),
+    })
+  }
+
+  pub fn add_view_data(&mut self, view_id: &str, data: &[u8]) -> FlowyResult<()> {
+    self
+      .zip
+      .start_file(format!("{}/{}", VIEW_DATA_DIR, view_id), file_options())
+      .map_err(internal_error)?;
+    self.zip.write_all(data)?;
+    Ok(())
+  }
+
+  /// Writes the manifest and finishes the archive.
+  pub fn finish(mut self, manifest: &WorkspaceManifest) -> FlowyResult<()> {
+    let manifest = serde_json::to_vec(manifest).map_err(internal_error)?;
+    self
+      .zip
+      .start_file(MANIFEST_FILE_NAME, file_options())
+      .map_err(internal_error)?;
+    self.zip.write_all(&manifest)?;
+    self.zip.finish().map_err(internal_error)?;
+    Ok(())
+  }
+}
+
+/// Reads the workspace archive written by the [WorkspaceArchiveWriter].
+pub struct WorkspaceArchiveReader<R: Read + Seek> {
+  archive: ZipArchive<R>,
+}
+
+impl<R: Read + Seek> WorkspaceArchiveReader<R> {
+  pub fn new(reader: R) -> FlowyResult<Self> {
+    let archive = ZipArchive::new(reader).map_err(|err| {
+      FlowyError::invalid_data().with_context(format!("Invalid archive: {}", err))
+    })?;
+    Ok(Self { archive })
+  }
+
+  pub fn read_manifest(&mut self) -> FlowyResult<WorkspaceManifest> {
+    let data = self.read_file(MANIFEST_FILE_NAME).map_err(|err| {
+      FlowyError::invalid_data().with_context(format!("Can't read the manifest: {}", err))
+    })?;
+    let manifest = serde_json::from_slice::<WorkspaceManifest>(&data).map_err(|err| {
+      FlowyError::invalid_data().with_context(format!("Invalid manifest: {}", err))
+    })?;
+    if manifest.version > WORKSPACE_ARCHIVE_VERSION {
+      return Err(FlowyError::not_support().with_context(format!(
+        "The archive version:{} is not supported",
+        manifest.version
+      )));
+    }
+    Ok(manifest)
+  }
+
+  /// Returns the data of the view. `None` if the archive doesn't contain the data of the view.
+  pub fn read_view_data(&mut self, view_id: &str) -> FlowyResult<Option<Vec<u8>>> {
+    match self.read_file(&format!("{}/{}", VIEW_DATA_DIR, view_id)) {
+      Ok(data) => Ok(Some(data)),
+      Err(ZipError::FileNotFound) => Ok(None),
+      Err(err) => Err(FlowyError::invalid_data().with_context(err)),
+    }
+  }
+
+  fn read_file(&mut self, name: &str) -> Result<Vec<u8>, ZipError> {
+    let mut file = self.archive.by_name(name)?;
+    let mut data = Vec::with_capacity(file.size() as usize);
+    file.read_to_end(&mut data)?;
+    Ok(data)
+  }
+}
+
+fn file_options() -> FileOptions {
+  FileOptions::default().compression_method(CompressionMethod::Deflated)
+}
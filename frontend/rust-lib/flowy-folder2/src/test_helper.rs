@@ -41,7 +41,7 @@ impl FolderManager {
       parent_view_id: app_id.to_string(),
       name: name.to_string(),
       desc: "".to_string(),
-      layout,
+      layout: Some(layout),
       view_id: view_id.clone(),
       initial_data: vec![],
       meta: ext,
@@ -226,6 +226,12 @@ pub trait FolderOperationHandler {
     path: String,
   ) -> FutureResult<(), FlowyError>;
 
+  /// Returns the content of the view in Markdown format. The handler of the views that can't be
+  /// represented in Markdown doesn't need to implement this method.
+  fn export_view_to_markdown(&self, _view_id: &str) -> FutureResult<String, FlowyError> {
+    FutureResult::new(async move { Err(FlowyError::not_support()) })
+  }
+
   /// Called when the view is updated. The handler is the `old` registered handler.
   fn did_update_view(&self, _old: &View, _new: &View) -> FutureResult<(), FlowyError> {
     FutureResult::new(async move { Ok(()) })
@@ -51,6 +51,10 @@ where
     FutureResult::new(async move { Ok(vec![]) })
   }
 
+  fn get_folder_snapshot_count(&self, _workspace_id: &str) -> FutureResult<usize, Error> {
+    FutureResult::new(async move { Ok(0) })
+  }
+
   fn get_folder_updates(&self, workspace_id: &str, _uid: i64) -> FutureResult<Vec<Vec<u8>>, Error> {
     let workspace_id = workspace_id.to_string();
     let try_get_client = self.0.try_get_client();
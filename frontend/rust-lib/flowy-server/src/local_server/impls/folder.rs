@@ -43,6 +43,10 @@ impl FolderCloudService for LocalServerFolderCloudServiceImpl {
     FutureResult::new(async move { Ok(vec![]) })
   }
 
+  fn get_folder_snapshot_count(&self, _workspace_id: &str) -> FutureResult<usize, Error> {
+    FutureResult::new(async move { Ok(0) })
+  }
+
   fn get_folder_updates(&self, workspace_id: &str, uid: i64) -> FutureResult<Vec<Vec<u8>>, Error> {
     let weak_db = Arc::downgrade(&self.db);
     let workspace_id = workspace_id.to_string();
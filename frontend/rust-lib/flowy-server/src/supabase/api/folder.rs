@@ -15,7 +15,8 @@ use lib_infra::future::FutureResult;
 
 use crate::response::ExtendedResponse;
 use crate::supabase::api::request::{
-  get_snapshots_from_server, get_updates_from_server, FetchObjectUpdateAction,
+  get_snapshot_count_from_server, get_snapshots_from_server, get_updates_from_server,
+  FetchObjectUpdateAction,
 };
 use crate::supabase::api::util::InsertParamsBuilder;
 use crate::supabase::api::SupabaseServerService;
@@ -118,6 +119,15 @@ where
     })
   }
 
+  fn get_folder_snapshot_count(&self, workspace_id: &str) -> FutureResult<usize, Error> {
+    let try_get_postgrest = self.server.try_get_postgrest();
+    let workspace_id = workspace_id.to_string();
+    FutureResult::new(async move {
+      let postgrest = try_get_postgrest?;
+      get_snapshot_count_from_server(&workspace_id, postgrest).await
+    })
+  }
+
   fn get_folder_updates(&self, workspace_id: &str, _uid: i64) -> FutureResult<Vec<Vec<u8>>, Error> {
     let try_get_postgrest = self.server.try_get_weak_postgrest();
     let workspace_id = workspace_id.to_string();
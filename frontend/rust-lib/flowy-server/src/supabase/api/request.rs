@@ -170,6 +170,27 @@ pub async fn create_snapshot(
   Ok(snapshot_id)
 }
 
+/// Returns the number of the snapshots of the object. Only the ids of the snapshots are fetched.
+pub async fn get_snapshot_count_from_server(
+  object_id: &str,
+  postgrest: Arc<PostgresWrapper>,
+) -> Result<usize, Error> {
+  let json: Value = postgrest
+    .from(AF_COLLAB_SNAPSHOT_TABLE)
+    .select(AF_COLLAB_SNAPSHOT_ID_COLUMN)
+    .eq(AF_COLLAB_SNAPSHOT_OID_COLUMN, object_id)
+    .execute()
+    .await?
+    .get_json()
+    .await?;
+  Ok(
+    json
+      .as_array()
+      .map(|snapshots| snapshots.len())
+      .unwrap_or(0),
+  )
+}
+
 pub async fn get_snapshots_from_server(
   object_id: &str,
   postgrest: Arc<PostgresWrapper>,
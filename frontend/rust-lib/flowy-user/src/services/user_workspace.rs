@@ -3,7 +3,7 @@ use std::sync::Arc;
 
 use collab_entity::{CollabObject, CollabType};
 
-use flowy_error::{FlowyError, FlowyResult};
+use flowy_error::{ErrorCode, FlowyError, FlowyResult};
 use flowy_sqlite::schema::user_workspace_table;
 use flowy_sqlite::{query_dsl::*, ConnectionPool, ExpressionMethods};
 use flowy_user_deps::entities::{Role, UserWorkspace, WorkspaceMember};
@@ -12,6 +12,7 @@ use lib_dispatch::prelude::af_spawn;
 use crate::entities::{RepeatedUserWorkspacePB, ResetWorkspacePB};
 use crate::manager::UserManager;
 use crate::notification::{send_notification, UserNotification};
+use crate::services::entities::Session;
 use crate::services::user_workspace_sql::UserWorkspaceTable;
 
 impl UserManager {
@@ -93,10 +94,7 @@ impl UserManager {
   }
 
   pub fn get_all_user_workspaces(&self, uid: i64) -> FlowyResult<Vec<UserWorkspace>> {
-    let conn = self.db_connection(uid)?;
-    let rows = user_workspace_table::dsl::user_workspace_table
-      .filter(user_workspace_table::uid.eq(uid))
-      .load::<UserWorkspaceTable>(&*conn)?;
+    let user_workspaces = self.get_local_user_workspaces(uid)?;
 
     if let Ok(service) = self.cloud_services.get_user_service() {
       if let Ok(pool) = self.db_pool(uid) {
@@ -111,9 +109,73 @@ impl UserManager {
         });
       }
     }
+    Ok(user_workspaces)
+  }
+
+  /// Returns the workspaces of the user that are stored in the local database. Unlike
+  /// [UserManager::get_all_user_workspaces], it doesn't fetch the workspaces from the server.
+  pub fn get_local_user_workspaces(&self, uid: i64) -> FlowyResult<Vec<UserWorkspace>> {
+    let conn = self.db_connection(uid)?;
+    let rows = user_workspace_table::dsl::user_workspace_table
+      .filter(user_workspace_table::uid.eq(uid))
+      .load::<UserWorkspaceTable>(&*conn)?;
     Ok(rows.into_iter().map(UserWorkspace::from).collect())
   }
 
+  /// Adds a new workspace to the local workspaces of the user. The workspace is not created on the
+  /// server.
+  pub fn add_local_workspace(&self, workspace_id: &str, name: &str) -> FlowyResult<UserWorkspace> {
+    let uid = self.user_id()?;
+    let mut user_workspace = UserWorkspace::new(workspace_id, uid);
+    user_workspace.name = name.to_string();
+    save_user_workspaces(uid, self.db_pool(uid)?, &[user_workspace.clone()])?;
+    Ok(user_workspace)
+  }
+
+  /// Removes the workspace from the local workspaces of the user. The last workspace can't be
+  /// deleted. If the deleted workspace is the current workspace, the oldest of the remaining
+  /// workspaces will be opened.
+  pub async fn delete_workspace(&self, workspace_id: &str) -> FlowyResult<()> {
+    let session = self.get_session()?;
+    let uid = session.user_id;
+    let mut user_workspaces = self.get_local_user_workspaces(uid)?;
+    if !user_workspaces
+      .iter()
+      .any(|user_workspace| user_workspace.id == workspace_id)
+    {
+      return Err(
+        FlowyError::record_not_found()
+          .with_context(format!("Can't find the workspace:{}", workspace_id)),
+      );
+    }
+    if user_workspaces.len() <= 1 {
+      return Err(FlowyError::new(
+        ErrorCode::CannotDeleteLastWorkspace,
+        "Can't delete the last workspace, create another workspace first",
+      ));
+    }
+
+    let conn = self.db_connection(uid)?;
+    diesel::delete(
+      user_workspace_table::dsl::user_workspace_table
+        .filter(user_workspace_table::id.eq(workspace_id)),
+    )
+    .execute(&*conn)?;
+
+    if session.user_workspace.id == workspace_id {
+      user_workspaces.retain(|user_workspace| user_workspace.id != workspace_id);
+      user_workspaces.sort_by_key(|user_workspace| user_workspace.created_at);
+      let user_workspace = user_workspaces.remove(0);
+      let workspace_id = user_workspace.id.clone();
+      self.set_session(Some(Session {
+        user_workspace,
+        ..session
+      }))?;
+      self.open_workspace(&workspace_id).await?;
+    }
+    Ok(())
+  }
+
   /// Reset the remote workspace using local workspace data. This is useful when a user wishes to
   /// open a workspace on a new device that hasn't fully synchronized with the server.
   pub async fn reset_workspace(